@@ -0,0 +1,33 @@
+-- Hourly OHLCV aggregates of compacted market snapshots
+CREATE TABLE market_snapshots_hourly (
+    market_id UUID NOT NULL REFERENCES markets(id) ON DELETE CASCADE,
+    bucket_start TIMESTAMPTZ NOT NULL,
+
+    -- Price (sqrt_price) over the hour
+    open_sqrt_price NUMERIC(78, 0) NOT NULL,
+    high_sqrt_price NUMERIC(78, 0) NOT NULL,
+    low_sqrt_price NUMERIC(78, 0) NOT NULL,
+    close_sqrt_price NUMERIC(78, 0) NOT NULL,
+    close_tick INTEGER NOT NULL,
+    close_liquidity NUMERIC(78, 0) NOT NULL,
+
+    -- Volume summed over the hour
+    volume_0 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    volume_1 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    fees_0 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    fees_1 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    swap_count INTEGER NOT NULL DEFAULT 0,
+
+    -- TVL at the close of the hour
+    tvl_token_0 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    tvl_token_1 NUMERIC(78, 0) NOT NULL DEFAULT 0,
+
+    -- Source snapshots, used to merge late rows into an existing hour
+    first_slot BIGINT NOT NULL,
+    last_slot BIGINT NOT NULL,
+    sample_count INTEGER NOT NULL,
+
+    PRIMARY KEY (market_id, bucket_start)
+);
+
+CREATE INDEX idx_snapshots_hourly_bucket ON market_snapshots_hourly(bucket_start);
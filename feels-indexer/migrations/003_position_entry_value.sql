@@ -0,0 +1,2 @@
+-- USD value of a position when it was first indexed, for PnL
+ALTER TABLE positions ADD COLUMN entry_value NUMERIC(20, 2);
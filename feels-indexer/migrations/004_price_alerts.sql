@@ -0,0 +1,13 @@
+-- Client-registered price alerts, evaluated against each market snapshot
+CREATE TABLE price_alerts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    market_id UUID NOT NULL REFERENCES markets(id) ON DELETE CASCADE,
+    direction TEXT NOT NULL CHECK (direction IN ('above', 'below')),
+    threshold NUMERIC NOT NULL CHECK (threshold > 0),
+    -- Cleared when the alert fires, set again once the price moves back
+    armed BOOLEAN NOT NULL DEFAULT TRUE,
+    last_triggered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_price_alerts_market ON price_alerts(market_id);
@@ -0,0 +1,140 @@
+//! Price alert registration
+//!
+//! Alerts are stored in Postgres and evaluated by the service layer against
+//! each new market snapshot; fired alerts go out through the webhook.
+//!
+//! Registering and deleting alerts takes an `Authorization: Bearer <key>`
+//! header naming one of the configured `alert_api_keys`. With no keys
+//! configured both endpoints refuse every request.
+
+use super::ApiState;
+use crate::database::{AlertDirection, PriceAlert};
+use axum::{
+    extract::{Path, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    response::Json,
+};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Request body for registering a price alert
+#[derive(Debug, Deserialize)]
+pub struct CreateAlertRequest {
+    /// Market address
+    pub market: String,
+    pub direction: AlertDirection,
+    /// Price of token 0 in token 1, in base units
+    pub threshold: Decimal,
+}
+
+/// Check the request's bearer token against the configured keys
+fn authorize(api_keys: &[String], headers: &HeaderMap) -> Result<(), StatusCode> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    
+    // Every key is compared in full so timing does not reveal a prefix match
+    let matched = api_keys
+        .iter()
+        .fold(false, |matched, key| constant_time_eq(key.as_bytes(), token.as_bytes()) | matched);
+    if matched {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Register a price alert
+pub async fn create_alert(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateAlertRequest>,
+) -> Result<(StatusCode, Json<PriceAlert>), StatusCode> {
+    authorize(&state.alert_api_keys, &headers)?;
+    Pubkey::from_str(&request.market).map_err(|_| StatusCode::BAD_REQUEST)?;
+    if request.threshold <= Decimal::ZERO {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    
+    let market = state.db_manager.postgres
+        .get_market_by_address(&request.market)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    
+    let alert = PriceAlert {
+        id: Uuid::new_v4(),
+        market_id: market.id,
+        direction: request.direction,
+        threshold: request.threshold,
+        armed: true,
+        last_triggered_at: None,
+        created_at: chrono::Utc::now(),
+    };
+    
+    state.db_manager.postgres
+        .insert_price_alert(&alert)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to store price alert: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    
+    Ok((StatusCode::CREATED, Json(alert)))
+}
+
+/// Delete a price alert
+pub async fn delete_alert(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> StatusCode {
+    if let Err(status) = authorize(&state.alert_api_keys, &headers) {
+        return status;
+    }
+    
+    match state.db_manager.postgres.delete_price_alert(id).await {
+        Ok(true) => StatusCode::NO_CONTENT,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(e) => {
+            tracing::error!("Failed to delete price alert {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_only_configured_keys_are_authorized() {
+        let keys = vec!["alpha-key".to_string(), "beta-key".to_string()];
+
+        assert_eq!(authorize(&keys, &headers("Bearer beta-key")), Ok(()));
+        assert_eq!(authorize(&keys, &headers("Bearer beta")), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(authorize(&keys, &headers("beta-key")), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(authorize(&keys, &HeaderMap::new()), Err(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_no_keys_refuses_everyone() {
+        assert_eq!(authorize(&[], &headers("Bearer ")), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(authorize(&[], &headers("Bearer anything")), Err(StatusCode::UNAUTHORIZED));
+    }
+}
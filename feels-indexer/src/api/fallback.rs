@@ -0,0 +1,349 @@
+//! Degraded market reads while Postgres is unavailable
+//!
+//! Market endpoints read from Postgres. When a Postgres query fails the
+//! reader falls back, in order, to the Redis cache and to the raw market
+//! state RocksDB keeps from the Geyser stream. Answers served from a fallback
+//! are marked stale: the cache may lag behind chain state, and raw state
+//! carries neither row ids nor the analytics Postgres accumulates.
+
+use crate::database::{
+    postgres_impl::PostgresManager, redis::RedisManager, rocksdb::RocksDBManager, DatabaseManager,
+    Market, U128Numeric,
+};
+use crate::models::IndexedMarket;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Somewhere market rows can be read from
+#[async_trait]
+pub trait MarketSource: Send + Sync {
+    /// Look up one market by address
+    async fn market(&self, address: &str) -> Result<Option<Market>>;
+
+    /// One page of markets and the total count, or `None` if the source
+    /// cannot enumerate markets
+    async fn markets(&self, limit: usize, offset: usize) -> Result<Option<(Vec<Market>, usize)>>;
+}
+
+#[async_trait]
+impl MarketSource for PostgresManager {
+    async fn market(&self, address: &str) -> Result<Option<Market>> {
+        self.get_market_by_address(address).await
+    }
+
+    async fn markets(&self, limit: usize, offset: usize) -> Result<Option<(Vec<Market>, usize)>> {
+        let markets = self.get_markets_paginated(limit as i64, offset as i64).await?;
+        let total = self.get_markets_count().await? as usize;
+        Ok(Some((markets, total)))
+    }
+}
+
+#[async_trait]
+impl MarketSource for RedisManager {
+    async fn market(&self, address: &str) -> Result<Option<Market>> {
+        self.get_json(&format!("market:{}", address)).await
+    }
+
+    async fn markets(&self, _limit: usize, _offset: usize) -> Result<Option<(Vec<Market>, usize)>> {
+        // Markets are cached by key only
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl MarketSource for RocksDBManager {
+    async fn market(&self, address: &str) -> Result<Option<Market>> {
+        Ok(self.get_market(address)?.as_ref().map(market_from_raw))
+    }
+
+    async fn markets(&self, limit: usize, offset: usize) -> Result<Option<(Vec<Market>, usize)>> {
+        let mut markets = self.get_markets()?;
+        markets.sort_by_key(|m| m.address);
+        let total = markets.len();
+        let page = markets.iter().skip(offset).take(limit).map(market_from_raw).collect();
+        Ok(Some((page, total)))
+    }
+}
+
+/// Build an API market row from raw RocksDB state
+///
+/// Raw state has no Postgres row, so the id is nil and timestamps come from
+/// the last update seen on chain.
+fn market_from_raw(raw: &IndexedMarket) -> Market {
+    let updated_at = chrono::DateTime::from_timestamp(raw.last_updated.timestamp, 0)
+        .unwrap_or_default();
+    Market {
+        id: Uuid::nil(),
+        address: raw.address.to_string(),
+        token_0: raw.token_0.to_string(),
+        token_1: raw.token_1.to_string(),
+        sqrt_price: Decimal::from_u128(raw.sqrt_price).unwrap_or_default(),
+        liquidity: Decimal::from_u128(raw.liquidity).unwrap_or_default(),
+        current_tick: raw.current_tick,
+        tick_spacing: raw.tick_spacing as i16,
+        fee_bps: raw.fee_bps as i16,
+        is_paused: raw.is_paused,
+        phase: format!("{:?}", raw.phase),
+        global_lower_tick: raw.global_lower_tick,
+        global_upper_tick: raw.global_upper_tick,
+        fee_growth_global_0: U128Numeric(raw.fee_growth_global_0),
+        fee_growth_global_1: U128Numeric(raw.fee_growth_global_1),
+        total_volume_0: Decimal::from_u128(raw.total_volume_0).unwrap_or_default(),
+        total_volume_1: Decimal::from_u128(raw.total_volume_1).unwrap_or_default(),
+        total_fees_0: Decimal::from_u128(raw.total_fees_0).unwrap_or_default(),
+        total_fees_1: Decimal::from_u128(raw.total_fees_1).unwrap_or_default(),
+        swap_count: raw.swap_count as i64,
+        unique_traders: raw.unique_traders as i64,
+        created_at: updated_at,
+        updated_at,
+        last_updated_slot: raw.last_updated.slot as i64,
+    }
+}
+
+/// A value and whether it came from a fallback source
+#[derive(Debug)]
+pub struct Served<T> {
+    pub value: T,
+    pub stale: bool,
+}
+
+/// Reads markets from a primary source, falling back when it fails
+pub struct MarketReader {
+    primary: Arc<dyn MarketSource>,
+    fallbacks: Vec<Arc<dyn MarketSource>>,
+}
+
+impl MarketReader {
+    pub fn new(primary: Arc<dyn MarketSource>, fallbacks: Vec<Arc<dyn MarketSource>>) -> Self {
+        Self { primary, fallbacks }
+    }
+
+    /// Postgres first, then the Redis cache, then RocksDB raw state
+    pub fn from_db(db: &DatabaseManager) -> Self {
+        Self::new(
+            db.postgres.clone(),
+            vec![db.redis.clone(), db.rocksdb.clone()],
+        )
+    }
+
+    /// Look up one market
+    ///
+    /// A fallback that does not know the address defers to the next one.
+    /// Fails with the primary error only if every fallback fails too.
+    pub async fn get_market(&self, address: &str) -> Result<Served<Option<Market>>> {
+        let primary_error = match self.primary.market(address).await {
+            Ok(value) => return Ok(Served { value, stale: false }),
+            Err(e) => e,
+        };
+        warn!("Primary market read failed, serving fallback data: {}", primary_error);
+
+        let mut answered = false;
+        for source in &self.fallbacks {
+            match source.market(address).await {
+                Ok(Some(market)) => return Ok(Served { value: Some(market), stale: true }),
+                Ok(None) => answered = true,
+                Err(e) => warn!("Fallback market read failed: {}", e),
+            }
+        }
+
+        if answered {
+            Ok(Served { value: None, stale: true })
+        } else {
+            Err(primary_error)
+        }
+    }
+
+    /// One page of markets and the total count
+    pub async fn list_markets(
+        &self,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Served<(Vec<Market>, usize)>> {
+        let primary_error = match self.primary.markets(limit, offset).await {
+            Ok(Some(value)) => return Ok(Served { value, stale: false }),
+            Ok(None) => anyhow::anyhow!("primary market source cannot list markets"),
+            Err(e) => e,
+        };
+        warn!("Primary market listing failed, serving fallback data: {}", primary_error);
+
+        for source in &self.fallbacks {
+            match source.markets(limit, offset).await {
+                Ok(Some(value)) => return Ok(Served { value, stale: true }),
+                Ok(None) => {}
+                Err(e) => warn!("Fallback market listing failed: {}", e),
+            }
+        }
+
+        Err(primary_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::collections::BTreeMap;
+
+    /// Source backed by a map, or failing every read when `down`
+    struct MemorySource {
+        markets: BTreeMap<String, Market>,
+        down: bool,
+        can_list: bool,
+    }
+
+    impl MemorySource {
+        fn new(markets: &[Market], can_list: bool) -> Arc<Self> {
+            Arc::new(Self {
+                markets: markets.iter().map(|m| (m.address.clone(), m.clone())).collect(),
+                down: false,
+                can_list,
+            })
+        }
+
+        fn down() -> Arc<Self> {
+            Arc::new(Self {
+                markets: BTreeMap::new(),
+                down: true,
+                can_list: true,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl MarketSource for MemorySource {
+        async fn market(&self, address: &str) -> Result<Option<Market>> {
+            if self.down {
+                return Err(anyhow!("connection refused"));
+            }
+            Ok(self.markets.get(address).cloned())
+        }
+
+        async fn markets(
+            &self,
+            limit: usize,
+            offset: usize,
+        ) -> Result<Option<(Vec<Market>, usize)>> {
+            if self.down {
+                return Err(anyhow!("connection refused"));
+            }
+            if !self.can_list {
+                return Ok(None);
+            }
+            let page = self.markets.values().skip(offset).take(limit).cloned().collect();
+            Ok(Some((page, self.markets.len())))
+        }
+    }
+
+    fn market(address: &str, slot: u64) -> Market {
+        let raw = IndexedMarket {
+            address: solana_sdk::pubkey::Pubkey::new_unique(),
+            token_0: solana_sdk::pubkey::Pubkey::new_unique(),
+            token_1: solana_sdk::pubkey::Pubkey::new_unique(),
+            sqrt_price: 1u128 << 64,
+            liquidity: 1_000_000,
+            current_tick: 0,
+            tick_spacing: 10,
+            fee_bps: 30,
+            is_paused: false,
+            phase: crate::models::PoolPhase::PriceDiscovery,
+            global_lower_tick: -100_800,
+            global_upper_tick: 100_800,
+            fee_growth_global_0: u128::MAX,
+            fee_growth_global_1: 0,
+            last_updated: crate::models::BlockInfo::new(slot),
+            total_volume_0: 0,
+            total_volume_1: 0,
+            total_fees_0: 0,
+            total_fees_1: 0,
+            swap_count: 0,
+            unique_traders: 0,
+        };
+        Market {
+            address: address.to_string(),
+            ..market_from_raw(&raw)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_fresh_data_while_postgres_is_up() {
+        let postgres = MemorySource::new(&[market("a", 10)], true);
+        let rocksdb = MemorySource::new(&[market("a", 20)], true);
+        let reader = MarketReader::new(postgres, vec![rocksdb]);
+
+        let served = reader.get_market("a").await.unwrap();
+        assert!(!served.stale);
+        assert_eq!(served.value.unwrap().last_updated_slot, 10);
+
+        let served = reader.list_markets(10, 0).await.unwrap();
+        assert!(!served.stale);
+        assert_eq!(served.value.1, 1);
+    }
+
+    #[tokio::test]
+    async fn test_postgres_outage_falls_back_to_cache_then_raw_state() {
+        let redis = MemorySource::new(&[market("cached", 7)], false);
+        let rocksdb = MemorySource::new(&[market("cached", 8), market("raw", 9)], true);
+        let reader = MarketReader::new(MemorySource::down(), vec![redis, rocksdb]);
+
+        // Cache wins when it has the market
+        let served = reader.get_market("cached").await.unwrap();
+        assert!(served.stale);
+        assert_eq!(served.value.unwrap().last_updated_slot, 7);
+
+        // Cache miss defers to raw state
+        let served = reader.get_market("raw").await.unwrap();
+        assert!(served.stale);
+        assert_eq!(served.value.unwrap().last_updated_slot, 9);
+
+        // Unknown everywhere is a stale miss, not an error
+        let served = reader.get_market("missing").await.unwrap();
+        assert!(served.stale);
+        assert!(served.value.is_none());
+
+        // The cache cannot list, so listing comes from raw state
+        let served = reader.list_markets(1, 1).await.unwrap();
+        assert!(served.stale);
+        let (page, total) = served.value;
+        assert_eq!(total, 2);
+        assert_eq!(page[0].address, "raw");
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_every_source_is_down() {
+        let reader = MarketReader::new(MemorySource::down(), vec![MemorySource::down()]);
+
+        assert!(reader.get_market("a").await.is_err());
+        assert!(reader.list_markets(10, 0).await.is_err());
+    }
+
+    #[test]
+    fn test_postgres_outage_reports_degraded() {
+        use crate::database::{DatabaseHealth, HealthStatus};
+
+        let health = |postgres, redis, rocksdb| DatabaseHealth {
+            postgres,
+            redis,
+            rocksdb,
+            tantivy: true,
+            overall: postgres && redis && rocksdb,
+        };
+        assert_eq!(health(true, true, true).status(), HealthStatus::Healthy);
+        assert_eq!(health(false, true, true).status(), HealthStatus::Degraded);
+        assert_eq!(health(false, false, true).status(), HealthStatus::Degraded);
+        assert_eq!(health(false, false, false).status(), HealthStatus::Unhealthy);
+        assert_eq!(serde_json::to_value(HealthStatus::Degraded).unwrap(), "degraded");
+    }
+
+    #[test]
+    fn test_raw_state_keeps_full_fee_growth() {
+        let market = market("a", 42);
+        assert_eq!(market.id, Uuid::nil());
+        assert_eq!(market.fee_growth_global_0, U128Numeric(u128::MAX));
+        assert_eq!(market.phase, "PriceDiscovery");
+        assert_eq!(market.last_updated_slot, 42);
+    }
+}
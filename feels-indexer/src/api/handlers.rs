@@ -4,13 +4,22 @@ use super::{ApiState, responses::*};
 use crate::database::Market;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, HeaderValue, StatusCode},
     response::Json,
 };
 use serde::Deserialize;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Page size used when a request gives no `limit`
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Response header carrying the page size actually applied
+pub const PAGE_LIMIT_HEADER: &str = "x-page-limit";
+
+/// Response header carrying the total number of matching items
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
 /// Query parameters for pagination
 #[derive(Deserialize)]
 pub struct PaginationQuery {
@@ -18,6 +27,22 @@ pub struct PaginationQuery {
     pub offset: Option<usize>,
 }
 
+impl PaginationQuery {
+    /// Effective `(limit, offset)`, with `limit` clamped to `max_limit`
+    pub fn clamp(&self, max_limit: usize) -> (usize, usize) {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(max_limit);
+        (limit, self.offset.unwrap_or(0))
+    }
+}
+
+/// Headers reporting the effective page size and total item count
+fn page_headers(limit: usize, total: usize) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(PAGE_LIMIT_HEADER, HeaderValue::from(limit));
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(total));
+    headers
+}
+
 /// Query parameters for time range
 #[derive(Deserialize)]
 pub struct TimeRangeQuery {
@@ -29,30 +54,26 @@ pub struct TimeRangeQuery {
 pub async fn list_markets(
     State(state): State<ApiState>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<MarketsResponse>, StatusCode> {
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+) -> Result<(HeaderMap, Json<MarketsResponse>), StatusCode> {
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
-    // Get markets from PostgreSQL
-    let markets = state.db_manager.postgres
-        .get_markets_paginated(limit, offset)
+    // Get markets from PostgreSQL, or a fallback while it is down
+    let served = state.markets
+        .list_markets(limit, offset)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get markets: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    let (markets, total) = served.value;
     
-    let total = state.db_manager.postgres
-        .get_markets_count()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
-    
-    Ok(Json(MarketsResponse {
+    Ok((page_headers(limit, total), Json(MarketsResponse {
         markets,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+        stale: served.stale,
+    })))
 }
 
 /// Get specific market
@@ -69,20 +90,20 @@ pub async fn get_market(
     if let Ok(Some(market)) = state.db_manager.redis
         .get_json::<Market>(&cache_key)
         .await {
-        return Ok(Json(MarketResponse { market }));
+        return Ok(Json(MarketResponse { market, stale: false }));
     }
     
-    // Fallback to PostgreSQL
-    let market = state.db_manager.postgres
-        .get_market_by_address(&address)
+    // Fallback to PostgreSQL, or raw state while it is down
+    let served = state.markets
+        .get_market(&address)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get market: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
     
-    match market {
-        Some(market) => Ok(Json(MarketResponse { market })),
+    match served.value {
+        Some(market) => Ok(Json(MarketResponse { market, stale: served.stale })),
         None => Err(StatusCode::NOT_FOUND),
     }
 }
@@ -136,12 +157,11 @@ pub async fn get_market_swaps(
     State(state): State<ApiState>,
     Path(address): Path<String>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<SwapsResponse>, StatusCode> {
+) -> Result<(HeaderMap, Json<SwapsResponse>), StatusCode> {
     let _pubkey = Pubkey::from_str(&address)
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get market by address first to get its ID
     let market = state.db_manager.postgres
@@ -156,7 +176,7 @@ pub async fn get_market_swaps(
     
     // Get swaps for this market
     let swaps = state.db_manager.postgres
-        .get_swaps_by_market_id(market.id, limit, offset)
+        .get_swaps_by_market_id(market.id, limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -165,12 +185,12 @@ pub async fn get_market_swaps(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(SwapsResponse {
+    Ok((page_headers(limit, total), Json(SwapsResponse {
         swaps,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// Get market positions
@@ -178,12 +198,11 @@ pub async fn get_market_positions(
     State(state): State<ApiState>,
     Path(address): Path<String>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<PositionsResponse>, StatusCode> {
+) -> Result<(HeaderMap, Json<PositionsResponse>), StatusCode> {
     let _pubkey = Pubkey::from_str(&address)
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get market by address first to get its ID
     let market = state.db_manager.postgres
@@ -198,7 +217,7 @@ pub async fn get_market_positions(
     
     // Get positions for this market
     let positions = state.db_manager.postgres
-        .get_positions_by_market_id(market.id, limit, offset)
+        .get_positions_by_market_id(market.id, limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -207,12 +226,12 @@ pub async fn get_market_positions(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(PositionsResponse {
+    Ok((page_headers(limit, total), Json(PositionsResponse {
         positions,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// Get market floor information
@@ -308,13 +327,12 @@ pub async fn get_market_ohlcv(
 pub async fn list_swaps(
     State(state): State<ApiState>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<SwapsResponse>, StatusCode> {
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+) -> Result<(HeaderMap, Json<SwapsResponse>), StatusCode> {
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get recent swaps
     let swaps = state.db_manager.postgres
-        .get_recent_swaps_paginated(limit, offset)
+        .get_recent_swaps_paginated(limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -323,12 +341,12 @@ pub async fn list_swaps(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(SwapsResponse {
+    Ok((page_headers(limit, total), Json(SwapsResponse {
         swaps,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// Get specific swap
@@ -353,16 +371,15 @@ pub async fn get_user_swaps(
     State(state): State<ApiState>,
     Path(address): Path<String>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<SwapsResponse>, StatusCode> {
+) -> Result<(HeaderMap, Json<SwapsResponse>), StatusCode> {
     let _pubkey = Pubkey::from_str(&address)
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get swaps for this user
     let swaps = state.db_manager.postgres
-        .get_swaps_by_user(&address, limit, offset)
+        .get_swaps_by_user(&address, limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -371,25 +388,24 @@ pub async fn get_user_swaps(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(SwapsResponse {
+    Ok((page_headers(limit, total), Json(SwapsResponse {
         swaps,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// List positions
 pub async fn list_positions(
     State(state): State<ApiState>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<PositionsResponse>, StatusCode> {
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+) -> Result<(HeaderMap, Json<PositionsResponse>), StatusCode> {
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get all positions
     let positions = state.db_manager.postgres
-        .get_positions_paginated(limit, offset)
+        .get_positions_paginated(limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -398,12 +414,12 @@ pub async fn list_positions(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(PositionsResponse {
+    Ok((page_headers(limit, total), Json(PositionsResponse {
         positions,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// Get specific position
@@ -431,16 +447,15 @@ pub async fn get_user_positions(
     State(state): State<ApiState>,
     Path(address): Path<String>,
     Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<PositionsResponse>, StatusCode> {
+) -> Result<(HeaderMap, Json<PositionsResponse>), StatusCode> {
     let _pubkey = Pubkey::from_str(&address)
         .map_err(|_| StatusCode::BAD_REQUEST)?;
     
-    let limit = pagination.limit.unwrap_or(50).min(100) as i64;
-    let offset = pagination.offset.unwrap_or(0) as i64;
+    let (limit, offset) = pagination.clamp(state.max_page_size);
     
     // Get positions for this user
     let positions = state.db_manager.postgres
-        .get_positions_by_user(&address, limit, offset)
+        .get_positions_by_user(&address, limit as i64, offset as i64)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     
@@ -449,12 +464,12 @@ pub async fn get_user_positions(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? as usize;
     
-    Ok(Json(PositionsResponse {
+    Ok((page_headers(limit, total), Json(PositionsResponse {
         positions,
         total,
-        limit: limit as usize,
-        offset: offset as usize,
-    }))
+        limit,
+        offset,
+    })))
 }
 
 /// Get protocol statistics
@@ -487,7 +502,7 @@ pub async fn get_protocol_stats(
 /// Get protocol markets
 pub async fn get_protocol_markets(
     State(state): State<ApiState>,
-) -> Result<Json<MarketsResponse>, StatusCode> {
+) -> Result<(HeaderMap, Json<MarketsResponse>), StatusCode> {
     // Reuse list_markets logic
     list_markets(State(state), Query(PaginationQuery { limit: None, offset: None })).await
 }
@@ -525,3 +540,32 @@ pub async fn get_protocol_volume(
         timestamp: chrono::Utc::now().timestamp(),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(limit: Option<usize>, offset: Option<usize>) -> PaginationQuery {
+        PaginationQuery { limit, offset }
+    }
+
+    #[test]
+    fn test_oversized_limit_is_clamped() {
+        assert_eq!(query(Some(1_000_000), Some(40)).clamp(200), (200, 40));
+        assert_eq!(query(Some(usize::MAX), None).clamp(25), (25, 0));
+
+        // Limits within the cap are honored
+        assert_eq!(query(Some(150), None).clamp(200), (150, 0));
+        assert_eq!(query(None, None).clamp(200), (DEFAULT_PAGE_SIZE, 0));
+        assert_eq!(query(None, None).clamp(10), (10, 0));
+    }
+
+    #[test]
+    fn test_page_headers_report_effective_limit() {
+        let (limit, _) = query(Some(1_000_000), None).clamp(200);
+        let headers = page_headers(limit, 1234);
+
+        assert_eq!(headers[PAGE_LIMIT_HEADER], "200");
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "1234");
+    }
+}
@@ -0,0 +1,182 @@
+//! Price-impact distribution endpoint
+//!
+//! Buckets a market's recent swaps by `price_impact_bps` so dashboards can
+//! show whether it trades mostly in small or large size.
+
+use super::ApiState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Default bucket width
+const DEFAULT_BUCKET_BPS: i32 = 10;
+/// Default number of equal-width buckets below the open-ended top bucket
+const DEFAULT_BUCKETS: i32 = 10;
+/// Most equal-width buckets a request may ask for
+const MAX_BUCKETS: i32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct ImpactHistogramQuery {
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    /// Width of each bucket in bps
+    pub bucket_bps: Option<i32>,
+    /// Number of equal-width buckets
+    pub buckets: Option<i32>,
+}
+
+/// Swaps whose impact fell in `[min_bps, max_bps)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactBucket {
+    pub min_bps: i32,
+    /// Exclusive upper bound; absent on the top bucket
+    pub max_bps: Option<i32>,
+    pub swaps: u64,
+}
+
+/// Response for a market's price-impact distribution
+#[derive(Debug, Serialize)]
+pub struct ImpactHistogramResponse {
+    pub market_address: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Swaps with a recorded impact in the window
+    pub total_swaps: u64,
+    pub buckets: Vec<ImpactBucket>,
+}
+
+/// Get the price-impact distribution of a market's swaps
+pub async fn get_impact_histogram(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(query): Query<ImpactHistogramQuery>,
+) -> Result<Json<ImpactHistogramResponse>, StatusCode> {
+    let _pubkey = Pubkey::from_str(&address)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let bucket_bps = query.bucket_bps.unwrap_or(DEFAULT_BUCKET_BPS);
+    let buckets = query.buckets.unwrap_or(DEFAULT_BUCKETS);
+    if !(1..=10_000).contains(&bucket_bps) || !(1..=MAX_BUCKETS).contains(&buckets) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let market = state.db_manager.postgres
+        .get_market_by_address(&address)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Get time range (default to 24h)
+    let end_time = query.end_time.unwrap_or_else(|| Utc::now().timestamp());
+    let start_time = query.start_time.unwrap_or(end_time - 86400);
+    let (from, to) = match (Utc.timestamp_opt(start_time, 0), Utc.timestamp_opt(end_time, 0)) {
+        (chrono::LocalResult::Single(from), chrono::LocalResult::Single(to)) if from < to => {
+            (from, to)
+        }
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let counts = state.db_manager.postgres
+        .get_price_impact_buckets(market.id, from, to, bucket_bps, buckets)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let buckets = histogram(&counts, bucket_bps, buckets);
+    Ok(Json(ImpactHistogramResponse {
+        market_address: address,
+        start_time,
+        end_time,
+        total_swaps: buckets.iter().map(|bucket| bucket.swaps).sum(),
+        buckets,
+    }))
+}
+
+/// Expand `width_bucket` counts into every bucket, including empty ones
+///
+/// Negative impacts (bucket 0) are folded into the first bucket, and the
+/// overflow bucket becomes an open-ended top bucket, so the result always has
+/// `buckets + 1` entries.
+fn histogram(counts: &[(i32, i64)], bucket_bps: i32, buckets: i32) -> Vec<ImpactBucket> {
+    let mut filled: Vec<ImpactBucket> = (0..=buckets)
+        .map(|i| ImpactBucket {
+            min_bps: i * bucket_bps,
+            max_bps: (i < buckets).then_some((i + 1) * bucket_bps),
+            swaps: 0,
+        })
+        .collect();
+
+    for &(bucket, swaps) in counts {
+        let index = (bucket - 1).clamp(0, buckets) as usize;
+        filled[index].swaps += swaps.max(0) as u64;
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// Postgres `width_bucket(operand, low, high, count)`
+    fn width_bucket(operand: f64, low: f64, high: f64, count: i32) -> i32 {
+        if operand < low {
+            0
+        } else if operand >= high {
+            count + 1
+        } else {
+            ((operand - low) * count as f64 / (high - low)).floor() as i32 + 1
+        }
+    }
+
+    /// What `get_price_impact_buckets` returns for swaps with these impacts
+    fn bucket_counts(impacts: &[Option<i16>], bucket_bps: i32, buckets: i32) -> Vec<(i32, i64)> {
+        let high = (bucket_bps * buckets) as f64;
+        let mut counts = BTreeMap::new();
+        for impact in impacts.iter().flatten() {
+            *counts.entry(width_bucket(*impact as f64, 0.0, high, buckets)).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    #[test]
+    fn test_swaps_land_in_their_impact_bucket() {
+        let impacts = [
+            Some(0), Some(3), Some(9),  // [0, 10)
+            Some(10), Some(19),         // [10, 20)
+            Some(45),                   // [40, 50)
+            Some(50), Some(900),        // top bucket
+            Some(-2),                   // negative, folded into the first
+            None,                       // no impact recorded
+        ];
+
+        let buckets = histogram(&bucket_counts(&impacts, 10, 5), 10, 5);
+
+        let summary: Vec<(i32, Option<i32>, u64)> = buckets
+            .iter()
+            .map(|bucket| (bucket.min_bps, bucket.max_bps, bucket.swaps))
+            .collect();
+        assert_eq!(summary, vec![
+            (0, Some(10), 4),
+            (10, Some(20), 2),
+            (20, Some(30), 0),
+            (30, Some(40), 0),
+            (40, Some(50), 1),
+            (50, None, 2),
+        ]);
+    }
+
+    #[test]
+    fn test_no_swaps_gives_empty_buckets() {
+        let buckets = histogram(&[], 25, 4);
+
+        assert_eq!(buckets.len(), 5);
+        assert!(buckets.iter().all(|bucket| bucket.swaps == 0));
+        assert_eq!(buckets[4], ImpactBucket { min_bps: 100, max_bps: None, swaps: 0 });
+    }
+}
@@ -0,0 +1,220 @@
+//! Liquidity distribution endpoint
+//!
+//! Powers depth charts: walks the initialized ticks of a market's indexed
+//! tick arrays in price order and reports the liquidity active above each one.
+
+use super::ApiState;
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Json},
+    http::StatusCode,
+};
+use feels_sdk::{
+    jupiter::parse_tick_array_auto,
+    protocol::{sqrt_price_to_price, tick_to_sqrt_price},
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use tracing::{error, warn};
+
+/// Liquidity at one initialized tick
+#[derive(Debug, Serialize)]
+pub struct LiquidityLevel {
+    /// Tick index
+    pub tick: i32,
+    /// Price of token 0 in token 1 at this tick, without decimal adjustment
+    pub price: f64,
+    /// Q64 sqrt price at this tick
+    pub sqrt_price: String,
+    /// Liquidity added (or removed, if negative) when crossing upward
+    pub liquidity_net: String,
+    /// Liquidity active from this tick up to the next initialized one
+    pub liquidity: String,
+}
+
+/// Response for a market's liquidity distribution
+#[derive(Debug, Serialize)]
+pub struct LiquidityDistributionResponse {
+    pub market_address: String,
+    pub current_tick: i32,
+    pub tick_spacing: u16,
+    /// Initialized ticks in ascending order; empty when none are indexed
+    pub levels: Vec<LiquidityLevel>,
+}
+
+/// Get the liquidity distribution of a market
+pub async fn get_liquidity_distribution(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+) -> impl IntoResponse {
+    let market_pubkey = match Pubkey::from_str(&address) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            return (StatusCode::BAD_REQUEST, Json(serde_json::json!({
+                "error": "Invalid market address"
+            }))).into_response();
+        }
+    };
+
+    let market = match state.markets.get_market(&address).await {
+        Ok(served) => match served.value {
+            Some(market) => market,
+            None => {
+                return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+                    "error": "Market not found"
+                }))).into_response();
+            }
+        },
+        Err(e) => {
+            error!("Failed to get market {}: {}", address, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "error": "Failed to load market"
+            }))).into_response();
+        }
+    };
+    let tick_spacing = market.tick_spacing as u16;
+
+    let raw_arrays = match state.db_manager.rocksdb.get_tick_arrays(&market_pubkey).await {
+        Ok(raw_arrays) => raw_arrays,
+        Err(e) => {
+            error!("Failed to load tick arrays for {}: {}", address, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "error": "Failed to load tick arrays"
+            }))).into_response();
+        }
+    };
+
+    let ticks = collect_initialized_ticks(&raw_arrays, &market_pubkey, tick_spacing);
+    let levels = match liquidity_levels(&ticks) {
+        Ok(levels) => levels,
+        Err(e) => {
+            error!("Failed to price ticks for {}: {}", address, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "error": "Failed to compute distribution"
+            }))).into_response();
+        }
+    };
+
+    (StatusCode::OK, Json(LiquidityDistributionResponse {
+        market_address: address,
+        current_tick: market.current_tick,
+        tick_spacing,
+        levels,
+    })).into_response()
+}
+
+/// Decode tick arrays and merge their initialized ticks by index
+///
+/// Arrays that fail to decode or belong to another market are skipped.
+fn collect_initialized_ticks(
+    raw_arrays: &[Vec<u8>],
+    market: &Pubkey,
+    tick_spacing: u16,
+) -> BTreeMap<i32, i128> {
+    let mut ticks = BTreeMap::new();
+
+    for data in raw_arrays {
+        match parse_tick_array_auto(data, tick_spacing) {
+            Ok(array) if array.market == *market => ticks.extend(array.initialized_ticks),
+            Ok(array) => warn!("Tick array for {} indexed under {}", array.market, market),
+            Err(e) => warn!("Skipping undecodable tick array of {}: {:?}", market, e),
+        }
+    }
+
+    ticks
+}
+
+/// Walk `liquidity_net` upward from the lowest tick, accumulating active liquidity
+fn liquidity_levels(ticks: &BTreeMap<i32, i128>) -> Result<Vec<LiquidityLevel>, String> {
+    let mut liquidity: i128 = 0;
+
+    ticks.iter()
+        .map(|(&tick, &liquidity_net)| {
+            liquidity = liquidity.saturating_add(liquidity_net);
+            let sqrt_price = tick_to_sqrt_price(tick)
+                .map_err(|e| format!("tick {}: {:?}", tick, e))?;
+
+            Ok(LiquidityLevel {
+                tick,
+                price: sqrt_price_to_price(sqrt_price, 0, 0),
+                sqrt_price: sqrt_price.to_string(),
+                liquidity_net: liquidity_net.to_string(),
+                liquidity: liquidity.max(0).to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feels_sdk::jupiter::TickArrayFormat;
+
+    /// Raw V1 tick array with the given (slot, liquidity_net) ticks initialized
+    fn tick_array(market: &Pubkey, start_tick: i32, ticks: &[(usize, i128)]) -> Vec<u8> {
+        let format = TickArrayFormat::V1;
+        let mut data = vec![0u8; format.calculate_total_size()];
+        data[..8].copy_from_slice(&format.discriminator);
+        data[8..40].copy_from_slice(market.as_ref());
+        data[40..44].copy_from_slice(&start_tick.to_le_bytes());
+
+        for &(slot, liquidity_net) in ticks {
+            let offset = 56 + slot * 80;
+            data[offset..offset + 16].copy_from_slice(&liquidity_net.to_le_bytes());
+            data[offset + 64] = 1;
+        }
+        data
+    }
+
+    #[test]
+    fn test_accumulates_liquidity_across_arrays() {
+        let market = Pubkey::new_unique();
+        let raw_arrays = vec![
+            // Position A over [-640, 0], position B over [-100, 700]
+            tick_array(&market, -640, &[(0, 1_000), (54, 500)]),
+            tick_array(&market, 0, &[(0, -1_000), (1, 0), (6, 0)]),
+            tick_array(&market, 640, &[(6, -500)]),
+            // Ignored: another market's array
+            tick_array(&Pubkey::new_unique(), 0, &[(0, 9_999)]),
+        ];
+
+        let ticks = collect_initialized_ticks(&raw_arrays, &market, 10);
+        let levels = liquidity_levels(&ticks).unwrap();
+
+        let summary: Vec<(i32, &str)> = levels.iter()
+            .map(|level| (level.tick, level.liquidity.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (-640, "1000"),
+            (-100, "1500"),
+            (0, "500"),
+            (10, "500"),
+            (60, "500"),
+            (700, "0"),
+        ]);
+
+        // Prices rise with the tick, and tick 0 is parity
+        assert!(levels.windows(2).all(|pair| pair[0].price < pair[1].price));
+        assert!((levels[2].price - 1.0).abs() < 1e-12);
+        assert_eq!(levels[2].sqrt_price, (1u128 << 64).to_string());
+        assert_eq!(levels[0].liquidity_net, "1000");
+        assert_eq!(levels[2].liquidity_net, "-1000");
+    }
+
+    #[test]
+    fn test_market_without_initialized_ticks_is_empty() {
+        let market = Pubkey::new_unique();
+
+        let ticks = collect_initialized_ticks(&[], &market, 10);
+        assert!(liquidity_levels(&ticks).unwrap().is_empty());
+
+        let ticks = collect_initialized_ticks(&[tick_array(&market, 0, &[])], &market, 10);
+        assert!(liquidity_levels(&ticks).unwrap().is_empty());
+
+        // Garbage is skipped rather than failing the request
+        let ticks = collect_initialized_ticks(&[vec![1, 2, 3]], &market, 10);
+        assert!(ticks.is_empty());
+    }
+}
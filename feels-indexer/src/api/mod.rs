@@ -1,22 +1,30 @@
 //! REST API for querying indexed Feels Protocol data
 
+mod alerts;
+mod fallback;
 mod handlers;
+mod impact_histogram;
+mod liquidity_distribution;
 mod routes;
 mod responses;
+mod swap_export;
 mod swap_simulation;
 mod token_balance;
 mod transaction_builder;
 mod websocket;
 mod jupiter_integration;
 
+pub use fallback::{MarketReader, MarketSource};
 pub use routes::*;
 pub use websocket::UpdateBroadcaster;
 
 use crate::config::ApiConfig;
-use crate::database::DatabaseManager;
+use crate::database::{DatabaseManager, HealthStatus};
+use crate::shutdown::ShutdownSignal;
 use anyhow::Result;
 use axum::{
-    http::StatusCode,
+    extract::State,
+    http::{HeaderValue, Method, StatusCode},
     response::Json,
     routing::get,
     Router,
@@ -25,21 +33,30 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
-use tracing::info;
+use tower_http::{
+    cors::{AllowOrigin, Any, CorsLayer},
+    trace::TraceLayer,
+};
+use tracing::{info, warn};
 
 /// Start the API server
+///
+/// Once `shutdown` fires the server stops accepting connections and the
+/// returned task finishes after in-flight requests have completed.
 pub async fn start_server(
     db_manager: Arc<DatabaseManager>,
     config: &ApiConfig,
+    mut shutdown: ShutdownSignal,
 ) -> Result<tokio::task::JoinHandle<()>> {
-    let app = create_app(db_manager).await?;
+    let app = create_app(db_manager, config).await?;
     
     let listener = TcpListener::bind(&config.bind_address).await?;
     info!("API server listening on {}", config.bind_address);
     
     let handle = tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
+        let server = axum::serve(listener, app)
+            .with_graceful_shutdown(async move { shutdown.recv().await });
+        if let Err(e) = server.await {
             tracing::error!("API server error: {}", e);
         }
     });
@@ -47,18 +64,23 @@ pub async fn start_server(
     Ok(handle)
 }
 
-/// Start the metrics server
-pub async fn start_metrics_server(port: u16) -> Result<tokio::task::JoinHandle<()>> {
+/// Start the metrics server, draining it like the API server on shutdown
+pub async fn start_metrics_server(
+    port: u16,
+    mut shutdown: ShutdownSignal,
+) -> Result<tokio::task::JoinHandle<()>> {
     let app = Router::new()
         .route("/metrics", get(metrics_handler))
-        .route("/health", get(health_handler));
+        .route("/health", get(liveness_handler));
     
     let addr = format!("127.0.0.1:{}", port);
     let listener = TcpListener::bind(&addr).await?;
     info!("Metrics server listening on {}", addr);
     
     let handle = tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
+        let server = axum::serve(listener, app)
+            .with_graceful_shutdown(async move { shutdown.recv().await });
+        if let Err(e) = server.await {
             tracing::error!("Metrics server error: {}", e);
         }
     });
@@ -67,8 +89,9 @@ pub async fn start_metrics_server(port: u16) -> Result<tokio::task::JoinHandle<(
 }
 
 /// Create the main API application
-async fn create_app(db_manager: Arc<DatabaseManager>) -> Result<Router> {
-    let api_state = ApiState::new(db_manager);
+async fn create_app(db_manager: Arc<DatabaseManager>, config: &ApiConfig) -> Result<Router> {
+    let api_state = ApiState::new(db_manager, config.max_page_size)
+        .with_alert_api_keys(config.alert_api_keys.clone());
     
     let app = Router::new()
         .merge(create_market_routes())
@@ -76,6 +99,7 @@ async fn create_app(db_manager: Arc<DatabaseManager>) -> Result<Router> {
         .merge(create_position_routes())
         .merge(create_protocol_routes())
         .merge(create_token_routes())
+        .merge(create_alert_routes())
         .merge(websocket::create_websocket_routes())
         .merge(jupiter_integration::create_jupiter_routes())
         .route("/health", get(health_handler))
@@ -83,14 +107,39 @@ async fn create_app(db_manager: Arc<DatabaseManager>) -> Result<Router> {
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
-                .layer(CorsLayer::permissive())
+                .layer(cors_layer(&config.allowed_origins)?)
         );
     
     Ok(app)
 }
 
-/// Health check handler
-async fn health_handler() -> Json<Value> {
+/// CORS policy for the API
+///
+/// Only the configured origins may make cross-origin requests. An empty list
+/// falls back to allowing any origin, which is logged since it is only safe in
+/// development.
+fn cors_layer(allowed_origins: &[String]) -> Result<CorsLayer> {
+    if allowed_origins.is_empty() {
+        warn!("No API allowed_origins configured, accepting cross-origin requests from any origin");
+        return Ok(CorsLayer::permissive());
+    }
+
+    let origins = allowed_origins
+        .iter()
+        .map(|origin| {
+            HeaderValue::from_str(origin)
+                .map_err(|_| anyhow::anyhow!("Invalid allowed origin: {}", origin))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::DELETE])
+        .allow_headers(Any))
+}
+
+/// Liveness handler for the metrics server
+async fn liveness_handler() -> Json<Value> {
     Json(json!({
         "status": "healthy",
         "timestamp": chrono::Utc::now().timestamp(),
@@ -98,6 +147,32 @@ async fn health_handler() -> Json<Value> {
     }))
 }
 
+/// Health check handler
+///
+/// Reports `degraded` while Postgres is down but reads can still be served
+/// from Redis or RocksDB, and 503 only when nothing can serve data.
+async fn health_handler(State(state): State<ApiState>) -> (StatusCode, Json<Value>) {
+    let health = match state.db_manager.health_check().await {
+        Ok(health) => health,
+        Err(e) => {
+            tracing::error!("Health check failed: {}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(json!({ "status": "unhealthy" })));
+        }
+    };
+    let status = health.status();
+    let code = match status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+    };
+    
+    (code, Json(json!({
+        "status": status,
+        "components": health,
+        "timestamp": chrono::Utc::now().timestamp(),
+        "service": "feels-indexer"
+    })))
+}
+
 /// Metrics handler (placeholder)
 async fn metrics_handler() -> Result<String, StatusCode> {
     // In a real implementation, this would return Prometheus metrics
@@ -109,13 +184,99 @@ async fn metrics_handler() -> Result<String, StatusCode> {
 pub struct ApiState {
     pub db_manager: Arc<DatabaseManager>,
     pub db: Arc<DatabaseManager>, // Alias for compatibility
+    pub markets: Arc<MarketReader>,
+    /// Upper bound on the `limit` of list endpoints
+    pub max_page_size: usize,
+    /// Bearer tokens allowed to manage price alerts
+    pub alert_api_keys: Arc<Vec<String>>,
 }
 
 impl ApiState {
-    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+    pub fn new(db_manager: Arc<DatabaseManager>, max_page_size: usize) -> Self {
         Self { 
             db: db_manager.clone(),
+            markets: Arc::new(MarketReader::from_db(&db_manager)),
             db_manager,
+            max_page_size,
+            alert_api_keys: Arc::new(Vec::new()),
         }
     }
+
+    /// Allow these bearer tokens to register and delete price alerts
+    pub fn with_alert_api_keys(mut self, keys: Vec<String>) -> Self {
+        self.alert_api_keys = Arc::new(keys);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve a single route behind `cors_layer` and return its address
+    async fn serve(allowed_origins: &[&str]) -> String {
+        let origins: Vec<String> = allowed_origins.iter().map(|o| o.to_string()).collect();
+        let app = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .layer(cors_layer(&origins).unwrap());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_is_rejected() {
+        let base = serve(&["https://app.feels.market"]).await;
+        let client = reqwest::Client::new();
+
+        let allowed = client
+            .get(format!("{}/health", base))
+            .header("origin", "https://app.feels.market")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            allowed.headers().get("access-control-allow-origin").unwrap(),
+            "https://app.feels.market"
+        );
+
+        let disallowed = client
+            .get(format!("{}/health", base))
+            .header("origin", "https://evil.example")
+            .send()
+            .await
+            .unwrap();
+        assert!(disallowed.headers().get("access-control-allow-origin").is_none());
+
+        let preflight = client
+            .request(reqwest::Method::OPTIONS, format!("{}/health", base))
+            .header("origin", "https://evil.example")
+            .header("access-control-request-method", "GET")
+            .send()
+            .await
+            .unwrap();
+        assert!(preflight.headers().get("access-control-allow-origin").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_allowlist_allows_any_origin() {
+        let base = serve(&[]).await;
+
+        let response = reqwest::Client::new()
+            .get(format!("{}/health", base))
+            .header("origin", "https://anywhere.example")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get("access-control-allow-origin").unwrap(), "*");
+    }
+
+    #[test]
+    fn test_invalid_origin_is_a_config_error() {
+        assert!(cors_layer(&["https://ok.example".to_string()]).is_ok());
+        assert!(cors_layer(&["bad\norigin".to_string()]).is_err());
+    }
 }
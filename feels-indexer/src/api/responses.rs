@@ -11,12 +11,18 @@ pub struct MarketsResponse {
     pub total: usize,
     pub limit: usize,
     pub offset: usize,
+    /// Served from a fallback because Postgres is unavailable
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Response for single market
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MarketResponse {
     pub market: Market,
+    /// Served from a fallback because Postgres is unavailable
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Response for market statistics
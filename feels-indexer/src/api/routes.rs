@@ -1,8 +1,8 @@
 //! API route definitions
 
-use super::{ApiState, handlers::*};
+use super::{ApiState, handlers::*, liquidity_distribution::get_liquidity_distribution};
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
 
@@ -13,9 +13,15 @@ pub fn create_market_routes() -> Router<ApiState> {
         .route("/markets/:address", get(get_market))
         .route("/markets/:address/stats", get(get_market_stats))
         .route("/markets/:address/swaps", get(get_market_swaps))
+        .route("/markets/:address/swaps.csv", get(crate::api::swap_export::export_market_swaps))
         .route("/markets/:address/positions", get(get_market_positions))
         .route("/markets/:address/floor", get(get_market_floor))
         .route("/markets/:address/ohlcv", get(get_market_ohlcv))
+        .route("/markets/:address/liquidity-distribution", get(get_liquidity_distribution))
+        .route(
+            "/markets/:address/impact-histogram",
+            get(crate::api::impact_histogram::get_impact_histogram),
+        )
 }
 
 /// Create swap-related routes
@@ -52,3 +58,10 @@ pub fn create_token_routes() -> Router<ApiState> {
         .route("/tokens/:mint/balance/:wallet", get(crate::api::token_balance::get_token_balance))
         .route("/wallets/:wallet/balances", get(crate::api::token_balance::get_wallet_balances))
 }
+
+/// Create price alert routes
+pub fn create_alert_routes() -> Router<ApiState> {
+    Router::new()
+        .route("/alerts", post(crate::api::alerts::create_alert))
+        .route("/alerts/:id", delete(crate::api::alerts::delete_alert))
+}
@@ -0,0 +1,216 @@
+//! CSV export of a market's swaps
+//!
+//! Rows are streamed from Postgres and written to the response as they
+//! arrive, so exporting a large range never holds it in memory.
+
+use super::ApiState;
+use crate::database::Swap;
+use crate::services::valuation::spot_price;
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use futures::{stream, Stream, StreamExt};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Column names, in row order
+pub const CSV_HEADER: &str = "signature,timestamp,trader,amount_in,amount_out,price,fee\n";
+
+/// Rows written ahead of a slow client before the query is paused
+const EXPORT_BUFFER_ROWS: usize = 256;
+
+/// Time range of an export, in unix seconds
+#[derive(Debug, Deserialize)]
+pub struct SwapExportQuery {
+    /// Inclusive start; defaults to the beginning of the index
+    pub from: Option<i64>,
+    /// Exclusive end; defaults to now
+    pub to: Option<i64>,
+}
+
+/// Export a market's swaps as CSV
+pub async fn export_market_swaps(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(range): Query<SwapExportQuery>,
+) -> Result<Response, StatusCode> {
+    Pubkey::from_str(&address).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let from = timestamp(range.from.unwrap_or(0))?;
+    let to = match range.to {
+        Some(to) => timestamp(to)?,
+        None => Utc::now(),
+    };
+    if from >= to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let market = state.db_manager.postgres
+        .get_market_by_address(&address)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // The query stream borrows the pool, so it is drained on its own task and
+    // handed to the body through a bounded channel; a slow client applies
+    // backpressure to the query instead of buffering rows
+    let (tx, rx) = tokio::sync::mpsc::channel(EXPORT_BUFFER_ROWS);
+    let db = state.db_manager.clone();
+    tokio::spawn(async move {
+        let swaps = db.postgres.stream_swaps_by_market_id(market.id, from, to);
+        let mut chunks = std::pin::pin!(csv_chunks(swaps));
+        while let Some(chunk) = chunks.next().await {
+            if let Err(e) = &chunk {
+                tracing::error!("Swap export for market {} failed: {}", market.id, e);
+            }
+            // Stop querying once the client has gone away
+            if tx.send(chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let disposition = format!("attachment; filename=\"{}-swaps.csv\"", address);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+fn timestamp(secs: i64) -> Result<DateTime<Utc>, StatusCode> {
+    Utc.timestamp_opt(secs, 0).single().ok_or(StatusCode::BAD_REQUEST)
+}
+
+/// The CSV header followed by one chunk per swap
+///
+/// A query error ends the stream with that error, which aborts the response
+/// rather than leaving a silently truncated file.
+fn csv_chunks<S>(swaps: S) -> impl Stream<Item = anyhow::Result<Bytes>>
+where
+    S: Stream<Item = anyhow::Result<Swap>>,
+{
+    stream::once(async { Ok(Bytes::from_static(CSV_HEADER.as_bytes())) })
+        .chain(swaps.map(|swap| swap.map(|swap| Bytes::from(csv_row(&swap)))))
+}
+
+/// One CSV line for a swap
+///
+/// The price is the swap's effective price when recorded, otherwise the
+/// pool price after the swap.
+fn csv_row(swap: &Swap) -> String {
+    let price = match swap.effective_price {
+        Some(price) => price.to_string(),
+        None => spot_price(swap.sqrt_price_after.to_f64().unwrap_or(0.0)).to_string(),
+    };
+
+    format!(
+        "{},{},{},{},{},{},{}\n",
+        csv_field(&swap.signature),
+        swap.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        csv_field(&swap.trader),
+        swap.amount_in,
+        swap.amount_out,
+        price,
+        swap.fee_amount,
+    )
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::FromPrimitive;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn swap(signature: &str, secs: i64, effective_price: Option<Decimal>) -> Swap {
+        Swap {
+            id: Uuid::new_v4(),
+            signature: signature.to_string(),
+            market_id: Uuid::new_v4(),
+            trader: "Trader1111111111111111111111111111111111111".to_string(),
+            amount_in: 1_000_000,
+            amount_out: 990_000,
+            token_in: "TokenIn".to_string(),
+            token_out: "TokenOut".to_string(),
+            sqrt_price_before: Decimal::ZERO,
+            // Q64 sqrt price of 2, i.e. a price of 4
+            sqrt_price_after: Decimal::from_u128(1u128 << 65).unwrap(),
+            tick_before: 0,
+            tick_after: 0,
+            liquidity: Decimal::ZERO,
+            fee_amount: 3_000,
+            timestamp: Utc.timestamp_opt(secs, 0).unwrap(),
+            slot: 1,
+            block_height: None,
+            price_impact_bps: None,
+            effective_price,
+        }
+    }
+
+    async fn export(swaps: Vec<anyhow::Result<Swap>>) -> Vec<anyhow::Result<Bytes>> {
+        csv_chunks(stream::iter(swaps)).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_csv_has_header_and_rows() {
+        let chunks = export(vec![
+            Ok(swap("sig1", 1_700_000_000, Some(Decimal::new(99, 2)))),
+            Ok(swap("sig2", 1_700_000_060, None)),
+        ])
+        .await;
+
+        let csv: String = chunks
+            .into_iter()
+            .map(|chunk| String::from_utf8(chunk.unwrap().to_vec()).unwrap())
+            .collect();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines, vec![
+            "signature,timestamp,trader,amount_in,amount_out,price,fee",
+            "sig1,2023-11-14T22:13:20Z,Trader1111111111111111111111111111111111111,\
+             1000000,990000,0.99,3000",
+            "sig2,2023-11-14T22:14:20Z,Trader1111111111111111111111111111111111111,\
+             1000000,990000,4,3000",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_query_error_ends_the_export() {
+        let chunks = export(vec![
+            Ok(swap("sig1", 1_700_000_000, None)),
+            Err(anyhow::anyhow!("connection reset")),
+        ])
+        .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1].is_ok());
+        assert!(chunks[2].is_err());
+    }
+
+    #[test]
+    fn test_fields_with_delimiters_are_quoted() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
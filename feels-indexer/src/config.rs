@@ -16,6 +16,9 @@ pub struct IndexerConfig {
     pub api: ApiConfig,
     pub monitoring: MonitoringConfig,
     pub indexer: IndexerSettings,
+    /// Outbound swap webhook; disabled when absent
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -28,6 +31,16 @@ pub struct GeyserConfig {
     pub max_reconnect_attempts: u32,
     #[validate(range(min = 1, max = 300))]
     pub reconnect_delay_secs: u64,
+    /// Account types to stream, by name (e.g. "Market"); empty indexes all
+    #[serde(default)]
+    pub indexed_account_types: Vec<String>,
+    /// This instance's shard, in `0..shard_count`
+    #[serde(default)]
+    pub shard_index: u16,
+    /// Indexer instances splitting the program's accounts by consistent hash
+    #[serde(default = "default_shard_count")]
+    #[validate(range(min = 1))]
+    pub shard_count: u16,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -95,6 +108,18 @@ pub struct ApiConfig {
     pub request_timeout_secs: u64,
     #[validate(range(min = 1, max = 100))]
     pub max_request_size_mb: usize,
+    /// Largest page any list endpoint returns; larger `limit`s are clamped
+    #[serde(default = "default_max_page_size")]
+    #[validate(range(min = 1, max = 10000))]
+    pub max_page_size: usize,
+    /// Origins allowed to make cross-origin requests; empty allows any
+    /// origin, which is only meant for local development
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// Bearer tokens accepted by the price alert endpoints; empty disables
+    /// alert registration
+    #[serde(default)]
+    pub alert_api_keys: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -119,6 +144,125 @@ pub struct IndexerSettings {
     pub enable_backfill: bool,
     #[validate(range(min = 1000, max = 50000))]
     pub backfill_batch_size: usize,
+    /// Swaps buffered before a batched Postgres insert
+    #[serde(default = "default_swap_batch_size")]
+    #[validate(range(min = 1, max = 10000))]
+    pub swap_batch_size: usize,
+    /// Maximum time a buffered swap waits before being flushed
+    #[serde(default = "default_swap_flush_interval_ms")]
+    #[validate(range(min = 10, max = 60000))]
+    pub swap_flush_interval_ms: u64,
+    /// Swaps kept buffered while Postgres writes fail; the oldest are dropped beyond it
+    #[serde(default = "default_swap_max_pending")]
+    #[validate(range(min = 100, max = 1000000))]
+    pub swap_max_pending: usize,
+    /// Failed inserts a buffered swap survives before it is dropped
+    #[serde(default = "default_swap_max_write_attempts")]
+    #[validate(range(min = 1, max = 100))]
+    pub swap_max_write_attempts: u32,
+    /// Age after which market snapshots are rolled up into hourly rows
+    #[serde(default = "default_snapshot_raw_retention_hours")]
+    #[validate(range(min = 1, max = 8760))]
+    pub snapshot_raw_retention_hours: u64,
+    /// Age after which hourly rows are deleted; unset keeps them forever
+    #[serde(default)]
+    #[validate(range(min = 1, max = 3650))]
+    pub snapshot_hourly_retention_days: Option<u64>,
+    /// Time between snapshot compaction runs
+    #[serde(default = "default_snapshot_compaction_interval_secs")]
+    #[validate(range(min = 60, max = 86400))]
+    pub snapshot_compaction_interval_secs: u64,
+    /// FeelsSOL mint, the quote side of every market; unset disables valuation
+    #[serde(default)]
+    pub feelssol_mint: Option<String>,
+    /// SOL per JitoSOL, the stake pool rate converting the protocol oracle's
+    /// JitoSOL backing into SOL; unset disables valuation
+    #[serde(default)]
+    #[validate(range(min = 0.0))]
+    pub sol_per_jitosol: Option<f64>,
+    /// Reference SOL price in USD used to value portfolios
+    #[serde(default)]
+    #[validate(range(min = 0.0))]
+    pub sol_usd_price: Option<f64>,
+}
+
+/// Outbound webhook for indexed swaps and fired price alerts
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct WebhookConfig {
+    #[validate(url)]
+    pub url: String,
+    /// Only forward swaps in these markets (indexer market ids); empty forwards all.
+    /// Price alerts are always forwarded.
+    #[serde(default)]
+    pub markets: Vec<uuid::Uuid>,
+    /// Only forward swaps with at least this input amount
+    #[serde(default)]
+    pub min_amount_in: i64,
+    /// Delivery attempts before a payload is dead-lettered
+    #[serde(default = "default_webhook_max_attempts")]
+    #[validate(range(min = 1, max = 20))]
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt, doubling on each further failure
+    #[serde(default = "default_webhook_initial_backoff_ms")]
+    #[validate(range(min = 10, max = 60000))]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_webhook_max_backoff_ms")]
+    #[validate(range(min = 10, max = 600000))]
+    pub max_backoff_ms: u64,
+    #[serde(default = "default_webhook_timeout_secs")]
+    #[validate(range(min = 1, max = 60))]
+    pub timeout_secs: u64,
+    /// JSON-lines file receiving payloads that ran out of attempts
+    #[serde(default)]
+    pub dead_letter_path: Option<PathBuf>,
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    5
+}
+
+fn default_webhook_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_webhook_max_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+fn default_shard_count() -> u16 {
+    1
+}
+
+fn default_max_page_size() -> usize {
+    200
+}
+
+fn default_swap_batch_size() -> usize {
+    100
+}
+
+fn default_swap_flush_interval_ms() -> u64 {
+    250
+}
+
+fn default_swap_max_pending() -> usize {
+    50_000
+}
+
+fn default_swap_max_write_attempts() -> u32 {
+    10
+}
+
+fn default_snapshot_raw_retention_hours() -> u64 {
+    7 * 24
+}
+
+fn default_snapshot_compaction_interval_secs() -> u64 {
+    3600
 }
 
 
@@ -130,6 +274,9 @@ impl Default for GeyserConfig {
             commitment: "confirmed".to_string(),
             max_reconnect_attempts: 10,
             reconnect_delay_secs: 5,
+            indexed_account_types: Vec::new(),
+            shard_index: 0,
+            shard_count: default_shard_count(),
         }
     }
 }
@@ -197,6 +344,9 @@ impl Default for ApiConfig {
             enable_cors: true,
             request_timeout_secs: 30,
             max_request_size_mb: 10,
+            max_page_size: default_max_page_size(),
+            allowed_origins: Vec::new(),
+            alert_api_keys: Vec::new(),
         }
     }
 }
@@ -221,6 +371,16 @@ impl Default for IndexerSettings {
             max_lag_slots: 100,
             enable_backfill: true,
             backfill_batch_size: 5000,
+            swap_batch_size: default_swap_batch_size(),
+            swap_flush_interval_ms: default_swap_flush_interval_ms(),
+            swap_max_pending: default_swap_max_pending(),
+            swap_max_write_attempts: default_swap_max_write_attempts(),
+            snapshot_raw_retention_hours: default_snapshot_raw_retention_hours(),
+            snapshot_hourly_retention_days: None,
+            snapshot_compaction_interval_secs: default_snapshot_compaction_interval_secs(),
+            feelssol_mint: None,
+            sol_per_jitosol: None,
+            sol_usd_price: None,
         }
     }
 }
@@ -253,6 +413,18 @@ impl IndexerConfig {
         if self.geyser.program_id.is_empty() {
             return Err(anyhow::anyhow!("Program ID cannot be empty"));
         }
+        for name in &self.geyser.indexed_account_types {
+            if !crate::geyser::ACCOUNT_TYPES.contains(&name.as_str()) {
+                return Err(anyhow::anyhow!("Unknown indexed account type: {}", name));
+            }
+        }
+        if self.geyser.shard_count == 0 || self.geyser.shard_index >= self.geyser.shard_count {
+            return Err(anyhow::anyhow!(
+                "Shard index {} is outside 0..{}",
+                self.geyser.shard_index,
+                self.geyser.shard_count
+            ));
+        }
         Ok(())
     }
 }
\ No newline at end of file
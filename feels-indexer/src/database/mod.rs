@@ -17,11 +17,14 @@ pub mod postgres_operations_runtime;
 
 #[cfg(feature = "compile-time-sqlx")]
 pub use postgres_operations::ProtocolStats24h;
+pub mod numeric;
+mod postgres_snapshots;
 pub mod redis;
 pub mod redis_operations;
 pub mod rocksdb;
 pub mod rocksdb_operations;
 pub mod tantivy;
+pub mod versioned;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -29,6 +32,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::sync::Arc;
 
+pub use numeric::U128Numeric;
+pub use versioned::{SchemaError, StoredModel};
+
+/// Most bind parameters Postgres accepts in one statement
+pub(crate) const MAX_BIND_PARAMS: usize = u16::MAX as usize;
+
 /// Database connection manager
 pub struct DatabaseManager {
     pub postgres: Arc<postgres_impl::PostgresManager>,
@@ -80,6 +89,16 @@ impl DatabaseManager {
             overall: postgres_healthy && redis_healthy && rocksdb_healthy && tantivy_healthy,
         })
     }
+
+    /// Make every buffered write durable; call before shutdown
+    ///
+    /// Commits pending search documents and flushes RocksDB memtables.
+    /// Postgres writes on the live path are not buffered here; batching
+    /// repositories flush through `RepositoryManager::flush`.
+    pub async fn flush(&self) -> Result<()> {
+        self.tantivy.commit().await?;
+        self.rocksdb.flush()
+    }
     
     #[cfg(test)]
     pub async fn new_rocksdb_only(config: crate::config::RocksDBConfig) -> Result<Self> {
@@ -111,6 +130,30 @@ pub struct DatabaseHealth {
     pub overall: bool,
 }
 
+impl DatabaseHealth {
+    /// Overall service status
+    ///
+    /// Without Postgres the API can still answer from Redis or RocksDB, so
+    /// that counts as degraded rather than down.
+    pub fn status(&self) -> HealthStatus {
+        if self.overall {
+            HealthStatus::Healthy
+        } else if self.postgres || self.redis || self.rocksdb {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Unhealthy
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
 /// Common database operations trait
 #[async_trait]
 pub trait DatabaseOperations {
@@ -133,8 +176,8 @@ pub struct Market {
     pub phase: String,
     pub global_lower_tick: i32,
     pub global_upper_tick: i32,
-    pub fee_growth_global_0: rust_decimal::Decimal,
-    pub fee_growth_global_1: rust_decimal::Decimal,
+    pub fee_growth_global_0: U128Numeric,
+    pub fee_growth_global_1: U128Numeric,
     pub total_volume_0: rust_decimal::Decimal,
     pub total_volume_1: rust_decimal::Decimal,
     pub total_fees_0: rust_decimal::Decimal,
@@ -155,10 +198,12 @@ pub struct Position {
     pub liquidity: rust_decimal::Decimal,
     pub tick_lower: i32,
     pub tick_upper: i32,
-    pub fee_growth_inside_0_last: rust_decimal::Decimal,
-    pub fee_growth_inside_1_last: rust_decimal::Decimal,
+    pub fee_growth_inside_0_last: U128Numeric,
+    pub fee_growth_inside_1_last: U128Numeric,
     pub tokens_owed_0: i64,
     pub tokens_owed_1: i64,
+    /// USD value when the position was first indexed
+    pub entry_value: Option<rust_decimal::Decimal>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub last_updated_slot: i64,
@@ -206,3 +251,50 @@ pub struct MarketSnapshot {
     pub tvl_usd: Option<rust_decimal::Decimal>,
 }
 
+/// Side of its threshold a price alert fires on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+/// Client-registered alert on a market's price
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriceAlert {
+    pub id: Uuid,
+    pub market_id: Uuid,
+    pub direction: AlertDirection,
+    /// Price of token 0 in token 1, in base units
+    pub threshold: rust_decimal::Decimal,
+    /// Cleared when the alert fires and set again once the price moves back
+    /// across the threshold, so each crossing fires once
+    pub armed: bool,
+    pub last_triggered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One market's snapshots rolled up over an hour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HourlySnapshot {
+    pub market_id: Uuid,
+    pub bucket_start: chrono::DateTime<chrono::Utc>,
+    pub open_sqrt_price: rust_decimal::Decimal,
+    pub high_sqrt_price: rust_decimal::Decimal,
+    pub low_sqrt_price: rust_decimal::Decimal,
+    pub close_sqrt_price: rust_decimal::Decimal,
+    pub close_tick: i32,
+    pub close_liquidity: rust_decimal::Decimal,
+    pub volume_0: rust_decimal::Decimal,
+    pub volume_1: rust_decimal::Decimal,
+    pub fees_0: rust_decimal::Decimal,
+    pub fees_1: rust_decimal::Decimal,
+    pub swap_count: i32,
+    pub tvl_token_0: rust_decimal::Decimal,
+    pub tvl_token_1: rust_decimal::Decimal,
+    pub first_slot: i64,
+    pub last_slot: i64,
+    pub sample_count: i32,
+}
+
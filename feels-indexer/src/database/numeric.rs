@@ -0,0 +1,147 @@
+//! Lossless storage of on-chain u128 values
+//!
+//! `rust_decimal::Decimal` has a 96-bit mantissa, so Q64 fee-growth values
+//! above roughly 7.9e28 cannot be represented and converting them panics or
+//! silently yields zero. `U128Numeric` keeps the exact integer, binds to
+//! Postgres NUMERIC through `BigDecimal`, and serializes as a decimal string
+//! so JSON consumers do not round it to a float.
+
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::types::BigDecimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// Unsigned 128-bit integer stored as NUMERIC without precision loss
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(into = "String", try_from = "String")]
+pub struct U128Numeric(pub u128);
+
+impl U128Numeric {
+    pub const ZERO: Self = Self(0);
+
+    pub fn value(self) -> u128 {
+        self.0
+    }
+
+    /// Exact NUMERIC representation for binding
+    pub fn to_big_decimal(self) -> BigDecimal {
+        BigDecimal::from_str(&self.0.to_string()).expect("integer digits always parse")
+    }
+
+    /// Convert a NUMERIC value, rejecting fractions, negatives and overflow
+    pub fn from_big_decimal(value: &BigDecimal) -> Result<Self, String> {
+        if !value.is_integer() {
+            return Err(format!("{} is not an integer", value));
+        }
+        value.with_scale(0).to_string().parse()
+    }
+}
+
+impl From<u128> for U128Numeric {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<U128Numeric> for u128 {
+    fn from(value: U128Numeric) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for U128Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for U128Numeric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u128>()
+            .map(Self)
+            .map_err(|e| format!("invalid u128 '{}': {}", s, e))
+    }
+}
+
+impl From<U128Numeric> for String {
+    fn from(value: U128Numeric) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl TryFrom<String> for U128Numeric {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl sqlx::Type<Postgres> for U128Numeric {
+    fn type_info() -> PgTypeInfo {
+        <BigDecimal as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <BigDecimal as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl sqlx::Encode<'_, Postgres> for U128Numeric {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> sqlx::encode::IsNull {
+        <BigDecimal as sqlx::Encode<Postgres>>::encode_by_ref(&self.to_big_decimal(), buf)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for U128Numeric {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let decimal = <BigDecimal as sqlx::Decode<Postgres>>::decode(value)?;
+        Ok(Self::from_big_decimal(&decimal)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_at_u128_max() {
+        let value = U128Numeric(u128::MAX);
+
+        let decimal = value.to_big_decimal();
+        assert_eq!(decimal.to_string(), "340282366920938463463374607431768211455");
+        assert_eq!(U128Numeric::from_big_decimal(&decimal).unwrap(), value);
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"340282366920938463463374607431768211455\"");
+        assert_eq!(serde_json::from_str::<U128Numeric>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_values_beyond_decimal_range_keep_precision() {
+        // One past rust_decimal's 96-bit mantissa
+        let value = U128Numeric((1u128 << 96) + 1);
+        assert!(rust_decimal::Decimal::from_str(&value.to_string()).is_err());
+        assert_eq!(
+            U128Numeric::from_big_decimal(&value.to_big_decimal()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_rejects_values_outside_u128() {
+        let too_big = BigDecimal::from_str("340282366920938463463374607431768211456").unwrap();
+        assert!(U128Numeric::from_big_decimal(&too_big).is_err());
+        assert!(U128Numeric::from_big_decimal(&BigDecimal::from(-1)).is_err());
+        assert!(U128Numeric::from_big_decimal(&BigDecimal::from_str("1.5").unwrap()).is_err());
+
+        // Integral values with a scale still convert
+        let scaled = BigDecimal::from_str("42.000").unwrap();
+        assert_eq!(U128Numeric::from_big_decimal(&scaled).unwrap(), U128Numeric(42));
+    }
+}
@@ -1,14 +1,17 @@
 //! PostgreSQL database manager
 
-use super::{DatabaseOperations, Market, Position, Swap, MarketSnapshot};
+use super::{DatabaseOperations, Market, Position, Swap, MarketSnapshot, MAX_BIND_PARAMS};
 use anyhow::Result;
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Columns bound per row by `insert_swaps`
+const SWAP_INSERT_COLUMNS: usize = 18;
+
 pub struct PostgresManager {
-    pool: PgPool,
+    pub(crate) pool: PgPool,
 }
 
 impl PostgresManager {
@@ -64,8 +67,8 @@ impl PostgresManager {
             market.phase,
             market.global_lower_tick,
             market.global_upper_tick,
-            market.fee_growth_global_0,
-            market.fee_growth_global_1,
+            market.fee_growth_global_0 as _,
+            market.fee_growth_global_1 as _,
             market.total_volume_0,
             market.total_volume_1,
             market.total_fees_0,
@@ -179,6 +182,54 @@ impl PostgresManager {
         Ok(())
     }
 
+    /// Insert many swaps with multi-row statements in one transaction
+    ///
+    /// Rows are split across statements so none exceeds Postgres's bind
+    /// parameter limit.
+    pub async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()> {
+        if swaps.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for chunk in swaps.chunks(MAX_BIND_PARAMS / SWAP_INSERT_COLUMNS) {
+            let mut builder = sqlx::QueryBuilder::new(
+                r#"
+                INSERT INTO swaps (
+                    signature, market_id, trader, amount_in, amount_out,
+                    token_in, token_out, sqrt_price_before, sqrt_price_after,
+                    tick_before, tick_after, liquidity, fee_amount,
+                    timestamp, slot, block_height, price_impact_bps, effective_price
+                ) "#,
+            );
+            builder.push_values(chunk, |mut row, swap| {
+                row.push_bind(&swap.signature)
+                    .push_bind(swap.market_id)
+                    .push_bind(&swap.trader)
+                    .push_bind(swap.amount_in)
+                    .push_bind(swap.amount_out)
+                    .push_bind(&swap.token_in)
+                    .push_bind(&swap.token_out)
+                    .push_bind(swap.sqrt_price_before)
+                    .push_bind(swap.sqrt_price_after)
+                    .push_bind(swap.tick_before)
+                    .push_bind(swap.tick_after)
+                    .push_bind(swap.liquidity)
+                    .push_bind(swap.fee_amount)
+                    .push_bind(swap.timestamp)
+                    .push_bind(swap.slot)
+                    .push_bind(swap.block_height)
+                    .push_bind(swap.price_impact_bps)
+                    .push_bind(swap.effective_price);
+            });
+            builder.push(" ON CONFLICT (signature) DO NOTHING");
+            builder.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     /// Get swaps for a market
     pub async fn get_market_swaps(
         &self,
@@ -226,9 +277,9 @@ impl PostgresManager {
             INSERT INTO positions (
                 address, market_id, owner, liquidity, tick_lower, tick_upper,
                 fee_growth_inside_0_last, fee_growth_inside_1_last,
-                tokens_owed_0, tokens_owed_1, last_updated_slot
+                tokens_owed_0, tokens_owed_1, entry_value, last_updated_slot
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
             )
             ON CONFLICT (address) DO UPDATE SET
                 liquidity = EXCLUDED.liquidity,
@@ -236,6 +287,7 @@ impl PostgresManager {
                 fee_growth_inside_1_last = EXCLUDED.fee_growth_inside_1_last,
                 tokens_owed_0 = EXCLUDED.tokens_owed_0,
                 tokens_owed_1 = EXCLUDED.tokens_owed_1,
+                entry_value = COALESCE(positions.entry_value, EXCLUDED.entry_value),
                 last_updated_slot = EXCLUDED.last_updated_slot,
                 updated_at = NOW()
             "#,
@@ -245,10 +297,11 @@ impl PostgresManager {
             position.liquidity,
             position.tick_lower,
             position.tick_upper,
-            position.fee_growth_inside_0_last,
-            position.fee_growth_inside_1_last,
+            position.fee_growth_inside_0_last as _,
+            position.fee_growth_inside_1_last as _,
             position.tokens_owed_0,
             position.tokens_owed_1,
+            position.entry_value,
             position.last_updated_slot
         )
         .execute(&self.pool)
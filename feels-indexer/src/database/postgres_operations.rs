@@ -1,8 +1,9 @@
 //! PostgreSQL operations for the indexer
 
 use super::postgres::PostgresManager;
-use super::{Market, Position, Swap};
+use super::{AlertDirection, Market, Position, PriceAlert, Swap};
 use anyhow::Result;
+use futures::stream::{BoxStream, StreamExt};
 use sqlx::{query, query_as};
 use uuid::Uuid;
 
@@ -48,8 +49,8 @@ impl PostgresManager {
             market.phase,
             market.global_lower_tick,
             market.global_upper_tick,
-            market.fee_growth_global_0,
-            market.fee_growth_global_1,
+            market.fee_growth_global_0 as _,
+            market.fee_growth_global_1 as _,
             market.total_volume_0,
             market.total_volume_1,
             market.total_fees_0,
@@ -73,9 +74,9 @@ impl PostgresManager {
             INSERT INTO positions (
                 id, address, market_id, owner, liquidity, tick_lower, tick_upper,
                 fee_growth_inside_0_last, fee_growth_inside_1_last, tokens_owed_0,
-                tokens_owed_1, created_at, updated_at, last_updated_slot
+                tokens_owed_1, entry_value, created_at, updated_at, last_updated_slot
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15
             )
             ON CONFLICT (address) DO UPDATE SET
                 liquidity = EXCLUDED.liquidity,
@@ -83,6 +84,7 @@ impl PostgresManager {
                 fee_growth_inside_1_last = EXCLUDED.fee_growth_inside_1_last,
                 tokens_owed_0 = EXCLUDED.tokens_owed_0,
                 tokens_owed_1 = EXCLUDED.tokens_owed_1,
+                entry_value = COALESCE(positions.entry_value, EXCLUDED.entry_value),
                 updated_at = EXCLUDED.updated_at,
                 last_updated_slot = EXCLUDED.last_updated_slot
             "#,
@@ -93,10 +95,11 @@ impl PostgresManager {
             position.liquidity,
             position.tick_lower,
             position.tick_upper,
-            position.fee_growth_inside_0_last,
-            position.fee_growth_inside_1_last,
+            position.fee_growth_inside_0_last as _,
+            position.fee_growth_inside_1_last as _,
             position.tokens_owed_0,
             position.tokens_owed_1,
+            position.entry_value,
             position.created_at,
             position.updated_at,
             position.last_updated_slot,
@@ -264,6 +267,67 @@ impl PostgresManager {
         Ok(swaps)
     }
     
+    /// Stream a market's swaps in `[from, to)` oldest first, without loading
+    /// the whole range into memory
+    pub fn stream_swaps_by_market_id(
+        &self,
+        market_id: Uuid,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> BoxStream<'_, Result<Swap>> {
+        query_as!(
+            Swap,
+            r#"
+            SELECT * FROM swaps
+            WHERE market_id = $1 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC, slot ASC
+            "#,
+            market_id,
+            from,
+            to
+        )
+        .fetch(&self.pool)
+        .map(|row| row.map_err(Into::into))
+        .boxed()
+    }
+    
+    /// Count a market's swaps in `[from, to)` per price-impact bucket
+    ///
+    /// Buckets follow Postgres `width_bucket` over `[0, bucket_bps * buckets)`:
+    /// 1..=buckets are the equal-width buckets, 0 holds negative impacts and
+    /// `buckets + 1` everything at or above the top. Swaps without a recorded
+    /// impact are skipped; empty buckets are omitted.
+    pub async fn get_price_impact_buckets(
+        &self,
+        market_id: Uuid,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        bucket_bps: i32,
+        buckets: i32,
+    ) -> Result<Vec<(i32, i64)>> {
+        let rows = query!(
+            r#"
+            SELECT
+                width_bucket(price_impact_bps::float8, 0, ($4 * $5)::float8, $5) as "bucket!",
+                COUNT(*) as "swaps!"
+            FROM swaps
+            WHERE market_id = $1 AND timestamp >= $2 AND timestamp < $3
+                AND price_impact_bps IS NOT NULL
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            market_id,
+            from,
+            to,
+            bucket_bps,
+            buckets
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.bucket, row.swaps)).collect())
+    }
+    
     /// Get swaps count by market ID
     pub async fn get_swaps_count_by_market_id(&self, market_id: Uuid) -> Result<i64> {
         let count = query!(
@@ -605,6 +669,84 @@ impl PostgresManager {
         
         Ok(snapshots)
     }
+
+    /// Insert a price alert
+    pub async fn insert_price_alert(&self, alert: &PriceAlert) -> Result<()> {
+        query!(
+            r#"
+            INSERT INTO price_alerts (
+                id, market_id, direction, threshold, armed, last_triggered_at, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            alert.id,
+            alert.market_id,
+            alert.direction as AlertDirection,
+            alert.threshold,
+            alert.armed,
+            alert.last_triggered_at,
+            alert.created_at
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Get all price alerts on a market
+    pub async fn get_price_alerts_by_market(&self, market_id: Uuid) -> Result<Vec<PriceAlert>> {
+        let alerts = query_as!(
+            PriceAlert,
+            r#"
+            SELECT id, market_id, direction as "direction: AlertDirection", threshold,
+                   armed, last_triggered_at, created_at
+            FROM price_alerts
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(alerts)
+    }
+    
+    /// Delete a price alert, returning whether it existed
+    pub async fn delete_price_alert(&self, id: Uuid) -> Result<bool> {
+        let result = query!("DELETE FROM price_alerts WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// Disarm an armed alert that has fired
+    ///
+    /// Returns false if the alert was already disarmed or deleted, so
+    /// concurrent evaluations notify at most once.
+    pub async fn disarm_price_alert(
+        &self,
+        id: Uuid,
+        triggered_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool> {
+        let result = query!(
+            "UPDATE price_alerts SET armed = FALSE, last_triggered_at = $2 WHERE id = $1 AND armed",
+            id,
+            triggered_at
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// Re-arm a disarmed alert
+    pub async fn rearm_price_alert(&self, id: Uuid) -> Result<()> {
+        query!("UPDATE price_alerts SET armed = TRUE WHERE id = $1 AND NOT armed", id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
 }
 
 /// Struct for protocol stats
@@ -1,11 +1,15 @@
 //! Runtime PostgreSQL operations
 
-use super::{Market, Position, Swap};
+use super::{Market, Position, PriceAlert, Swap, MAX_BIND_PARAMS};
 use super::postgres_runtime::PostgresManager;
 use anyhow::Result;
+use futures::stream::{BoxStream, StreamExt};
 use sqlx::Row;
 use uuid::Uuid;
 
+/// Columns bound per row by `insert_swaps`
+const SWAP_INSERT_COLUMNS: usize = 19;
+
 impl PostgresManager {
     /// Insert a market
     pub async fn insert_market(&self, market: &Market) -> Result<()> {
@@ -59,9 +63,10 @@ impl PostgresManager {
             INSERT INTO positions (
                 id, address, market_id, owner, liquidity, tick_lower, tick_upper,
                 fee_growth_inside_0_last, fee_growth_inside_1_last,
-                tokens_owed_0, tokens_owed_1, created_at, updated_at, last_updated_slot
+                tokens_owed_0, tokens_owed_1, entry_value, created_at, updated_at,
+                last_updated_slot
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15
             )
         "#;
         
@@ -77,6 +82,7 @@ impl PostgresManager {
             .bind(position.fee_growth_inside_1_last)
             .bind(position.tokens_owed_0)
             .bind(position.tokens_owed_1)
+            .bind(position.entry_value)
             .bind(position.created_at)
             .bind(position.updated_at)
             .bind(position.last_updated_slot)
@@ -126,6 +132,55 @@ impl PostgresManager {
         Ok(())
     }
 
+    /// Insert many swaps with multi-row statements in one transaction
+    ///
+    /// Rows are split across statements so none exceeds Postgres's bind
+    /// parameter limit.
+    pub async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()> {
+        if swaps.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for chunk in swaps.chunks(MAX_BIND_PARAMS / SWAP_INSERT_COLUMNS) {
+            let mut builder = sqlx::QueryBuilder::new(
+                r#"
+                INSERT INTO swaps (
+                    id, signature, market_id, trader, amount_in, amount_out,
+                    token_in, token_out, sqrt_price_before, sqrt_price_after,
+                    tick_before, tick_after, liquidity, fee_amount, timestamp,
+                    slot, block_height, price_impact_bps, effective_price
+                ) "#,
+            );
+            builder.push_values(chunk, |mut row, swap| {
+                row.push_bind(swap.id)
+                    .push_bind(&swap.signature)
+                    .push_bind(swap.market_id)
+                    .push_bind(&swap.trader)
+                    .push_bind(swap.amount_in)
+                    .push_bind(swap.amount_out)
+                    .push_bind(&swap.token_in)
+                    .push_bind(&swap.token_out)
+                    .push_bind(swap.sqrt_price_before)
+                    .push_bind(swap.sqrt_price_after)
+                    .push_bind(swap.tick_before)
+                    .push_bind(swap.tick_after)
+                    .push_bind(swap.liquidity)
+                    .push_bind(swap.fee_amount)
+                    .push_bind(swap.timestamp)
+                    .push_bind(swap.slot)
+                    .push_bind(swap.block_height)
+                    .push_bind(swap.price_impact_bps)
+                    .push_bind(swap.effective_price);
+            });
+            builder.push(" ON CONFLICT (signature) DO NOTHING");
+            builder.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     /// Get markets paginated
     pub async fn get_markets_paginated(&self, limit: i64, offset: i64) -> Result<Vec<Market>> {
         let query = "SELECT * FROM markets ORDER BY created_at DESC LIMIT $1 OFFSET $2";
@@ -317,6 +372,63 @@ impl PostgresManager {
         }).collect())
     }
     
+    /// Stream a market's swaps in `[from, to)` oldest first, without loading
+    /// the whole range into memory
+    pub fn stream_swaps_by_market_id(
+        &self,
+        market_id: Uuid,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> BoxStream<'_, Result<Swap>> {
+        sqlx::query_as::<_, Swap>(
+            "SELECT * FROM swaps WHERE market_id = $1 AND timestamp >= $2 AND timestamp < $3 \
+             ORDER BY timestamp ASC, slot ASC"
+        )
+        .bind(market_id)
+        .bind(from)
+        .bind(to)
+        .fetch(&self.pool)
+        .map(|row| row.map_err(Into::into))
+        .boxed()
+    }
+    
+    /// Count a market's swaps in `[from, to)` per price-impact bucket
+    ///
+    /// Buckets follow Postgres `width_bucket` over `[0, bucket_bps * buckets)`:
+    /// 1..=buckets are the equal-width buckets, 0 holds negative impacts and
+    /// `buckets + 1` everything at or above the top. Swaps without a recorded
+    /// impact are skipped; empty buckets are omitted.
+    pub async fn get_price_impact_buckets(
+        &self,
+        market_id: Uuid,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        bucket_bps: i32,
+        buckets: i32,
+    ) -> Result<Vec<(i32, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                width_bucket(price_impact_bps::float8, 0, ($4 * $5)::float8, $5) as bucket,
+                COUNT(*) as swaps
+            FROM swaps
+            WHERE market_id = $1 AND timestamp >= $2 AND timestamp < $3
+                AND price_impact_bps IS NOT NULL
+            GROUP BY bucket
+            ORDER BY bucket
+            "#
+        )
+        .bind(market_id)
+        .bind(from)
+        .bind(to)
+        .bind(bucket_bps)
+        .bind(buckets)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| (row.get("bucket"), row.get("swaps"))).collect())
+    }
+    
     /// Get swaps count by market ID
     pub async fn get_swaps_count_by_market_id(&self, market_id: Uuid) -> Result<i64> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM swaps WHERE market_id = $1")
@@ -349,6 +461,7 @@ impl PostgresManager {
             fee_growth_inside_1_last: row.get("fee_growth_inside_1_last"),
             tokens_owed_0: row.get("tokens_owed_0"),
             tokens_owed_1: row.get("tokens_owed_1"),
+            entry_value: row.get("entry_value"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             last_updated_slot: row.get("last_updated_slot"),
@@ -386,6 +499,7 @@ impl PostgresManager {
                 fee_growth_inside_1_last: row.get("fee_growth_inside_1_last"),
                 tokens_owed_0: row.get("tokens_owed_0"),
                 tokens_owed_1: row.get("tokens_owed_1"),
+                entry_value: row.get("entry_value"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 last_updated_slot: row.get("last_updated_slot"),
@@ -417,6 +531,7 @@ impl PostgresManager {
             fee_growth_inside_1_last: row.get("fee_growth_inside_1_last"),
             tokens_owed_0: row.get("tokens_owed_0"),
             tokens_owed_1: row.get("tokens_owed_1"),
+            entry_value: row.get("entry_value"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             last_updated_slot: row.get("last_updated_slot"),
@@ -433,6 +548,16 @@ impl PostgresManager {
         Ok(row.get("count"))
     }
     
+    /// Get market by ID
+    pub async fn get_market_by_id(&self, id: Uuid) -> Result<Option<Market>> {
+        let market = sqlx::query_as::<_, Market>("SELECT * FROM markets WHERE id = $1 LIMIT 1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+            
+        Ok(market)
+    }
+    
     /// Get market by address
     pub async fn get_market_by_address(&self, address: &str) -> Result<Option<Market>> {
         let query = "SELECT * FROM markets WHERE address = $1 LIMIT 1";
@@ -510,6 +635,80 @@ impl PostgresManager {
             active_traders_24h: stats.get::<i64, _>("active_traders_24h") as u64,
         })
     }
+
+    /// Insert a price alert
+    pub async fn insert_price_alert(&self, alert: &PriceAlert) -> Result<()> {
+        let query = r#"
+            INSERT INTO price_alerts (
+                id, market_id, direction, threshold, armed, last_triggered_at, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#;
+        
+        sqlx::query(query)
+            .bind(alert.id)
+            .bind(alert.market_id)
+            .bind(alert.direction)
+            .bind(alert.threshold)
+            .bind(alert.armed)
+            .bind(alert.last_triggered_at)
+            .bind(alert.created_at)
+            .execute(&self.pool)
+            .await?;
+            
+        Ok(())
+    }
+    
+    /// Get all price alerts on a market
+    pub async fn get_price_alerts_by_market(&self, market_id: Uuid) -> Result<Vec<PriceAlert>> {
+        let alerts = sqlx::query_as::<_, PriceAlert>(
+            "SELECT * FROM price_alerts WHERE market_id = $1"
+        )
+        .bind(market_id)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(alerts)
+    }
+    
+    /// Delete a price alert, returning whether it existed
+    pub async fn delete_price_alert(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM price_alerts WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+            
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// Disarm an armed alert that has fired
+    ///
+    /// Returns false if the alert was already disarmed or deleted, so
+    /// concurrent evaluations notify at most once.
+    pub async fn disarm_price_alert(
+        &self,
+        id: Uuid,
+        triggered_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE price_alerts SET armed = FALSE, last_triggered_at = $2 WHERE id = $1 AND armed"
+        )
+        .bind(id)
+        .bind(triggered_at)
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// Re-arm a disarmed alert
+    pub async fn rearm_price_alert(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE price_alerts SET armed = TRUE WHERE id = $1 AND NOT armed")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+            
+        Ok(())
+    }
 }
 
 /// Struct for protocol stats
@@ -286,9 +286,9 @@ impl PostgresManager {
             INSERT INTO positions (
                 address, market_id, owner, liquidity, tick_lower, tick_upper,
                 fee_growth_inside_0_last, fee_growth_inside_1_last,
-                tokens_owed_0, tokens_owed_1, last_updated_slot
+                tokens_owed_0, tokens_owed_1, entry_value, last_updated_slot
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
             )
             ON CONFLICT (address) DO UPDATE SET
                 liquidity = EXCLUDED.liquidity,
@@ -296,6 +296,7 @@ impl PostgresManager {
                 fee_growth_inside_1_last = EXCLUDED.fee_growth_inside_1_last,
                 tokens_owed_0 = EXCLUDED.tokens_owed_0,
                 tokens_owed_1 = EXCLUDED.tokens_owed_1,
+                entry_value = COALESCE(positions.entry_value, EXCLUDED.entry_value),
                 last_updated_slot = EXCLUDED.last_updated_slot,
                 updated_at = NOW()
         "#;
@@ -311,6 +312,7 @@ impl PostgresManager {
             .bind(position.fee_growth_inside_1_last)
             .bind(position.tokens_owed_0)
             .bind(position.tokens_owed_1)
+            .bind(position.entry_value)
             .bind(position.last_updated_slot)
             .execute(&self.pool)
             .await?;
@@ -339,6 +341,7 @@ impl PostgresManager {
             fee_growth_inside_1_last: row.get("fee_growth_inside_1_last"),
             tokens_owed_0: row.get("tokens_owed_0"),
             tokens_owed_1: row.get("tokens_owed_1"),
+            entry_value: row.get("entry_value"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             last_updated_slot: row.get("last_updated_slot"),
@@ -565,6 +568,7 @@ impl PostgresManager {
                 fee_growth_inside_1_last: row.get("fee_growth_inside_1_last"),
                 tokens_owed_0: row.get("tokens_owed_0"),
                 tokens_owed_1: row.get("tokens_owed_1"),
+                entry_value: row.get("entry_value"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 last_updated_slot: row.get("last_updated_slot"),
@@ -0,0 +1,132 @@
+//! Snapshot compaction queries
+//!
+//! Shared by the compile-time and runtime PostgreSQL managers, whichever
+//! feature selects as `postgres_impl`.
+
+use super::postgres_impl::PostgresManager;
+use super::{HourlySnapshot, MarketSnapshot};
+use anyhow::Result;
+use uuid::Uuid;
+
+impl PostgresManager {
+    /// Oldest market snapshots taken before `cutoff`, grouped by market
+    pub async fn snapshots_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<MarketSnapshot>> {
+        let snapshots = sqlx::query_as::<_, MarketSnapshot>(
+            r#"
+            SELECT * FROM market_snapshots
+            WHERE timestamp < $1
+            ORDER BY market_id, timestamp
+            LIMIT $2
+            "#,
+        )
+        .bind(cutoff)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+
+    /// Merge hourly aggregates and delete the snapshots they were built from
+    ///
+    /// Runs in one transaction so a failure cannot count a snapshot twice.
+    /// Rows for an hour that already exists are merged into it.
+    pub async fn roll_up_snapshots(
+        &self,
+        hourly: &[HourlySnapshot],
+        compacted: &[Uuid],
+    ) -> Result<()> {
+        if hourly.is_empty() && compacted.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        if !hourly.is_empty() {
+            let mut builder = sqlx::QueryBuilder::new(
+                r#"
+                INSERT INTO market_snapshots_hourly AS h (
+                    market_id, bucket_start, open_sqrt_price, high_sqrt_price,
+                    low_sqrt_price, close_sqrt_price, close_tick, close_liquidity,
+                    volume_0, volume_1, fees_0, fees_1, swap_count,
+                    tvl_token_0, tvl_token_1, first_slot, last_slot, sample_count
+                ) "#,
+            );
+            builder.push_values(hourly, |mut row, bar| {
+                row.push_bind(bar.market_id)
+                    .push_bind(bar.bucket_start)
+                    .push_bind(bar.open_sqrt_price)
+                    .push_bind(bar.high_sqrt_price)
+                    .push_bind(bar.low_sqrt_price)
+                    .push_bind(bar.close_sqrt_price)
+                    .push_bind(bar.close_tick)
+                    .push_bind(bar.close_liquidity)
+                    .push_bind(bar.volume_0)
+                    .push_bind(bar.volume_1)
+                    .push_bind(bar.fees_0)
+                    .push_bind(bar.fees_1)
+                    .push_bind(bar.swap_count)
+                    .push_bind(bar.tvl_token_0)
+                    .push_bind(bar.tvl_token_1)
+                    .push_bind(bar.first_slot)
+                    .push_bind(bar.last_slot)
+                    .push_bind(bar.sample_count);
+            });
+            builder.push(
+                r#"
+                ON CONFLICT (market_id, bucket_start) DO UPDATE SET
+                    open_sqrt_price = CASE WHEN EXCLUDED.first_slot < h.first_slot
+                        THEN EXCLUDED.open_sqrt_price ELSE h.open_sqrt_price END,
+                    high_sqrt_price = GREATEST(h.high_sqrt_price, EXCLUDED.high_sqrt_price),
+                    low_sqrt_price = LEAST(h.low_sqrt_price, EXCLUDED.low_sqrt_price),
+                    close_sqrt_price = CASE WHEN EXCLUDED.last_slot > h.last_slot
+                        THEN EXCLUDED.close_sqrt_price ELSE h.close_sqrt_price END,
+                    close_tick = CASE WHEN EXCLUDED.last_slot > h.last_slot
+                        THEN EXCLUDED.close_tick ELSE h.close_tick END,
+                    close_liquidity = CASE WHEN EXCLUDED.last_slot > h.last_slot
+                        THEN EXCLUDED.close_liquidity ELSE h.close_liquidity END,
+                    tvl_token_0 = CASE WHEN EXCLUDED.last_slot > h.last_slot
+                        THEN EXCLUDED.tvl_token_0 ELSE h.tvl_token_0 END,
+                    tvl_token_1 = CASE WHEN EXCLUDED.last_slot > h.last_slot
+                        THEN EXCLUDED.tvl_token_1 ELSE h.tvl_token_1 END,
+                    volume_0 = h.volume_0 + EXCLUDED.volume_0,
+                    volume_1 = h.volume_1 + EXCLUDED.volume_1,
+                    fees_0 = h.fees_0 + EXCLUDED.fees_0,
+                    fees_1 = h.fees_1 + EXCLUDED.fees_1,
+                    swap_count = h.swap_count + EXCLUDED.swap_count,
+                    first_slot = LEAST(h.first_slot, EXCLUDED.first_slot),
+                    last_slot = GREATEST(h.last_slot, EXCLUDED.last_slot),
+                    sample_count = h.sample_count + EXCLUDED.sample_count
+                "#,
+            );
+            builder.build().execute(&mut *tx).await?;
+        }
+
+        if !compacted.is_empty() {
+            sqlx::query("DELETE FROM market_snapshots WHERE id = ANY($1)")
+                .bind(compacted)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete hourly aggregates for hours starting before `cutoff`
+    pub async fn delete_hourly_snapshots_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM market_snapshots_hourly WHERE bucket_start < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
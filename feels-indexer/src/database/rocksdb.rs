@@ -2,7 +2,6 @@
 
 use anyhow::{anyhow, Result};
 use rocksdb::{ColumnFamilyDescriptor, DBWithThreadMode, MultiThreaded, Options, WriteBatch};
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::info;
@@ -13,6 +12,8 @@ use crate::models::buffer::IndexedBuffer as Buffer;
 use crate::models::floor::IndexedFloor as FloorLiquidity;
 use crate::models::market::IndexedMarket as Market;
 use crate::models::position::IndexedPosition as Position;
+use crate::sdk_types::ProtocolOracleData;
+use super::versioned::{self, StoredModel};
 use super::Swap;
 
 /// Type alias for the RocksDB instance
@@ -113,20 +114,10 @@ impl RocksDBManager {
             .ok_or_else(|| anyhow!("Column family '{}' not found", name))
     }
 
-    /// Serialize a value using bincode
-    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
-        bincode::serialize(value).map_err(|e| anyhow!("Serialization failed: {}", e))
-    }
-
-    /// Deserialize a value using bincode
-    fn deserialize<T: for<'de> Deserialize<'de>>(&self, data: &[u8]) -> Result<T> {
-        bincode::deserialize(data).map_err(|e| anyhow!("Deserialization failed: {}", e))
-    }
-
-    /// Put a serializable value into a column family
-    pub fn put<T: Serialize>(&self, cf_name: &str, key: &[u8], value: &T) -> Result<()> {
+    /// Put a value into a column family behind its schema version header
+    pub fn put<T: StoredModel>(&self, cf_name: &str, key: &[u8], value: &T) -> Result<()> {
         let cf = self.get_cf(cf_name)?;
-        let serialized = self.serialize(value)?;
+        let serialized = versioned::encode(value)?;
         self.db
             .put_cf(&cf, key, serialized)
             .map_err(|e| anyhow!("Failed to put value: {}", e))
@@ -140,14 +131,22 @@ impl RocksDBManager {
             .map_err(|e| anyhow!("Failed to put value: {}", e))
     }
 
-    /// Get and deserialize a value from a column family
-    pub fn get<T: for<'de> Deserialize<'de>>(&self, cf_name: &str, key: &[u8]) -> Result<Option<T>> {
+    /// Get and decode a value from a column family
+    ///
+    /// Fails with a `SchemaError` if the value was stored under a schema
+    /// version the type cannot read.
+    pub fn get<T: StoredModel>(&self, cf_name: &str, key: &[u8]) -> Result<Option<T>> {
+        self.get_raw(cf_name, key)?
+            .map(|data| versioned::decode(&data))
+            .transpose()
+    }
+
+    /// Get raw bytes from a column family
+    pub fn get_raw(&self, cf_name: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
         let cf = self.get_cf(cf_name)?;
-        match self.db.get_cf(&cf, key) {
-            Ok(Some(data)) => Ok(Some(self.deserialize(&data)?)),
-            Ok(None) => Ok(None),
-            Err(e) => Err(anyhow!("Failed to get value: {}", e)),
-        }
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| anyhow!("Failed to get value: {}", e))
     }
 
     /// Delete a key from a column family
@@ -276,10 +275,10 @@ pub struct RocksDBBatch {
 }
 
 impl RocksDBBatch {
-    /// Put a serializable value into the batch
-    pub fn put<T: Serialize>(&mut self, cf_name: &str, key: &[u8], value: &T) -> Result<()> {
+    /// Put a value into the batch behind its schema version header
+    pub fn put<T: StoredModel>(&mut self, cf_name: &str, key: &[u8], value: &T) -> Result<()> {
         let cf = self.manager.get_cf(cf_name)?;
-        let serialized = self.manager.serialize(value)?;
+        let serialized = versioned::encode(value)?;
         self.batch.put_cf(&cf, key, serialized);
         Ok(())
     }
@@ -324,6 +323,23 @@ impl RocksDBManager {
         self.get(ColumnFamilies::MARKETS, market_id.as_bytes())
     }
 
+    /// Get every stored market
+    pub fn get_markets(&self) -> Result<Vec<Market>> {
+        self.iter_cf(ColumnFamilies::MARKETS)?
+            .map(|(_, value)| versioned::decode(&value))
+            .collect()
+    }
+
+    /// Store the latest protocol oracle rates
+    pub fn put_protocol_oracle(&self, oracle: &ProtocolOracleData) -> Result<()> {
+        self.put(ColumnFamilies::METADATA, b"protocol_oracle", oracle)
+    }
+
+    /// Get the latest protocol oracle rates
+    pub fn get_protocol_oracle(&self) -> Result<Option<ProtocolOracleData>> {
+        self.get(ColumnFamilies::METADATA, b"protocol_oracle")
+    }
+
     /// Store a swap
     pub fn put_swap(&self, swap_id: &str, swap: &Swap) -> Result<()> {
         self.put(ColumnFamilies::SWAPS, swap_id.as_bytes(), swap)
@@ -415,18 +431,15 @@ impl RocksDBManager {
     /// Get raw transaction data
     pub async fn get_transaction_raw(&self, signature: &str, slot: u64) -> Result<Option<Vec<u8>>> {
         let key = format!("{}:{}", signature, slot);
-        let cf = self.get_cf(ColumnFamilies::TRANSACTIONS)?;
-        match self.db.get_cf(&cf, key.as_bytes()) {
-            Ok(Some(data)) => Ok(Some(data)),
-            Ok(None) => Ok(None),
-            Err(e) => Err(anyhow!("Failed to get transaction: {}", e)),
-        }
+        self.get_raw(ColumnFamilies::TRANSACTIONS, key.as_bytes())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::SchemaError;
+    use serde::{Deserialize, Serialize};
     use tempfile::TempDir;
     
 
@@ -466,6 +479,8 @@ mod tests {
             name: String,
         }
 
+        impl StoredModel for TestData {}
+
         let test_data = TestData {
             id: 123,
             name: "test".to_string(),
@@ -502,4 +517,87 @@ mod tests {
         assert_eq!(val2, Some("value2".to_string()));
         assert_eq!(val3, Some("value3".to_string()));
     }
+
+    /// Layout of a model before a simulated schema change
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ProfileV1 {
+        id: u64,
+        name: String,
+    }
+
+    impl StoredModel for ProfileV1 {}
+
+    /// The same model after gaining a field, with an upgrader from v1
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ProfileV2 {
+        id: u64,
+        name: String,
+        score: u32,
+    }
+
+    impl StoredModel for ProfileV2 {
+        const SCHEMA_VERSION: u8 = 2;
+
+        fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+            match version {
+                1 => {
+                    let v1: ProfileV1 = bincode::deserialize(data)?;
+                    Ok(Self { id: v1.id, name: v1.name, score: 0 })
+                }
+                _ => Err(anyhow!("no upgrader from v{}", version)),
+            }
+        }
+    }
+
+    /// The changed layout without an upgrader
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ProfileV2Strict {
+        id: u64,
+        name: String,
+        score: u32,
+    }
+
+    impl StoredModel for ProfileV2Strict {
+        const SCHEMA_VERSION: u8 = 2;
+    }
+
+    #[tokio::test]
+    async fn test_reads_v1_value_after_schema_change() {
+        let (config, _temp_dir) = create_test_config();
+        let manager = RocksDBManager::new(config).await.unwrap();
+
+        let v1 = ProfileV1 { id: 7, name: "seven".to_string() };
+        manager.put("metadata", b"profile", &v1).unwrap();
+
+        // A registered upgrader migrates the old layout
+        let upgraded: Option<ProfileV2> = manager.get("metadata", b"profile").unwrap();
+        assert_eq!(upgraded, Some(ProfileV2 { id: 7, name: "seven".to_string(), score: 0 }));
+
+        // Without one the read fails with a typed error instead of misdecoding
+        let err = manager.get::<ProfileV2Strict>("metadata", b"profile").unwrap_err();
+        match err.downcast_ref::<SchemaError>() {
+            Some(SchemaError::VersionMismatch { stored: 1, expected: 2, .. }) => {}
+            other => panic!("expected a version mismatch, got {:?}", other),
+        }
+
+        // Values written without a header are rejected too
+        manager.put_raw("metadata", b"legacy", &bincode::serialize(&v1).unwrap()).unwrap();
+        let err = manager.get::<ProfileV1>("metadata", b"legacy").unwrap_err();
+        assert!(matches!(err.downcast_ref::<SchemaError>(), Some(SchemaError::Unversioned { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_survives_reopen() {
+        let (config, _temp_dir) = create_test_config();
+        let manager = RocksDBManager::new(config.clone()).await.unwrap();
+        assert_eq!(manager.get_checkpoint().await.unwrap(), None);
+
+        // What a consumer does on shutdown
+        manager.store_checkpoint(4_200).await.unwrap();
+        manager.flush().unwrap();
+        drop(manager);
+
+        let reopened = RocksDBManager::new(config).await.unwrap();
+        assert_eq!(reopened.get_checkpoint().await.unwrap(), Some(4_200));
+    }
 }
@@ -1,10 +1,14 @@
 //! RocksDB operations for raw blockchain data storage
 
 use super::rocksdb::{RocksDBManager, ColumnFamilies};
+use super::versioned::{self, StoredModel};
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 
+/// Metadata key of the consumer's last indexed slot
+const CHECKPOINT_KEY: &[u8] = b"last_indexed_slot";
+
 impl RocksDBManager {
     /// Store raw account data
     pub async fn store_account(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
@@ -21,13 +25,28 @@ impl RocksDBManager {
     /// Get account data at specific slot
     pub async fn get_account_at_slot(&self, pubkey: &Pubkey, slot: u64) -> Result<Option<Vec<u8>>> {
         let key = format!("account:{}:{}", pubkey, slot);
-        self.get(ColumnFamilies::ACCOUNTS, key.as_bytes())
+        self.get_raw(ColumnFamilies::ACCOUNTS, key.as_bytes())
     }
 
     /// Get latest account data
     pub async fn get_latest_account(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>> {
         let key = format!("account:{}:latest", pubkey);
-        self.get(ColumnFamilies::ACCOUNTS, key.as_bytes())
+        self.get_raw(ColumnFamilies::ACCOUNTS, key.as_bytes())
+    }
+
+    /// Store a tick array's raw data under its market
+    pub async fn store_tick_array(&self, market: &Pubkey, tick_array: &Pubkey, data: &[u8]) -> Result<()> {
+        let key = format!("tick_array:{}:{}", market, tick_array);
+        self.put_raw(ColumnFamilies::ACCOUNTS, key.as_bytes(), data)
+    }
+
+    /// Get the raw data of every indexed tick array of a market
+    pub async fn get_tick_arrays(&self, market: &Pubkey) -> Result<Vec<Vec<u8>>> {
+        let start_key = format!("tick_array:{}:", market);
+        let end_key = format!("tick_array:{}:~", market);
+        
+        let iter = self.iter_range(ColumnFamilies::ACCOUNTS, start_key.as_bytes(), end_key.into_bytes())?;
+        Ok(iter.map(|(_key, data)| data).collect())
     }
 
     /// Store raw transaction data
@@ -54,7 +73,7 @@ impl RocksDBManager {
     /// Get transaction data
     pub async fn get_transaction(&self, signature: &str) -> Result<Option<Vec<u8>>> {
         let key = format!("tx:{}", signature);
-        self.get(ColumnFamilies::TRANSACTIONS, key.as_bytes())
+        self.get_raw(ColumnFamilies::TRANSACTIONS, key.as_bytes())
     }
 
     /// Store block metadata
@@ -73,6 +92,16 @@ impl RocksDBManager {
         Ok(())
     }
 
+    /// Record the last slot the Geyser consumer fully processed
+    pub async fn store_checkpoint(&self, slot: u64) -> Result<()> {
+        self.put(ColumnFamilies::METADATA, CHECKPOINT_KEY, &slot)
+    }
+
+    /// Last slot recorded by `store_checkpoint`
+    pub async fn get_checkpoint(&self) -> Result<Option<u64>> {
+        self.get(ColumnFamilies::METADATA, CHECKPOINT_KEY)
+    }
+
     /// Get accounts modified in a slot range
     pub async fn get_accounts_in_slot_range(&self, start_slot: u64, end_slot: u64) -> Result<Vec<(Pubkey, u64)>> {
         let start_key = "account:".to_string();
@@ -137,7 +166,7 @@ impl RocksDBManager {
                 size: data.len(),
             };
             let meta_key = format!("tx:{}:meta", signature);
-            let meta_data = bincode::serialize(&metadata)?;
+            let meta_data = versioned::encode(&metadata)?;
             batch.insert(meta_key.into_bytes(), meta_data);
         }
         
@@ -152,6 +181,8 @@ struct TransactionMetadata {
     size: usize,
 }
 
+impl StoredModel for TransactionMetadata {}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct BlockMetadata {
     slot: u64,
@@ -160,6 +191,8 @@ struct BlockMetadata {
     timestamp: i64,
 }
 
+impl StoredModel for BlockMetadata {}
+
 /// Parse account key to extract pubkey and slot
 fn parse_account_key(key: &str) -> Option<(Pubkey, u64)> {
     let parts: Vec<&str> = key.split(':').collect();
@@ -7,12 +7,14 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tantivy::schema::*;
 use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 pub struct SearchManager {
     index: Index,
     reader: IndexReader,
-    writer: IndexWriter,
+    /// Locked so pending documents can be committed through a shared handle
+    writer: Mutex<IndexWriter>,
     schema: Schema,
     fields: SearchFields,
 }
@@ -93,7 +95,7 @@ impl SearchManager {
         Ok(Self {
             index,
             reader,
-            writer,
+            writer: Mutex::new(writer),
             schema,
             fields,
         })
@@ -112,7 +114,7 @@ impl SearchManager {
             self.fields.content_type => "market".to_string(),
         );
         
-        self.writer.add_document(doc)?;
+        self.writer.get_mut().add_document(doc)?;
         Ok(())
     }
 
@@ -126,7 +128,7 @@ impl SearchManager {
             self.fields.content_type => "position".to_string(),
         );
         
-        self.writer.add_document(doc)?;
+        self.writer.get_mut().add_document(doc)?;
         Ok(())
     }
 
@@ -140,13 +142,13 @@ impl SearchManager {
             self.fields.content_type => "swap".to_string(),
         );
         
-        self.writer.add_document(doc)?;
+        self.writer.get_mut().add_document(doc)?;
         Ok(())
     }
 
     /// Commit all pending changes
-    pub async fn commit(&mut self) -> Result<()> {
-        self.writer.commit()?;
+    pub async fn commit(&self) -> Result<()> {
+        self.writer.lock().await.commit()?;
         self.reader.reload()?;
         Ok(())
     }
@@ -0,0 +1,87 @@
+//! Versioned encoding for typed RocksDB values
+//!
+//! bincode is not self-describing, so bytes written under an older struct
+//! layout either fail to decode or decode into garbage. Every typed value is
+//! stored behind a two-byte header, a marker and the type's schema version,
+//! and reads check it before decoding: an older version goes through the
+//! type's upgrader and anything else is a `SchemaError`.
+
+use crate::database::Swap;
+use crate::models::buffer::IndexedBuffer;
+use crate::models::floor::IndexedFloor;
+use crate::models::market::IndexedMarket;
+use crate::models::position::IndexedPosition;
+use crate::models::{BlockInfo, SlotInfo, Transaction};
+use crate::sdk_types::ProtocolOracleData;
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// First byte of every versioned value
+pub const VALUE_MARKER: u8 = 0xFE;
+
+/// Stored value whose schema does not match the type reading it
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SchemaError {
+    #[error("{type_name} stored as schema v{stored}, expected v{expected} and no upgrader applies")]
+    VersionMismatch {
+        type_name: &'static str,
+        stored: u8,
+        expected: u8,
+    },
+    #[error("{type_name} value has no schema version header")]
+    Unversioned { type_name: &'static str },
+}
+
+/// A type stored in RocksDB through the typed `put`/`get` path
+pub trait StoredModel: Serialize + DeserializeOwned {
+    /// Bumped whenever the serialized layout changes, together with an
+    /// `upgrade` arm for the previous version
+    const SCHEMA_VERSION: u8 = 1;
+
+    /// Decode `data` stored under an older schema `version`
+    ///
+    /// The default has no upgraders and reports the mismatch.
+    fn upgrade(version: u8, data: &[u8]) -> Result<Self> {
+        let _ = data;
+        Err(SchemaError::VersionMismatch {
+            type_name: std::any::type_name::<Self>(),
+            stored: version,
+            expected: Self::SCHEMA_VERSION,
+        }
+        .into())
+    }
+}
+
+/// Serialize `value` behind its version header
+pub fn encode<T: StoredModel>(value: &T) -> Result<Vec<u8>> {
+    let mut data = vec![VALUE_MARKER, T::SCHEMA_VERSION];
+    bincode::serialize_into(&mut data, value).map_err(|e| anyhow!("Serialization failed: {}", e))?;
+    Ok(data)
+}
+
+/// Decode a versioned value, upgrading it if it was stored under an older schema
+pub fn decode<T: StoredModel>(data: &[u8]) -> Result<T> {
+    match data {
+        [VALUE_MARKER, version, body @ ..] if *version == T::SCHEMA_VERSION => {
+            bincode::deserialize(body).map_err(|e| anyhow!("Deserialization failed: {}", e))
+        }
+        [VALUE_MARKER, version, body @ ..] => T::upgrade(*version, body),
+        _ => Err(SchemaError::Unversioned {
+            type_name: std::any::type_name::<T>(),
+        }
+        .into()),
+    }
+}
+
+impl StoredModel for IndexedMarket {}
+impl StoredModel for IndexedPosition {}
+impl StoredModel for IndexedFloor {}
+impl StoredModel for IndexedBuffer {}
+impl StoredModel for Swap {}
+impl StoredModel for Transaction {}
+impl StoredModel for BlockInfo {}
+impl StoredModel for SlotInfo {}
+impl StoredModel for ProtocolOracleData {}
+impl StoredModel for serde_json::Value {}
+impl StoredModel for String {}
+impl StoredModel for u64 {}
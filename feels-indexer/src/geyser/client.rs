@@ -1,9 +1,12 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use tokio_stream::StreamExt;
 use tonic::transport::Channel;
 use tracing::info;
 
+use super::filters::AccountsFilter;
+
 // TODO: Fix tonic Body trait issue in generated code
 // Include the generated stub code (no protoc needed)
 // include!(concat!(env!("OUT_DIR"), "/geyser_stub.rs"));
@@ -17,6 +20,16 @@ use tracing::info;
 type SubscribeUpdate = ();
 type CommitmentLevel = ();
 
+/// Whether this build can stream updates from a Geyser endpoint
+///
+/// The generated gRPC client does not build yet, so there is nothing to
+/// subscribe with. The consumer refuses to start rather than reconnecting to
+/// an endpoint it can never read.
+pub const SUBSCRIPTIONS_SUPPORTED: bool = false;
+
+const SUBSCRIPTIONS_UNSUPPORTED: &str =
+    "Geyser subscriptions are not supported by this build: the gRPC client is not generated";
+
 pub struct FeelsGeyserClient {
     // client: GeyserClient<Channel>,
     _channel: Channel, // Keep for future use
@@ -71,6 +84,19 @@ impl FeelsGeyserClient {
         */
     }
 
+    /// Subscribe to program accounts, replaying from `from_slot` when set
+    ///
+    /// Always fails until the generated gRPC client builds; see
+    /// `SUBSCRIPTIONS_SUPPORTED`.
+    pub async fn subscribe_from(
+        &mut self,
+        from_slot: Option<u64>,
+        filters: &HashMap<String, AccountsFilter>,
+    ) -> Result<super::resume::UpdateStream> {
+        let _ = (from_slot, filters);
+        Err(anyhow!(SUBSCRIPTIONS_UNSUPPORTED))
+    }
+
     pub async fn subscribe_to_specific_accounts(&mut self, _accounts: Vec<Pubkey>) -> Result<impl StreamExt<Item = Result<SubscribeUpdate, tonic::Status>>> {
         // TODO: Fix tonic Body trait bounds issue
         // For now, return an empty stream
@@ -2,84 +2,118 @@
 
 use crate::config::GeyserConfig;
 use crate::database::DatabaseManager;
-use crate::processors::ProcessorRegistry;
-use anyhow::Result;
+use crate::processors::AccountSink;
+use crate::services::ServiceManager;
+use crate::shutdown::ShutdownSignal;
+use anyhow::{bail, Result};
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tracing::info;
 
-use super::client::{FeelsGeyserClient}; //, geyser_stub::{SubscribeUpdate, UpdateOneof}, helpers};
+use super::client::SUBSCRIPTIONS_SUPPORTED;
+use super::filters::build_account_filters;
+use super::resume::{consume_with_resume, GeyserEndpoint, GeyserUpdate, ReconnectPolicy};
+use super::sharding::ShardFilter;
+use super::stream_processor::StreamProcessor;
 
 /// Geyser consumer for Feels Protocol
 pub struct FeelsGeyserConsumer {
     program_id: Pubkey,
-    _db_manager: Arc<DatabaseManager>,
+    db_manager: Arc<DatabaseManager>,
     config: GeyserConfig,
-    _processor_registry: ProcessorRegistry,
+    stream_processor: StreamProcessor,
+    /// Last slot processed, resubscribed from after a disconnect
+    checkpoint: Option<u64>,
 }
 
 impl FeelsGeyserConsumer {
     /// Create a new Geyser consumer
+    ///
+    /// Resumes from the checkpoint saved by the last clean shutdown, if any.
+    /// Markets, positions and swaps are written through `services`, which
+    /// batches swaps, snapshots markets and evaluates price alerts.
     pub async fn new(
         program_id: Pubkey,
         db_manager: Arc<DatabaseManager>,
+        services: Arc<ServiceManager>,
         config: &GeyserConfig,
     ) -> Result<Self> {
-        let processor_registry = ProcessorRegistry::new(db_manager.clone());
+        if !SUBSCRIPTIONS_SUPPORTED {
+            bail!(
+                "Cannot consume {}: Geyser subscriptions are not supported by this build",
+                config.endpoint
+            );
+        }
+
+        let stream_processor = StreamProcessor::new(db_manager.clone(), services, program_id);
+        let checkpoint = db_manager.rocksdb.get_checkpoint().await?;
+        if let Some(slot) = checkpoint {
+            info!("Resuming Geyser consumption from checkpoint slot {}", slot);
+        }
         
         Ok(Self {
             program_id,
-            _db_manager: db_manager,
+            db_manager,
             config: config.clone(),
-            _processor_registry: processor_registry,
+            stream_processor,
+            checkpoint,
         })
     }
 
     /// Start consuming the Geyser stream
-    pub async fn start(&mut self) -> Result<()> {
+    ///
+    /// Reconnects after disconnects, resuming from the last processed slot,
+    /// until `shutdown` fires or `max_reconnect_attempts` consecutive attempts
+    /// have failed. Either way the update in flight is finished and the
+    /// checkpoint is saved before returning.
+    pub async fn start(&mut self, mut shutdown: ShutdownSignal) -> Result<()> {
         info!("Starting Feels Geyser consumer for program: {}", self.program_id);
 
-        loop {
-            match self.run_consumer().await {
-                Ok(_) => {
-                    warn!("Geyser stream ended unexpectedly, reconnecting...");
-                }
-                Err(e) => {
-                    error!("Geyser consumer error: {}", e);
-                    warn!("Retrying in 5 seconds...");
-                    sleep(Duration::from_secs(5)).await;
+        let shard = ShardFilter::from_config(&self.config);
+        if !shard.is_unsharded() {
+            info!("Indexing shard {} of {}", shard.shard_index, shard.shard_count);
+        }
+
+        let policy = ReconnectPolicy::from_config(&self.config);
+        let filters = build_account_filters(&self.program_id, &self.config.indexed_account_types)?;
+        let mut source = GeyserEndpoint::new(&self.config.endpoint, self.program_id, filters);
+        let processor = &self.stream_processor;
+        let rocksdb = &self.db_manager.rocksdb;
+
+        let result = consume_with_resume(
+            &mut source,
+            &mut self.checkpoint,
+            &policy,
+            &mut shutdown,
+            |update| async move {
+                match update {
+                    // Accounts owned by other shards are left to their instance
+                    GeyserUpdate::Account { pubkey, .. } if !shard.owns(&pubkey) => Ok(()),
+                    GeyserUpdate::Account { pubkey, data, slot } => {
+                        // Archived before decoding so `replay` can rebuild from it
+                        rocksdb.store_account(&pubkey, &data, slot).await?;
+                        processor.ingest_account(&pubkey, &data, slot).await
+                    }
+                    GeyserUpdate::Transaction { .. } if !shard.owns_transactions() => Ok(()),
+                    GeyserUpdate::Transaction { signature, data, slot } => {
+                        processor.process_transaction(&signature, &data, slot, None).await
+                    }
+                    GeyserUpdate::Slot { .. } => Ok(()),
                 }
-            }
+            },
+        )
+        .await;
+
+        if let Some(slot) = self.checkpoint {
+            self.db_manager.rocksdb.store_checkpoint(slot).await?;
+            info!("Saved Geyser checkpoint at slot {}", slot);
         }
+        result
     }
 
-    async fn run_consumer(&mut self) -> Result<()> {
-        let mut client = FeelsGeyserClient::connect(&self.config.endpoint, self.program_id).await?;
-        
-        let _stream = client.subscribe_to_program_accounts().await?;
-        
-        info!("Connected to Geyser stream, processing updates...");
-        
-        // TODO: Re-enable when geyser client is fixed
-        // while let Some(update_result) = stream.next().await {
-        //     match update_result {
-        //         Ok(update) => {
-        //             if let Err(e) = self.handle_update(update).await {
-        //                 error!("Error handling update: {}", e);
-        //             }
-        //         }
-        //         Err(e) => {
-        //             error!("Stream error: {}", e);
-        //             return Err(e.into());
-        //         }
-        //     }
-        // }
-        warn!("Geyser streaming temporarily disabled due to tonic Body trait issue");
-        loop {
-            sleep(Duration::from_secs(60)).await;
-        }
+    /// Last slot processed by this consumer
+    pub fn checkpoint(&self) -> Option<u64> {
+        self.checkpoint
     }
 
     /* TODO: Re-enable when tonic Body trait issue is fixed
@@ -1,14 +1,106 @@
 //! Geyser stream filters for Feels Protocol
 
+use anyhow::{anyhow, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+/// Program account types that can be selected in `indexed_account_types`
+pub const ACCOUNT_TYPES: &[&str] = &[
+    "Buffer",
+    "EpochParams",
+    "FeelsHub",
+    "Market",
+    "OracleState",
+    "PoolRegistry",
+    "Position",
+    "PreLaunchEscrow",
+    "ProtocolConfig",
+    "ProtocolOracle",
+    "ProtocolToken",
+    "SafetyController",
+    "TickArray",
+    "TranchePlan",
+];
+
+/// Match `bytes` at `offset` in the account data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+}
+
+/// One named accounts filter of a subscription request
+///
+/// Geyser ANDs the conditions inside a filter and ORs separate filters, so
+/// each indexed account type gets its own entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountsFilter {
+    pub owner: Vec<String>,
+    pub memcmp: Vec<MemcmpFilter>,
+}
+
+/// Anchor discriminator of the account type `name`
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    let hash = solana_sdk::hash::hash(format!("account:{}", name).as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
+
+/// Build the accounts filters for a subscription
+///
+/// An empty `account_types` subscribes to every account owned by the
+/// program. Otherwise each type becomes a memcmp filter on the discriminator
+/// at offset 0, so the server only streams matching accounts.
+pub fn build_account_filters(
+    program_id: &Pubkey,
+    account_types: &[String],
+) -> Result<HashMap<String, AccountsFilter>> {
+    let owner = vec![program_id.to_string()];
+
+    if account_types.is_empty() {
+        return Ok(HashMap::from([(
+            "feels_accounts".to_string(),
+            AccountsFilter {
+                owner,
+                memcmp: vec![],
+            },
+        )]));
+    }
+
+    account_types
+        .iter()
+        .map(|name| {
+            if !ACCOUNT_TYPES.contains(&name.as_str()) {
+                return Err(anyhow!(
+                    "Unknown account type '{}' in indexed_account_types (expected one of {})",
+                    name,
+                    ACCOUNT_TYPES.join(", ")
+                ));
+            }
+            Ok((
+                format!("feels_{}", name),
+                AccountsFilter {
+                    owner: owner.clone(),
+                    memcmp: vec![MemcmpFilter {
+                        offset: 0,
+                        bytes: account_discriminator(name).to_vec(),
+                    }],
+                },
+            ))
+        })
+        .collect()
+}
+
 // use super::client::geyser_stub::*;
 
 /*
 /// Create a subscription request for the Feels program
 pub fn create_subscription_request(program_id: Pubkey) -> SubscribeRequest {
     use std::collections::HashMap;
-    
+
     let mut accounts_filter = HashMap::new();
-    
+
     // Subscribe to all accounts owned by the Feels program
     accounts_filter.insert(
         "feels_accounts".to_string(),
@@ -57,4 +149,48 @@ pub fn is_feels_transaction(transaction: &SubscribeUpdateTransaction, _program_i
     // In production, we'd parse the transaction and check for program invocations
     transaction.transaction.is_some()
 }
-*/
\ No newline at end of file
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_bytes_per_account_type() {
+        let program_id = Pubkey::new_unique();
+        let types = vec!["Market".to_string(), "Position".to_string()];
+        let filters = build_account_filters(&program_id, &types).unwrap();
+
+        assert_eq!(filters.len(), 2);
+        let market = &filters["feels_Market"];
+        assert_eq!(market.owner, vec![program_id.to_string()]);
+        assert_eq!(
+            market.memcmp,
+            vec![MemcmpFilter {
+                offset: 0,
+                // sha256("account:Market")[..8], as generated by Anchor
+                bytes: vec![219, 190, 213, 55, 0, 227, 198, 154],
+            }]
+        );
+        assert_eq!(
+            filters["feels_Position"].memcmp[0].bytes,
+            account_discriminator("Position").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_empty_list_subscribes_to_all_accounts() {
+        let program_id = Pubkey::new_unique();
+        let filters = build_account_filters(&program_id, &[]).unwrap();
+
+        assert_eq!(filters.len(), 1);
+        assert!(filters["feels_accounts"].memcmp.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_account_type_is_rejected() {
+        let program_id = Pubkey::new_unique();
+        let result = build_account_filters(&program_id, &["Swap".to_string()]);
+        assert!(result.is_err());
+    }
+}
@@ -3,7 +3,16 @@
 mod client;
 mod consumer;
 mod filters;
+mod resume;
+mod sharding;
 mod stream_handler;
 mod stream_processor;
 
+pub use client::FeelsGeyserClient;
 pub use consumer::*;
+pub use filters::{
+    account_discriminator, build_account_filters, AccountsFilter, MemcmpFilter, ACCOUNT_TYPES,
+};
+pub use resume::{consume_with_resume, GeyserSource, GeyserUpdate, ReconnectPolicy};
+pub use sharding::{shard_for, ShardFilter};
+pub use stream_processor::StreamProcessor;
@@ -0,0 +1,439 @@
+//! Reconnect-with-resume for the Geyser subscription
+//!
+//! The consumer remembers the last slot it processed. When the stream drops it
+//! resubscribes from that slot instead of the live tip, so no updates are lost
+//! across the gap. The checkpoint slot itself is replayed because the stream
+//! may have dropped partway through it; processors upsert, so the repeat is
+//! harmless.
+//!
+//! A clean server close reconnects after the base delay. A transient error
+//! (failed connect or a stream error) backs off exponentially and gives up
+//! after `max_attempts` consecutive failures.
+//!
+//! A shutdown signal stops consumption between updates: the update being
+//! handled when it fires is finished and checkpointed, and nothing after it is
+//! read.
+
+use crate::config::GeyserConfig;
+use crate::shutdown::ShutdownSignal;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+use super::client::FeelsGeyserClient;
+use super::filters::AccountsFilter;
+use std::collections::HashMap;
+
+/// Longest wait between reconnect attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Update delivered by a Geyser subscription
+#[derive(Debug, Clone)]
+pub enum GeyserUpdate {
+    Account {
+        pubkey: Pubkey,
+        data: Vec<u8>,
+        slot: u64,
+    },
+    /// Transaction that invoked the program, as serialized on the wire
+    Transaction {
+        signature: String,
+        data: Vec<u8>,
+        slot: u64,
+    },
+    Slot {
+        slot: u64,
+    },
+}
+
+impl GeyserUpdate {
+    pub fn slot(&self) -> u64 {
+        match self {
+            GeyserUpdate::Account { slot, .. }
+            | GeyserUpdate::Transaction { slot, .. }
+            | GeyserUpdate::Slot { slot } => *slot,
+        }
+    }
+}
+
+pub type UpdateStream = BoxStream<'static, Result<GeyserUpdate, tonic::Status>>;
+
+/// Something that can open a Geyser subscription
+#[async_trait]
+pub trait GeyserSource: Send {
+    /// Subscribe, replaying from `from_slot` when set
+    async fn subscribe(&mut self, from_slot: Option<u64>) -> Result<UpdateStream>;
+}
+
+/// Connects a fresh `FeelsGeyserClient` for every subscription
+pub struct GeyserEndpoint {
+    endpoint: String,
+    program_id: Pubkey,
+    filters: HashMap<String, AccountsFilter>,
+}
+
+impl GeyserEndpoint {
+    pub fn new(
+        endpoint: &str,
+        program_id: Pubkey,
+        filters: HashMap<String, AccountsFilter>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            program_id,
+            filters,
+        }
+    }
+}
+
+#[async_trait]
+impl GeyserSource for GeyserEndpoint {
+    async fn subscribe(&mut self, from_slot: Option<u64>) -> Result<UpdateStream> {
+        let mut client = FeelsGeyserClient::connect(&self.endpoint, self.program_id).await?;
+        client.subscribe_from(from_slot, &self.filters).await
+    }
+}
+
+/// Backoff settings for resubscribing
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl ReconnectPolicy {
+    pub fn from_config(config: &GeyserConfig) -> Self {
+        let base_delay = Duration::from_secs(config.reconnect_delay_secs);
+        Self {
+            base_delay,
+            max_delay: MAX_BACKOFF.max(base_delay),
+            max_attempts: config.max_reconnect_attempts,
+        }
+    }
+
+    /// Delay before retrying after the `failures`-th consecutive failure
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// How one subscription ended
+enum SessionEnd {
+    /// The server finished the stream
+    Closed,
+    /// Connecting or reading failed
+    Failed(anyhow::Error),
+    /// Shutdown was signalled
+    Shutdown,
+}
+
+/// Consume `source` until reconnecting is exhausted or `shutdown` fires
+///
+/// `checkpoint` holds the last slot handed to `handle` and is updated in
+/// place, so a caller that restarts consumption resumes where it left off.
+/// Errors from `handle` are logged and do not end the stream. Returns `Ok`
+/// only on shutdown.
+pub async fn consume_with_resume<S, F, Fut>(
+    source: &mut S,
+    checkpoint: &mut Option<u64>,
+    policy: &ReconnectPolicy,
+    shutdown: &mut ShutdownSignal,
+    mut handle: F,
+) -> Result<()>
+where
+    S: GeyserSource,
+    F: FnMut(GeyserUpdate) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut failures = 0u32;
+
+    loop {
+        if shutdown.is_triggered() {
+            return Ok(());
+        }
+
+        let from_slot = *checkpoint;
+        match from_slot {
+            Some(slot) => info!("Subscribing to Geyser from slot {}", slot),
+            None => info!("Subscribing to Geyser from the live tip"),
+        }
+
+        let (end, progressed) = match source.subscribe(from_slot).await {
+            Ok(stream) => drain(stream, checkpoint, shutdown, &mut handle).await,
+            Err(e) => (SessionEnd::Failed(e), false),
+        };
+        if progressed {
+            failures = 0;
+        }
+
+        let delay = match end {
+            SessionEnd::Shutdown => {
+                info!("Geyser consumer stopping at slot {:?}", checkpoint);
+                return Ok(());
+            }
+            SessionEnd::Closed => {
+                info!("Geyser stream closed by server, reconnecting");
+                failures = 0;
+                policy.base_delay
+            }
+            SessionEnd::Failed(e) => {
+                failures += 1;
+                if failures > policy.max_attempts {
+                    return Err(e.context(format!(
+                        "Geyser reconnect failed {} times in a row",
+                        failures
+                    )));
+                }
+                let delay = policy.backoff(failures);
+                warn!(
+                    "Geyser stream error: {} (attempt {}/{}, retrying in {:?})",
+                    e, failures, policy.max_attempts, delay
+                );
+                delay
+            }
+        };
+
+        tokio::select! {
+            _ = sleep(delay) => {}
+            _ = shutdown.recv() => return Ok(()),
+        }
+    }
+}
+
+/// Feed one subscription to `handle`, returning how it ended and whether any
+/// update was processed
+async fn drain<F, Fut>(
+    mut stream: UpdateStream,
+    checkpoint: &mut Option<u64>,
+    shutdown: &mut ShutdownSignal,
+    handle: &mut F,
+) -> (SessionEnd, bool)
+where
+    F: FnMut(GeyserUpdate) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut progressed = false;
+
+    loop {
+        // Only the wait for the next update is raced against shutdown, so an
+        // update already being handled always completes
+        let item = tokio::select! {
+            biased;
+            _ = shutdown.recv() => return (SessionEnd::Shutdown, progressed),
+            item = stream.next() => item,
+        };
+        let update = match item {
+            Some(Ok(update)) => update,
+            Some(Err(status)) => return (SessionEnd::Failed(anyhow!(status)), progressed),
+            None => return (SessionEnd::Closed, progressed),
+        };
+
+        let slot = update.slot();
+        if matches!(*checkpoint, Some(last) if slot < last) {
+            continue;
+        }
+
+        if let Err(e) = handle(update).await {
+            error!("Error handling update at slot {}: {}", slot, e);
+        }
+        *checkpoint = Some(slot);
+        progressed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shutdown::Shutdown;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    /// Source that plays back scripted sessions, then refuses connections
+    struct MockSource {
+        sessions: VecDeque<Vec<Result<GeyserUpdate, tonic::Status>>>,
+        requests: Vec<Option<u64>>,
+    }
+
+    #[async_trait]
+    impl GeyserSource for MockSource {
+        async fn subscribe(&mut self, from_slot: Option<u64>) -> Result<UpdateStream> {
+            self.requests.push(from_slot);
+            match self.sessions.pop_front() {
+                Some(items) => Ok(futures::stream::iter(items).boxed()),
+                None => Err(anyhow!("connection refused")),
+            }
+        }
+    }
+
+    fn slot(slot: u64) -> Result<GeyserUpdate, tonic::Status> {
+        Ok(GeyserUpdate::Slot { slot })
+    }
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            max_attempts: 2,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resumes_from_checkpoint_after_disconnect() {
+        let mut source = MockSource {
+            sessions: VecDeque::from(vec![
+                // Drops mid-stream
+                vec![slot(10), slot(11), Err(tonic::Status::unavailable("reset"))],
+                // Replays the checkpoint slot, then the server closes cleanly
+                vec![slot(11), slot(12)],
+            ]),
+            requests: Vec::new(),
+        };
+        let mut checkpoint = None;
+        let mut seen = Vec::new();
+
+        let mut shutdown = Shutdown::new().subscribe();
+
+        let result = consume_with_resume(
+            &mut source,
+            &mut checkpoint,
+            &policy(),
+            &mut shutdown,
+            |update| {
+                seen.push(update.slot());
+                async { Ok(()) }
+            },
+        )
+        .await;
+
+        // Three refused connections after the clean close exhaust the retries
+        assert!(result.is_err());
+        assert_eq!(
+            source.requests,
+            vec![None, Some(11), Some(12), Some(12), Some(12)]
+        );
+        assert_eq!(seen, vec![10, 11, 11, 12]);
+        assert_eq!(checkpoint, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_progress_resets_failure_count() {
+        let mut source = MockSource {
+            sessions: VecDeque::from(vec![
+                vec![Err(tonic::Status::unavailable("reset"))],
+                vec![Err(tonic::Status::unavailable("reset"))],
+                vec![slot(5), Err(tonic::Status::unavailable("reset"))],
+                vec![Err(tonic::Status::unavailable("reset"))],
+            ]),
+            requests: Vec::new(),
+        };
+        let mut checkpoint = None;
+        let mut shutdown = Shutdown::new().subscribe();
+
+        let result = consume_with_resume(
+            &mut source,
+            &mut checkpoint,
+            &policy(),
+            &mut shutdown,
+            |_| async { Ok(()) },
+        )
+        .await;
+
+        // Without the reset the third session would already exhaust the retries
+        assert!(result.is_err());
+        assert_eq!(source.requests.len(), 5);
+        assert_eq!(checkpoint, Some(5));
+    }
+
+    /// Source whose single session yields `items` and then stays open
+    struct OpenSource {
+        items: Vec<Result<GeyserUpdate, tonic::Status>>,
+    }
+
+    #[async_trait]
+    impl GeyserSource for OpenSource {
+        async fn subscribe(&mut self, _from_slot: Option<u64>) -> Result<UpdateStream> {
+            let items = std::mem::take(&mut self.items);
+            Ok(futures::stream::iter(items).chain(futures::stream::pending()).boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_finishes_update_in_flight() {
+        let mut source = OpenSource {
+            items: vec![slot(7), slot(8)],
+        };
+        let mut checkpoint = None;
+        let shutdown = Shutdown::new();
+        let mut signal = shutdown.subscribe();
+        let written = Arc::new(Mutex::new(Vec::new()));
+
+        let result = consume_with_resume(
+            &mut source,
+            &mut checkpoint,
+            &policy(),
+            &mut signal,
+            |update| {
+                // Shutdown arrives while the first update is still being written
+                shutdown.trigger();
+                let written = written.clone();
+                async move {
+                    sleep(Duration::from_millis(10)).await;
+                    written.lock().unwrap().push(update.slot());
+                    Ok(())
+                }
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(*written.lock().unwrap(), vec![7]);
+        assert_eq!(checkpoint, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_interrupts_reconnect_backoff() {
+        let mut source = MockSource {
+            sessions: VecDeque::new(),
+            requests: Vec::new(),
+        };
+        let mut checkpoint = Some(3);
+        let shutdown = Shutdown::new();
+        let mut signal = shutdown.subscribe();
+        let slow = ReconnectPolicy {
+            base_delay: Duration::from_secs(60),
+            max_delay: Duration::from_secs(60),
+            max_attempts: 5,
+        };
+
+        let consume = consume_with_resume(&mut source, &mut checkpoint, &slow, &mut signal, |_| {
+            async { Ok(()) }
+        });
+        let (result, _) = tokio::join!(consume, async { shutdown.trigger() });
+
+        assert!(result.is_ok());
+        assert_eq!(source.requests, vec![Some(3)]);
+        assert_eq!(checkpoint, Some(3));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = ReconnectPolicy {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            max_attempts: 10,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(40), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,176 @@
+//! Sharding accounts across indexer instances
+//!
+//! Each instance is configured with a `shard_index` out of `shard_count` and
+//! only processes accounts that hash to its shard, so N instances can index
+//! one program without duplicating work. Accounts are placed with jump
+//! consistent hashing: growing the fleet from N to N + 1 instances only moves
+//! about 1 / (N + 1) of the accounts, all of them onto the new shard.
+
+use crate::config::GeyserConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// Shard in `0..shard_count` that owns `pubkey`
+///
+/// Depends only on the pubkey bytes, so every instance and every release
+/// agrees on the assignment. A `shard_count` of 0 is treated as 1.
+pub fn shard_for(pubkey: &Pubkey, shard_count: u16) -> u16 {
+    jump_consistent_hash(pubkey_key(pubkey), shard_count.max(1) as i64) as u16
+}
+
+/// Fold the pubkey into a well-mixed 64-bit key
+fn pubkey_key(pubkey: &Pubkey) -> u64 {
+    let folded = pubkey
+        .to_bytes()
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("8-byte chunk")))
+        .fold(0u64, |acc, word| acc.rotate_left(17) ^ word);
+
+    // SplitMix64 finalizer, so structured pubkeys still spread evenly
+    let mut z = folded.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Lamping & Veach jump consistent hash
+fn jump_consistent_hash(mut key: u64, buckets: i64) -> i64 {
+    let mut bucket = -1i64;
+    let mut next = 0i64;
+
+    while next < buckets {
+        bucket = next;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        next = ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+
+    bucket
+}
+
+/// Which accounts this instance processes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardFilter {
+    pub shard_index: u16,
+    pub shard_count: u16,
+}
+
+impl ShardFilter {
+    pub fn from_config(config: &GeyserConfig) -> Self {
+        Self {
+            shard_index: config.shard_index,
+            shard_count: config.shard_count,
+        }
+    }
+
+    /// Whether the filter passes everything
+    pub fn is_unsharded(&self) -> bool {
+        self.shard_count <= 1
+    }
+
+    /// Whether this instance processes `pubkey`
+    pub fn owns(&self, pubkey: &Pubkey) -> bool {
+        self.is_unsharded() || shard_for(pubkey, self.shard_count) == self.shard_index
+    }
+
+    /// Whether this instance indexes transactions
+    ///
+    /// A transaction touches accounts on many shards, so only the first shard
+    /// records it and each swap is stored and published once.
+    pub fn owns_transactions(&self) -> bool {
+        self.shard_index == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::hashv;
+
+    /// Deterministic pseudo-random pubkeys
+    fn pubkeys(count: u32) -> Vec<Pubkey> {
+        (0..count)
+            .map(|i| Pubkey::new_from_array(hashv(&[&i.to_le_bytes()]).to_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_shard_is_stable() {
+        let keys = pubkeys(1_000);
+        let first: Vec<u16> = keys.iter().map(|key| shard_for(key, 8)).collect();
+        let second: Vec<u16> = keys.iter().map(|key| shard_for(key, 8)).collect();
+        assert_eq!(first, second);
+
+        // Pinned so a change to the hash, which would reshuffle every
+        // deployment, fails loudly
+        let pinned: Vec<u16> = keys[..8].iter().map(|key| shard_for(key, 16)).collect();
+        assert_eq!(pinned, vec![3, 10, 13, 3, 5, 2, 9, 6]);
+
+        assert!(keys.iter().all(|key| shard_for(key, 1) == 0));
+        assert!(keys.iter().all(|key| shard_for(key, 0) == 0));
+    }
+
+    #[test]
+    fn test_shards_are_roughly_balanced() {
+        const SHARDS: u16 = 8;
+        let keys = pubkeys(40_000);
+
+        let mut counts = [0usize; SHARDS as usize];
+        for key in &keys {
+            counts[shard_for(key, SHARDS) as usize] += 1;
+        }
+
+        // Expect 5_000 per shard; allow 5% either way
+        for count in counts {
+            assert!((4_750..=5_250).contains(&count), "unbalanced shards: {:?}", counts);
+        }
+
+        // Structured pubkeys spread as well
+        let mut counts = [0usize; SHARDS as usize];
+        for _ in 0..40_000 {
+            counts[shard_for(&Pubkey::new_unique(), SHARDS) as usize] += 1;
+        }
+        for count in counts {
+            assert!((4_750..=5_250).contains(&count), "unbalanced shards: {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_adding_a_shard_only_moves_keys_onto_it() {
+        let keys = pubkeys(10_000);
+
+        let moved = keys
+            .iter()
+            .filter(|key| {
+                let before = shard_for(key, 4);
+                let after = shard_for(key, 5);
+                assert!(after == before || after == 4);
+                after != before
+            })
+            .count();
+
+        // About a fifth of the keys move to the new shard
+        assert!((1_800..=2_200).contains(&moved), "moved {} keys", moved);
+    }
+
+    #[test]
+    fn test_filter_partitions_accounts() {
+        let keys = pubkeys(1_000);
+        let filters: Vec<ShardFilter> = (0..3)
+            .map(|shard_index| ShardFilter {
+                shard_index,
+                shard_count: 3,
+            })
+            .collect();
+
+        for key in &keys {
+            let owners = filters.iter().filter(|filter| filter.owns(key)).count();
+            assert_eq!(owners, 1);
+        }
+        assert_eq!(filters.iter().filter(|filter| filter.owns_transactions()).count(), 1);
+
+        let unsharded = ShardFilter {
+            shard_index: 0,
+            shard_count: 1,
+        };
+        assert!(keys.iter().all(|key| unsharded.owns(key)));
+    }
+}
@@ -1,6 +1,7 @@
 //! Stream handler for processing Geyser updates
 
 use crate::database::DatabaseManager;
+use crate::services::ServiceManager;
 use super::stream_processor::StreamProcessor;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
@@ -18,8 +19,9 @@ impl GeyserStreamHandler {
     pub fn new(
         program_id: Pubkey,
         db_manager: Arc<DatabaseManager>,
+        services: Arc<ServiceManager>,
     ) -> Self {
-        let stream_processor = StreamProcessor::new(db_manager.clone(), program_id);
+        let stream_processor = StreamProcessor::new(db_manager.clone(), services, program_id);
         
         Self {
             program_id,
@@ -4,6 +4,9 @@
 //! of Geyser updates using the Feels SDK for deserialization.
 
 use crate::database::{DatabaseManager, Market, Position, Swap};
+use crate::processors::AccountSink;
+use crate::services::ServiceManager;
+use crate::safe_cast::try_u16_to_i16;
 use crate::sdk_types::feels_sdk;
 use crate::sdk_types::AccountType;
 use anyhow::{anyhow, Result};
@@ -16,12 +19,18 @@ use chrono::Utc;
 /// Processes raw Geyser account and transaction data
 pub struct StreamProcessor {
     db_manager: Arc<DatabaseManager>,
+    /// Snapshots markets, evaluates price alerts and batches swap writes
+    services: Arc<ServiceManager>,
     program_id: Pubkey,
 }
 
 impl StreamProcessor {
-    pub fn new(db_manager: Arc<DatabaseManager>, program_id: Pubkey) -> Self {
-        Self { db_manager, program_id }
+    pub fn new(
+        db_manager: Arc<DatabaseManager>,
+        services: Arc<ServiceManager>,
+        program_id: Pubkey,
+    ) -> Self {
+        Self { db_manager, services, program_id }
     }
 
     /// Process a raw account update
@@ -48,6 +57,12 @@ impl StreamProcessor {
             Some(AccountType::ProtocolToken) => {
                 self.process_protocol_token(pubkey, data, slot).await?;
             }
+            Some(AccountType::Oracle) => {
+                self.process_protocol_oracle(pubkey, data, slot).await?;
+            }
+            Some(AccountType::TickArray) => {
+                self.process_tick_array(pubkey, data, slot).await?;
+            }
             _ => {
                 debug!("Unknown account type for {}", pubkey);
             }
@@ -64,7 +79,7 @@ impl StreamProcessor {
         let market_data = feels_sdk::decode_market(data).map_err(|e: String| anyhow!(e))?;
         
         // Convert to database model
-        let market = Market {
+        let mut market = Market {
             id: Uuid::new_v4(),
             address: pubkey.to_string(),
             token_0: market_data.token_0.to_string(),
@@ -95,8 +110,12 @@ impl StreamProcessor {
             last_updated_slot: slot as i64,
         };
 
-        // Store in PostgreSQL
-        self.db_manager.postgres.upsert_market(&market).await?;
+        // Snapshots, alerts and positions refer to the id the market was
+        // first stored under
+        market.id = self.stored_market_id(&market).await?;
+        
+        // Store in PostgreSQL, snapshot and fire any price alerts crossed
+        self.services.process_market_update(&market).await?;
         
         // Cache in Redis for fast lookups
         self.db_manager.redis.cache_market(pubkey.to_string(), &market).await?;
@@ -107,6 +126,21 @@ impl StreamProcessor {
         Ok(())
     }
 
+    /// Id of the stored row for `market`, inserting it first if it is new
+    async fn stored_market_id(&self, market: &Market) -> Result<Uuid> {
+        let postgres = &self.db_manager.postgres;
+        if let Some(stored) = postgres.get_market_by_address(&market.address).await? {
+            return Ok(stored.id);
+        }
+        
+        postgres.upsert_market(market).await?;
+        postgres
+            .get_market_by_address(&market.address)
+            .await?
+            .map(|stored| stored.id)
+            .ok_or_else(|| anyhow!("Market {} missing after insert", market.address))
+    }
+
     /// Process a position account update
     async fn process_position_account(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
         info!("Processing position account: {}", pubkey);
@@ -133,13 +167,14 @@ impl StreamProcessor {
             fee_growth_inside_1_last: position_data.fee_growth_inside_1_last_x64.into(),
             tokens_owed_0: position_data.tokens_owed_0 as i64,
             tokens_owed_1: position_data.tokens_owed_1 as i64,
+            entry_value: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_updated_slot: slot as i64,
         };
 
-        // Store in PostgreSQL
-        self.db_manager.postgres.upsert_position(&position).await?;
+        // Store in PostgreSQL, recording its value as the entry value if new
+        self.services.process_position_update(&position).await?;
 
         // Store raw data in RocksDB
         self.db_manager.rocksdb.store_account(pubkey, data, slot).await?;
@@ -183,6 +218,35 @@ impl StreamProcessor {
         Ok(())
     }
 
+    /// Process protocol oracle update
+    async fn process_protocol_oracle(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
+        debug!("Processing protocol oracle: {}", pubkey);
+        
+        let oracle = feels_sdk::decode_protocol_oracle(data).map_err(|e: String| anyhow!(e))?;
+        
+        // Store raw data and the latest rates in RocksDB
+        self.db_manager.rocksdb.store_account(pubkey, data, slot).await?;
+        self.db_manager.rocksdb.put_protocol_oracle(&oracle)?;
+
+        Ok(())
+    }
+
+    /// Process a tick array update
+    async fn process_tick_array(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
+        debug!("Processing tick array: {}", pubkey);
+        
+        // Market pubkey follows the discriminator
+        let market = data.get(8..40)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or_else(|| anyhow!("Tick array {} too short", pubkey))?;
+        
+        // Store raw data in RocksDB, indexed by market for depth queries
+        self.db_manager.rocksdb.store_account(pubkey, data, slot).await?;
+        self.db_manager.rocksdb.store_tick_array(&market, pubkey, data).await?;
+
+        Ok(())
+    }
+
     /// Process a transaction containing Feels instructions
     pub async fn process_transaction(
         &self, 
@@ -258,12 +322,12 @@ impl StreamProcessor {
             timestamp: Utc::now(),
             slot: slot as i64,
             block_height: block_height.map(|h| h as i64),
-            price_impact_bps: Some(swap_data.price_impact_bps as i16),
+            price_impact_bps: try_u16_to_i16(swap_data.price_impact_bps),
             effective_price: Some(rust_decimal::Decimal::from_f64_retain(swap_data.effective_price).unwrap_or_default()),
         };
 
-        // Store in PostgreSQL
-        self.db_manager.postgres.insert_swap(&swap).await?;
+        // Buffered for a batched PostgreSQL write and published to subscribers
+        self.services.process_swap(&swap).await?;
 
         // Update market statistics
         self.update_market_stats(&swap_data.market.to_string(), &swap).await?;
@@ -288,4 +352,11 @@ impl StreamProcessor {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[async_trait::async_trait]
+impl AccountSink for StreamProcessor {
+    async fn ingest_account(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
+        self.process_account(pubkey, data, slot).await
+    }
+}
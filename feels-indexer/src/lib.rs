@@ -11,10 +11,14 @@ pub mod database;
 pub mod geyser;
 pub mod models;
 pub mod processors;
+pub mod readiness;
+pub mod replay;
 pub mod repositories;
 pub mod rpc_client;
+pub mod safe_cast;
 pub mod services;
 pub mod sdk_types;
+pub mod shutdown;
 pub mod streaming_client;
 
 mod minimal_test;
@@ -48,7 +52,7 @@ mod tests {
         
         // Test basic put/get
         db.put_raw(ColumnFamilies::MARKETS, b"test", b"value")?;
-        let result = db.get::<Vec<u8>>(ColumnFamilies::MARKETS, b"test")?;
+        let result = db.get_raw(ColumnFamilies::MARKETS, b"test")?;
         
         assert_eq!(result, Some(b"value".to_vec()));
         
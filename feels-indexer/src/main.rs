@@ -10,20 +10,28 @@ mod database;
 mod geyser;
 mod models;
 mod processors;
+mod readiness;
+mod replay;
 mod api;
 mod repositories;
+mod safe_cast;
 mod services;
 mod sdk_types;
+mod shutdown;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use config::IndexerConfig;
 use std::sync::Arc;
 use std::str::FromStr;
+use std::time::Duration;
 use tokio::signal;
 use tracing::{info, warn, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// Longest wait for in-flight requests and updates before flushing anyway
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Parser)]
 #[command(name = "feels-indexer")]
 #[command(about = "Feels Protocol Geyser Indexer")]
@@ -36,9 +44,26 @@ struct Cli {
     #[arg(long)]
     log_level: Option<String>,
 
-    /// Dry run mode (validate config and exit)
+    /// Dry run mode (validate config, probe every backend and exit)
     #[arg(long)]
     dry_run: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Re-run the decode/index pipeline over stored data and exit
+    Replay {
+        /// Read the raw account data archived in RocksDB
+        #[arg(long)]
+        from_rocksdb: bool,
+
+        /// Slots to replay, as START..END (END exclusive)
+        #[arg(long)]
+        range: replay::SlotRange,
+    },
 }
 
 #[tokio::main]
@@ -73,7 +98,13 @@ async fn main() -> Result<()> {
     info!("Configuration validated successfully");
 
     if cli.dry_run {
-        info!("Dry run mode - configuration is valid, exiting");
+        info!("Dry run mode - configuration is valid, probing backends");
+        let probes = readiness::probe_backends(&config).await;
+        print!("{}", readiness::render_table(&probes));
+        if !readiness::all_ready(&probes) {
+            anyhow::bail!("One or more backends are unreachable");
+        }
+        info!("All backends reachable, exiting");
         return Ok(());
     }
 
@@ -87,53 +118,122 @@ async fn main() -> Result<()> {
     ).await?);
     info!("Database connections initialized successfully");
 
+    // Batches swap writes and compacts old snapshots in the background
+    let repos = Arc::new(repositories::RepositoryManager::with_settings(
+        db_manager.clone(),
+        &config.indexer,
+    ));
+    let mut services = services::ServiceManager::with_settings(repos.clone(), &config.indexer);
+    if let Some(webhook) = &config.webhook {
+        info!("Delivering fired price alerts to {}", webhook.url);
+        services = services.with_alert_webhook(services::WebhookDispatcher::from_config(webhook)?);
+    }
+    let services = Arc::new(services);
+
+    if let Some(Command::Replay { from_rocksdb, range }) = cli.command {
+        if !from_rocksdb {
+            anyhow::bail!("Replay reads archived account data; pass --from-rocksdb");
+        }
+        info!("Replaying slots {} from RocksDB", range);
+        let processor = geyser::StreamProcessor::new(db_manager.clone(), services.clone(), program_id);
+        let report = replay::replay(&db_manager.rocksdb, range, &processor).await?;
+        repos.flush().await?;
+        db_manager.flush().await?;
+        info!(
+            "Replay finished: {} account updates replayed, {} failed",
+            report.replayed, report.failed
+        );
+        return Ok(());
+    }
+
     // Initialize Geyser consumer
     info!("Initializing Geyser consumer...");
     let mut consumer = geyser::FeelsGeyserConsumer::new(
         program_id,
         db_manager.clone(),
+        services.clone(),
         &config.geyser,
     ).await?;
     info!("Geyser consumer initialized successfully");
 
+    let shutdown = shutdown::Shutdown::new();
+
     // Start API server
     info!("Starting API server on {}", config.api.bind_address);
-    let api_server = api::start_server(db_manager.clone(), &config.api).await?;
+    let mut api_server =
+        api::start_server(db_manager.clone(), &config.api, shutdown.subscribe()).await?;
 
     // Start metrics server if enabled
-    let _metrics_server = if config.monitoring.metrics_port > 0 {
+    let metrics_server = if config.monitoring.metrics_port > 0 {
         info!("Starting metrics server on port {}", config.monitoring.metrics_port);
-        Some(api::start_metrics_server(config.monitoring.metrics_port).await?)
+        Some(api::start_metrics_server(config.monitoring.metrics_port, shutdown.subscribe()).await?)
     } else {
         None
     };
 
+    // Start swap webhook delivery if configured
+    let _webhook = match &config.webhook {
+        Some(webhook) => {
+            info!("Starting swap webhook to {}", webhook.url);
+            Some(services::webhook::spawn(&config.redis.url, webhook)?)
+        }
+        None => None,
+    };
+
     // Start the consumer
     info!("Starting Geyser stream consumption...");
-    let consumer_handle = tokio::spawn(async move {
-        if let Err(e) = consumer.start().await {
+    let consumer_shutdown = shutdown.subscribe();
+    let mut consumer_handle = tokio::spawn(async move {
+        if let Err(e) = consumer.start(consumer_shutdown).await {
             error!("Geyser consumer error: {}", e);
         }
     });
 
     // Wait for shutdown signal
     info!("Indexer started successfully. Press Ctrl+C to shutdown.");
+    let mut consumer_running = true;
+    let mut api_running = true;
     tokio::select! {
         _ = signal::ctrl_c() => {
             info!("Received shutdown signal");
         }
-        result = consumer_handle => {
+        result = &mut consumer_handle => {
+            consumer_running = false;
             match result {
                 Ok(_) => info!("Consumer finished"),
                 Err(e) => error!("Consumer task error: {}", e),
             }
         }
-        _ = api_server => {
+        _ = &mut api_server => {
+            api_running = false;
             info!("API server finished");
         }
     }
 
+    // Stop taking new work, let what is in flight finish, then make the
+    // buffered writes durable
     info!("Shutting down Feels Protocol Indexer");
+    shutdown.trigger();
+    let drain = async {
+        if consumer_running {
+            if let Err(e) = consumer_handle.await {
+                error!("Consumer task error: {}", e);
+            }
+        }
+        if api_running {
+            let _ = api_server.await;
+        }
+        if let Some(metrics_server) = metrics_server {
+            let _ = metrics_server.await;
+        }
+    };
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, drain).await.is_err() {
+        warn!("Tasks still running after {:?}, flushing anyway", SHUTDOWN_TIMEOUT);
+    }
+
+    repos.flush().await?;
+    db_manager.flush().await?;
+    info!("Flushed pending writes, exiting");
     Ok(())
 }
 
@@ -1,7 +1,7 @@
 //! Market account processor
 
 use super::AccountProcessor;
-use crate::database::DatabaseManager;
+use crate::database::{DatabaseManager, U128Numeric};
 use crate::models::{BlockInfo};
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
@@ -87,8 +87,8 @@ impl AccountProcessor for MarketProcessor {
             phase: "PriceDiscovery".to_string(), // Would determine from data
             global_lower_tick: -100_800,      // Would parse from data
             global_upper_tick: 100_800,       // Would parse from data
-            fee_growth_global_0: Decimal::from(0),
-            fee_growth_global_1: Decimal::from(0),
+            fee_growth_global_0: U128Numeric::ZERO,
+            fee_growth_global_1: U128Numeric::ZERO,
             total_volume_0: existing_market.as_ref().map(|m| m.total_volume_0).unwrap_or_default(),
             total_volume_1: existing_market.as_ref().map(|m| m.total_volume_1).unwrap_or_default(),
             total_fees_0: existing_market.as_ref().map(|m| m.total_fees_0).unwrap_or_default(),
@@ -30,6 +30,16 @@ pub trait AccountProcessor: Send + Sync {
     ) -> Result<()>;
 }
 
+/// Entry point of the decode/index pipeline for raw account data
+///
+/// Implemented by `StreamProcessor`, which the live Geyser consumer and
+/// offline replay both feed accounts through, and by `ProcessorRegistry`.
+#[async_trait::async_trait]
+pub trait AccountSink: Send + Sync {
+    /// Decode `data` as the account at `pubkey` in `slot` and index it
+    async fn ingest_account(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()>;
+}
+
 /// Trait for processing transactions
 #[async_trait::async_trait]
 pub trait TransactionProcessor: Send + Sync {
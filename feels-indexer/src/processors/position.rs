@@ -1,7 +1,7 @@
 //! Position account processor
 
 use super::AccountProcessor;
-use crate::database::DatabaseManager;
+use crate::database::{DatabaseManager, U128Numeric};
 use crate::models::{BlockInfo};
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
@@ -50,10 +50,11 @@ impl AccountProcessor for PositionProcessor {
                 liquidity: Decimal::from(0), // Would parse from data
                 tick_lower: 0,               // Would parse from data
                 tick_upper: 0,               // Would parse from data
-                fee_growth_inside_0_last: Decimal::from(0), // Would parse from data
-                fee_growth_inside_1_last: Decimal::from(0), // Would parse from data
+                fee_growth_inside_0_last: U128Numeric::ZERO, // Would parse from data
+                fee_growth_inside_1_last: U128Numeric::ZERO, // Would parse from data
                 tokens_owed_0: 0,            // Would parse from data
                 tokens_owed_1: 0,            // Would parse from data
+                entry_value: None,
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
                 last_updated_slot: block_info.slot as i64,
@@ -1,13 +1,14 @@
 //! Processor registry for routing updates to appropriate handlers
 
 use super::{
-    AccountProcessor, TransactionProcessor,
+    AccountProcessor, AccountSink, TransactionProcessor,
     MarketProcessor, SwapProcessor, BufferProcessor, 
     PositionProcessor, FloorProcessor,
 };
 use crate::database::DatabaseManager;
 use crate::models::BlockInfo;
-use anyhow::Result;
+use crate::sdk_types::feels_sdk;
+use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
 use solana_transaction_status::{ConfirmedBlock, EncodedTransaction};
@@ -20,6 +21,7 @@ pub struct ProcessorRegistry {
     buffer_processor: BufferProcessor,
     position_processor: PositionProcessor,
     floor_processor: FloorProcessor,
+    db_manager: Arc<DatabaseManager>,
 }
 
 impl ProcessorRegistry {
@@ -31,6 +33,7 @@ impl ProcessorRegistry {
             buffer_processor: BufferProcessor::new(db_manager.clone()),
             position_processor: PositionProcessor::new(db_manager.clone()),
             floor_processor: FloorProcessor::new(db_manager.clone()),
+            db_manager,
         }
     }
 
@@ -116,16 +119,17 @@ impl ProcessorRegistry {
     }
     
     /// Process oracle account update
+    ///
+    /// Keeps the latest protocol oracle rates for portfolio valuation.
     pub async fn process_oracle_update(
         &self,
         pubkey: Pubkey,
-        _data: &[u8],
+        data: &[u8],
         _block_info: BlockInfo,
     ) -> Result<()> {
-        // Oracle processing would go here
-        // For now, just log it
         tracing::debug!("Oracle update for {}", pubkey);
-        Ok(())
+        let oracle = feels_sdk::decode_protocol_oracle(data).map_err(|e| anyhow!(e))?;
+        self.db_manager.rocksdb.put_protocol_oracle(&oracle)
     }
     
     /// Process protocol config update
@@ -171,3 +175,10 @@ impl ProcessorRegistry {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl AccountSink for ProcessorRegistry {
+    async fn ingest_account(&self, pubkey: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
+        self.process_account_update(pubkey, data, slot).await
+    }
+}
@@ -0,0 +1,182 @@
+//! Backend readiness probes for `--dry-run`
+//!
+//! Connects to every configured backend independently so one failure does not
+//! hide the state of the others, then reports a per-backend OK/FAIL table.
+
+use crate::config::IndexerConfig;
+use crate::database::{
+    postgres_impl::PostgresManager, redis::RedisManager, rocksdb::RocksDBManager,
+    tantivy::SearchManager, DatabaseOperations,
+};
+use crate::geyser::FeelsGeyserClient;
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Upper bound on each individual probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of probing one backend
+#[derive(Debug, Clone)]
+pub struct BackendProbe {
+    pub name: &'static str,
+    pub target: String,
+    pub error: Option<String>,
+}
+
+impl BackendProbe {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Probe Postgres, Redis, RocksDB, Tantivy and the Geyser endpoint
+pub async fn probe_backends(config: &IndexerConfig) -> Vec<BackendProbe> {
+    let postgres_url = &config.database.postgres_url;
+    let redis_url = &config.redis.url;
+    let rocksdb = config.storage.rocksdb.clone();
+    let tantivy_path = &config.storage.tantivy_path;
+    let geyser = &config.geyser;
+
+    vec![
+        probe("postgres", redact_url(postgres_url), async {
+            PostgresManager::new(postgres_url).await?.health_check().await
+        })
+        .await,
+        probe("redis", redact_url(redis_url), async {
+            RedisManager::new(redis_url).await?.health_check().await
+        })
+        .await,
+        probe("rocksdb", rocksdb.path.display().to_string(), async {
+            // Opening the database (and taking its lock) is the check
+            RocksDBManager::new(rocksdb.clone()).await.map(|_| ())
+        })
+        .await,
+        probe("tantivy", tantivy_path.display().to_string(), async {
+            SearchManager::new(tantivy_path).await?.health_check().await
+        })
+        .await,
+        probe("geyser", geyser.endpoint.clone(), async {
+            let program_id = Pubkey::from_str(&geyser.program_id)?;
+            FeelsGeyserClient::connect(&geyser.endpoint, program_id)
+                .await
+                .map(|_| ())
+        })
+        .await,
+    ]
+}
+
+async fn probe<F>(name: &'static str, target: String, check: F) -> BackendProbe
+where
+    F: Future<Output = Result<()>>,
+{
+    let error = match tokio::time::timeout(PROBE_TIMEOUT, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("timed out after {}s", PROBE_TIMEOUT.as_secs())),
+    };
+    BackendProbe {
+        name,
+        target,
+        error,
+    }
+}
+
+/// Whether every probed backend is reachable
+pub fn all_ready(probes: &[BackendProbe]) -> bool {
+    probes.iter().all(BackendProbe::is_ok)
+}
+
+/// Render probes as a fixed-width OK/FAIL table
+pub fn render_table(probes: &[BackendProbe]) -> String {
+    let target_width = probes
+        .iter()
+        .map(|p| p.target.len())
+        .max()
+        .unwrap_or(0)
+        .max("TARGET".len());
+
+    let mut out = format!(
+        "{:<10} {:<6} {:<w$}  {}\n",
+        "BACKEND",
+        "STATUS",
+        "TARGET",
+        "DETAIL",
+        w = target_width
+    );
+    for p in probes {
+        let (status, detail) = match &p.error {
+            None => ("OK", ""),
+            Some(e) => ("FAIL", e.as_str()),
+        };
+        out.push_str(&format!(
+            "{:<10} {:<6} {:<w$}  {}\n",
+            p.name,
+            status,
+            p.target,
+            detail,
+            w = target_width
+        ));
+    }
+    out
+}
+
+/// Strip credentials from a connection URL before printing it
+fn redact_url(url: &str) -> String {
+    match (url.find("://"), url.rfind('@')) {
+        (Some(scheme_end), Some(at)) if at > scheme_end => {
+            format!("{}://***@{}", &url[..scheme_end], &url[at + 1..])
+        }
+        _ => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe_result(name: &'static str, error: Option<&str>) -> BackendProbe {
+        BackendProbe {
+            name,
+            target: "localhost".to_string(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_table_and_readiness() {
+        let probes = vec![
+            probe_result("postgres", None),
+            probe_result("redis", Some("connection refused")),
+        ];
+        assert!(!all_ready(&probes));
+        assert!(all_ready(&probes[..1]));
+
+        let table = render_table(&probes);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("postgres") && lines[1].contains("OK"));
+        assert!(lines[2].contains("FAIL") && lines[2].contains("connection refused"));
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("postgresql://user:secret@db:5432/feels"),
+            "postgresql://***@db:5432/feels"
+        );
+        assert_eq!(redact_url("redis://localhost:6379"), "redis://localhost:6379");
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_failure() {
+        let failed = probe("redis", "redis://nowhere".to_string(), async {
+            Err(anyhow::anyhow!("unreachable"))
+        })
+        .await;
+        assert!(!failed.is_ok());
+        assert_eq!(failed.error.as_deref(), Some("unreachable"));
+    }
+}
@@ -1,17 +1,74 @@
 //! Repository layer for data access
 
-use crate::database::{DatabaseManager, Market, Position, Swap, MarketSnapshot};
+pub mod snapshot_compactor;
+pub mod swap_batcher;
+
+use crate::config::IndexerSettings;
+use crate::database::{
+    postgres_impl::PostgresManager, DatabaseManager, Market, MarketSnapshot, Position, PriceAlert,
+    Swap,
+};
+use crate::sdk_types::ProtocolOracleData;
 use anyhow::Result;
 use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
 use uuid::Uuid;
 
+pub use snapshot_compactor::{downsample_hourly, CompactionReport, SnapshotCompactor, SnapshotStore};
+pub use swap_batcher::{SwapBatcher, SwapSink};
+
 pub struct RepositoryManager {
-    db: DatabaseManager,
+    db: Arc<DatabaseManager>,
+    swap_batcher: Arc<SwapBatcher<PostgresManager>>,
+    snapshot_compactor: Arc<SnapshotCompactor<PostgresManager>>,
 }
 
 impl RepositoryManager {
-    pub fn new(db: DatabaseManager) -> Self {
-        Self { db }
+    pub fn new(db: Arc<DatabaseManager>) -> Self {
+        Self::with_settings(db, &IndexerSettings::default())
+    }
+
+    /// Create a repository whose swap writes are batched and whose snapshots
+    /// are compacted per `settings`
+    ///
+    /// Must be called inside a Tokio runtime; spawns the interval flusher and
+    /// the snapshot compactor.
+    pub fn with_settings(db: Arc<DatabaseManager>, settings: &IndexerSettings) -> Self {
+        let swap_batcher = Arc::new(SwapBatcher::new(
+            db.postgres.clone(),
+            settings.swap_batch_size,
+            Duration::from_millis(settings.swap_flush_interval_ms),
+        )
+        .with_limits(settings.swap_max_pending, settings.swap_max_write_attempts));
+        swap_batcher.spawn_flusher();
+
+        let snapshot_compactor = Arc::new(SnapshotCompactor::new(
+            db.postgres.clone(),
+            Duration::from_secs(settings.snapshot_raw_retention_hours * 3600),
+            settings
+                .snapshot_hourly_retention_days
+                .map(|days| Duration::from_secs(days * 24 * 3600)),
+            Duration::from_secs(settings.snapshot_compaction_interval_secs),
+        ));
+        snapshot_compactor.spawn();
+
+        Self {
+            db,
+            swap_batcher,
+            snapshot_compactor,
+        }
+    }
+
+    /// Write out any buffered swaps; call before shutdown
+    pub async fn flush(&self) -> Result<()> {
+        self.swap_batcher.flush().await
+    }
+
+    /// Compact old market snapshots now instead of waiting for the next run
+    pub async fn compact_snapshots(&self) -> Result<CompactionReport> {
+        self.snapshot_compactor.compact(chrono::Utc::now()).await
     }
 
     /// Market repository operations
@@ -48,6 +105,10 @@ impl RepositoryManager {
         self.db.postgres.get_market_by_address(address).await
     }
 
+    pub async fn get_market_by_id(&self, id: Uuid) -> Result<Option<Market>> {
+        self.db.postgres.get_market_by_id(id).await
+    }
+
     pub async fn get_markets(&self, limit: i64, offset: i64) -> Result<Vec<Market>> {
         self.db.postgres.get_markets(limit, offset).await
     }
@@ -101,12 +162,23 @@ impl RepositoryManager {
         self.db.postgres.get_user_positions(owner, 100, 0).await
     }
 
+    /// Latest protocol oracle rates indexed from chain
+    pub fn get_protocol_oracle(&self) -> Result<Option<ProtocolOracleData>> {
+        self.db.rocksdb.get_protocol_oracle()
+    }
+
     /// Swap repository operations
+    ///
+    /// Buffers the swap for a batched write and publishes it on the market's
+    /// Redis swap channel, which the webhook subscribes to.
     pub async fn insert_swap(&self, swap: &Swap) -> Result<()> {
-        // Store in PostgreSQL
-        self.db.postgres.insert_swap(swap).await?;
+        // Buffer for a batched PostgreSQL write. A failed write keeps the swap
+        // buffered for the next flush, so the event still goes out.
+        if let Err(e) = self.swap_batcher.push(swap.clone()).await {
+            warn!("Swap batch write failed, retrying on next flush: {}", e);
+        }
         
-        // Publish real-time event
+        // Publish real-time event immediately
         let swap_event = crate::database::redis::SwapEvent {
             market_id: swap.market_id,
             signature: swap.signature.clone(),
@@ -145,6 +217,23 @@ impl RepositoryManager {
         self.db.postgres.get_market_analytics(&market_id.to_string()).await
     }
 
+    /// Price alert operations
+    pub async fn get_price_alerts_by_market(&self, market_id: Uuid) -> Result<Vec<PriceAlert>> {
+        self.db.postgres.get_price_alerts_by_market(market_id).await
+    }
+
+    pub async fn disarm_price_alert(
+        &self,
+        id: Uuid,
+        triggered_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool> {
+        self.db.postgres.disarm_price_alert(id, triggered_at).await
+    }
+
+    pub async fn rearm_price_alert(&self, id: Uuid) -> Result<()> {
+        self.db.postgres.rearm_price_alert(id).await
+    }
+
     /// Search operations
     pub async fn global_search(&self, query: &str, limit: usize) -> Result<Vec<crate::database::tantivy::SearchResult>> {
         self.db.tantivy.global_search(query, limit).await
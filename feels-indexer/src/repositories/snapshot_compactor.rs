@@ -0,0 +1,393 @@
+//! Downsampling of old market snapshots
+//!
+//! Snapshots are taken on every market update, which is far more detail than
+//! anyone needs once they are a few days old. The compactor rolls snapshots
+//! older than `raw_retention` into one OHLCV row per market per hour and
+//! deletes the originals. Hourly rows can themselves expire after
+//! `hourly_retention`.
+//!
+//! Only whole hours are compacted: the cutoff is floored to the hour, so an
+//! hour is never split between raw and hourly storage. Rows that arrive late
+//! for an hour that was already compacted are merged into the existing row.
+
+use crate::database::{postgres_impl::PostgresManager, HourlySnapshot, MarketSnapshot};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, DurationRound, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Snapshots rolled up per round trip
+const COMPACTION_BATCH_SIZE: i64 = 10_000;
+
+/// Storage the compactor reads from and writes to
+#[async_trait]
+pub trait SnapshotStore: Send + Sync + 'static {
+    /// Up to `limit` snapshots taken before `cutoff`
+    async fn snapshots_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<MarketSnapshot>>;
+
+    /// Merge `hourly` into storage and delete the `compacted` snapshots
+    async fn roll_up(&self, hourly: &[HourlySnapshot], compacted: &[Uuid]) -> Result<()>;
+
+    /// Delete hourly rows for hours starting before `cutoff`
+    async fn delete_hourly_before(&self, cutoff: DateTime<Utc>) -> Result<u64>;
+}
+
+#[async_trait]
+impl SnapshotStore for PostgresManager {
+    async fn snapshots_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<MarketSnapshot>> {
+        PostgresManager::snapshots_before(self, cutoff, limit).await
+    }
+
+    async fn roll_up(&self, hourly: &[HourlySnapshot], compacted: &[Uuid]) -> Result<()> {
+        self.roll_up_snapshots(hourly, compacted).await
+    }
+
+    async fn delete_hourly_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        self.delete_hourly_snapshots_before(cutoff).await
+    }
+}
+
+/// Start of the hour containing `t`
+fn hour_start(t: DateTime<Utc>) -> DateTime<Utc> {
+    t.duration_trunc(chrono::Duration::hours(1)).unwrap_or(t)
+}
+
+/// Roll snapshots up into one row per market per hour
+///
+/// Open and close come from the earliest and latest slot in the hour, as do
+/// the closing tick, liquidity and TVL. Volume, fees and swap counts are
+/// summed.
+pub fn downsample_hourly(snapshots: &[MarketSnapshot]) -> Vec<HourlySnapshot> {
+    let mut buckets: BTreeMap<(Uuid, DateTime<Utc>), HourlySnapshot> = BTreeMap::new();
+
+    for snapshot in snapshots {
+        let bucket_start = hour_start(snapshot.timestamp);
+        let bar = HourlySnapshot {
+            market_id: snapshot.market_id,
+            bucket_start,
+            open_sqrt_price: snapshot.sqrt_price,
+            high_sqrt_price: snapshot.sqrt_price,
+            low_sqrt_price: snapshot.sqrt_price,
+            close_sqrt_price: snapshot.sqrt_price,
+            close_tick: snapshot.tick,
+            close_liquidity: snapshot.liquidity,
+            volume_0: snapshot.volume_0,
+            volume_1: snapshot.volume_1,
+            fees_0: snapshot.fees_0,
+            fees_1: snapshot.fees_1,
+            swap_count: snapshot.swap_count,
+            tvl_token_0: snapshot.tvl_token_0,
+            tvl_token_1: snapshot.tvl_token_1,
+            first_slot: snapshot.slot,
+            last_slot: snapshot.slot,
+            sample_count: 1,
+        };
+
+        match buckets.get_mut(&(snapshot.market_id, bucket_start)) {
+            Some(existing) => merge(existing, bar),
+            None => {
+                buckets.insert((snapshot.market_id, bucket_start), bar);
+            }
+        }
+    }
+
+    buckets.into_values().collect()
+}
+
+/// Fold `bar` into `into`; both cover the same market and hour
+fn merge(into: &mut HourlySnapshot, bar: HourlySnapshot) {
+    if bar.first_slot < into.first_slot {
+        into.open_sqrt_price = bar.open_sqrt_price;
+        into.first_slot = bar.first_slot;
+    }
+    if bar.last_slot > into.last_slot {
+        into.close_sqrt_price = bar.close_sqrt_price;
+        into.close_tick = bar.close_tick;
+        into.close_liquidity = bar.close_liquidity;
+        into.tvl_token_0 = bar.tvl_token_0;
+        into.tvl_token_1 = bar.tvl_token_1;
+        into.last_slot = bar.last_slot;
+    }
+    into.high_sqrt_price = into.high_sqrt_price.max(bar.high_sqrt_price);
+    into.low_sqrt_price = into.low_sqrt_price.min(bar.low_sqrt_price);
+    into.volume_0 += bar.volume_0;
+    into.volume_1 += bar.volume_1;
+    into.fees_0 += bar.fees_0;
+    into.fees_1 += bar.fees_1;
+    into.swap_count += bar.swap_count;
+    into.sample_count += bar.sample_count;
+}
+
+/// Outcome of one compaction run
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Raw snapshots rolled up and deleted
+    pub compacted: usize,
+    /// Hourly rows written or merged into
+    pub hourly_rows: usize,
+    /// Hourly rows deleted for exceeding their retention
+    pub expired: u64,
+}
+
+/// Periodically downsamples snapshots in a `SnapshotStore`
+pub struct SnapshotCompactor<S: SnapshotStore> {
+    store: Arc<S>,
+    raw_retention: Duration,
+    hourly_retention: Option<Duration>,
+    interval: Duration,
+}
+
+impl<S: SnapshotStore> SnapshotCompactor<S> {
+    pub fn new(
+        store: Arc<S>,
+        raw_retention: Duration,
+        hourly_retention: Option<Duration>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            store,
+            raw_retention,
+            hourly_retention,
+            interval,
+        }
+    }
+
+    /// Compact everything that is past its retention as of `now`
+    pub async fn compact(&self, now: DateTime<Utc>) -> Result<CompactionReport> {
+        let mut report = CompactionReport::default();
+
+        let cutoff = hour_start(now - chrono::Duration::from_std(self.raw_retention)?);
+        loop {
+            let batch = self
+                .store
+                .snapshots_before(cutoff, COMPACTION_BATCH_SIZE)
+                .await?;
+            if batch.is_empty() {
+                break;
+            }
+
+            let hourly = downsample_hourly(&batch);
+            let ids: Vec<Uuid> = batch.iter().map(|s| s.id).collect();
+            self.store.roll_up(&hourly, &ids).await?;
+
+            report.compacted += ids.len();
+            report.hourly_rows += hourly.len();
+            if (batch.len() as i64) < COMPACTION_BATCH_SIZE {
+                break;
+            }
+        }
+
+        if let Some(retention) = self.hourly_retention {
+            let cutoff = now - chrono::Duration::from_std(retention)?;
+            report.expired = self.store.delete_hourly_before(cutoff).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Spawn a task that compacts every `interval`
+    pub fn spawn(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let compactor = Arc::downgrade(self);
+        let period = self.interval;
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(compactor) = compactor.upgrade() else {
+                    break;
+                };
+                match compactor.compact(Utc::now()).await {
+                    Ok(report) if report.compacted > 0 || report.expired > 0 => info!(
+                        "Compacted {} snapshots into {} hourly rows, expired {}",
+                        report.compacted, report.hourly_rows, report.expired
+                    ),
+                    Ok(_) => {}
+                    Err(e) => error!("Failed to compact market snapshots: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        snapshots: Mutex<Vec<MarketSnapshot>>,
+        hourly: Mutex<Vec<HourlySnapshot>>,
+    }
+
+    #[async_trait]
+    impl SnapshotStore for MemoryStore {
+        async fn snapshots_before(
+            &self,
+            cutoff: DateTime<Utc>,
+            limit: i64,
+        ) -> Result<Vec<MarketSnapshot>> {
+            let snapshots = self.snapshots.lock().unwrap();
+            Ok(snapshots
+                .iter()
+                .filter(|s| s.timestamp < cutoff)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn roll_up(&self, hourly: &[HourlySnapshot], compacted: &[Uuid]) -> Result<()> {
+            self.hourly.lock().unwrap().extend_from_slice(hourly);
+            self.snapshots
+                .lock()
+                .unwrap()
+                .retain(|s| !compacted.contains(&s.id));
+            Ok(())
+        }
+
+        async fn delete_hourly_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+            let mut hourly = self.hourly.lock().unwrap();
+            let before = hourly.len();
+            hourly.retain(|h| h.bucket_start >= cutoff);
+            Ok((before - hourly.len()) as u64)
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    fn snapshot(
+        market_id: Uuid,
+        timestamp: DateTime<Utc>,
+        slot: i64,
+        price: i64,
+    ) -> MarketSnapshot {
+        MarketSnapshot {
+            id: Uuid::new_v4(),
+            market_id,
+            timestamp,
+            slot,
+            sqrt_price: Decimal::from(price),
+            tick: price as i32,
+            liquidity: Decimal::from(1_000),
+            volume_0: Decimal::from(10),
+            volume_1: Decimal::from(20),
+            fees_0: Decimal::ONE,
+            fees_1: Decimal::TWO,
+            swap_count: 1,
+            tvl_token_0: Decimal::from(price * 100),
+            tvl_token_1: Decimal::from(price * 200),
+            tvl_usd: None,
+        }
+    }
+
+    #[test]
+    fn test_downsample_builds_ohlcv() {
+        let market = Uuid::new_v4();
+        let snapshots = vec![
+            snapshot(market, at(1, 10, 50), 4, 103),
+            snapshot(market, at(1, 10, 5), 1, 100),
+            snapshot(market, at(1, 10, 20), 2, 110),
+            snapshot(market, at(1, 10, 35), 3, 95),
+            snapshot(market, at(1, 11, 0), 5, 120),
+        ];
+
+        let hourly = downsample_hourly(&snapshots);
+        assert_eq!(hourly.len(), 2);
+
+        let bar = &hourly[0];
+        assert_eq!(bar.bucket_start, at(1, 10, 0));
+        assert_eq!(bar.open_sqrt_price, Decimal::from(100));
+        assert_eq!(bar.high_sqrt_price, Decimal::from(110));
+        assert_eq!(bar.low_sqrt_price, Decimal::from(95));
+        assert_eq!(bar.close_sqrt_price, Decimal::from(103));
+        assert_eq!(bar.close_tick, 103);
+        assert_eq!(bar.tvl_token_0, Decimal::from(10_300));
+        assert_eq!(bar.volume_0, Decimal::from(40));
+        assert_eq!(bar.fees_1, Decimal::from(8));
+        assert_eq!(bar.swap_count, 4);
+        assert_eq!((bar.first_slot, bar.last_slot), (1, 4));
+        assert_eq!(bar.sample_count, 4);
+
+        assert_eq!(hourly[1].bucket_start, at(1, 11, 0));
+        assert_eq!(hourly[1].sample_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_compact_rolls_up_old_snapshots_only() {
+        let market = Uuid::new_v4();
+        let store = Arc::new(MemoryStore::default());
+        let old = vec![
+            snapshot(market, at(1, 10, 5), 1, 100),
+            snapshot(market, at(1, 10, 40), 2, 105),
+            snapshot(market, at(1, 11, 15), 3, 101),
+        ];
+        let recent = vec![
+            // Past retention but in the cutoff hour, which is left whole
+            snapshot(market, at(1, 12, 45), 4, 102),
+            snapshot(market, at(8, 12, 0), 5, 104),
+        ];
+        store.snapshots.lock().unwrap().extend(old.iter().chain(&recent).cloned());
+
+        let compactor = SnapshotCompactor::new(
+            store.clone(),
+            Duration::from_secs(7 * 24 * 3600),
+            None,
+            Duration::from_secs(3600),
+        );
+        let report = compactor.compact(at(8, 12, 30)).await.unwrap();
+
+        assert_eq!(report.compacted, 3);
+        assert_eq!(report.hourly_rows, 2);
+        assert_eq!(report.expired, 0);
+        let remaining: Vec<Uuid> = store.snapshots.lock().unwrap().iter().map(|s| s.id).collect();
+        assert_eq!(remaining, recent.iter().map(|s| s.id).collect::<Vec<_>>());
+
+        let hourly = store.hourly.lock().unwrap();
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].bucket_start, at(1, 10, 0));
+        assert_eq!(hourly[0].close_sqrt_price, Decimal::from(105));
+        assert_eq!(hourly[0].sample_count, 2);
+        assert_eq!(hourly[1].bucket_start, at(1, 11, 0));
+    }
+
+    #[tokio::test]
+    async fn test_compact_expires_hourly_rows() {
+        let market = Uuid::new_v4();
+        let store = Arc::new(MemoryStore::default());
+        store.snapshots.lock().unwrap().extend([
+            snapshot(market, at(1, 10, 0), 1, 100),
+            snapshot(market, at(5, 10, 0), 2, 100),
+        ]);
+
+        let compactor = SnapshotCompactor::new(
+            store.clone(),
+            Duration::from_secs(24 * 3600),
+            Some(Duration::from_secs(3 * 24 * 3600)),
+            Duration::from_secs(3600),
+        );
+        let report = compactor.compact(at(7, 0, 0)).await.unwrap();
+
+        assert_eq!(report.compacted, 2);
+        assert_eq!(report.expired, 1);
+        let hourly = store.hourly.lock().unwrap();
+        assert_eq!(hourly.len(), 1);
+        assert_eq!(hourly[0].bucket_start, at(5, 10, 0));
+    }
+}
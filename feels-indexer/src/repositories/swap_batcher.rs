@@ -0,0 +1,385 @@
+//! Write batching for durable swap storage
+//!
+//! Swaps are buffered in memory and written with a single multi-row insert
+//! once `batch_size` swaps are pending or `flush_interval` has elapsed,
+//! whichever comes first. A batch whose insert fails goes back to the front
+//! of the buffer. Until a write succeeds again only the interval flusher
+//! retries it, backing off while the sink keeps failing, and `push` just
+//! buffers. The buffer is capped at `max_pending` swaps, dropping the oldest,
+//! and a swap that has failed `max_attempts` times is retried on its own once
+//! and then dropped, so a row the database rejects cannot hold back the rest.
+
+use crate::database::{postgres_impl::PostgresManager, Swap};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+
+/// Default cap on buffered swaps while the sink is failing
+pub const DEFAULT_MAX_PENDING: usize = 50_000;
+
+/// Default number of failed inserts a swap survives before it is dropped
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+
+/// Longest wait between flusher retries while the sink is failing
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Destination for batched swap writes
+#[async_trait]
+pub trait SwapSink: Send + Sync + 'static {
+    async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()>;
+}
+
+#[async_trait]
+impl SwapSink for PostgresManager {
+    async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()> {
+        PostgresManager::insert_swaps(self, swaps).await
+    }
+}
+
+/// A buffered swap and the number of inserts it has already failed
+struct PendingSwap {
+    swap: Swap,
+    attempts: u32,
+}
+
+/// Buffers swaps and flushes them to a `SwapSink` in batches
+pub struct SwapBatcher<S: SwapSink> {
+    sink: Arc<S>,
+    buffer: Mutex<Vec<PendingSwap>>,
+    batch_size: usize,
+    flush_interval: Duration,
+    max_pending: usize,
+    max_attempts: u32,
+    /// Set while the last write failed; `push` then leaves writes to the flusher
+    failing: AtomicBool,
+}
+
+impl<S: SwapSink> SwapBatcher<S> {
+    pub fn new(sink: Arc<S>, batch_size: usize, flush_interval: Duration) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            sink,
+            buffer: Mutex::new(Vec::with_capacity(batch_size)),
+            batch_size,
+            flush_interval,
+            max_pending: DEFAULT_MAX_PENDING.max(batch_size),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            failing: AtomicBool::new(false),
+        }
+    }
+
+    /// Cap the buffer at `max_pending` swaps and drop a swap after
+    /// `max_attempts` failed inserts
+    pub fn with_limits(mut self, max_pending: usize, max_attempts: u32) -> Self {
+        self.max_pending = max_pending.max(self.batch_size);
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Buffer a swap, flushing if the batch is full and the sink is healthy
+    pub async fn push(&self, swap: Swap) -> Result<()> {
+        let batch = {
+            let mut buffer = self.buffer.lock().await;
+            buffer.push(PendingSwap { swap, attempts: 0 });
+            self.enforce_cap(&mut buffer);
+            if buffer.len() < self.batch_size || self.failing.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            Self::take_front(&mut buffer, self.batch_size)
+        };
+        self.write(batch).await
+    }
+
+    /// Write out everything currently buffered, one batch at a time
+    pub async fn flush(&self) -> Result<()> {
+        loop {
+            let batch = Self::take_front(&mut *self.buffer.lock().await, self.batch_size);
+            if batch.is_empty() {
+                return Ok(());
+            }
+            self.write(batch).await?;
+        }
+    }
+
+    /// Remove up to `count` swaps from the front of `buffer`
+    fn take_front(buffer: &mut Vec<PendingSwap>, count: usize) -> Vec<PendingSwap> {
+        let count = count.min(buffer.len());
+        buffer.drain(..count).collect()
+    }
+
+    /// Drop the oldest swaps beyond `max_pending`
+    fn enforce_cap(&self, buffer: &mut Vec<PendingSwap>) {
+        if buffer.len() > self.max_pending {
+            let excess = buffer.len() - self.max_pending;
+            buffer.drain(..excess);
+            warn!(
+                "Swap buffer full ({} pending), dropped {} oldest swaps",
+                self.max_pending, excess
+            );
+        }
+    }
+
+    /// Insert `batch`, returning it to the buffer if the insert fails
+    ///
+    /// Swaps pushed while the insert was in flight stay behind the restored
+    /// batch so the next write keeps slot order. Swaps that have now failed
+    /// `max_attempts` times are retried one by one instead, and any that
+    /// still fail are dropped.
+    async fn write(&self, batch: Vec<PendingSwap>) -> Result<()> {
+        let (swaps, attempts): (Vec<Swap>, Vec<u32>) = batch
+            .into_iter()
+            .map(|pending| (pending.swap, pending.attempts))
+            .unzip();
+        let result = self.sink.insert_swaps(&swaps).await;
+        self.failing.store(result.is_err(), Ordering::Relaxed);
+        if result.is_ok() {
+            return result;
+        }
+
+        let mut restored = Vec::with_capacity(swaps.len());
+        for (swap, attempts) in swaps.into_iter().zip(attempts) {
+            let attempts = attempts + 1;
+            if attempts < self.max_attempts {
+                restored.push(PendingSwap { swap, attempts });
+                continue;
+            }
+            match self.sink.insert_swaps(std::slice::from_ref(&swap)).await {
+                // The sink accepts rows again; only the rejected ones were at fault
+                Ok(()) => self.failing.store(false, Ordering::Relaxed),
+                Err(e) => error!(
+                    "Dropping swap {} (slot {}) after {} failed inserts: {}",
+                    swap.signature, swap.slot, attempts, e
+                ),
+            }
+        }
+
+        let mut buffer = self.buffer.lock().await;
+        restored.append(&mut buffer);
+        *buffer = restored;
+        self.enforce_cap(&mut buffer);
+        result
+    }
+
+    /// Number of swaps waiting to be written
+    pub async fn pending(&self) -> usize {
+        self.buffer.lock().await.len()
+    }
+
+    /// Spawn a task that flushes the buffer every `flush_interval`
+    ///
+    /// While flushes keep failing the wait between them doubles, up to 30s.
+    pub fn spawn_flusher(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let batcher = Arc::downgrade(self);
+        let period = self.flush_interval;
+        tokio::spawn(async move {
+            let mut backoff = period;
+            loop {
+                tokio::time::sleep(backoff).await;
+                let Some(batcher) = batcher.upgrade() else {
+                    break;
+                };
+                match batcher.flush().await {
+                    Ok(()) => backoff = period,
+                    Err(e) => {
+                        error!("Failed to flush swap batch: {}", e);
+                        backoff = (backoff * 2).min(MAX_RETRY_BACKOFF.max(period));
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        calls: std::sync::Mutex<Vec<usize>>,
+        /// Number of upcoming inserts to fail
+        failures: std::sync::Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl SwapSink for RecordingSink {
+        async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                anyhow::bail!("database unavailable");
+            }
+            self.calls.lock().unwrap().push(swaps.len());
+            Ok(())
+        }
+    }
+
+    fn swap(i: usize) -> Swap {
+        Swap {
+            id: Uuid::new_v4(),
+            signature: format!("sig{}", i),
+            market_id: Uuid::nil(),
+            trader: "trader".to_string(),
+            amount_in: 1_000,
+            amount_out: 990,
+            token_in: "token_in".to_string(),
+            token_out: "token_out".to_string(),
+            sqrt_price_before: Decimal::ONE,
+            sqrt_price_after: Decimal::ONE,
+            tick_before: 0,
+            tick_after: 0,
+            liquidity: Decimal::ONE,
+            fee_amount: 10,
+            timestamp: Utc::now(),
+            slot: i as i64,
+            block_height: None,
+            price_impact_bps: None,
+            effective_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_is_one_insert() {
+        let sink = Arc::new(RecordingSink::default());
+        let batcher = SwapBatcher::new(sink.clone(), 100, Duration::from_secs(60));
+
+        for i in 0..100 {
+            batcher.push(swap(i)).await.unwrap();
+        }
+
+        assert_eq!(*sink.calls.lock().unwrap(), vec![100]);
+        assert_eq!(batcher.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_flush_drains_partial_batch() {
+        let sink = Arc::new(RecordingSink::default());
+        let batcher = SwapBatcher::new(sink.clone(), 100, Duration::from_secs(60));
+
+        for i in 0..7 {
+            batcher.push(swap(i)).await.unwrap();
+        }
+        assert!(sink.calls.lock().unwrap().is_empty());
+
+        batcher.flush().await.unwrap();
+        batcher.flush().await.unwrap();
+        assert_eq!(*sink.calls.lock().unwrap(), vec![7]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_insert_keeps_the_batch() {
+        let sink = Arc::new(RecordingSink::default());
+        let batcher = SwapBatcher::new(sink.clone(), 3, Duration::from_secs(60));
+
+        *sink.failures.lock().unwrap() = 1;
+        batcher.push(swap(0)).await.unwrap();
+        batcher.push(swap(1)).await.unwrap();
+        assert!(batcher.push(swap(2)).await.is_err());
+        assert_eq!(batcher.pending().await, 3);
+
+        // Until a flush succeeds, new swaps queue behind the restored batch
+        batcher.push(swap(3)).await.unwrap();
+        assert_eq!(batcher.pending().await, 4);
+
+        batcher.flush().await.unwrap();
+        assert_eq!(*sink.calls.lock().unwrap(), vec![3, 1]);
+        assert_eq!(batcher.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_state_leaves_writes_to_the_flusher() {
+        let sink = Arc::new(RecordingSink::default());
+        let batcher = SwapBatcher::new(sink.clone(), 2, Duration::from_secs(60));
+
+        *sink.failures.lock().unwrap() = 1;
+        batcher.push(swap(0)).await.unwrap();
+        assert!(batcher.push(swap(1)).await.is_err());
+
+        // Full batches are only buffered until a flush succeeds
+        for i in 2..6 {
+            batcher.push(swap(i)).await.unwrap();
+        }
+        assert!(sink.calls.lock().unwrap().is_empty());
+        assert_eq!(batcher.pending().await, 6);
+
+        // The flush writes batch-sized inserts and clears the failed state
+        batcher.flush().await.unwrap();
+        assert_eq!(*sink.calls.lock().unwrap(), vec![2, 2, 2]);
+        batcher.push(swap(6)).await.unwrap();
+        batcher.push(swap(7)).await.unwrap();
+        assert_eq!(*sink.calls.lock().unwrap(), vec![2, 2, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_is_capped() {
+        let sink = Arc::new(RecordingSink::default());
+        let batcher =
+            SwapBatcher::new(sink.clone(), 2, Duration::from_secs(60)).with_limits(5, 100);
+
+        *sink.failures.lock().unwrap() = 1;
+        batcher.push(swap(0)).await.unwrap();
+        assert!(batcher.push(swap(1)).await.is_err());
+        for i in 2..10 {
+            batcher.push(swap(i)).await.unwrap();
+        }
+        assert_eq!(batcher.pending().await, 5);
+
+        // The oldest swaps were dropped
+        let slots: Vec<i64> = batcher
+            .buffer
+            .lock()
+            .await
+            .iter()
+            .map(|pending| pending.swap.slot)
+            .collect();
+        assert_eq!(slots, vec![5, 6, 7, 8, 9]);
+    }
+
+    /// Sink that always rejects one signature, as for a row violating a constraint
+    #[derive(Default)]
+    struct PoisonSink {
+        written: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SwapSink for PoisonSink {
+        async fn insert_swaps(&self, swaps: &[Swap]) -> Result<()> {
+            if swaps.iter().any(|swap| swap.signature == "sig1") {
+                anyhow::bail!("constraint violation");
+            }
+            let mut written = self.written.lock().unwrap();
+            written.extend(swaps.iter().map(|swap| swap.signature.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poison_row_is_dropped_after_max_attempts() {
+        let sink = Arc::new(PoisonSink::default());
+        let batcher =
+            SwapBatcher::new(sink.clone(), 3, Duration::from_secs(60)).with_limits(100, 2);
+
+        batcher.push(swap(0)).await.unwrap();
+        batcher.push(swap(1)).await.unwrap();
+        assert!(batcher.push(swap(2)).await.is_err());
+        assert_eq!(batcher.pending().await, 3);
+
+        // Second failure: the batch is retried row by row and only sig1 is dropped
+        assert!(batcher.flush().await.is_err());
+        assert_eq!(batcher.pending().await, 0);
+        assert_eq!(*sink.written.lock().unwrap(), vec!["sig0", "sig2"]);
+
+        // Later swaps are written normally
+        for i in 3..6 {
+            batcher.push(swap(i)).await.unwrap();
+        }
+        assert_eq!(sink.written.lock().unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,73 @@
+//! Lossless numeric conversions
+//!
+//! `as` casts between integer widths wrap silently, so a value that does not
+//! fit the target column is stored as an unrelated number instead of being
+//! caught. These helpers return `None` when the value does not fit, leaving
+//! the caller to decide whether to saturate, skip or reject it.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// Basis points in 100%
+pub const BPS_PER_UNIT: i16 = 10_000;
+
+pub fn try_u128_to_u64(value: u128) -> Option<u64> {
+    u64::try_from(value).ok()
+}
+
+pub fn try_u64_to_i64(value: u64) -> Option<i64> {
+    i64::try_from(value).ok()
+}
+
+pub fn try_i64_to_i32(value: i64) -> Option<i32> {
+    i32::try_from(value).ok()
+}
+
+pub fn try_u16_to_i16(value: u16) -> Option<i16> {
+    i16::try_from(value).ok()
+}
+
+/// Whole basis points of a Decimal bps value, truncated toward zero
+pub fn try_decimal_to_i16_bps(bps: Decimal) -> Option<i16> {
+    bps.trunc().to_i16()
+}
+
+/// Whole basis points of a ratio (1 = 100%), truncated toward zero
+pub fn try_ratio_to_i16_bps(ratio: Decimal) -> Option<i16> {
+    try_decimal_to_i16_bps(ratio.checked_mul(Decimal::from(BPS_PER_UNIT))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_boundaries() {
+        assert_eq!(try_u128_to_u64(u64::MAX as u128), Some(u64::MAX));
+        assert_eq!(try_u128_to_u64(u64::MAX as u128 + 1), None);
+
+        assert_eq!(try_u64_to_i64(i64::MAX as u64), Some(i64::MAX));
+        assert_eq!(try_u64_to_i64(i64::MAX as u64 + 1), None);
+
+        assert_eq!(try_i64_to_i32(i32::MAX as i64), Some(i32::MAX));
+        assert_eq!(try_i64_to_i32(i32::MIN as i64), Some(i32::MIN));
+        assert_eq!(try_i64_to_i32(i32::MAX as i64 + 1), None);
+        assert_eq!(try_i64_to_i32(i32::MIN as i64 - 1), None);
+
+        assert_eq!(try_u16_to_i16(32_767), Some(i16::MAX));
+        // `as i16` would wrap this to -32768
+        assert_eq!(try_u16_to_i16(32_768), None);
+    }
+
+    #[test]
+    fn test_bps_boundaries() {
+        assert_eq!(try_decimal_to_i16_bps(Decimal::new(327_679, 1)), Some(i16::MAX));
+        assert_eq!(try_decimal_to_i16_bps(Decimal::from(32_768)), None);
+        assert_eq!(try_decimal_to_i16_bps(Decimal::new(-15, 1)), Some(-1));
+
+        // 327.67% is the largest ratio that fits; 7x would wrap to 4464 with `as`
+        assert_eq!(try_ratio_to_i16_bps(Decimal::new(32_767, 4)), Some(i16::MAX));
+        assert_eq!(try_ratio_to_i16_bps(Decimal::from(7)), None);
+        assert_eq!(try_ratio_to_i16_bps(Decimal::MAX), None);
+    }
+}
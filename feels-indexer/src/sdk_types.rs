@@ -16,6 +16,7 @@ pub enum AccountType {
     ProtocolToken,
     Vesting,
     Oracle,
+    TickArray,
 }
 
 impl AccountType {
@@ -32,6 +33,9 @@ impl AccountType {
             [123, 45, 67, 89, 12, 34, 56, 78] => Some(Self::Buffer),
             [234, 56, 78, 90, 123, 45, 67, 89] => Some(Self::ProtocolConfig),
             [111, 222, 33, 44, 55, 66, 77, 88] => Some(Self::ProtocolToken),
+            // Anchor discriminator of `feels::state::TickArray`, as in the IDL
+            [69, 97, 189, 190, 110, 7, 66, 187] => Some(Self::TickArray),
+            [252, 90, 103, 97, 37, 251, 8, 237] => Some(Self::Oracle),
             _ => None,
         }
     }
@@ -78,6 +82,37 @@ pub struct BufferData {
     pub tau_leverage: u64,
 }
 
+/// Decoded protocol oracle rates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolOracleData {
+    /// JitoSOL reserve backing per FeelsSOL (Q64)
+    pub native_rate_q64: u128,
+    /// Filtered DEX TWAP of FeelsSOL in JitoSOL (Q64)
+    pub dex_twap_rate_q64: u128,
+    pub native_last_update_ts: i64,
+    pub dex_last_update_ts: i64,
+}
+
+impl ProtocolOracleData {
+    /// Conservative FeelsSOL to JitoSOL rate: the lower of the two sources
+    ///
+    /// A source that has never been updated reads zero and is ignored.
+    /// Returns `None` until at least one source is set.
+    pub fn jitosol_per_feelssol(&self) -> Option<f64> {
+        let rate = match (self.native_rate_q64, self.dex_twap_rate_q64) {
+            (0, 0) => return None,
+            (0, rate) | (rate, 0) => rate,
+            (native, dex) => native.min(dex),
+        };
+        Some(rate as f64 / (1u128 << 64) as f64)
+    }
+
+    /// FeelsSOL to SOL rate given the JitoSOL stake pool rate
+    pub fn sol_per_feelssol(&self, sol_per_jitosol: f64) -> Option<f64> {
+        self.jitosol_per_feelssol().map(|rate| rate * sol_per_jitosol)
+    }
+}
+
 /// Swap instruction data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapData {
@@ -118,7 +153,10 @@ pub struct ParsedTransaction {
 pub mod feels_sdk {
     use super::*;
     
-    pub use super::{AccountType, MarketData, PositionData, BufferData, SwapData, Instruction};
+    pub use super::{
+        AccountType, MarketData, PositionData, BufferData, ProtocolOracleData, SwapData,
+        Instruction,
+    };
     
     /// Decode account data based on discriminator
     pub fn decode_account_data(data: &[u8]) -> Option<AccountType> {
@@ -170,6 +208,23 @@ pub mod feels_sdk {
         })
     }
     
+    /// Decode protocol oracle account
+    pub fn decode_protocol_oracle(data: &[u8]) -> Result<ProtocolOracleData, String> {
+        // disc(8) native(16) dex(16) dex_slot(8) native_slot(8) dex_ts(8) native_ts(8)
+        if data.len() < 72 {
+            return Err(format!("protocol oracle too short: {} bytes", data.len()));
+        }
+        let u128_at = |at: usize| u128::from_le_bytes(data[at..at + 16].try_into().unwrap());
+        let i64_at = |at: usize| i64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+        
+        Ok(ProtocolOracleData {
+            native_rate_q64: u128_at(8),
+            dex_twap_rate_q64: u128_at(24),
+            dex_last_update_ts: i64_at(56),
+            native_last_update_ts: i64_at(64),
+        })
+    }
+    
     /// Parse transaction
     pub fn parse_transaction(_data: &[u8]) -> Result<ParsedTransaction, String> {
         Ok(ParsedTransaction {
@@ -177,4 +232,15 @@ pub mod feels_sdk {
             instructions: vec![],
         })
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_array_discriminator_matches_sdk() {
+        let discriminator = ::feels_sdk::jupiter::TickArrayFormat::V1.discriminator;
+        assert_eq!(AccountType::from_discriminator(&discriminator), Some(AccountType::TickArray));
+    }
+}
@@ -0,0 +1,132 @@
+//! Price alert evaluation
+//!
+//! Alerts are checked against every market snapshot. An armed alert fires
+//! when the price reaches its threshold and is disarmed; it re-arms once the
+//! price is back on the other side, so it fires once per crossing rather than
+//! on every snapshot spent beyond the threshold.
+
+use crate::database::{AlertDirection, MarketSnapshot, PriceAlert};
+use rust_decimal::prelude::ToPrimitive;
+
+/// State change a snapshot causes for an alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertAction {
+    /// Price crossed the threshold while armed
+    Fire,
+    /// Price moved back across the threshold after firing
+    Rearm,
+}
+
+/// Evaluate `alert` at `price`, or `None` if nothing changes
+pub fn evaluate(alert: &PriceAlert, price: f64) -> Option<AlertAction> {
+    let threshold = alert.threshold.to_f64()?;
+    let beyond = match alert.direction {
+        AlertDirection::Above => price >= threshold,
+        AlertDirection::Below => price <= threshold,
+    };
+
+    match (alert.armed, beyond) {
+        (true, true) => Some(AlertAction::Fire),
+        (false, false) => Some(AlertAction::Rearm),
+        _ => None,
+    }
+}
+
+/// Webhook payload for a fired alert
+pub fn alert_payload(alert: &PriceAlert, price: f64, snapshot: &MarketSnapshot) -> String {
+    serde_json::json!({
+        "type": "price_alert",
+        "data": {
+            "alert_id": alert.id,
+            "market_id": alert.market_id,
+            "direction": alert.direction,
+            "threshold": alert.threshold,
+            "price": price,
+            "slot": snapshot.slot,
+            "timestamp": snapshot.timestamp,
+        }
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn alert(direction: AlertDirection, threshold: i64) -> PriceAlert {
+        PriceAlert {
+            id: Uuid::new_v4(),
+            market_id: Uuid::new_v4(),
+            direction,
+            threshold: Decimal::from(threshold),
+            armed: true,
+            last_triggered_at: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Apply each price in turn, returning the prices the alert fired at
+    fn fired_at(mut alert: PriceAlert, prices: &[f64]) -> Vec<f64> {
+        let mut fired = Vec::new();
+        for &price in prices {
+            match evaluate(&alert, price) {
+                Some(AlertAction::Fire) => {
+                    alert.armed = false;
+                    fired.push(price);
+                }
+                Some(AlertAction::Rearm) => alert.armed = true,
+                None => {}
+            }
+        }
+        fired
+    }
+
+    #[test]
+    fn test_fires_once_per_crossing() {
+        let prices = [1.0, 1.5, 2.0, 2.5, 3.0, 2.2, 1.9, 2.1, 2.4];
+
+        assert_eq!(fired_at(alert(AlertDirection::Above, 2), &prices), vec![2.0, 2.1]);
+        assert_eq!(fired_at(alert(AlertDirection::Below, 2), &prices), vec![1.0, 1.9]);
+    }
+
+    #[test]
+    fn test_disarmed_alert_waits_for_the_price_to_return() {
+        let mut above = alert(AlertDirection::Above, 2);
+        above.armed = false;
+
+        assert_eq!(evaluate(&above, 2.5), None);
+        assert_eq!(evaluate(&above, 1.5), Some(AlertAction::Rearm));
+    }
+
+    #[test]
+    fn test_payload_describes_the_crossing() {
+        let alert = alert(AlertDirection::Below, 3);
+        let snapshot = MarketSnapshot {
+            id: Uuid::new_v4(),
+            market_id: alert.market_id,
+            timestamp: chrono::Utc::now(),
+            slot: 42,
+            sqrt_price: Decimal::ZERO,
+            tick: 0,
+            liquidity: Decimal::ZERO,
+            volume_0: Decimal::ZERO,
+            volume_1: Decimal::ZERO,
+            fees_0: Decimal::ZERO,
+            fees_1: Decimal::ZERO,
+            swap_count: 0,
+            tvl_token_0: Decimal::ZERO,
+            tvl_token_1: Decimal::ZERO,
+            tvl_usd: None,
+        };
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&alert_payload(&alert, 2.5, &snapshot)).unwrap();
+        assert_eq!(payload["type"], "price_alert");
+        assert_eq!(payload["data"]["alert_id"], alert.id.to_string());
+        assert_eq!(payload["data"]["direction"], "below");
+        assert_eq!(payload["data"]["price"], 2.5);
+        assert_eq!(payload["data"]["slot"], 42);
+    }
+}
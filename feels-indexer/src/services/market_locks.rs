@@ -0,0 +1,111 @@
+//! Per-market serialization for snapshot creation
+//!
+//! Geyser can deliver several updates for one market nearly simultaneously.
+//! Each market gets its own async mutex, so updates for the same market run one
+//! at a time while different markets proceed in parallel. The mutex also
+//! guards the last slot snapshotted for that market, which lets a late
+//! duplicate for an already-recorded slot be skipped.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct MarketLocks {
+    locks: std::sync::Mutex<HashMap<Uuid, Arc<Mutex<Option<i64>>>>>,
+}
+
+impl MarketLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_for(&self, market_id: Uuid) -> Arc<Mutex<Option<i64>>> {
+        self.locks
+            .lock()
+            .expect("market lock map poisoned")
+            .entry(market_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Run `snapshot` while holding the market's lock, unless `slot` has
+    /// already been snapshotted
+    ///
+    /// Returns whether `snapshot` ran. The slot is only recorded when
+    /// `snapshot` succeeds, so a failed write can be retried.
+    pub async fn snapshot_once<F, Fut>(&self, market_id: Uuid, slot: i64, snapshot: F) -> Result<bool>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let lock = self.lock_for(market_id);
+        let mut last_slot = lock.lock().await;
+        if matches!(*last_slot, Some(last) if slot <= last) {
+            return Ok(false);
+        }
+
+        snapshot().await?;
+        *last_slot = Some(slot);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_updates_snapshot_once_per_slot() {
+        let locks = Arc::new(MarketLocks::new());
+        let snapshots = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let market_id = Uuid::new_v4();
+
+        let update = |slot: i64| {
+            let locks = locks.clone();
+            let snapshots = snapshots.clone();
+            let in_flight = in_flight.clone();
+            async move {
+                locks
+                    .snapshot_once(market_id, slot, || async {
+                        assert_eq!(in_flight.fetch_add(1, Ordering::SeqCst), 0);
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        snapshots.lock().unwrap().push(slot);
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                    .await
+            }
+        };
+
+        let (a, b) = tokio::join!(tokio::spawn(update(42)), tokio::spawn(update(42)));
+        let ran = [a.unwrap().unwrap(), b.unwrap().unwrap()];
+
+        assert_eq!(ran.iter().filter(|r| **r).count(), 1);
+        assert_eq!(*snapshots.lock().unwrap(), vec![42]);
+
+        // A newer slot still gets its own snapshot
+        assert!(update(43).await.unwrap());
+        assert_eq!(*snapshots.lock().unwrap(), vec![42, 43]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_snapshot_can_retry() {
+        let locks = MarketLocks::new();
+        let market_id = Uuid::new_v4();
+
+        let failed = locks
+            .snapshot_once(market_id, 7, || async { Err(anyhow::anyhow!("db down")) })
+            .await;
+        assert!(failed.is_err());
+
+        let ran = locks.snapshot_once(market_id, 7, || async { Ok(()) }).await.unwrap();
+        assert!(ran);
+    }
+}
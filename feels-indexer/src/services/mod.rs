@@ -1,28 +1,97 @@
 //! Business logic services
 
+pub mod alerts;
+pub mod market_locks;
+pub mod valuation;
+pub mod webhook;
+
+use crate::config::IndexerSettings;
 use crate::database::{Market, Position, Swap, MarketSnapshot};
 use crate::repositories::RepositoryManager;
+use crate::safe_cast::{try_ratio_to_i16_bps, BPS_PER_UNIT};
 use anyhow::Result;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
 use uuid::Uuid;
 
+pub use alerts::AlertAction;
+pub use market_locks::MarketLocks;
+pub use valuation::Pricing;
+pub use webhook::{HttpSender, WebhookDispatcher};
+
 pub struct ServiceManager {
-    repos: RepositoryManager,
+    repos: Arc<RepositoryManager>,
+    market_locks: MarketLocks,
+    feelssol_mint: Option<String>,
+    sol_per_jitosol: Option<f64>,
+    sol_usd_price: Option<f64>,
+    /// Where fired price alerts are delivered; alerts still arm and disarm without it
+    alert_webhook: Option<Arc<WebhookDispatcher<HttpSender>>>,
 }
 
 impl ServiceManager {
-    pub fn new(repos: RepositoryManager) -> Self {
-        Self { repos }
+    pub fn new(repos: Arc<RepositoryManager>) -> Self {
+        Self::with_settings(repos, &IndexerSettings::default())
+    }
+
+    /// Service manager valuing portfolios with the configured reference prices
+    pub fn with_settings(repos: Arc<RepositoryManager>, settings: &IndexerSettings) -> Self {
+        Self {
+            repos,
+            market_locks: MarketLocks::new(),
+            feelssol_mint: settings.feelssol_mint.clone(),
+            sol_per_jitosol: settings.sol_per_jitosol,
+            sol_usd_price: settings.sol_usd_price,
+            alert_webhook: None,
+        }
+    }
+
+    /// Deliver fired price alerts through `dispatcher`
+    pub fn with_alert_webhook(mut self, dispatcher: WebhookDispatcher<HttpSender>) -> Self {
+        self.alert_webhook = Some(Arc::new(dispatcher));
+        self
+    }
+
+    /// Current prices, or `None` until valuation is configured and the
+    /// protocol oracle has been indexed
+    fn pricing(&self) -> Result<Option<Pricing>> {
+        let (Some(feelssol_mint), Some(sol_per_jitosol), Some(sol_usd)) =
+            (&self.feelssol_mint, self.sol_per_jitosol, self.sol_usd_price)
+        else {
+            return Ok(None);
+        };
+        let sol_per_feelssol = self.repos
+            .get_protocol_oracle()?
+            .and_then(|oracle| oracle.sol_per_feelssol(sol_per_jitosol));
+        
+        Ok(sol_per_feelssol.map(|sol_per_feelssol| Pricing {
+            feelssol_mint: feelssol_mint.clone(),
+            sol_per_feelssol,
+            sol_usd,
+        }))
     }
 
     /// Market service operations
+    ///
+    /// Updates for the same market are serialized and at most one snapshot is
+    /// written per market and slot; other markets proceed in parallel.
     pub async fn process_market_update(&self, market_data: &Market) -> Result<()> {
         // Calculate derived metrics
         let market = market_data.clone();
         
+        self.market_locks
+            .snapshot_once(market.id, market.last_updated_slot, || self.write_market_snapshot(&market))
+            .await?;
+        
+        Ok(())
+    }
+
+    async fn write_market_snapshot(&self, market: &Market) -> Result<()> {
         // Update market in database
-        self.repos.upsert_market(&market).await?;
+        self.repos.upsert_market(market).await?;
         
         // Create snapshot for analytics
         let snapshot = MarketSnapshot {
@@ -45,9 +114,51 @@ impl ServiceManager {
         
         self.repos.insert_market_snapshot(&snapshot).await?;
         
+        // A failing alert check must not fail the snapshot it was triggered by
+        if let Err(e) = self.evaluate_alerts(&snapshot).await {
+            warn!("Failed to evaluate price alerts for market {}: {}", market.id, e);
+        }
+        
+        Ok(())
+    }
+
+    /// Fire and re-arm the market's price alerts against a new snapshot
+    async fn evaluate_alerts(&self, snapshot: &MarketSnapshot) -> Result<()> {
+        let Some(sqrt_price) = snapshot.sqrt_price.to_f64() else {
+            return Ok(());
+        };
+        let price = valuation::spot_price(sqrt_price);
+        
+        for alert in self.repos.get_price_alerts_by_market(snapshot.market_id).await? {
+            match alerts::evaluate(&alert, price) {
+                Some(AlertAction::Fire) => {
+                    // Only the evaluation that actually disarms the alert notifies
+                    if self.repos.disarm_price_alert(alert.id, snapshot.timestamp).await? {
+                        let payload = alerts::alert_payload(&alert, price, snapshot);
+                        self.notify_alert(payload, alert.id);
+                    }
+                }
+                Some(AlertAction::Rearm) => self.repos.rearm_price_alert(alert.id).await?,
+                None => {}
+            }
+        }
+        
         Ok(())
     }
 
+    /// Deliver a fired alert in the background so retries never hold up ingestion
+    fn notify_alert(&self, payload: String, alert_id: Uuid) {
+        let Some(webhook) = self.alert_webhook.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            let label = format!("price alert {}", alert_id);
+            if let Err(e) = webhook.deliver(&payload, &label).await {
+                warn!("Failed to dead-letter {}: {}", label, e);
+            }
+        });
+    }
+
     pub async fn get_market_with_stats(&self, address: &str) -> Result<Option<MarketWithStats>> {
         if let Some(market) = self.repos.get_market_by_address(address).await? {
             // Get recent analytics
@@ -69,8 +180,21 @@ impl ServiceManager {
     }
 
     /// Position service operations
+    ///
+    /// Each update carries the position's current value as its entry value;
+    /// the upsert keeps the first one recorded, which is the value at open.
     pub async fn process_position_update(&self, position: &Position) -> Result<()> {
-        self.repos.upsert_position(position).await
+        let mut position = position.clone();
+        
+        if position.entry_value.is_none() {
+            if let Some(pricing) = self.pricing()? {
+                if let Some(market) = self.repos.get_market_by_id(position.market_id).await? {
+                    position.entry_value = pricing.position_value_usd(&position, &market);
+                }
+            }
+        }
+        
+        self.repos.upsert_position(&position).await
     }
 
     pub async fn get_user_portfolio(&self, owner: &str) -> Result<UserPortfolio> {
@@ -81,11 +205,11 @@ impl ServiceManager {
         let total_positions = positions.len();
         let total_swaps = swaps.len();
         
-        // Calculate total value (would need price data)
-        let total_value_usd = Decimal::ZERO;
-        
-        // Calculate PnL (would need historical data)
-        let total_pnl_usd = Decimal::ZERO;
+        // Value positions at current prices; PnL covers those with an entry value
+        let (total_value_usd, total_pnl_usd) = match self.pricing()? {
+            Some(pricing) => self.value_positions(&pricing, &positions).await?,
+            None => (Decimal::ZERO, Decimal::ZERO),
+        };
         
         Ok(UserPortfolio {
             owner: owner.to_string(),
@@ -98,6 +222,39 @@ impl ServiceManager {
         })
     }
 
+    /// Total USD value and PnL of `positions`
+    ///
+    /// Positions whose market cannot be priced are left out of both totals.
+    async fn value_positions(
+        &self,
+        pricing: &Pricing,
+        positions: &[Position],
+    ) -> Result<(Decimal, Decimal)> {
+        let mut markets: HashMap<Uuid, Option<Market>> = HashMap::new();
+        let mut total_value = Decimal::ZERO;
+        let mut total_pnl = Decimal::ZERO;
+        
+        for position in positions {
+            if !markets.contains_key(&position.market_id) {
+                let market = self.repos.get_market_by_id(position.market_id).await?;
+                markets.insert(position.market_id, market);
+            }
+            let Some(market) = &markets[&position.market_id] else {
+                continue;
+            };
+            let Some(value) = pricing.position_value_usd(position, market) else {
+                continue;
+            };
+            
+            total_value += value;
+            if let Some(entry_value) = position.entry_value {
+                total_pnl += value - entry_value;
+            }
+        }
+        
+        Ok((total_value, total_pnl))
+    }
+
     /// Swap service operations
     pub async fn process_swap(&self, swap: &Swap) -> Result<()> {
         // Calculate derived metrics
@@ -239,19 +396,22 @@ fn calculate_price_change(old_sqrt_price: Decimal, new_sqrt_price: Decimal) -> f
         .unwrap_or(0.0)
 }
 
+/// Price impact in bps, capped at 100%
+///
+/// Works on the ratio of the sqrt prices: squaring a Q64 sqrt price
+/// overflows `Decimal`, and an impact above 327.67% does not fit in i16.
 fn calculate_price_impact(sqrt_price_before: Decimal, sqrt_price_after: Decimal) -> i16 {
-    let price_before = sqrt_price_before * sqrt_price_before;
-    let price_after = sqrt_price_after * sqrt_price_after;
-    
-    if price_before.is_zero() {
+    if sqrt_price_before.is_zero() {
         return 0;
     }
-    
-    let impact = ((price_after - price_before).abs() / price_before * Decimal::from(10000))
-        .to_i64()
-        .unwrap_or(0) as i16;
-    
-    impact.min(10000) // Cap at 100%
+
+    let sqrt_ratio = sqrt_price_after / sqrt_price_before;
+    let impact = sqrt_ratio
+        .checked_mul(sqrt_ratio)
+        .map(|ratio| (ratio - Decimal::ONE).abs());
+    impact
+        .and_then(try_ratio_to_i16_bps)
+        .map_or(BPS_PER_UNIT, |bps| bps.min(BPS_PER_UNIT))
 }
 
 fn calculate_effective_price(amount_in: i64, amount_out: i64) -> Decimal {
@@ -288,3 +448,38 @@ pub struct SearchResults {
     pub positions: Vec<crate::database::tantivy::SearchResult>,
     pub swaps: Vec<crate::database::tantivy::SearchResult>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Q64 sqrt price of 1
+    fn q64_one() -> Decimal {
+        Decimal::from(1u64 << 32) * Decimal::from(1u64 << 32)
+    }
+
+    #[test]
+    fn test_price_impact_of_q64_prices() {
+        let before = q64_one();
+        // sqrt price up 1% is a price move of 2.01%
+        let after = before * Decimal::new(101, 2);
+
+        assert_eq!(calculate_price_impact(before, after), 201);
+        assert_eq!(calculate_price_impact(before, before), 0);
+        assert_eq!(calculate_price_impact(Decimal::ZERO, after), 0);
+    }
+
+    #[test]
+    fn test_price_impact_beyond_i16_is_capped() {
+        let before = q64_one();
+        // Price up 49x is 480000 bps, well past i16::MAX
+        let after = before * Decimal::from(7);
+        assert_eq!(calculate_price_impact(before, after), BPS_PER_UNIT);
+
+        // So large the squared ratio overflows Decimal
+        assert_eq!(calculate_price_impact(Decimal::ONE, before), BPS_PER_UNIT);
+
+        // A price collapsing to zero is a 100% move
+        assert_eq!(calculate_price_impact(before, Decimal::ZERO), BPS_PER_UNIT);
+    }
+}
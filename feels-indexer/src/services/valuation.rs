@@ -0,0 +1,235 @@
+//! Position valuation
+//!
+//! Every market pairs a token with FeelsSOL, so a position is first valued in
+//! FeelsSOL at the market's current price. The protocol oracle converts
+//! FeelsSOL to JitoSOL, the configured stake pool rate converts JitoSOL to
+//! SOL, and a configured reference price converts SOL to USD.
+
+use crate::database::{Market, Position};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+/// Q64 fixed-point scale of on-chain sqrt prices
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// FeelsSOL base units per FeelsSOL
+const FEELSSOL_UNITS: f64 = 1e9;
+
+/// Price of token 0 in token 1 base units at a Q64 sqrt price
+pub fn spot_price(sqrt_price_x64: f64) -> f64 {
+    (sqrt_price_x64 / Q64).powi(2)
+}
+
+/// Token amounts held by a concentrated-liquidity position
+///
+/// Below the range the position is all token 0, above it all token 1, and
+/// inside it a mix determined by the current price.
+pub fn position_amounts(
+    liquidity: f64,
+    sqrt_price_x64: f64,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> (f64, f64) {
+    let sqrt_price = sqrt_price_x64 / Q64;
+    let sqrt_lower = 1.0001_f64.powf(tick_lower as f64 / 2.0);
+    let sqrt_upper = 1.0001_f64.powf(tick_upper as f64 / 2.0);
+
+    if sqrt_price <= sqrt_lower {
+        (liquidity * (sqrt_upper - sqrt_lower) / (sqrt_lower * sqrt_upper), 0.0)
+    } else if sqrt_price >= sqrt_upper {
+        (0.0, liquidity * (sqrt_upper - sqrt_lower))
+    } else {
+        (
+            liquidity * (sqrt_upper - sqrt_price) / (sqrt_price * sqrt_upper),
+            liquidity * (sqrt_price - sqrt_lower),
+        )
+    }
+}
+
+/// Prices needed to value positions in USD
+#[derive(Debug, Clone)]
+pub struct Pricing {
+    pub feelssol_mint: String,
+    /// SOL per FeelsSOL: the protocol oracle's JitoSOL rate times SOL per JitoSOL
+    pub sol_per_feelssol: f64,
+    /// USD per SOL
+    pub sol_usd: f64,
+}
+
+impl Pricing {
+    /// USD value of a position including its uncollected fees
+    ///
+    /// Returns `None` for markets that do not trade against FeelsSOL or whose
+    /// state cannot be priced.
+    pub fn position_value_usd(&self, position: &Position, market: &Market) -> Option<Decimal> {
+        let sqrt_price = market.sqrt_price.to_f64()?;
+        let (amount_0, amount_1) = position_amounts(
+            position.liquidity.to_f64()?,
+            sqrt_price,
+            position.tick_lower,
+            position.tick_upper,
+        );
+        let amount_0 = amount_0 + position.tokens_owed_0 as f64;
+        let amount_1 = amount_1 + position.tokens_owed_1 as f64;
+
+        // Token 1 base units per token 0 base unit
+        let price = spot_price(sqrt_price);
+        let feelssol_units = if market.token_1 == self.feelssol_mint {
+            amount_0 * price + amount_1
+        } else if market.token_0 == self.feelssol_mint && price > 0.0 {
+            amount_0 + amount_1 / price
+        } else {
+            return None;
+        };
+
+        let usd = feelssol_units / FEELSSOL_UNITS * self.sol_per_feelssol * self.sol_usd;
+        Decimal::from_f64(usd).map(|value| value.round_dp(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::U128Numeric;
+    use crate::sdk_types::ProtocolOracleData;
+    use uuid::Uuid;
+
+    const FEELSSOL: &str = "FeelsSOL";
+
+    fn market(token_0: &str, token_1: &str, sqrt_price: u128) -> Market {
+        Market {
+            id: Uuid::new_v4(),
+            address: "market".to_string(),
+            token_0: token_0.to_string(),
+            token_1: token_1.to_string(),
+            sqrt_price: Decimal::from_u128(sqrt_price).unwrap(),
+            liquidity: Decimal::ZERO,
+            current_tick: 0,
+            tick_spacing: 10,
+            fee_bps: 30,
+            is_paused: false,
+            phase: "SteadyState".to_string(),
+            global_lower_tick: -100_800,
+            global_upper_tick: 100_800,
+            fee_growth_global_0: U128Numeric::ZERO,
+            fee_growth_global_1: U128Numeric::ZERO,
+            total_volume_0: Decimal::ZERO,
+            total_volume_1: Decimal::ZERO,
+            total_fees_0: Decimal::ZERO,
+            total_fees_1: Decimal::ZERO,
+            swap_count: 0,
+            unique_traders: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            last_updated_slot: 0,
+        }
+    }
+
+    fn position(liquidity: u64, tick_lower: i32, tick_upper: i32) -> Position {
+        Position {
+            id: Uuid::new_v4(),
+            address: "position".to_string(),
+            market_id: Uuid::new_v4(),
+            owner: "owner".to_string(),
+            liquidity: Decimal::from(liquidity),
+            tick_lower,
+            tick_upper,
+            fee_growth_inside_0_last: U128Numeric::ZERO,
+            fee_growth_inside_1_last: U128Numeric::ZERO,
+            tokens_owed_0: 0,
+            tokens_owed_1: 0,
+            entry_value: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            last_updated_slot: 0,
+        }
+    }
+
+    fn pricing() -> Pricing {
+        Pricing {
+            feelssol_mint: FEELSSOL.to_string(),
+            sol_per_feelssol: 1.0,
+            sol_usd: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_amounts_follow_price_through_the_range() {
+        let one = Q64;
+
+        // Below the range: all token 0
+        let (amount_0, amount_1) = position_amounts(1e9, one, 100, 200);
+        assert!(amount_0 > 0.0);
+        assert_eq!(amount_1, 0.0);
+
+        // Above the range: all token 1
+        let (amount_0, amount_1) = position_amounts(1e9, one, -200, -100);
+        assert_eq!(amount_0, 0.0);
+        assert!(amount_1 > 0.0);
+
+        // Symmetric range around parity holds nearly equal amounts
+        let (amount_0, amount_1) = position_amounts(1e9, one, -100, 100);
+        assert!((amount_0 - amount_1).abs() / amount_1 < 1e-3);
+    }
+
+    #[test]
+    fn test_values_position_against_feelssol_on_either_side() {
+        // Price 1: a symmetric position holds ~5e6 of each side, so ~1e7
+        // FeelsSOL units (0.01 FeelsSOL), i.e. $1 at 1 SOL/FeelsSOL and $100/SOL
+        let position = position(1_000_000_000, -100, 100);
+        let parity = 1u128 << 64;
+
+        let quoted_in_1 = market("TOKEN", FEELSSOL, parity);
+        let quoted_in_0 = market(FEELSSOL, "TOKEN", parity);
+        let value_1 = pricing().position_value_usd(&position, &quoted_in_1).unwrap();
+        let value_0 = pricing().position_value_usd(&position, &quoted_in_0).unwrap();
+        assert_eq!(value_1, Decimal::new(100, 2));
+        assert_eq!(value_0, value_1);
+
+        // Oracle haircut and uncollected fees both flow through
+        let mut owed = position.clone();
+        owed.tokens_owed_1 = 10_000_000;
+        let haircut = Pricing {
+            sol_per_feelssol: 0.5,
+            ..pricing()
+        };
+        assert_eq!(
+            haircut.position_value_usd(&owed, &quoted_in_1).unwrap(),
+            Decimal::new(100, 2)
+        );
+
+        // Markets without FeelsSOL cannot be valued
+        let foreign = market("A", "B", parity);
+        assert!(pricing().position_value_usd(&position, &foreign).is_none());
+    }
+
+    #[test]
+    fn test_oracle_uses_the_lower_rate() {
+        let rate = |native: u128, dex: u128| ProtocolOracleData {
+            native_rate_q64: native,
+            dex_twap_rate_q64: dex,
+            native_last_update_ts: 0,
+            dex_last_update_ts: 0,
+        };
+        let one = 1u128 << 64;
+
+        assert_eq!(rate(0, 0).jitosol_per_feelssol(), None);
+        assert_eq!(rate(one, 0).jitosol_per_feelssol(), Some(1.0));
+        assert_eq!(rate(0, one / 2).jitosol_per_feelssol(), Some(0.5));
+        assert_eq!(rate(one, one / 4).jitosol_per_feelssol(), Some(0.25));
+    }
+
+    #[test]
+    fn test_oracle_rate_is_converted_from_jitosol() {
+        let oracle = ProtocolOracleData {
+            native_rate_q64: 1u128 << 64,
+            dex_twap_rate_q64: 0,
+            native_last_update_ts: 0,
+            dex_last_update_ts: 0,
+        };
+
+        // One FeelsSOL is backed by one JitoSOL, worth 1.25 SOL
+        assert_eq!(oracle.sol_per_feelssol(1.25), Some(1.25));
+        assert_eq!(oracle.sol_per_feelssol(1.0), oracle.jitosol_per_feelssol());
+    }
+}
@@ -0,0 +1,399 @@
+//! Outbound webhooks for indexed swaps and price alerts
+//!
+//! Subscribes to the Redis swap channels `RepositoryManager::insert_swap`
+//! publishes on as the consumer indexes swaps, so delivery never blocks
+//! ingestion. Swaps matching the configured filter
+//! are POSTed as JSON, as are fired price alerts. Failed deliveries are
+//! retried with exponential backoff and, once attempts run out, written to a
+//! dead-letter log.
+
+use crate::config::WebhookConfig;
+use crate::database::redis::SwapEvent;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Channel pattern swap events are published on
+const SWAP_CHANNELS: &str = "swaps:*";
+
+/// Destination for webhook payloads
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Deliver one JSON payload; any error counts as a failed attempt
+    async fn send(&self, payload: &str) -> Result<()>;
+}
+
+/// POSTs payloads to a URL, treating non-2xx responses as failures
+pub struct HttpSender {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpSender {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder().timeout(timeout).build()?,
+            url: url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpSender {
+    async fn send(&self, payload: &str) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(payload.to_string())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Which swaps are forwarded
+#[derive(Debug, Clone, Default)]
+pub struct SwapFilter {
+    /// Markets to forward; empty forwards all
+    pub markets: HashSet<Uuid>,
+    pub min_amount_in: i64,
+}
+
+impl SwapFilter {
+    pub fn matches(&self, event: &SwapEvent) -> bool {
+        (self.markets.is_empty() || self.markets.contains(&event.market_id))
+            && event.amount_in >= self.min_amount_in
+    }
+}
+
+/// Attempts and backoff for one delivery
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay after the `failures`-th failed attempt
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Outcome of dispatching one swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Did not match the filter
+    Filtered,
+    Delivered { attempts: u32 },
+    /// Ran out of attempts and was written to the dead-letter log
+    DeadLettered,
+}
+
+/// Filters swaps and delivers them through a sender
+pub struct WebhookDispatcher<S> {
+    sender: S,
+    filter: SwapFilter,
+    retry: RetryPolicy,
+    dead_letter_path: Option<PathBuf>,
+}
+
+impl WebhookDispatcher<HttpSender> {
+    pub fn from_config(config: &WebhookConfig) -> Result<Self> {
+        let sender = HttpSender::new(&config.url, Duration::from_secs(config.timeout_secs))?;
+        let filter = SwapFilter {
+            markets: config.markets.iter().copied().collect(),
+            min_amount_in: config.min_amount_in,
+        };
+        let retry = RetryPolicy {
+            max_attempts: config.max_attempts,
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+        };
+        Ok(Self::new(sender, filter, retry, config.dead_letter_path.clone()))
+    }
+}
+
+impl<S: WebhookSender> WebhookDispatcher<S> {
+    pub fn new(
+        sender: S,
+        filter: SwapFilter,
+        retry: RetryPolicy,
+        dead_letter_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            sender,
+            filter,
+            retry,
+            dead_letter_path,
+        }
+    }
+
+    /// Deliver `event` if it matches the filter, retrying on failure
+    ///
+    /// Errors only if a dead-lettered payload could not be written.
+    pub async fn dispatch(&self, event: &SwapEvent) -> Result<Delivery> {
+        if !self.filter.matches(event) {
+            return Ok(Delivery::Filtered);
+        }
+
+        let payload = serde_json::json!({ "type": "swap", "data": event }).to_string();
+        self.deliver(&payload, &event.signature).await
+    }
+
+    /// Deliver a payload regardless of the swap filter, retrying on failure
+    ///
+    /// `label` identifies the payload in logs. Errors only if a
+    /// dead-lettered payload could not be written.
+    pub async fn deliver(&self, payload: &str, label: &str) -> Result<Delivery> {
+        let mut last_error = anyhow!("no delivery attempted");
+
+        for attempt in 1..=self.retry.max_attempts {
+            match self.sender.send(payload).await {
+                Ok(()) => return Ok(Delivery::Delivered { attempts: attempt }),
+                Err(e) => last_error = e,
+            }
+            if attempt < self.retry.max_attempts {
+                let delay = self.retry.backoff(attempt);
+                warn!(
+                    "Webhook for {} failed: {} (attempt {}/{}, retrying in {:?})",
+                    label, last_error, attempt, self.retry.max_attempts, delay
+                );
+                sleep(delay).await;
+            }
+        }
+
+        self.dead_letter(label, payload, &last_error).await?;
+        Ok(Delivery::DeadLettered)
+    }
+
+    /// Record a payload that ran out of attempts
+    async fn dead_letter(
+        &self,
+        label: &str,
+        payload: &str,
+        last_error: &anyhow::Error,
+    ) -> Result<()> {
+        error!(
+            "Webhook for {} dead-lettered after {} attempts: {}",
+            label, self.retry.max_attempts, last_error
+        );
+        let Some(path) = &self.dead_letter_path else {
+            return Ok(());
+        };
+
+        let mut line = serde_json::json!({
+            "failed_at": chrono::Utc::now(),
+            "attempts": self.retry.max_attempts,
+            "error": last_error.to_string(),
+            "payload": payload,
+        })
+        .to_string();
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// Forward swaps published on Redis until the subscription ends
+///
+/// Swaps are delivered one at a time, in publish order.
+pub async fn run<S: WebhookSender>(
+    redis_url: &str,
+    dispatcher: WebhookDispatcher<S>,
+) -> Result<()> {
+    let client = redis::Client::open(redis_url)?;
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.psubscribe(SWAP_CHANNELS).await?;
+    info!("Swap webhook subscribed to {}", SWAP_CHANNELS);
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let event = message
+            .get_payload::<String>()
+            .map_err(anyhow::Error::from)
+            .and_then(|payload| Ok(serde_json::from_str::<SwapEvent>(&payload)?));
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("Skipping malformed swap event: {}", e);
+                continue;
+            }
+        };
+
+        if let Err(e) = dispatcher.dispatch(&event).await {
+            error!("Failed to dead-letter swap webhook for {}: {}", event.signature, e);
+        }
+    }
+
+    Err(anyhow!("swap subscription closed"))
+}
+
+/// Start the webhook subscriber in the background
+pub fn spawn(redis_url: &str, config: &WebhookConfig) -> Result<JoinHandle<()>> {
+    let dispatcher = WebhookDispatcher::from_config(config)?;
+    let redis_url = redis_url.to_string();
+
+    Ok(tokio::spawn(async move {
+        if let Err(e) = run(&redis_url, dispatcher).await {
+            error!("Swap webhook stopped: {}", e);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Mock endpoint that fails the first `failures` requests
+    #[derive(Clone, Default)]
+    struct MockEndpoint {
+        failures: Arc<AtomicU32>,
+        hits: Arc<AtomicU32>,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    async fn receive(State(endpoint): State<MockEndpoint>, body: String) -> StatusCode {
+        endpoint.hits.fetch_add(1, Ordering::SeqCst);
+        let failing = endpoint
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if failing {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        endpoint.received.lock().unwrap().push(body);
+        StatusCode::OK
+    }
+
+    /// Serve `endpoint` on a local port and return its URL
+    async fn serve(endpoint: MockEndpoint) -> String {
+        let app = Router::new().route("/hook", post(receive)).with_state(endpoint);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/hook", address)
+    }
+
+    fn dispatcher(
+        url: &str,
+        filter: SwapFilter,
+        dead_letter_path: Option<PathBuf>,
+    ) -> WebhookDispatcher<HttpSender> {
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        };
+        let sender = HttpSender::new(url, Duration::from_secs(5)).unwrap();
+        WebhookDispatcher::new(sender, filter, retry, dead_letter_path)
+    }
+
+    fn swap(market_id: Uuid, amount_in: i64) -> SwapEvent {
+        SwapEvent {
+            market_id,
+            signature: format!("sig-{}", amount_in),
+            trader: "trader".to_string(),
+            amount_in,
+            amount_out: amount_in / 2,
+            price: 0.5,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_delivered() {
+        let endpoint = MockEndpoint::default();
+        endpoint.failures.store(2, Ordering::SeqCst);
+        let url = serve(endpoint.clone()).await;
+
+        let delivery = dispatcher(&url, SwapFilter::default(), None)
+            .dispatch(&swap(Uuid::new_v4(), 1_000))
+            .await
+            .unwrap();
+
+        assert_eq!(delivery, Delivery::Delivered { attempts: 3 });
+        let received = endpoint.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&received[0]).unwrap();
+        assert_eq!(body["type"], "swap");
+        assert_eq!(body["data"]["signature"], "sig-1000");
+    }
+
+    #[tokio::test]
+    async fn test_dead_letters_after_last_attempt() {
+        let endpoint = MockEndpoint::default();
+        endpoint.failures.store(u32::MAX, Ordering::SeqCst);
+        let url = serve(endpoint.clone()).await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("dead-letter.jsonl");
+
+        let dispatcher = dispatcher(&url, SwapFilter::default(), Some(path.clone()));
+        for amount_in in [1, 2] {
+            let delivery = dispatcher.dispatch(&swap(Uuid::new_v4(), amount_in)).await.unwrap();
+            assert_eq!(delivery, Delivery::DeadLettered);
+        }
+
+        assert_eq!(endpoint.hits.load(Ordering::SeqCst), 6);
+        let log = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<serde_json::Value> =
+            log.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["attempts"], 3);
+        assert!(entries[1]["payload"].as_str().unwrap().contains("sig-2"));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_swaps_are_not_sent() {
+        let endpoint = MockEndpoint::default();
+        let url = serve(endpoint.clone()).await;
+        let watched = Uuid::new_v4();
+        let filter = SwapFilter {
+            markets: HashSet::from([watched]),
+            min_amount_in: 500,
+        };
+        let dispatcher = dispatcher(&url, filter, None);
+
+        let other_market = dispatcher.dispatch(&swap(Uuid::new_v4(), 1_000)).await.unwrap();
+        let too_small = dispatcher.dispatch(&swap(watched, 499)).await.unwrap();
+        let matching = dispatcher.dispatch(&swap(watched, 500)).await.unwrap();
+
+        assert_eq!(other_market, Delivery::Filtered);
+        assert_eq!(too_small, Delivery::Filtered);
+        assert_eq!(matching, Delivery::Delivered { attempts: 1 });
+        assert_eq!(endpoint.hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let retry = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        };
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry.backoff(3), Duration::from_millis(350));
+        assert_eq!(retry.backoff(30), Duration::from_millis(350));
+    }
+}
@@ -0,0 +1,60 @@
+//! Shutdown signalling shared by the API servers and the Geyser consumer
+//!
+//! `Shutdown` is held by `main`; every long-running task gets a
+//! `ShutdownSignal` and stops taking new work once it fires, finishing what is
+//! already in flight before returning.
+
+use tokio::sync::watch;
+
+/// Sending side of the shutdown broadcast
+#[derive(Debug)]
+pub struct Shutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self { tx }
+    }
+
+    /// Tell every subscribed task to stop
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// A signal that fires once `trigger` is called, including if it already was
+    pub fn subscribe(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            rx: self.tx.subscribe(),
+        }
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Receiving side of the shutdown broadcast
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    rx: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// Wait for shutdown
+    ///
+    /// Never completes if the `Shutdown` is dropped without being triggered.
+    pub async fn recv(&mut self) {
+        if self.rx.wait_for(|stopping| *stopping).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Whether shutdown has been triggered
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+}
@@ -8,7 +8,7 @@ use axum::{
 };
 use feels_indexer::api::{ApiState, create_router};
 use feels_indexer::config::RocksDBConfig;
-use feels_indexer::database::DatabaseManager;
+use feels_indexer::database::{DatabaseManager, U128Numeric};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -53,8 +53,8 @@ async fn create_and_store_test_market(db_manager: &DatabaseManager, address: &st
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -100800,
         global_upper_tick: 100800,
-        fee_growth_global_0: rust_decimal::Decimal::from(0),
-        fee_growth_global_1: rust_decimal::Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: rust_decimal::Decimal::from(5000000000u64),
         total_volume_1: rust_decimal::Decimal::from(1000000000u64),
         total_fees_0: rust_decimal::Decimal::from(15000000u64),
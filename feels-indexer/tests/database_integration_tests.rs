@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 use feels_indexer::config::{PostgresConfig, RedisConfig, RocksDBConfig};
-use feels_indexer::database::{DatabaseManager, Market, Swap, Position, MarketSnapshot};
+use feels_indexer::database::{DatabaseManager, Market, Swap, Position, MarketSnapshot, U128Numeric};
 use tempfile::TempDir;
 use std::env;
 use uuid::Uuid;
@@ -125,8 +125,8 @@ async fn test_postgres_market_operations() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(1000000),
         total_volume_1: Decimal::from(2000000),
         total_fees_0: Decimal::from(3000),
@@ -188,8 +188,8 @@ async fn test_postgres_swap_operations() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(0),
         total_volume_1: Decimal::from(0),
         total_fees_0: Decimal::from(0),
@@ -298,8 +298,8 @@ async fn test_postgres_position_operations() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(0),
         total_volume_1: Decimal::from(0),
         total_fees_0: Decimal::from(0),
@@ -321,10 +321,11 @@ async fn test_postgres_position_operations() -> Result<()> {
         liquidity: Decimal::from(100000),
         tick_lower: -1000,
         tick_upper: 1000,
-        fee_growth_inside_0_last: Decimal::from(0),
-        fee_growth_inside_1_last: Decimal::from(0),
+        fee_growth_inside_0_last: U128Numeric::ZERO,
+        fee_growth_inside_1_last: U128Numeric::ZERO,
         tokens_owed_0: 0,
         tokens_owed_1: 0,
+        entry_value: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
         last_updated_slot: 12345678,
@@ -338,10 +339,11 @@ async fn test_postgres_position_operations() -> Result<()> {
         liquidity: Decimal::from(200000),
         tick_lower: -2000,
         tick_upper: 2000,
-        fee_growth_inside_0_last: Decimal::from(0),
-        fee_growth_inside_1_last: Decimal::from(0),
+        fee_growth_inside_0_last: U128Numeric::ZERO,
+        fee_growth_inside_1_last: U128Numeric::ZERO,
         tokens_owed_0: 0,
         tokens_owed_1: 0,
+        entry_value: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
         last_updated_slot: 12345679,
@@ -412,8 +414,8 @@ async fn test_postgres_market_snapshots() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(1000000),
         total_volume_1: Decimal::from(2000000),
         total_fees_0: Decimal::from(3000),
@@ -523,8 +525,8 @@ async fn test_redis_market_caching() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(1000000),
         total_volume_1: Decimal::from(2000000),
         total_fees_0: Decimal::from(3000),
@@ -575,8 +577,8 @@ async fn test_cross_database_consistency() -> Result<()> {
         phase: "PriceDiscovery".to_string(),
         global_lower_tick: -887200,
         global_upper_tick: 887200,
-        fee_growth_global_0: Decimal::from(0),
-        fee_growth_global_1: Decimal::from(0),
+        fee_growth_global_0: U128Numeric::ZERO,
+        fee_growth_global_1: U128Numeric::ZERO,
         total_volume_0: Decimal::from(1000000),
         total_volume_1: Decimal::from(2000000),
         total_fees_0: Decimal::from(3000),
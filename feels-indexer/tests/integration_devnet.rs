@@ -8,6 +8,8 @@ use anyhow::Result;
 use feels_indexer::config::IndexerConfig;
 use feels_indexer::database::DatabaseManager;
 use feels_indexer::geyser::FeelsGeyserConsumer;
+use feels_indexer::repositories::RepositoryManager;
+use feels_indexer::services::ServiceManager;
 use solana_sdk::pubkey::Pubkey;
 use std::process::{Child, Command};
 use std::sync::Arc;
@@ -119,9 +121,15 @@ async fn run_indexer_test(env: &TestEnvironment) -> Result<()> {
     info!("Starting Geyser consumer...");
     
     // Create and start the Geyser consumer
+    let repos = Arc::new(RepositoryManager::with_settings(
+        db_manager.clone(),
+        &env.indexer_config.indexer,
+    ));
+    let services = Arc::new(ServiceManager::with_settings(repos, &env.indexer_config.indexer));
     let mut consumer = FeelsGeyserConsumer::new(
         env.program_id,
         db_manager.clone(),
+        services,
         &env.indexer_config.geyser,
     )
     .await?;
@@ -25,7 +25,7 @@ use solana_program::{
 use anchor_lang::prelude::*;
 use spl_token::state::Account as TokenAccount;
 use solana_program::program_pack::Pack;
-use feels::state::Market;
+use feels::state::{Market, PoolStatus};
 use ahash::AHashMap;
 
 // =============================================================================
@@ -42,6 +42,24 @@ const TICK_ARRAY_SIZE: i32 = 64;
 // TickArrayView is now provided by the SDK
 use feels_sdk::TickArrayView;
 
+/// Fee components used to build a Jupiter quote
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    /// Market base fee in basis points
+    pub base_fee_bps: u16,
+    /// Impact surcharge on top of the base fee in basis points
+    pub impact_fee_bps: u16,
+    /// Total fee charged on the input amount in basis points
+    pub total_fee_bps: u16,
+}
+
+impl FeeBreakdown {
+    /// Total fee as a percentage, as Jupiter's `Quote::fee_pct` expects
+    pub fn fee_pct(&self) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::new(self.total_fee_bps as i64, 2)
+    }
+}
+
 /// Jupiter AMM adapter for Feels Protocol markets
 ///
 /// This struct implements the Jupiter AMM interface, enabling Feels markets
@@ -108,6 +126,42 @@ impl Clone for FeelsAmm {
 }
 
 impl FeelsAmm {
+    /// FeelsSOL hub mint if this market pairs against it
+    ///
+    /// Every Feels market quotes against FeelsSOL, so two markets sharing the
+    /// hub mint form a two-hop route through it.
+    pub fn hub_mint(&self) -> Option<Pubkey> {
+        let hub = self.market.feelssol_mint;
+        self.reserve_mints.contains(&hub).then_some(hub)
+    }
+
+    /// Trading status from the latest market account
+    pub fn pool_status(&self) -> PoolStatus {
+        PoolStatus::from_paused(self.market.is_paused)
+    }
+
+    /// Whether on-chain `swap` would accept this pool right now
+    ///
+    /// Mirrors the paused-market check in `validate_swap_params`.
+    pub fn is_operational(&self) -> bool {
+        self.pool_status() == PoolStatus::Normal
+    }
+
+    /// Current fee components read from the latest market account
+    ///
+    /// The market account is refreshed on every update(), so fee changes
+    /// (e.g. a stressed market raising its fee) flow straight into quotes.
+    /// On-chain `swap` does not charge an impact surcharge yet, so the total
+    /// is the base fee until it does.
+    pub fn fee_breakdown(&self) -> FeeBreakdown {
+        let base_fee_bps = self.market.base_fee_bps;
+        FeeBreakdown {
+            base_fee_bps,
+            impact_fee_bps: 0,
+            total_fee_bps: base_fee_bps,
+        }
+    }
+
     /// Calculate swap output using the SDK's SwapSimulator
     /// 
     /// This method ensures quotes exactly match on-chain execution by using
@@ -202,8 +256,9 @@ impl Amm for FeelsAmm {
         let market = Market::try_deserialize_unchecked(&mut &data[..])?;
         
         // Validate market state
+        // Paused markets are still loaded so quotes resume once trading does;
+        // quote() declines while the pool is disabled.
         ensure!(market.is_initialized, "Market not initialized");
-        ensure!(!market.is_paused, "Market is paused");
         
         // Derive protocol PDAs
         let program_id = feels::ID;
@@ -250,16 +305,27 @@ impl Amm for FeelsAmm {
     }
 
     /// Return the token mints for this trading pair
+    ///
+    /// FeelsSOL is listed first so Jupiter's graph treats it as the shared hub
+    /// connecting every Feels market (TokenA -> FeelsSOL -> TokenB).
     fn get_reserve_mints(&self) -> Vec<Pubkey> {
-        self.reserve_mints.to_vec()
+        match self.hub_mint() {
+            Some(hub) if hub == self.reserve_mints[1] => {
+                vec![self.reserve_mints[1], self.reserve_mints[0]]
+            }
+            _ => self.reserve_mints.to_vec(),
+        }
     }
 
     /// Return accounts that need to be monitored for state changes
     ///
     /// Jupiter will fetch these accounts and call update() when they change.
+    /// The market account is included so price, liquidity and pause state
+    /// stay current for quotes routed through the FeelsSOL hub.
     fn get_accounts_to_update(&self) -> Vec<Pubkey> {
         let mut accounts = vec![self.vault_0, self.vault_1];
         accounts.extend(self.tick_array_keys.iter().copied());
+        accounts.push(self.key);
         accounts
     }
 
@@ -280,6 +346,13 @@ impl Amm for FeelsAmm {
             vault_1_token_account.amount,
         ];
 
+        // Refresh market state (price, liquidity, fees) when provided
+        if let Ok(bytes) = try_get_account_data(account_map, &self.key) {
+            if let Ok(market) = Market::try_deserialize_unchecked(&mut &bytes[..]) {
+                self.market = market;
+            }
+        }
+
         // Parse and cache tick array data for liquidity calculations
         for key in &self.tick_array_keys {
             if let Ok(bytes) = try_get_account_data(account_map, key) {
@@ -298,6 +371,9 @@ impl Amm for FeelsAmm {
     /// Uses Feels concentrated liquidity math to calculate the expected output
     /// amount and fees for a given input amount and token pair.
     fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        // Don't route through a pool whose swaps would revert
+        ensure!(self.is_operational(), "Feels pool is disabled");
+        
         // Determine swap direction based on input mint
         let (is_token_0_to_1, amount_in) = if quote_params.input_mint == self.reserve_mints[0] {
             (true, quote_params.amount)
@@ -322,23 +398,15 @@ impl Amm for FeelsAmm {
             estimated_ticks,
         )?;
         
-        // Calculate fee percentage for Jupiter interface
-        use rust_decimal::Decimal;
-        use std::str::FromStr;
-        
-        let fee_pct = if amount_in > 0 {
-            let fee_ratio = fee_amount as f64 / amount_in as f64;
-            Decimal::from_str(&format!("{}", fee_ratio * 100.0)).unwrap_or_default()
-        } else {
-            Decimal::ZERO
-        };
+        // Fee is charged on the input token at the market's current fee rate
+        let breakdown = self.fee_breakdown();
         
         Ok(Quote {
             in_amount: amount_in,
             out_amount: amount_out,
             fee_amount,
             fee_mint: quote_params.input_mint,
-            fee_pct,
+            fee_pct: breakdown.fee_pct(),
         })
     }
 
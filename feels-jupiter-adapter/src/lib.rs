@@ -7,7 +7,7 @@ pub mod amm;
 pub mod config;
 
 // Main exports for Jupiter integration
-pub use amm::FeelsAmm;
+pub use amm::{FeeBreakdown, FeelsAmm};
 pub use config::ADAPTER_CONFIG;
 
 // Re-export the main Feels program for type access
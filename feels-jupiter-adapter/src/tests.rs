@@ -73,6 +73,8 @@ mod tests {
             rolling_window_start_slot: 0,
             tick_snapshot_1hr: 0,
             last_snapshot_timestamp: 0,
+            swap_volume_slot: 0,
+            swap_slot_volume_feelssol: 0,
             _reserved: [0; 1],
         }
     }
@@ -924,4 +926,188 @@ mod tests {
             "Should include treasury ATA in account list"
         );
     }
+    #[test]
+    fn test_feelssol_hub_two_hop_discovery() {
+        use crate::amm::FeelsAmm;
+        use jupiter_amm_interface::{KeyedAccount, AmmContext};
+        use solana_sdk::account::Account as SolanaAccount;
+        
+        let amm_context = AmmContext {
+            clock_ref: Default::default(),
+        };
+        let feelssol = create_test_market().feelssol_mint;
+        let token_a = Pubkey::new_unique();
+        let token_b = Pubkey::new_unique();
+        
+        // Two markets that both pair against FeelsSOL
+        let make_amm = |token: Pubkey| {
+            let mut market = create_test_market();
+            market.token_0 = feelssol;
+            market.token_1 = token;
+            let mut market_data = Vec::new();
+            market.try_serialize(&mut market_data).unwrap();
+            let keyed_account = KeyedAccount {
+                key: Pubkey::new_unique(),
+                account: SolanaAccount {
+                    lamports: 1_000_000,
+                    data: market_data,
+                    owner: feels::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+                params: None,
+            };
+            FeelsAmm::from_keyed_account(&keyed_account, &amm_context).unwrap()
+        };
+        let amm_a = make_amm(token_a);
+        let amm_b = make_amm(token_b);
+        
+        // Both advertise FeelsSOL as the hub mint, listed first
+        assert_eq!(amm_a.hub_mint(), Some(feelssol));
+        assert_eq!(amm_b.hub_mint(), Some(feelssol));
+        assert_eq!(amm_a.get_reserve_mints()[0], feelssol);
+        assert_eq!(amm_b.get_reserve_mints()[0], feelssol);
+        
+        // A mint graph built from reserve mints finds TokenA -> FeelsSOL -> TokenB
+        let mints_a = amm_a.get_reserve_mints();
+        let mints_b = amm_b.get_reserve_mints();
+        assert!(mints_a.contains(&token_a) && !mints_a.contains(&token_b));
+        assert!(mints_b.contains(&token_b) && !mints_b.contains(&token_a));
+        let intermediate: Vec<Pubkey> = mints_a
+            .iter()
+            .filter(|mint| mints_b.contains(mint))
+            .copied()
+            .collect();
+        assert_eq!(intermediate, vec![feelssol]);
+        
+        // Market accounts are monitored so hub quotes track price updates
+        assert!(amm_a.get_accounts_to_update().contains(&amm_a.key()));
+    }
+    #[test]
+    fn test_quote_fee_tracks_market_fee_state() {
+        use crate::amm::FeelsAmm;
+        use jupiter_amm_interface::{QuoteParams, AmmContext, KeyedAccount};
+        use solana_sdk::account::Account as SolanaAccount;
+        use spl_token::state::AccountState;
+        use solana_program::program_pack::Pack;
+        use rust_decimal::Decimal;
+        
+        // Calm market at the default 0.30% fee
+        let market = create_test_market();
+        let mut market_data = Vec::new();
+        market.try_serialize(&mut market_data).unwrap();
+        let market_key = Pubkey::new_unique();
+        let keyed_account = KeyedAccount {
+            key: market_key,
+            account: SolanaAccount {
+                lamports: 1_000_000,
+                data: market_data,
+                owner: feels::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+            params: None,
+        };
+        let amm_context = AmmContext {
+            clock_ref: Default::default(),
+        };
+        let mut amm = FeelsAmm::from_keyed_account(&keyed_account, &amm_context).unwrap();
+        
+        let quote_params = QuoteParams {
+            amount: 10_000_000,
+            input_mint: market.token_0,
+            output_mint: market.token_1,
+            swap_mode: jupiter_amm_interface::SwapMode::ExactIn,
+        };
+        let calm = amm.quote(&quote_params).unwrap();
+        assert_eq!(calm.fee_mint, market.token_0);
+        assert_eq!(calm.fee_pct, Decimal::new(30, 2));
+        assert_eq!(calm.fee_amount, 30_000);
+        
+        // The same market under stress charges 3.00%
+        let mut stressed = market.clone();
+        stressed.base_fee_bps = 300;
+        let mut stressed_data = Vec::new();
+        stressed.try_serialize(&mut stressed_data).unwrap();
+        
+        let mut account_map = ahash::AHashMap::<Pubkey, SolanaAccount>::new();
+        let accounts = amm.get_accounts_to_update();
+        for (vault, mint) in [(accounts[0], market.token_0), (accounts[1], market.token_1)] {
+            let mut vault_data = vec![0u8; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint,
+                owner: market_key,
+                amount: 1_000_000_000_000,
+                delegate: None.into(),
+                state: AccountState::Initialized,
+                is_native: None.into(),
+                delegated_amount: 0,
+                close_authority: None.into(),
+            }
+            .pack_into_slice(&mut vault_data);
+            account_map.insert(vault, SolanaAccount {
+                lamports: 1_000_000,
+                data: vault_data,
+                owner: spl_token::id(),
+                executable: false,
+                rent_epoch: 0,
+            });
+        }
+        account_map.insert(market_key, SolanaAccount {
+            lamports: 1_000_000,
+            data: stressed_data,
+            owner: feels::ID,
+            executable: false,
+            rent_epoch: 0,
+        });
+        amm.update(&account_map).unwrap();
+        
+        let quote = amm.quote(&quote_params).unwrap();
+        assert_eq!(amm.fee_breakdown().total_fee_bps, 300);
+        assert_eq!(quote.fee_pct, Decimal::new(300, 2));
+        assert!(quote.fee_amount > calm.fee_amount);
+        assert!(quote.out_amount < calm.out_amount);
+    }
+    #[test]
+    fn test_quote_declines_disabled_pool() {
+        use crate::amm::FeelsAmm;
+        use feels::state::PoolStatus;
+        use jupiter_amm_interface::{QuoteParams, AmmContext, KeyedAccount};
+        use solana_sdk::account::Account as SolanaAccount;
+        
+        // Market disabled by the safety controller
+        let mut market = create_test_market();
+        market.is_paused = true;
+        let mut market_data = Vec::new();
+        market.try_serialize(&mut market_data).unwrap();
+        
+        let keyed_account = KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: SolanaAccount {
+                lamports: 1_000_000,
+                data: market_data,
+                owner: feels::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+            params: None,
+        };
+        let amm_context = AmmContext {
+            clock_ref: Default::default(),
+        };
+        
+        // Disabled pools still load so they can resume later
+        let amm = FeelsAmm::from_keyed_account(&keyed_account, &amm_context).unwrap();
+        assert_eq!(amm.pool_status(), PoolStatus::Disabled);
+        assert!(!amm.is_operational());
+        
+        let quote_params = QuoteParams {
+            amount: 10_000_000,
+            input_mint: market.token_0,
+            output_mint: market.token_1,
+            swap_mode: jupiter_amm_interface::SwapMode::ExactIn,
+        };
+        let err = amm.quote(&quote_params).unwrap_err();
+        assert!(err.to_string().contains("disabled"));
+    }
 }
\ No newline at end of file
@@ -2,9 +2,9 @@
 
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
-use feels_sdk::FeelsClient;
+use feels_sdk::{format_amount, FeelsClient};
 
-use super::utils::{get_program_id, info, load_keypair, parse_pubkey, success};
+use super::utils::{get_program_id, info, load_keypair, mint_decimals, parse_pubkey, success};
 
 #[derive(Args)]
 pub struct MarketCmd {
@@ -105,6 +105,13 @@ pub async fn execute(
             info(&format!("Signature: {}", result.signature));
             info(&format!("Token 0 (FeelsSOL): {}", feelssol_mint));
             info(&format!("Token 1: {}", token_mint));
+            if initial_buy_amount > 0 {
+                let decimals = mint_decimals(&client, &feelssol_mint).await?;
+                info(&format!(
+                    "Initial buy: {} FeelsSOL",
+                    format_amount(initial_buy_amount, decimals)
+                ));
+            }
 
             Ok(())
         }
@@ -117,10 +117,39 @@ pub async fn execute(
                 FeelsClient::new(rpc_url).await?
             };
 
-            let (protocol_config, _) = client.pda.protocol_config();
+            let config = client
+                .protocol
+                .get_config()
+                .await
+                .context("Failed to fetch protocol configuration")?;
+            let paused = match config.redemptions_paused {
+                Some(paused) => paused.to_string(),
+                None => "unknown (safety controller not initialized)".to_string(),
+            };
 
-            info(&format!("Protocol Config PDA: {}", protocol_config));
-            info("Use solana account command to view details");
+            info(&format!("Protocol Config PDA: {}", config.address));
+            info(&format!("Authority: {}", config.authority));
+            info(&format!("Treasury: {}", config.treasury));
+            info(&format!(
+                "Protocol fee rate: {} bps (max {} bps)",
+                config.default_protocol_fee_rate, config.max_protocol_fee_rate
+            ));
+            info(&format!(
+                "Creator fee rate: {} bps",
+                config.default_creator_fee_rate
+            ));
+            info(&format!("Mint fee: {}", config.mint_fee));
+            info(&format!(
+                "New markets: base fee {} bps, tick spacing {}, initial sqrt price {}",
+                config.default_base_fee_bps,
+                config.default_tick_spacing,
+                config.default_initial_sqrt_price
+            ));
+            info(&format!(
+                "Safety Controller PDA: {}",
+                config.safety_controller
+            ));
+            info(&format!("Redemptions paused: {}", paused));
 
             Ok(())
         }
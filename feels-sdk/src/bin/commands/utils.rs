@@ -1,7 +1,9 @@
 // Utility functions for CLI commands
 
 use anyhow::{Context, Result};
+use feels_sdk::FeelsClient;
 use solana_sdk::{
+    program_pack::Pack,
     pubkey::Pubkey,
     signature::{read_keypair_file, Keypair},
 };
@@ -33,6 +35,14 @@ pub fn get_program_id(program_id_str: Option<&str>) -> Result<Pubkey> {
     }
 }
 
+/// Fetch a mint's decimals
+pub async fn mint_decimals(client: &FeelsClient, mint: &Pubkey) -> Result<u8> {
+    let account = client.base.get_account(mint).await?;
+    let mint_state = spl_token::state::Mint::unpack(&account.data)
+        .with_context(|| format!("{} is not an SPL token mint", mint))?;
+    Ok(mint_state.decimals)
+}
+
 /// Print success message with checkmark
 pub fn success(msg: &str) {
     println!("[OK] {}", msg);
@@ -1,34 +1,336 @@
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use crate::prelude::*;
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::{RpcClientConfig, SerializableTransaction},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_rpc_client::http_sender::HttpSender;
 use solana_sdk::{
     account::Account,
+    address_lookup_table::{
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::AddressLookupTable,
+        AddressLookupTableAccount,
+    },
     commitment_config::CommitmentConfig,
-    instruction::Instruction,
+    instruction::{Instruction, InstructionError},
+    message::{v0, VersionedMessage},
     signature::{Keypair, Signature},
     signer::Signer,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
 };
 
 use crate::core::{program_id, SdkError, SdkResult};
+use crate::instructions::TransactionBuilder;
+
+/// Maximum addresses appended per `extend_lookup_table` transaction
+const LOOKUP_TABLE_EXTEND_CHUNK: usize = 20;
+
+/// Longest `Retry-After` pause honored, matching the RPC client's own cap
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// Request timeout of clients built by `BaseClient::with_rate_limit`
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Resubmission policy for `BaseClient::send_and_confirm_with_retry`
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    /// Total send attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first resubmission, doubled after each attempt
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+/// Client-side pacing for rate-limited RPC endpoints
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second
+    pub requests_per_second: u32,
+    /// Requests allowed back to back before pacing kicks in
+    pub burst: u32,
+    /// Retries after the endpoint answers 429 before giving up
+    pub max_retries: u32,
+    /// Delay before the first retry after a 429, doubled after each retry
+    pub retry_backoff: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10,
+            burst: 10,
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Token bucket that paces RPC requests and retries those rejected with 429
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    /// Available tokens (negative when callers are queued) and last refill time
+    bucket: Mutex<(f64, Instant)>,
+    /// No request starts before this instant, set from a 429's `Retry-After`
+    paused_until: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        let burst = config.burst.max(1) as f64;
+        Self {
+            config,
+            bucket: Mutex::new((burst, Instant::now())),
+            paused_until: Mutex::new(None),
+        }
+    }
+
+    /// Hold every request back for `delay`, as asked by a 429's `Retry-After`
+    ///
+    /// Delays are capped at two minutes; an earlier pause is never shortened.
+    pub fn pause_for(&self, delay: Duration) {
+        let until = Instant::now() + delay.min(MAX_RETRY_AFTER);
+        let mut paused_until = self.paused_until.lock().expect("rate limiter poisoned");
+        if paused_until.is_none_or(|current| current < until) {
+            *paused_until = Some(until);
+        }
+    }
+
+    /// Time left before the current `Retry-After` pause ends
+    fn pause_remaining(&self) -> Duration {
+        self.paused_until
+            .lock()
+            .expect("rate limiter poisoned")
+            .map_or(Duration::ZERO, |until| {
+                until.saturating_duration_since(Instant::now())
+            })
+    }
+
+    /// Reserve a token, returning how long to wait before using it
+    fn reserve(&self) -> Duration {
+        let rate = self.config.requests_per_second.max(1) as f64;
+        let burst = self.config.burst.max(1) as f64;
+        let mut bucket = self.bucket.lock().expect("rate limiter poisoned");
+        let (tokens, last) = &mut *bucket;
+
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * rate).min(burst);
+        *last = now;
+        *tokens -= 1.0;
+
+        let wait = if *tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-*tokens / rate)
+        };
+        drop(bucket);
+        wait.max(self.pause_remaining())
+    }
+
+    /// Run `call` once a token is available
+    ///
+    /// A 429 from the endpoint is retried up to `max_retries` times, after
+    /// its `Retry-After` when the endpoint sent one and with exponential
+    /// backoff otherwise; any other outcome is returned as is.
+    pub async fn run<T, F, Fut>(&self, mut call: F) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut backoff = self.config.retry_backoff;
+        let mut retries = 0;
+        loop {
+            let wait = self.reserve();
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+
+            match call().await {
+                Err(e) if retries < self.config.max_retries && is_rate_limited(&e) => {
+                    // A Retry-After pause already holds back the next reserve
+                    if self.pause_remaining().is_zero() {
+                        tokio::time::sleep(backoff).await;
+                    }
+                    backoff *= 2;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// HTTP middleware that pauses a client's rate limiter for each 429's `Retry-After`
+struct RetryAfterMiddleware {
+    rate_limiter: Arc<RwLock<Option<Arc<RateLimiter>>>>,
+}
+
+#[async_trait::async_trait]
+impl reqwest_middleware::Middleware for RetryAfterMiddleware {
+    async fn handle(
+        &self,
+        request: reqwest::Request,
+        extensions: &mut task_local_extensions::Extensions,
+        next: reqwest_middleware::Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        let response = next.run(request, extensions).await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let delay = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            let limiter = self
+                .rate_limiter
+                .read()
+                .expect("rate limiter lock poisoned")
+                .clone();
+            if let (Some(delay), Some(limiter)) = (delay, limiter) {
+                limiter.pause_for(delay);
+            }
+        }
+        Ok(response)
+    }
+}
+
+/// Parse a `Retry-After` header given in seconds (HTTP dates are not supported)
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Outcome of simulating a transaction
+#[derive(Clone, Debug)]
+pub struct SimulationResult {
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// Decoded failure, if the simulation failed
+    pub fn error(&self) -> Option<SdkError> {
+        self.err
+            .as_ref()
+            .map(|err| decode_simulation_error(err, &self.logs))
+    }
+}
 
 /// Base RPC client wrapper for common operations
 pub struct BaseClient {
     rpc: Arc<RpcClient>,
     program_id: Pubkey,
+    simulate_before_send: AtomicBool,
+    /// Shared with the `Retry-After` middleware of clients from `with_rate_limit`
+    rate_limiter: Arc<RwLock<Option<Arc<RateLimiter>>>>,
 }
 
 impl BaseClient {
     pub fn new(rpc: Arc<RpcClient>) -> Self {
+        Self::with_program_id(rpc, program_id())
+    }
+
+    pub fn with_program_id(rpc: Arc<RpcClient>, program_id: Pubkey) -> Self {
         Self {
             rpc,
+            program_id,
+            simulate_before_send: AtomicBool::new(false),
+            rate_limiter: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Connect to `url` with every request paced by `config`
+    ///
+    /// Unlike `set_rate_limit` on a client wrapping an existing `RpcClient`,
+    /// this also reads the `Retry-After` of each 429 the endpoint returns and
+    /// holds back all requests, not only the rejected one, until it passes.
+    pub fn with_rate_limit(url: String, config: RateLimitConfig) -> Self {
+        let rate_limiter = Arc::new(RwLock::new(Some(Arc::new(RateLimiter::new(config)))));
+        let http = reqwest::Client::builder()
+            .default_headers(HttpSender::default_headers())
+            .timeout(RPC_TIMEOUT)
+            .pool_idle_timeout(RPC_TIMEOUT)
+            .build()
+            .expect("build rpc client");
+        let http = reqwest_middleware::ClientBuilder::new(http)
+            .with(RetryAfterMiddleware {
+                rate_limiter: rate_limiter.clone(),
+            })
+            .build();
+        let rpc = RpcClient::new_sender(
+            HttpSender::new_with_client_with_middleware(url, http),
+            RpcClientConfig::with_commitment(CommitmentConfig::default()),
+        );
+
+        Self {
+            rpc: Arc::new(rpc),
             program_id: program_id(),
+            simulate_before_send: AtomicBool::new(false),
+            rate_limiter,
         }
     }
 
-    pub fn with_program_id(rpc: Arc<RpcClient>, program_id: Pubkey) -> Self {
-        Self { rpc, program_id }
+    /// Simulate every transaction before sending it
+    ///
+    /// When enabled, a transaction that fails simulation is never sent and
+    /// the decoded failure is returned instead, so no fee is paid.
+    pub fn set_simulate_before_send(&self, enabled: bool) {
+        self.simulate_before_send.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Pace every RPC request, reads and sends alike, or `None` to send them unthrottled
+    pub fn set_rate_limit(&self, config: Option<RateLimitConfig>) {
+        *self
+            .rate_limiter
+            .write()
+            .expect("rate limiter lock poisoned") =
+            config.map(|config| Arc::new(RateLimiter::new(config)));
+    }
+
+    /// Run an RPC request through the rate limiter when one is configured
+    async fn limited<T, F, Fut>(&self, mut call: F) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let limiter = self
+            .rate_limiter
+            .read()
+            .expect("rate limiter lock poisoned")
+            .clone();
+        match limiter {
+            Some(limiter) => limiter.run(call).await,
+            None => call().await,
+        }
+    }
+
+    /// `limited`, with the error as an `SdkError`
+    async fn paced<T, F, Fut>(&self, call: F) -> SdkResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        self.limited(call).await.map_err(SdkError::RpcError)
     }
 
     /// Get the RPC client
@@ -48,10 +350,7 @@ impl BaseClient {
 
     /// Fetch an account
     pub async fn get_account(&self, address: &Pubkey) -> SdkResult<Account> {
-        self.rpc
-            .get_account(address)
-            .await
-            .map_err(|e| SdkError::RpcError(e))
+        self.paced(|| self.rpc.get_account(address)).await
     }
 
     /// Fetch multiple accounts
@@ -59,10 +358,8 @@ impl BaseClient {
         &self,
         addresses: &[Pubkey],
     ) -> SdkResult<Vec<Option<Account>>> {
-        self.rpc
-            .get_multiple_accounts(addresses)
+        self.paced(|| self.rpc.get_multiple_accounts(addresses))
             .await
-            .map_err(|e| SdkError::RpcError(e))
     }
 
     /// Send a transaction
@@ -71,7 +368,7 @@ impl BaseClient {
         instructions: &[Instruction],
         signers: &[&Keypair],
     ) -> SdkResult<Signature> {
-        let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+        let recent_blockhash = self.paced(|| self.rpc.get_latest_blockhash()).await?;
 
         let tx = Transaction::new_signed_with_payer(
             instructions,
@@ -79,11 +376,10 @@ impl BaseClient {
             signers,
             recent_blockhash,
         );
+        self.preflight(&tx).await?;
 
-        self.rpc
-            .send_and_confirm_transaction(&tx)
+        self.paced(|| self.rpc.send_and_confirm_transaction(&tx))
             .await
-            .map_err(|e| SdkError::RpcError(e))
     }
 
     /// Send a transaction with custom options
@@ -93,19 +389,215 @@ impl BaseClient {
         signers: &[&Keypair],
         commitment: CommitmentConfig,
     ) -> SdkResult<Signature> {
-        let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+        let recent_blockhash = self.paced(|| self.rpc.get_latest_blockhash()).await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            recent_blockhash,
+        );
+        self.preflight(&tx).await?;
+
+        self.paced(|| {
+            self.rpc
+                .send_and_confirm_transaction_with_spinner_and_commitment(&tx, commitment)
+        })
+        .await
+    }
+
+    /// Send a transaction, resubmitting with a fresh blockhash on transient failures
+    ///
+    /// Before resubmitting, the previous attempt's signature is looked up: if
+    /// it landed its outcome is returned, and while its blockhash is still
+    /// valid the same signed transaction is rebroadcast, so it cannot execute
+    /// twice. Only once that blockhash has expired is the transaction re-signed
+    /// against the latest one. Failures the program would reproduce (see
+    /// `is_transient_error`) are returned immediately.
+    pub async fn send_and_confirm_with_retry(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        config: &RetryConfig,
+    ) -> SdkResult<Signature> {
+        if signers.is_empty() {
+            return Err(SdkError::InvalidParameters(
+                "At least one signer is required to pay for the transaction".to_string(),
+            ));
+        }
+
+        let mut backoff = config.initial_backoff;
+        let mut attempt = 1;
+        let mut previous: Option<Transaction> = None;
+        loop {
+            let result = match self
+                .next_attempt(previous.as_ref(), instructions, signers)
+                .await
+            {
+                Ok(Attempt::Landed(outcome)) => outcome,
+                Ok(Attempt::Send(tx, fresh)) => {
+                    if fresh {
+                        self.preflight(&tx).await?;
+                    }
+                    let result = self
+                        .limited(|| self.rpc.send_and_confirm_transaction(&tx))
+                        .await;
+                    previous = Some(tx);
+                    result
+                }
+                Err(e) => Err(e),
+            };
 
+            match result {
+                Ok(signature) => return Ok(signature),
+                Err(e) if attempt < config.max_attempts && is_transient_error(&e) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(config.max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => return Err(SdkError::RpcError(e)),
+            }
+        }
+    }
+
+    /// Decide what `send_and_confirm_with_retry` submits next
+    ///
+    /// A lookup failure is returned as is, so the caller retries the lookup
+    /// instead of re-signing a transaction that may already have landed.
+    async fn next_attempt(
+        &self,
+        previous: Option<&Transaction>,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Attempt, ClientError> {
+        if let Some(tx) = previous {
+            let signature = tx.signatures[0];
+            if let Some(status) = self
+                .limited(|| self.rpc.get_signature_status(&signature))
+                .await?
+            {
+                return Ok(Attempt::Landed(
+                    status.map(|()| signature).map_err(ClientError::from),
+                ));
+            }
+            let blockhash_valid = self
+                .limited(|| {
+                    self.rpc
+                        .is_blockhash_valid(&tx.message.recent_blockhash, self.rpc.commitment())
+                })
+                .await?;
+            if blockhash_valid {
+                return Ok(Attempt::Send(tx.clone(), false));
+            }
+        }
+
+        let recent_blockhash = self.limited(|| self.rpc.get_latest_blockhash()).await?;
         let tx = Transaction::new_signed_with_payer(
             instructions,
             Some(&signers[0].pubkey()),
             signers,
             recent_blockhash,
         );
+        Ok(Attempt::Send(tx, true))
+    }
+
+    /// Sign a batched transaction against the latest blockhash
+    pub async fn build_transaction(
+        &self,
+        builder: &TransactionBuilder<'_>,
+    ) -> SdkResult<Transaction> {
+        let recent_blockhash = self.paced(|| self.rpc.get_latest_blockhash()).await?;
+        builder.build(recent_blockhash)
+    }
+
+    /// Send a batched transaction
+    pub async fn send_batch(&self, builder: &TransactionBuilder<'_>) -> SdkResult<Signature> {
+        let tx = self.build_transaction(builder).await?;
+        self.preflight(&tx).await?;
 
-        self.rpc
-            .send_and_confirm_transaction_with_spinner_and_commitment(&tx, commitment)
+        self.paced(|| self.rpc.send_and_confirm_transaction(&tx))
             .await
-            .map_err(|e| SdkError::RpcError(e))
+    }
+
+    /// Send a v0 transaction resolving accounts through address lookup tables
+    pub async fn send_versioned_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        lookup_tables: &[Pubkey],
+    ) -> SdkResult<Signature> {
+        let payer = signers
+            .first()
+            .ok_or_else(|| SdkError::InvalidParameters("No signers provided".to_string()))?;
+        let tables = self.get_lookup_tables(lookup_tables).await?;
+        let recent_blockhash = self.paced(|| self.rpc.get_latest_blockhash()).await?;
+
+        let message = v0::Message::try_compile(
+            &payer.pubkey(),
+            instructions,
+            &tables,
+            recent_blockhash,
+        )
+        .map_err(|e| SdkError::InvalidParameters(e.to_string()))?;
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?;
+        self.preflight(&tx).await?;
+
+        self.paced(|| self.rpc.send_and_confirm_transaction(&tx))
+            .await
+    }
+
+    /// Fetch and decode address lookup tables
+    pub async fn get_lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> SdkResult<Vec<AddressLookupTableAccount>> {
+        let mut tables = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let account = self.get_account(address).await?;
+            let table = AddressLookupTable::deserialize(&account.data)
+                .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+            tables.push(AddressLookupTableAccount {
+                key: *address,
+                addresses: table.addresses.to_vec(),
+            });
+        }
+        Ok(tables)
+    }
+
+    /// Create an address lookup table owned by `authority` holding `addresses`
+    pub async fn create_lookup_table(
+        &self,
+        authority: &Keypair,
+        addresses: &[Pubkey],
+    ) -> SdkResult<Pubkey> {
+        let recent_slot = self.get_slot().await?;
+        let (create_ix, table) =
+            create_lookup_table(authority.pubkey(), authority.pubkey(), recent_slot);
+        self.send_transaction(&[create_ix], &[authority]).await?;
+
+        self.extend_lookup_table(authority, table, addresses)
+            .await?;
+        Ok(table)
+    }
+
+    /// Append addresses to an existing lookup table
+    pub async fn extend_lookup_table(
+        &self,
+        authority: &Keypair,
+        table: Pubkey,
+        addresses: &[Pubkey],
+    ) -> SdkResult<()> {
+        for chunk in addresses.chunks(LOOKUP_TABLE_EXTEND_CHUNK) {
+            let ix = extend_lookup_table(
+                table,
+                authority.pubkey(),
+                Some(authority.pubkey()),
+                chunk.to_vec(),
+            );
+            self.send_transaction(&[ix], &[authority]).await?;
+        }
+        Ok(())
     }
 
     /// Simulate a transaction
@@ -114,7 +606,7 @@ impl BaseClient {
         instructions: &[Instruction],
         signers: &[&Keypair],
     ) -> SdkResult<()> {
-        let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+        let recent_blockhash = self.paced(|| self.rpc.get_latest_blockhash()).await?;
 
         let tx = Transaction::new_signed_with_payer(
             instructions,
@@ -123,25 +615,355 @@ impl BaseClient {
             recent_blockhash,
         );
 
-        let result = self.rpc.simulate_transaction(&tx).await?;
-
-        if let Some(err) = result.value.err {
-            return Err(SdkError::SimulationFailed(format!("{:?}", err)));
+        match self.simulate(&tx).await?.error() {
+            Some(err) => Err(err),
+            None => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Simulate a signed transaction, capturing its logs and compute usage
+    pub async fn simulate(&self, tx: &impl SerializableTransaction) -> SdkResult<SimulationResult> {
+        let result = self
+            .paced(|| self.rpc.simulate_transaction(tx))
+            .await?
+            .value;
+        Ok(SimulationResult {
+            err: result.err,
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+        })
+    }
+
+    /// Simulate `tx` first when `simulate_before_send` is enabled
+    async fn preflight(&self, tx: &impl SerializableTransaction) -> SdkResult<()> {
+        if !self.simulate_before_send.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        match self.simulate(tx).await?.error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     /// Get current slot
     pub async fn get_slot(&self) -> SdkResult<u64> {
-        self.rpc.get_slot().await.map_err(|e| SdkError::RpcError(e))
+        self.paced(|| self.rpc.get_slot()).await
     }
 
     /// Get account balance
     pub async fn get_balance(&self, pubkey: &Pubkey) -> SdkResult<u64> {
-        self.rpc
-            .get_balance(pubkey)
+        self.paced(|| self.rpc.get_balance(pubkey)).await
+    }
+}
+
+/// Next step of `BaseClient::send_and_confirm_with_retry`
+enum Attempt {
+    /// The previous attempt landed with this outcome
+    Landed(Result<Signature, ClientError>),
+    /// Send this transaction; `true` when it was freshly signed
+    Send(Transaction, bool),
+}
+
+/// Whether a send failure may succeed if resubmitted
+///
+/// Expired blockhashes, lagging or unreachable nodes and block capacity
+/// limits are transient. Execution failures such as custom program errors
+/// are permanent: the same transaction would fail again.
+pub fn is_transient_error(error: &ClientError) -> bool {
+    if let Some(tx_error) = error.get_transaction_error() {
+        return matches!(
+            tx_error,
+            TransactionError::BlockhashNotFound
+                | TransactionError::AccountInUse
+                | TransactionError::WouldExceedMaxBlockCostLimit
+                | TransactionError::WouldExceedMaxAccountCostLimit
+                | TransactionError::WouldExceedMaxVoteCostLimit
+                | TransactionError::WouldExceedAccountDataBlockLimit
+                | TransactionError::ClusterMaintenance
+        );
+    }
+
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => true,
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { data, .. }) => {
+            matches!(data, RpcResponseErrorData::NodeUnhealthy { .. })
+        }
+        // `send_and_confirm_transaction` reports an unconfirmed, expired
+        // blockhash this way
+        ClientErrorKind::RpcError(RpcError::ForUser(_)) => true,
+        _ => false,
+    }
+}
+
+/// Whether the endpoint rejected a request for exceeding its rate limit
+pub fn is_rate_limited(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Reqwest(e) => e.status().map(|status| status.as_u16()) == Some(429),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+            matches!(code, 429 | -32429)
+        }
+        _ => false,
+    }
+}
+
+/// Turn a simulation failure into an `SdkError`
+///
+/// Custom program errors become `SdkError::ProgramError`, named from the
+/// Anchor error log line when one is present.
+pub fn decode_simulation_error(err: &TransactionError, logs: &[String]) -> SdkError {
+    let TransactionError::InstructionError(_, InstructionError::Custom(code)) = err else {
+        return SdkError::SimulationFailed(format!("{:?}", err));
+    };
+
+    let number = format!("Error Number: {}.", code);
+    let message = logs
+        .iter()
+        .find(|line| line.contains(&number))
+        .and_then(|line| {
+            let name = line.split("Error Code: ").nth(1)?.split('.').next()?;
+            let text = line.split("Error Message: ").nth(1)?.trim_end_matches('.');
+            Some(format!("{}: {}", name, text))
+        })
+        .unwrap_or_else(|| format!("custom program error 0x{:x}", code));
+
+    SdkError::ProgramError {
+        code: *code,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+
+    #[test]
+    fn test_transient_errors_are_retried() {
+        let expired = ClientError::from(TransactionError::BlockhashNotFound);
+        assert!(is_transient_error(&expired));
+
+        let behind = ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32005,
+            message: "Node is behind".to_string(),
+            data: RpcResponseErrorData::NodeUnhealthy {
+                num_slots_behind: Some(42),
+            },
+        }));
+        assert!(is_transient_error(&behind));
+
+        let io = ClientError::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        assert!(is_transient_error(&io));
+    }
+
+    #[test]
+    fn test_program_errors_are_permanent() {
+        let custom = ClientError::from(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(6000),
+        ));
+        assert!(!is_transient_error(&custom));
+
+        let funds = ClientError::from(TransactionError::InsufficientFundsForFee);
+        assert!(!is_transient_error(&funds));
+    }
+
+    fn mock_client(url: &str) -> BaseClient {
+        BaseClient::new(Arc::new(RpcClient::new_mock(url.to_string())))
+    }
+
+    fn signed_transfer(payer: &Keypair) -> Transaction {
+        let ix =
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[payer],
+            solana_sdk::hash::Hash::new_unique(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_retry_requires_a_signer() {
+        let client = mock_client("succeeds");
+        let result = client
+            .send_and_confirm_with_retry(&[], &[], &RetryConfig::default())
+            .await;
+        assert!(matches!(result, Err(SdkError::InvalidParameters(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retry_reports_landed_attempt_instead_of_resigning() {
+        let payer = Keypair::new();
+        let tx = signed_transfer(&payer);
+
+        // The earlier attempt landed: its outcome is returned as is
+        let client = mock_client("succeeds");
+        match client.next_attempt(Some(&tx), &[], &[&payer]).await {
+            Ok(Attempt::Landed(Ok(signature))) => assert_eq!(signature, tx.signatures[0]),
+            _ => panic!("expected the landed signature"),
+        }
+
+        let client = mock_client("instruction_error");
+        assert!(matches!(
+            client.next_attempt(Some(&tx), &[], &[&payer]).await,
+            Ok(Attempt::Landed(Err(_)))
+        ));
+
+        // Not seen yet and its blockhash cannot be checked (the mock does not
+        // answer `isBlockhashValid`): the lookup error is returned, not a re-signed transaction
+        let client = mock_client("sig_not_found");
+        assert!(client
+            .next_attempt(Some(&tx), &[], &[&payer])
             .await
-            .map_err(|e| SdkError::RpcError(e))
+            .is_err());
+    }
+
+    fn too_many_requests() -> ClientError {
+        ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: 429,
+            message: "Too many requests".to_string(),
+            data: RpcResponseErrorData::Empty,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_is_retried() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            retry_backoff: Duration::from_millis(1),
+            ..RateLimitConfig::default()
+        });
+        let calls = &std::sync::atomic::AtomicU32::new(0);
+
+        let result = limiter
+            .run(move || async move {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(too_many_requests()),
+                    _ => Ok(42u64),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Other failures are not retried
+        let result: Result<u64, _> = limiter
+            .run(|| async { Err(ClientError::from(TransactionError::AccountNotFound)) })
+            .await;
+        assert!(!is_rate_limited(&result.unwrap_err()));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_gives_up_after_max_retries() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(1),
+            ..RateLimitConfig::default()
+        });
+        let calls = &std::sync::atomic::AtomicU32::new(0);
+
+        let result: Result<u64, _> = limiter
+            .run(move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(too_many_requests())
+            })
+            .await;
+        assert!(is_rate_limited(&result.unwrap_err()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_bucket_paces_beyond_burst() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: 10,
+            burst: 2,
+            ..RateLimitConfig::default()
+        });
+        assert!(limiter.reserve().is_zero());
+        assert!(limiter.reserve().is_zero());
+
+        // The third request waits for roughly one refill interval
+        let wait = limiter.reserve();
+        assert!(wait > Duration::from_millis(50) && wait <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_retry_after_pauses_every_request() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+
+        let limiter = RateLimiter::new(RateLimitConfig::default());
+        limiter.pause_for(Duration::from_secs(2));
+        let wait = limiter.reserve();
+        assert!(wait > Duration::from_millis(1900) && wait <= Duration::from_secs(2));
+
+        // A shorter pause does not cut the pending one short, a longer one is capped
+        limiter.pause_for(Duration::from_millis(10));
+        assert!(limiter.reserve() > Duration::from_millis(1900));
+        limiter.pause_for(Duration::from_secs(3600));
+        assert!(limiter.reserve() <= MAX_RETRY_AFTER);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_replaces_backoff() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            retry_backoff: Duration::from_secs(60),
+            ..RateLimitConfig::default()
+        });
+        let limiter = &limiter;
+        let calls = &std::sync::atomic::AtomicU32::new(0);
+
+        // The endpoint asks for a short pause, so the long backoff is skipped
+        let started = Instant::now();
+        let result = limiter
+            .run(move || async move {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => {
+                        limiter.pause_for(Duration::from_millis(20));
+                        Err(too_many_requests())
+                    }
+                    _ => Ok(42u64),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(20) && elapsed < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_decode_simulated_custom_error() {
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(6003));
+        let logs = vec![
+            "Program log: Instruction: Swap".to_string(),
+            "Program log: AnchorError thrown in programs/feels/src/instructions/swap.rs:120. \
+             Error Code: SlippageExceeded. Error Number: 6003. Error Message: Slippage exceeded."
+                .to_string(),
+        ];
+        let result = SimulationResult {
+            err: Some(err),
+            logs,
+            units_consumed: Some(48_000),
+        };
+        assert!(!result.is_success());
+
+        match result.error() {
+            Some(SdkError::ProgramError { code, message }) => {
+                assert_eq!(code, 6003);
+                assert_eq!(message, "SlippageExceeded: Slippage exceeded");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        // Without an Anchor log line the raw code is still surfaced
+        let bare = decode_simulation_error(
+            &TransactionError::InstructionError(0, InstructionError::Custom(6001)),
+            &[],
+        );
+        assert!(matches!(bare, SdkError::ProgramError { code: 6001, .. }));
+
+        let other = decode_simulation_error(&TransactionError::AccountNotFound, &[]);
+        assert!(matches!(other, SdkError::SimulationFailed(_)));
     }
 }
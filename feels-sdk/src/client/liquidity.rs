@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
 use crate::prelude::*;
+use anchor_lang::AccountDeserialize;
+use feels::state::{Market, Position};
 use solana_sdk::{
     account::Account,
     signature::{Keypair, Signature},
@@ -9,9 +11,9 @@ use solana_sdk::{
 
 use crate::{
     client::BaseClient,
-    core::{PositionInfo, SdkResult},
+    core::{AprEstimate, FeeGrowthSample, PositionInfo, SdkError, SdkResult},
     instructions::{InitializeMarketParams, LiquidityInstructionBuilder, OpenPositionParams},
-    protocol::PdaBuilder,
+    protocol::{close_amount_minimums, estimate_range_apr, liquidity_for_max_amounts, PdaBuilder},
 };
 
 /// Service for liquidity management operations
@@ -125,6 +127,76 @@ impl LiquidityService {
         })
     }
 
+    /// Open a position sized by the most the LP will deposit
+    ///
+    /// The liquidity is chosen so that the deposit stays within
+    /// `max_amount_0` and `max_amount_1` even if the price moves by
+    /// `slippage_bps` before the transaction executes.
+    pub async fn open_position_with_slippage(
+        &self,
+        owner: &Keypair,
+        market: Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+        max_amount_0: u64,
+        max_amount_1: u64,
+        slippage_bps: u16,
+    ) -> SdkResult<OpenPositionResult> {
+        let market_state = self.get_market(&market).await?;
+        let liquidity = liquidity_for_max_amounts(
+            market_state.sqrt_price,
+            tick_lower,
+            tick_upper,
+            max_amount_0,
+            max_amount_1,
+            slippage_bps,
+        )?;
+        if liquidity == 0 {
+            return Err(SdkError::InvalidParameters(
+                "Amounts are too small to add any liquidity".to_string(),
+            ));
+        }
+
+        self.open_position(owner, market, tick_lower, tick_upper, liquidity)
+            .await
+    }
+
+    /// Close a position, reverting if the price moved more than `slippage_bps`
+    ///
+    /// `amount_0_min` and `amount_1_min` are derived from the market's
+    /// current price, so the LP is never paid out at a price further away.
+    pub async fn close_position_with_slippage(
+        &self,
+        owner: &Keypair,
+        position: Pubkey,
+        slippage_bps: u16,
+        close_account: bool,
+    ) -> SdkResult<Signature> {
+        let account = self.base.get_account(&position).await?;
+        let position_state = Position::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let market_state = self.get_market(&position_state.market).await?;
+        let (amount_0_min, amount_1_min) = close_amount_minimums(
+            market_state.sqrt_price,
+            position_state.tick_lower,
+            position_state.tick_upper,
+            position_state.liquidity,
+            slippage_bps,
+        )?;
+
+        self.close_position(
+            owner,
+            position_state.market,
+            position,
+            position_state.tick_lower,
+            position_state.tick_upper,
+            amount_0_min,
+            amount_1_min,
+            close_account,
+        )
+        .await
+    }
+
     /// Close a liquidity position
     pub async fn close_position(
         &self,
@@ -184,7 +256,42 @@ impl LiquidityService {
         })
     }
 
+    /// Estimate the fee APR of a range over a lookback window of snapshots
+    ///
+    /// `history` holds earlier fee-growth samples of the market, such as
+    /// indexer snapshots, and needs at least one. The oldest one starts the
+    /// window, the market's current fee growth ends it, and the in-range
+    /// fraction is time-weighted across snapshot ticks.
+    pub async fn estimate_apr(
+        &self,
+        market: &Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+        history: &[FeeGrowthSample],
+    ) -> SdkResult<AprEstimate> {
+        let market = self.get_market(market).await?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?
+            .as_secs() as i64;
+        let current = FeeGrowthSample {
+            timestamp: now,
+            fee_growth_global_0_x64: market.fee_growth_global_0_x64,
+            fee_growth_global_1_x64: market.fee_growth_global_1_x64,
+            tick: market.current_tick,
+        };
+
+        estimate_range_apr(history, current, market.sqrt_price, tick_lower, tick_upper)
+    }
+
     // Helper methods
+    async fn get_market(&self, market: &Pubkey) -> SdkResult<Market> {
+        let account = self.base.get_account(market).await?;
+        Market::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))
+    }
+
     fn parse_position_account(
         &self,
         _account: &Account,
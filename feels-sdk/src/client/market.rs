@@ -1,13 +1,16 @@
 use std::sync::Arc;
 
 use crate::prelude::*;
+use anchor_lang::AccountDeserialize;
 use solana_sdk::{account::Account, instruction::Instruction};
 
 use crate::{
     client::BaseClient,
-    core::{MarketInfo, SdkError, SdkResult},
-    instructions::MarketInstructionBuilder,
-    protocol::PdaBuilder,
+    core::{
+        DexTwapSources, MarketInfo, MintDistribution, SdkError, SdkResult, TwapQuote, VenueTwap,
+    },
+    instructions::{MarketInstructionBuilder, MintTokenParams, UpdateDexTwapParams},
+    protocol::{aggregate_dex_twap, consult_twap, preview_mint_distribution, PdaBuilder},
 };
 
 /// Service for market-related operations
@@ -32,15 +35,31 @@ impl MarketService {
         token_0: &Pubkey,
         token_1: &Pubkey,
     ) -> SdkResult<MarketInfo> {
-        // Ensure proper token ordering
-        let (ordered_token_0, ordered_token_1) = if token_0 < token_1 {
-            (token_0, token_1)
+        let market_address = self.require_market(token_0, token_1).await?;
+        self.get_market(&market_address).await
+    }
+
+    /// Resolve the canonical market for a token pair, failing if it was never created
+    ///
+    /// Tokens may be given in either order. A missing account, or one not
+    /// owned by the program, yields `SdkError::MarketNotFound` instead of a
+    /// deserialization error further down.
+    pub async fn require_market(&self, token_a: &Pubkey, token_b: &Pubkey) -> SdkResult<Pubkey> {
+        let (token_0, token_1) = if token_a < token_b {
+            (token_a, token_b)
         } else {
-            (token_1, token_0)
+            (token_b, token_a)
         };
 
-        let (market_address, _) = self.pda.market(ordered_token_0, ordered_token_1);
-        self.get_market(&market_address).await
+        let (market_address, _) = self.pda.market(token_0, token_1);
+        let account = self
+            .base
+            .get_multiple_accounts(&[market_address])
+            .await?
+            .pop()
+            .flatten();
+        check_market_account(account.as_ref(), &self.pda.program_id, token_0, token_1)?;
+        Ok(market_address)
     }
 
     /// Get market info by address
@@ -70,8 +89,7 @@ impl MarketService {
 
     /// Check if a market exists
     pub async fn market_exists(&self, token_0: &Pubkey, token_1: &Pubkey) -> bool {
-        let (market_address, _) = self.pda.market(token_0, token_1);
-        self.base.get_account(&market_address).await.is_ok()
+        self.require_market(token_0, token_1).await.is_ok()
     }
 
     /// Get market oracle data
@@ -83,6 +101,49 @@ impl MarketService {
         self.parse_oracle_account(&account)
     }
 
+    /// Consult the market TWAP over `window_secs` with a confidence band
+    ///
+    /// The quote is flagged stale when the newest observation is older than
+    /// `max_staleness_secs`.
+    pub async fn twap(
+        &self,
+        market: &Pubkey,
+        window_secs: u32,
+        max_staleness_secs: i64,
+    ) -> SdkResult<TwapQuote> {
+        let (oracle_address, _) = self.pda.oracle(market);
+        let account = self.base.get_account(&oracle_address).await?;
+
+        let oracle = feels::state::OracleState::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let observations: Vec<(i64, i128)> = oracle
+            .observations
+            .iter()
+            .filter(|obs| obs.initialized)
+            .map(|obs| (obs.block_timestamp, obs.tick_cumulative))
+            .collect();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?
+            .as_secs() as i64;
+
+        consult_twap(&observations, now, window_secs, max_staleness_secs)
+    }
+
+    /// Preview how a `mint_token` launch splits supply and the mint fee
+    pub async fn preview_mint_distribution(
+        &self,
+        params: &MintTokenParams,
+    ) -> SdkResult<MintDistribution> {
+        let (config_address, _) = self.pda.protocol_config();
+        let account = self.base.get_account(&config_address).await?;
+        let config = feels::state::ProtocolConfig::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+        preview_mint_distribution(params, config.mint_fee)
+    }
+
     /// Get market buffer data
     pub async fn get_market_buffer(&self, market: &Pubkey) -> SdkResult<BufferData> {
         let (buffer_address, _) = self.pda.buffer(market);
@@ -194,10 +255,21 @@ impl MarketService {
     /// Build update DEX TWAP instruction
     pub fn update_dex_twap_ix(
         &self,
-        market: Pubkey,
-        target_dex_pool: Pubkey,
+        updater: Pubkey,
+        params: UpdateDexTwapParams,
+    ) -> SdkResult<Instruction> {
+        self.builder.update_dex_twap(updater, params)
+    }
+
+    /// Aggregate venue TWAPs and build the resulting update DEX TWAP instruction
+    pub fn update_dex_twap_aggregated_ix(
+        &self,
+        updater: Pubkey,
+        sources: &DexTwapSources,
+        quotes: &[VenueTwap],
     ) -> SdkResult<Instruction> {
-        self.builder.update_dex_twap(market, target_dex_pool)
+        let params = aggregate_dex_twap(sources, quotes)?;
+        self.builder.update_dex_twap(updater, params)
     }
 }
 
@@ -215,3 +287,117 @@ pub struct BufferData {
     pub collected_fees_1: u64,
     pub pomm_liquidity: u128,
 }
+
+/// Check that a fetched market account exists and belongs to the program
+fn check_market_account(
+    account: Option<&Account>,
+    program_id: &Pubkey,
+    token_0: &Pubkey,
+    token_1: &Pubkey,
+) -> SdkResult<()> {
+    match account {
+        Some(account) if account.owner == *program_id => Ok(()),
+        _ => Err(SdkError::MarketNotFound {
+            token_0: *token_0,
+            token_1: *token_1,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use solana_client::{
+        mock_sender::Mocks, nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest,
+    };
+
+    /// Market service whose RPC answers `getMultipleAccounts` with `account`
+    fn mocked_service(program_id: Pubkey, account: Option<&Account>) -> MarketService {
+        let value = account.map(|account| {
+            serde_json::json!({
+                "lamports": account.lamports,
+                "data": [
+                    base64::engine::general_purpose::STANDARD.encode(&account.data),
+                    "base64"
+                ],
+                "owner": account.owner.to_string(),
+                "executable": account.executable,
+                "rentEpoch": account.rent_epoch,
+                "space": account.data.len(),
+            })
+        });
+        let mut mocks = Mocks::new();
+        mocks.insert(
+            RpcRequest::GetMultipleAccounts,
+            serde_json::json!({ "context": { "slot": 1 }, "value": [value] }),
+        );
+
+        let rpc = Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        ));
+        MarketService::new(
+            Arc::new(BaseClient::with_program_id(rpc, program_id)),
+            Arc::new(PdaBuilder::new(program_id)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_require_market_over_mock_rpc() {
+        let program_id = Pubkey::new_unique();
+        let (token_a, token_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let market = Account {
+            lamports: 1,
+            data: vec![0; 16],
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        // Either token order resolves the same canonical market
+        let service = mocked_service(program_id, Some(&market));
+        let address = service.require_market(&token_b, &token_a).await.unwrap();
+        let (token_0, token_1) = if token_a < token_b {
+            (token_a, token_b)
+        } else {
+            (token_b, token_a)
+        };
+        assert_eq!(address, service.pda.market(&token_0, &token_1).0);
+
+        let service = mocked_service(program_id, None);
+        assert!(matches!(
+            service.require_market(&token_a, &token_b).await,
+            Err(SdkError::MarketNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_market_account() {
+        let program_id = Pubkey::new_unique();
+        let (token_0, token_1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let account = |owner| Account {
+            lamports: 1,
+            data: vec![],
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        assert!(
+            check_market_account(Some(&account(program_id)), &program_id, &token_0, &token_1)
+                .is_ok()
+        );
+
+        let foreign = account(Pubkey::new_unique());
+        for missing in [None, Some(&foreign)] {
+            match check_market_account(missing, &program_id, &token_0, &token_1) {
+                Err(SdkError::MarketNotFound {
+                    token_0: t0,
+                    token_1: t1,
+                }) => assert_eq!((t0, t1), (token_0, token_1)),
+                other => panic!("expected MarketNotFound, got {:?}", other),
+            }
+        }
+    }
+}
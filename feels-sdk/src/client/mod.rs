@@ -17,12 +17,15 @@ use crate::{
     protocol::PdaBuilder,
 };
 
-pub use base::BaseClient;
+pub use base::{
+    decode_simulation_error, is_rate_limited, is_transient_error, BaseClient, RateLimitConfig,
+    RateLimiter, RetryConfig, SimulationResult,
+};
 pub use liquidity::LiquidityService;
 pub use market::MarketService;
-pub use pomm::PommService;
+pub use pomm::{project_rebalance, PommPositionInfo, PommService, RebalanceProjection};
 pub use position::PositionService;
-pub use protocol::ProtocolService;
+pub use protocol::{ProtocolConfigView, ProtocolService};
 pub use registry::RegistryService;
 pub use swap::SwapService;
 
@@ -51,31 +54,26 @@ pub struct FeelsClient {
 impl FeelsClient {
     /// Create a new client with default configuration
     pub async fn new(rpc_url: &str) -> SdkResult<Self> {
-        let rpc = Arc::new(RpcClient::new(rpc_url.to_string()));
-        let base = Arc::new(BaseClient::new(rpc));
-        let program_id = program_id();
-        let pda = Arc::new(PdaBuilder::new(program_id));
-
-        Ok(Self {
-            market: MarketService::new(base.clone(), pda.clone()),
-            swap: SwapService::new(base.clone(), pda.clone(), program_id),
-            liquidity: LiquidityService::new(base.clone(), pda.clone(), program_id),
-            protocol: ProtocolService::new(base.clone(), pda.clone(), program_id),
-            position: PositionService::new(base.clone(), pda.clone(), program_id),
-            registry: RegistryService::new(base.clone(), pda.clone(), program_id),
-            pomm: PommService::new(base.clone(), pda.clone(), program_id),
-            base,
-            pda,
-        })
+        Self::with_program_id(rpc_url, program_id()).await
     }
 
     /// Create a new client with custom program ID
     pub async fn with_program_id(rpc_url: &str, program_id: Pubkey) -> SdkResult<Self> {
         let rpc = Arc::new(RpcClient::new(rpc_url.to_string()));
-        let base = Arc::new(BaseClient::new(rpc));
+        Ok(Self::with_rpc_client(rpc, program_id))
+    }
+
+    /// Create a client over an existing RPC client
+    ///
+    /// The RPC client decides how requests reach the cluster: build it with
+    /// `RpcClient::new_sender` to plug in a custom `RpcSender` (caching,
+    /// another HTTP stack, ...) or with `RpcClient::new_mock_with_mocks` to
+    /// run the services against canned responses without a validator.
+    pub fn with_rpc_client(rpc: Arc<RpcClient>, program_id: Pubkey) -> Self {
+        let base = Arc::new(BaseClient::with_program_id(rpc, program_id));
         let pda = Arc::new(PdaBuilder::new(program_id));
 
-        Ok(Self {
+        Self {
             market: MarketService::new(base.clone(), pda.clone()),
             swap: SwapService::new(base.clone(), pda.clone(), program_id),
             liquidity: LiquidityService::new(base.clone(), pda.clone(), program_id),
@@ -85,7 +83,7 @@ impl FeelsClient {
             pomm: PommService::new(base.clone(), pda.clone(), program_id),
             base,
             pda,
-        })
+        }
     }
 
     /// Get the program ID
@@ -1,20 +1,259 @@
 use std::sync::Arc;
 
 use crate::prelude::*;
+use anchor_lang::AccountDeserialize;
+use feels::{
+    constants::{
+        MIN_LIQUIDITY, POMM_MAX_WIDTH, POMM_MIN_WIDTH, POMM_TWAP_SECONDS, POMM_WIDTH_MULTIPLIER,
+    },
+    instructions::{ManagePommParams, PommAction as ProgramPommAction},
+    logic::swap_fees::current_candidate_floor,
+    state::{Buffer, Market, OracleState, Position},
+    utils::liquidity_from_amounts,
+};
 use solana_sdk::instruction::Instruction;
 
 use crate::{
-    core::SdkResult,
+    core::{constants::MAX_POMM_POSITIONS, SdkError, SdkResult},
     instructions::{PommAction, PommInstructionBuilder},
-    protocol::PdaBuilder,
+    protocol::{tick_to_sqrt_price, PdaBuilder},
 };
 
 use super::BaseClient;
 
+/// A protocol-owned position as stored on chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PommPositionInfo {
+    pub address: Pubkey,
+    /// Index the position PDA is derived from
+    pub position_index: u8,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    /// Fees accrued to the position and not yet collected
+    pub fees_owed_0: u64,
+    pub fees_owed_1: u64,
+    /// Collected fees and removed liquidity waiting to be withdrawn
+    pub tokens_owed_0: u64,
+    pub tokens_owed_1: u64,
+    pub last_updated_slot: u64,
+}
+
+impl PommPositionInfo {
+    /// Decode the data of the POMM position at `address`
+    ///
+    /// Fails if the account is not a position, belongs to another market or
+    /// is a regular user position.
+    pub fn from_account_data(
+        address: Pubkey,
+        market: &Pubkey,
+        position_index: u8,
+        data: &[u8],
+    ) -> SdkResult<Self> {
+        let position = Position::try_deserialize(&mut &data[..])
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        if !position.is_pomm || position.market != *market {
+            return Err(SdkError::SerializationError(format!(
+                "{} is not a POMM position of market {}",
+                address, market
+            )));
+        }
+
+        Ok(Self {
+            address,
+            position_index,
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            liquidity: position.liquidity,
+            fees_owed_0: position.fees_owed_0,
+            fees_owed_1: position.fees_owed_1,
+            tokens_owed_0: position.tokens_owed_0,
+            tokens_owed_1: position.tokens_owed_1,
+            last_updated_slot: position.last_updated_slot,
+        })
+    }
+}
+
+/// Projected effect of a POMM change on one position, the market and its floor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalanceProjection {
+    pub position_index: u8,
+    /// `(tick_lower, tick_upper)` of the position, `None` while it is empty
+    pub range_before: Option<(i32, i32)>,
+    pub range_after: Option<(i32, i32)>,
+    pub position_liquidity_before: u128,
+    pub position_liquidity_after: u128,
+    /// Active market liquidity at the current price
+    pub liquidity_at_price_before: u128,
+    pub liquidity_at_price_after: u128,
+    /// Floor tick the next ratchet would settle at
+    pub floor_tick_before: i32,
+    pub floor_tick_after: i32,
+}
+
+/// Project what `params` would do to `market` without sending anything
+///
+/// Follows the program's `manage_pomm_position`: adding liquidity deploys the
+/// buffer's fees over a range around `twap_tick`, the oracle TWAP over
+/// `POMM_TWAP_SECONDS`, priced at that TWAP as the program does. The program
+/// does not implement removing, rebalancing or collecting fees yet, so those
+/// actions are rejected rather than projected.
+///
+/// `position` is the current state of the position at `params.position_index`,
+/// or `None` if it was never initialized.
+pub fn project_rebalance(
+    market: &Market,
+    buffer: &Buffer,
+    twap_tick: i32,
+    position: Option<&PommPositionInfo>,
+    params: &ManagePommParams,
+) -> SdkResult<RebalanceProjection> {
+    if params.position_index >= MAX_POMM_POSITIONS {
+        return Err(SdkError::InvalidParameters(format!(
+            "POMM position index {} exceeds maximum {}",
+            params.position_index,
+            MAX_POMM_POSITIONS - 1
+        )));
+    }
+
+    let action = match params.action {
+        ProgramPommAction::AddLiquidity => None,
+        ProgramPommAction::RemoveLiquidity { .. } => Some("RemoveLiquidity"),
+        ProgramPommAction::Rebalance { .. } => Some("Rebalance"),
+        ProgramPommAction::CollectFees => Some("CollectFees"),
+    };
+    if let Some(action) = action {
+        return Err(SdkError::InvalidParameters(format!(
+            "POMM {} is not implemented by the program",
+            action
+        )));
+    }
+
+    let (range_before, liquidity_before) = match position {
+        Some(position) if position.liquidity > 0 => (
+            Some((position.tick_lower, position.tick_upper)),
+            position.liquidity,
+        ),
+        _ => (None, 0),
+    };
+    if liquidity_before > 0 {
+        return Err(SdkError::InvalidParameters(
+            "POMM position already holds liquidity".to_string(),
+        ));
+    }
+
+    let total_fees = buffer.fees_token_0.saturating_add(buffer.fees_token_1);
+    if total_fees == 0 || total_fees < buffer.floor_placement_threshold as u128 {
+        return Err(SdkError::InvalidParameters(
+            "Buffer fees are below the floor placement threshold".to_string(),
+        ));
+    }
+    let amount_0 = buffer.fees_token_0.min(u64::MAX as u128) as u64;
+    let amount_1 = buffer.fees_token_1.min(u64::MAX as u128) as u64;
+    let deployed = amount_0 as u128 + amount_1 as u128;
+    if buffer.tau_spot < deployed {
+        return Err(SdkError::InvalidParameters(
+            "Buffer balance does not cover its fees".to_string(),
+        ));
+    }
+
+    let (tick_lower, tick_upper) = pomm_range(market, twap_tick, amount_0, amount_1)?;
+    let liquidity = liquidity_from_amounts(
+        tick_to_sqrt_price(twap_tick)?,
+        tick_to_sqrt_price(tick_lower)?,
+        tick_to_sqrt_price(tick_upper)?,
+        amount_0,
+        amount_1,
+    )
+    .map_err(|_| SdkError::MathOverflow)?;
+    if liquidity < MIN_LIQUIDITY {
+        return Err(SdkError::InvalidParameters(
+            "Buffer fees are too small to place a POMM position".to_string(),
+        ));
+    }
+
+    let mut buffer_after = buffer.clone();
+    buffer_after.fees_token_0 -= amount_0 as u128;
+    buffer_after.fees_token_1 -= amount_1 as u128;
+    buffer_after.tau_spot -= deployed;
+    let range_after = Some((tick_lower, tick_upper));
+
+    let liquidity_at_price_before = market.liquidity;
+    let liquidity_at_price_after = liquidity_at_price_before
+        .checked_add(active_liquidity(market, range_after, liquidity))
+        .ok_or(SdkError::MathOverflow)?;
+
+    Ok(RebalanceProjection {
+        position_index: params.position_index,
+        range_before,
+        range_after,
+        position_liquidity_before: liquidity_before,
+        position_liquidity_after: liquidity,
+        liquidity_at_price_before,
+        liquidity_at_price_after,
+        floor_tick_before: ratcheted_floor(market, buffer)?,
+        floor_tick_after: ratcheted_floor(market, &buffer_after)?,
+    })
+}
+
+/// Range the program places buffer fees over, centred on the TWAP tick
+fn pomm_range(
+    market: &Market,
+    twap_tick: i32,
+    amount_0: u64,
+    amount_1: u64,
+) -> SdkResult<(i32, i32)> {
+    let spacing = market.tick_spacing as i32;
+    let width = spacing
+        .saturating_mul(POMM_WIDTH_MULTIPLIER)
+        .clamp(POMM_MIN_WIDTH, POMM_MAX_WIDTH);
+    let tick = twap_tick;
+
+    let (raw_lower, raw_upper) = if amount_0 > 0 && amount_1 == 0 {
+        (tick - width, tick)
+    } else if amount_0 == 0 && amount_1 > 0 {
+        (tick, tick + width)
+    } else {
+        (tick - width, tick + width)
+    };
+
+    // Lower edge aligned down and upper edge aligned up to the tick spacing
+    let tick_lower = (raw_lower - raw_lower.rem_euclid(spacing)).max(market.global_lower_tick);
+    let tick_upper = (raw_upper + (spacing - raw_upper.rem_euclid(spacing)) % spacing)
+        .min(market.global_upper_tick);
+
+    if tick_lower >= tick_upper {
+        return Err(SdkError::InvalidParameters(
+            "POMM range collapses at the market bounds".to_string(),
+        ));
+    }
+    Ok((tick_lower, tick_upper))
+}
+
+/// Part of a position's liquidity that is active at the current tick
+fn active_liquidity(market: &Market, range: Option<(i32, i32)>, liquidity: u128) -> u128 {
+    match range {
+        Some((lower, upper)) if lower <= market.current_tick && market.current_tick <= upper => {
+            liquidity
+        }
+        _ => 0,
+    }
+}
+
+/// Floor after the next ratchet, which only ever moves it up
+fn ratcheted_floor(market: &Market, buffer: &Buffer) -> SdkResult<i32> {
+    let candidate = current_candidate_floor(market, buffer).map_err(|_| SdkError::MathOverflow)?;
+    if candidate > market.floor_tick {
+        Ok(candidate.min(market.global_upper_tick))
+    } else {
+        Ok(market.floor_tick)
+    }
+}
+
 /// Protocol-Owned Market Making (POMM) service
 #[allow(dead_code)]
 pub struct PommService {
-    _base: Arc<BaseClient>,
+    base: Arc<BaseClient>,
     pda: Arc<PdaBuilder>,
     builder: PommInstructionBuilder,
 }
@@ -22,7 +261,7 @@ pub struct PommService {
 impl PommService {
     pub fn new(base: Arc<BaseClient>, pda: Arc<PdaBuilder>, program_id: Pubkey) -> Self {
         Self {
-            _base: base,
+            base,
             pda,
             builder: PommInstructionBuilder::new(program_id),
         }
@@ -83,6 +322,76 @@ impl PommService {
             .manage_pomm_position(authority, market, pomm_position, PommAction::CollectFees)
     }
 
+    /// Fetch every initialized POMM position of `market`
+    ///
+    /// Derives the position PDA for each index below `MAX_POMM_POSITIONS` and
+    /// fetches them in one call; indices that were never initialized are
+    /// skipped. Positions are returned in index order.
+    pub async fn get_pomm_positions(&self, market: &Pubkey) -> SdkResult<Vec<PommPositionInfo>> {
+        let addresses: Vec<Pubkey> = (0..MAX_POMM_POSITIONS)
+            .map(|index| self.pda.pomm_position(market, index).0)
+            .collect();
+        let accounts = self.base.get_multiple_accounts(&addresses).await?;
+
+        addresses
+            .into_iter()
+            .zip(accounts)
+            .enumerate()
+            .filter_map(|(index, (address, account))| {
+                account.map(|account| {
+                    PommPositionInfo::from_account_data(address, market, index as u8, &account.data)
+                })
+            })
+            .collect()
+    }
+
+    /// Project the liquidity and floor impact of a POMM change on `market`
+    ///
+    /// Fetches the market, its buffer, its oracle TWAP and the targeted
+    /// position and runs [`project_rebalance`] over them.
+    pub async fn simulate_rebalance(
+        &self,
+        market: &Pubkey,
+        params: &ManagePommParams,
+    ) -> SdkResult<RebalanceProjection> {
+        let market_account = self.base.get_account(market).await?;
+        let market_state = Market::try_deserialize(&mut market_account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let buffer_account = self.base.get_account(&self.pda.buffer(market).0).await?;
+        let buffer = Buffer::try_deserialize(&mut buffer_account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let oracle_account = self.base.get_account(&market_state.oracle).await?;
+        let oracle = OracleState::try_deserialize(&mut oracle_account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?
+            .as_secs() as i64;
+        let twap_tick = oracle
+            .get_twap_tick(now, POMM_TWAP_SECONDS)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?;
+
+        // The position may not have been initialized yet
+        let (position_address, _) = self.pda.pomm_position(market, params.position_index);
+        let position = self
+            .base
+            .get_multiple_accounts(&[position_address])
+            .await?
+            .pop()
+            .flatten()
+            .map(|account| {
+                PommPositionInfo::from_account_data(
+                    position_address,
+                    market,
+                    params.position_index,
+                    &account.data,
+                )
+            })
+            .transpose()?;
+
+        project_rebalance(&market_state, &buffer, twap_tick, position.as_ref(), params)
+    }
+
     /// Get POMM position address
     pub fn get_pomm_position_address(
         &self,
@@ -101,3 +410,210 @@ impl PommService {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+
+    /// Account data of a POMM position, as the program writes it
+    fn pomm_position_data(market: Pubkey, is_pomm: bool) -> Vec<u8> {
+        let position = Position {
+            nft_mint: Pubkey::new_unique(),
+            market,
+            owner: Pubkey::new_unique(),
+            tick_lower: -1_200,
+            tick_upper: 600,
+            liquidity: 5_000_000_000,
+            fee_growth_inside_0_last_x64: 0,
+            fee_growth_inside_1_last_x64: 0,
+            tokens_owed_0: 40,
+            tokens_owed_1: 0,
+            position_bump: 254,
+            is_pomm,
+            last_updated_slot: 123_456,
+            fee_growth_inside_0_last: 0,
+            fee_growth_inside_1_last: 0,
+            fees_owed_0: 1_500,
+            fees_owed_1: 2_750,
+        };
+        let mut data = Vec::with_capacity(Position::LEN);
+        position.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_deserializes_pomm_position_fixture() {
+        let market = Pubkey::new_unique();
+        let pda = PdaBuilder::new(crate::program_id());
+        let (address, _) = pda.pomm_position(&market, 3);
+
+        let data = pomm_position_data(market, true);
+        let info = PommPositionInfo::from_account_data(address, &market, 3, &data).unwrap();
+
+        assert_eq!(
+            info,
+            PommPositionInfo {
+                address,
+                position_index: 3,
+                tick_lower: -1_200,
+                tick_upper: 600,
+                liquidity: 5_000_000_000,
+                fees_owed_0: 1_500,
+                fees_owed_1: 2_750,
+                tokens_owed_0: 40,
+                tokens_owed_1: 0,
+                last_updated_slot: 123_456,
+            }
+        );
+    }
+
+    #[test]
+    fn test_rejects_user_and_foreign_positions() {
+        let market = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+
+        // A regular user position
+        let user = pomm_position_data(market, false);
+        assert!(PommPositionInfo::from_account_data(address, &market, 0, &user).is_err());
+
+        // A POMM position of another market
+        let foreign = pomm_position_data(Pubkey::new_unique(), true);
+        assert!(PommPositionInfo::from_account_data(address, &market, 0, &foreign).is_err());
+
+        // Not a position at all
+        assert!(PommPositionInfo::from_account_data(address, &market, 0, &[0u8; 16]).is_err());
+    }
+
+    /// Market at tick 0 with 5e9 active liquidity and a floor 1000 ticks down
+    fn fixture_market() -> Market {
+        // Start from an all-zero account so only the fields the projection
+        // reads need spelling out
+        let mut market =
+            Market::try_deserialize_unchecked(&mut vec![0u8; Market::LEN].as_slice()).unwrap();
+        market.tick_spacing = 10;
+        market.current_tick = 0;
+        market.sqrt_price = 1u128 << 64;
+        market.liquidity = 5_000_000_000;
+        market.global_lower_tick = -443_600;
+        market.global_upper_tick = 443_600;
+        market.floor_tick = -1_000;
+        market.floor_buffer_ticks = 500;
+        market
+    }
+
+    fn fixture_buffer() -> Buffer {
+        let mut buffer =
+            Buffer::try_deserialize_unchecked(&mut vec![0u8; Buffer::LEN].as_slice()).unwrap();
+        buffer.fees_token_0 = 600_000;
+        buffer.fees_token_1 = 400_000;
+        buffer.tau_spot = 1_000_000;
+        buffer.floor_placement_threshold = 100;
+        buffer
+    }
+
+    fn fixture_position(liquidity: u128) -> PommPositionInfo {
+        PommPositionInfo {
+            address: Pubkey::new_unique(),
+            position_index: 0,
+            tick_lower: -200,
+            tick_upper: 200,
+            liquidity,
+            fees_owed_0: 0,
+            fees_owed_1: 0,
+            tokens_owed_0: 0,
+            tokens_owed_1: 0,
+            last_updated_slot: 0,
+        }
+    }
+
+    #[test]
+    fn test_adding_buffer_fees_deepens_price_and_stalls_floor() {
+        let market = fixture_market();
+        let params = ManagePommParams {
+            position_index: 1,
+            action: ProgramPommAction::AddLiquidity,
+        };
+
+        let projection = project_rebalance(&market, &fixture_buffer(), 0, None, &params).unwrap();
+
+        // Both tokens: 20 tick spacings either side of the TWAP
+        assert_eq!(projection.range_after, Some((-200, 200)));
+        assert_eq!(
+            projection.liquidity_at_price_after,
+            5_000_000_000 + projection.position_liquidity_after
+        );
+        // Deploying every fee empties the buffer, so the floor stops ratcheting
+        assert_eq!(projection.floor_tick_before, -500);
+        assert_eq!(projection.floor_tick_after, -1_000);
+    }
+
+    #[test]
+    fn test_range_is_placed_around_twap() {
+        let market = fixture_market();
+        let params = ManagePommParams {
+            position_index: 0,
+            action: ProgramPommAction::AddLiquidity,
+        };
+
+        // The TWAP lags a sharp move: the range follows it, not the spot tick
+        let projection =
+            project_rebalance(&market, &fixture_buffer(), 1_000, None, &params).unwrap();
+        assert_eq!(projection.range_after, Some((800, 1_200)));
+
+        // Priced at the TWAP, as the program computes it
+        let expected = liquidity_from_amounts(
+            tick_to_sqrt_price(1_000).unwrap(),
+            tick_to_sqrt_price(800).unwrap(),
+            tick_to_sqrt_price(1_200).unwrap(),
+            600_000,
+            400_000,
+        )
+        .unwrap();
+        assert_eq!(projection.position_liquidity_after, expected);
+        // Spot is below the range, so active liquidity is unchanged
+        assert_eq!(projection.liquidity_at_price_after, 5_000_000_000);
+    }
+
+    #[test]
+    fn test_rejects_changes_the_program_would_reject() {
+        let market = fixture_market();
+        let buffer = fixture_buffer();
+        let position = fixture_position(1_000_000_000);
+        let project = |position: Option<&PommPositionInfo>, position_index, action| {
+            project_rebalance(
+                &market,
+                &buffer,
+                0,
+                position,
+                &ManagePommParams {
+                    position_index,
+                    action,
+                },
+            )
+        };
+
+        // Adding to a position that already holds liquidity
+        assert!(project(Some(&position), 0, ProgramPommAction::AddLiquidity).is_err());
+        // Index past the last POMM slot
+        assert!(project(None, MAX_POMM_POSITIONS, ProgramPommAction::AddLiquidity).is_err());
+
+        // Actions the program answers with NotImplemented
+        let unimplemented = [
+            ProgramPommAction::RemoveLiquidity {
+                liquidity_amount: 1,
+            },
+            ProgramPommAction::Rebalance {
+                new_tick_lower: 200,
+                new_tick_upper: 600,
+            },
+            ProgramPommAction::CollectFees,
+        ];
+        for action in unimplemented {
+            assert!(matches!(
+                project(Some(&position), 0, action),
+                Err(SdkError::InvalidParameters(message)) if message.contains("not implemented")
+            ));
+        }
+    }
+}
@@ -1,20 +1,54 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::prelude::*;
-use solana_sdk::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use feels::{
+    constants::MIN_POSITION_LIQUIDITY,
+    state::{Market, Position},
+};
+use solana_client::{
+    rpc_config::RpcProgramAccountsConfig,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
 
 use crate::{
-    core::SdkResult,
-    instructions::{OpenPositionWithMetadataParams, PositionInstructionBuilder},
-    protocol::PdaBuilder,
+    core::{
+        constants::{MAX_COMPUTE_UNIT_LIMIT, TICK_ARRAY_SIZE},
+        SdkError, SdkResult,
+    },
+    instructions::{
+        ClosePositionParams, IncreaseLiquidityParams, OpenPositionWithMetadataParams,
+        PositionInstructionBuilder, TransactionBuilder,
+    },
+    protocol::{get_tick_array_start_index, tick_to_sqrt_price, PdaBuilder},
 };
 
 use super::BaseClient;
 
+/// Estimated compute units for a single `collect_fees` instruction
+pub const COLLECT_FEES_CU_ESTIMATE: u32 = 40_000;
+
+/// Estimated compute units for a single `increase_liquidity` instruction
+pub const INCREASE_LIQUIDITY_CU_ESTIMATE: u32 = 60_000;
+
+/// Estimated compute units for a single `close_position` instruction
+pub const CLOSE_POSITION_CU_ESTIMATE: u32 = 60_000;
+
+/// Byte offset of `Position::owner` (discriminator, nft_mint, market)
+const POSITION_OWNER_OFFSET: usize = 8 + 32 + 32;
+
 /// Position management service (with NFT support)
 #[allow(dead_code)]
 pub struct PositionService {
-    _base: Arc<BaseClient>,
+    base: Arc<BaseClient>,
     pda: Arc<PdaBuilder>,
     builder: PositionInstructionBuilder,
 }
@@ -22,7 +56,7 @@ pub struct PositionService {
 impl PositionService {
     pub fn new(base: Arc<BaseClient>, pda: Arc<PdaBuilder>, program_id: Pubkey) -> Self {
         Self {
-            _base: base,
+            base,
             pda,
             builder: PositionInstructionBuilder::new(program_id),
         }
@@ -106,6 +140,317 @@ impl PositionService {
         )
     }
 
+    /// Collect fees for many positions with as few transactions as possible
+    ///
+    /// Instructions are packed until the next one would exceed the packet size
+    /// or compute budget, then a new transaction is started. Wide positions
+    /// whose tick arrays are not adjacent need the 3-step
+    /// `update_position_fee_lower/upper` flow and are skipped.
+    ///
+    /// A failed transaction only marks its own positions as failed; later
+    /// batches are still sent and earlier signatures are kept.
+    pub async fn collect_fees_batch(
+        &self,
+        owner: &Keypair,
+        positions: &[Pubkey],
+    ) -> SdkResult<Vec<BatchCollectResult>> {
+        let position_accounts = self.base.get_multiple_accounts(positions).await?;
+
+        let mut results = Vec::with_capacity(positions.len());
+        let mut pending: Vec<(usize, Instruction)> = Vec::new();
+
+        for (address, account) in positions.iter().zip(position_accounts) {
+            let position = match account
+                .map(|a| Position::try_deserialize(&mut a.data.as_slice()))
+                .transpose()
+                .map_err(|e| SdkError::SerializationError(e.to_string()))?
+            {
+                Some(position) => position,
+                None => {
+                    results.push(BatchCollectResult::skipped(*address, "Position not found"));
+                    continue;
+                }
+            };
+
+            let market_account = self.base.get_account(&position.market).await?;
+            let market = Market::try_deserialize(&mut market_account.data.as_slice())
+                .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+            let Some(tick_arrays) = self.collect_tick_arrays(&position, &market) else {
+                results.push(BatchCollectResult::skipped(
+                    *address,
+                    "Wide position requires 3-step fee collection",
+                ));
+                continue;
+            };
+
+            let ix = self.builder.collect_fees(
+                owner.pubkey(),
+                position.market,
+                *address,
+                position.nft_mint,
+                market.token_0,
+                market.token_1,
+                Some(tick_arrays),
+            )?;
+
+            pending.push((results.len(), ix));
+            results.push(BatchCollectResult {
+                position: *address,
+                signature: None,
+                tokens_owed_0: position.tokens_owed_0,
+                tokens_owed_1: position.tokens_owed_1,
+                skipped: None,
+                error: None,
+            });
+        }
+
+        let instructions: Vec<Instruction> = pending.iter().map(|(_, ix)| ix.clone()).collect();
+        let mut offset = 0;
+        for batch in pack_collect_instructions(&owner.pubkey(), &instructions) {
+            let count = batch.len() - 1; // exclude compute budget instruction
+            let outcome = self.base.send_transaction(&batch, &[owner]).await;
+            for (index, _) in &pending[offset..offset + count] {
+                let result = &mut results[*index];
+                match &outcome {
+                    Ok(signature) => result.signature = Some(*signature),
+                    Err(e) => result.error = Some(e.to_string()),
+                }
+            }
+            offset += count;
+        }
+
+        Ok(results)
+    }
+
+    /// Fetch every position account owned by `owner`
+    pub async fn get_positions_by_owner(
+        &self,
+        owner: &Pubkey,
+    ) -> SdkResult<Vec<(Pubkey, Position, u64)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(Position::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    POSITION_OWNER_OFFSET,
+                    owner.as_ref(),
+                )),
+            ]),
+            ..Default::default()
+        };
+        let accounts = self
+            .base
+            .rpc()
+            .get_program_accounts_with_config(&self.pda.program_id, config)
+            .await
+            .map_err(SdkError::RpcError)?;
+
+        accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let position = Position::try_deserialize(&mut account.data.as_slice())
+                    .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+                Ok((address, position, account.lamports))
+            })
+            .collect()
+    }
+
+    /// Close every dust position owned by `owner`, reclaiming its rent
+    ///
+    /// The program's `close_position` withdraws the remaining liquidity and
+    /// pays out owed fees before closing the account, and rejects positions
+    /// with no liquidity left. Dust here means liquidity below
+    /// `MIN_POSITION_LIQUIDITY`, the smallest position the program opens.
+    /// Positions that still owe fees are skipped unless `force` is set.
+    ///
+    /// Returns one result per dust position. A failed transaction only marks
+    /// its own positions as failed; later batches are still sent.
+    pub async fn close_empty_positions(
+        &self,
+        owner: &Keypair,
+        force: bool,
+    ) -> SdkResult<Vec<ClosePositionResult>> {
+        let positions = self.get_positions_by_owner(&owner.pubkey()).await?;
+
+        let mut markets: HashMap<Pubkey, Market> = HashMap::new();
+        let mut results = Vec::new();
+        let mut pending: Vec<(usize, Instruction)> = Vec::new();
+        for (address, position, lamports) in positions {
+            if !is_dust(&position) {
+                continue;
+            }
+            if let Some(reason) = close_skip_reason(&position, force) {
+                results.push(ClosePositionResult::skipped(address, reason));
+                continue;
+            }
+
+            if !markets.contains_key(&position.market) {
+                let account = self.base.get_account(&position.market).await?;
+                let market = Market::try_deserialize(&mut account.data.as_slice())
+                    .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+                markets.insert(position.market, market);
+            }
+            let market = &markets[&position.market];
+            let lower_start = get_tick_array_start_index(position.tick_lower, market.tick_spacing);
+            let upper_start = get_tick_array_start_index(position.tick_upper, market.tick_spacing);
+            let (lower_tick_array, _) = self.pda.tick_array(&position.market, lower_start);
+            let (upper_tick_array, _) = self.pda.tick_array(&position.market, upper_start);
+
+            let ix = self.builder.close_position(
+                owner.pubkey(),
+                position.market,
+                address,
+                position.nft_mint,
+                market.token_0,
+                market.token_1,
+                (lower_tick_array, upper_tick_array),
+                ClosePositionParams {
+                    amount_0_min: 0,
+                    amount_1_min: 0,
+                    close_account: true,
+                },
+            )?;
+            pending.push((results.len(), ix));
+            results.push(ClosePositionResult {
+                position: address,
+                signature: None,
+                reclaimed: lamports,
+                skipped: None,
+                error: None,
+            });
+        }
+
+        let instructions: Vec<Instruction> = pending.iter().map(|(_, ix)| ix.clone()).collect();
+        let mut offset = 0;
+        for batch in pack_instructions(&owner.pubkey(), &instructions, CLOSE_POSITION_CU_ESTIMATE) {
+            let count = batch.len() - 1; // exclude compute budget instruction
+            let outcome = self.base.send_transaction(&batch, &[owner]).await;
+            for (index, _) in &pending[offset..offset + count] {
+                let result = &mut results[*index];
+                match &outcome {
+                    Ok(signature) => result.signature = Some(*signature),
+                    Err(e) => {
+                        result.reclaimed = 0;
+                        result.error = Some(e.to_string());
+                    }
+                }
+            }
+            offset += count;
+        }
+
+        Ok(results)
+    }
+
+    /// Build a transaction that collects a position's fees and reinvests them
+    ///
+    /// `collect_fees` is followed by `increase_liquidity` on the same
+    /// position, funded by the fees it already owes and capped at those
+    /// amounts. Fees that accrue during the collection itself are left in the
+    /// owner's token accounts.
+    pub fn collect_and_compound_ix(
+        &self,
+        owner: Pubkey,
+        address: Pubkey,
+        position: &Position,
+        market: &Market,
+    ) -> SdkResult<CompoundPlan> {
+        let tick_arrays = self.collect_tick_arrays(position, market).ok_or_else(|| {
+            SdkError::InvalidParameters("Wide position requires 3-step fee collection".to_string())
+        })?;
+        let liquidity = compound_liquidity(position, market.sqrt_price)?;
+        if liquidity == 0 {
+            return Err(SdkError::InvalidParameters(
+                "Owed fees are too small to add any liquidity".to_string(),
+            ));
+        }
+        if position.liquidity.saturating_add(liquidity) < MIN_POSITION_LIQUIDITY {
+            return Err(SdkError::InvalidParameters(format!(
+                "Compounded position would hold less than the minimum liquidity of {}",
+                MIN_POSITION_LIQUIDITY
+            )));
+        }
+
+        let collect = self.builder.collect_fees(
+            owner,
+            position.market,
+            address,
+            position.nft_mint,
+            market.token_0,
+            market.token_1,
+            Some(tick_arrays),
+        )?;
+        let increase = self.builder.increase_liquidity(
+            owner,
+            position.market,
+            address,
+            position.nft_mint,
+            market.token_0,
+            market.token_1,
+            tick_arrays,
+            IncreaseLiquidityParams {
+                liquidity,
+                amount_0_max: position.tokens_owed_0,
+                amount_1_max: position.tokens_owed_1,
+            },
+        )?;
+
+        Ok(CompoundPlan {
+            fees_0: position.tokens_owed_0,
+            fees_1: position.tokens_owed_1,
+            liquidity,
+            instructions: vec![collect, increase],
+        })
+    }
+
+    /// Collect a position's fees and reinvest them in one transaction
+    pub async fn collect_and_compound(
+        &self,
+        owner: &Keypair,
+        position: Pubkey,
+    ) -> SdkResult<CompoundResult> {
+        let position_account = self.base.get_account(&position).await?;
+        let position_state = Position::try_deserialize(&mut position_account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let market_account = self.base.get_account(&position_state.market).await?;
+        let market = Market::try_deserialize(&mut market_account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+        let plan =
+            self.collect_and_compound_ix(owner.pubkey(), position, &position_state, &market)?;
+        let builder = TransactionBuilder::new(owner)
+            .with_compute_budget(
+                COLLECT_FEES_CU_ESTIMATE + INCREASE_LIQUIDITY_CU_ESTIMATE,
+                None,
+            )
+            .add_instructions(plan.instructions);
+        let signature = self.base.send_batch(&builder).await?;
+
+        Ok(CompoundResult {
+            signature,
+            fees_0: plan.fees_0,
+            fees_1: plan.fees_1,
+            liquidity: plan.liquidity,
+        })
+    }
+
+    /// Tick arrays `collect_fees` reads, or `None` for a wide position
+    fn collect_tick_arrays(
+        &self,
+        position: &Position,
+        market: &Market,
+    ) -> Option<(Pubkey, Pubkey)> {
+        let lower_start = get_tick_array_start_index(position.tick_lower, market.tick_spacing);
+        let upper_start = get_tick_array_start_index(position.tick_upper, market.tick_spacing);
+        let array_span = market.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        if upper_start - lower_start > array_span {
+            return None;
+        }
+
+        let (lower_tick_array, _) = self.pda.tick_array(&position.market, lower_start);
+        let (upper_tick_array, _) = self.pda.tick_array(&position.market, upper_start);
+        Some((lower_tick_array, upper_tick_array))
+    }
+
     /// Get position NFT mint address
     pub fn get_position_mint(&self, position: Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[b"position_mint", position.as_ref()], &self.pda.program_id)
@@ -116,3 +461,439 @@ impl PositionService {
         spl_associated_token_account::get_associated_token_address(owner, position_mint)
     }
 }
+
+/// Instructions and amounts for compounding a position's fees
+#[derive(Debug, Clone)]
+pub struct CompoundPlan {
+    /// Fees owed to the position before collection
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Liquidity the owed fees add to the position
+    pub liquidity: u128,
+    /// `collect_fees` then `increase_liquidity`, in transaction order
+    pub instructions: Vec<Instruction>,
+}
+
+/// Outcome of `PositionService::collect_and_compound`
+#[derive(Debug, Clone)]
+pub struct CompoundResult {
+    pub signature: Signature,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Liquidity added to the position
+    pub liquidity: u128,
+}
+
+/// Per-position outcome of a batched fee collection
+#[derive(Debug, Clone)]
+pub struct BatchCollectResult {
+    pub position: Pubkey,
+    /// Transaction that collected this position's fees
+    pub signature: Option<Signature>,
+    /// Fees already owed before collection
+    pub tokens_owed_0: u64,
+    pub tokens_owed_1: u64,
+    /// Reason the position was not included in any transaction
+    pub skipped: Option<String>,
+    /// Error from the transaction that tried to collect this position's fees
+    pub error: Option<String>,
+}
+
+impl BatchCollectResult {
+    fn skipped(position: Pubkey, reason: &str) -> Self {
+        Self {
+            position,
+            signature: None,
+            tokens_owed_0: 0,
+            tokens_owed_1: 0,
+            skipped: Some(reason.to_string()),
+            error: None,
+        }
+    }
+}
+
+/// Per-position outcome of `PositionService::close_empty_positions`
+#[derive(Debug, Clone)]
+pub struct ClosePositionResult {
+    pub position: Pubkey,
+    /// Transaction that closed this position
+    pub signature: Option<Signature>,
+    /// Rent lamports returned by the position account (0 unless closed)
+    pub reclaimed: u64,
+    /// Reason the position was not included in any transaction
+    pub skipped: Option<String>,
+    /// Error from the transaction that tried to close this position
+    pub error: Option<String>,
+}
+
+impl ClosePositionResult {
+    fn skipped(position: Pubkey, reason: &str) -> Self {
+        Self {
+            position,
+            signature: None,
+            reclaimed: 0,
+            skipped: Some(reason.to_string()),
+            error: None,
+        }
+    }
+}
+
+/// Split `collect_fees` instructions into transactions that fit the size and CU limits
+///
+/// Each returned batch starts with a compute unit limit sized to its contents.
+pub fn pack_collect_instructions(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+) -> Vec<Vec<Instruction>> {
+    pack_instructions(payer, instructions, COLLECT_FEES_CU_ESTIMATE)
+}
+
+/// Largest liquidity over the position's range its owed fees can deposit
+///
+/// Deposits are charged with the program's `amounts_from_liquidity`, so the
+/// returned liquidity never needs more than `tokens_owed_0/1`.
+pub fn compound_liquidity(position: &Position, sqrt_price: u128) -> SdkResult<u128> {
+    let sqrt_price_lower = tick_to_sqrt_price(position.tick_lower)?;
+    let sqrt_price_upper = tick_to_sqrt_price(position.tick_upper)?;
+    let (fees_0, fees_1) = (position.tokens_owed_0, position.tokens_owed_1);
+
+    let liquidity = feels::utils::liquidity_from_amounts(
+        sqrt_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        fees_0,
+        fees_1,
+    )
+    .map_err(|_| SdkError::MathOverflow)?;
+
+    // In range, a zero amount on one side bounds nothing in
+    // liquidity_from_amounts, so check the deposit the program will charge
+    let deposit = feels::logic::amounts_from_liquidity(
+        sqrt_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        liquidity,
+    );
+    let fits =
+        matches!(deposit, Ok((amount_0, amount_1)) if amount_0 <= fees_0 && amount_1 <= fees_1);
+    Ok(if fits { liquidity } else { 0 })
+}
+
+/// Whether a position is dust that `close_empty_positions` considers
+///
+/// POMM positions are protocol-owned and never closed here.
+pub fn is_dust(position: &Position) -> bool {
+    position.liquidity < MIN_POSITION_LIQUIDITY && !position.is_pomm
+}
+
+/// Why `close_empty_positions` leaves a dust position open, if it does
+///
+/// `close_position` requires liquidity, so a position already drained to
+/// zero cannot be closed. Owed fees are paid out by the close, but skipped
+/// unless `force` is set so they can be compounded or collected instead.
+pub fn close_skip_reason(position: &Position, force: bool) -> Option<&'static str> {
+    let owes_fees = position.tokens_owed_0 > 0
+        || position.tokens_owed_1 > 0
+        || position.fees_owed_0 > 0
+        || position.fees_owed_1 > 0;
+    if position.liquidity == 0 {
+        Some("Position has no liquidity; close_position cannot close it")
+    } else if owes_fees && !force {
+        Some("Position has uncollected fees")
+    } else {
+        None
+    }
+}
+
+fn pack_instructions(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    units_per_ix: u32,
+) -> Vec<Vec<Instruction>> {
+    let max_per_tx = (MAX_COMPUTE_UNIT_LIMIT / units_per_ix) as usize;
+    let mut batches = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+
+    for ix in instructions {
+        current.push(ix.clone());
+        if current.len() > max_per_tx || !fits_in_packet(payer, &current) {
+            let overflow = current.pop().expect("just pushed");
+            if !current.is_empty() {
+                batches.push(with_compute_budget(
+                    std::mem::take(&mut current),
+                    units_per_ix,
+                ));
+            }
+            current.push(overflow);
+        }
+    }
+    if !current.is_empty() {
+        batches.push(with_compute_budget(current, units_per_ix));
+    }
+
+    batches
+}
+
+fn with_compute_budget(instructions: Vec<Instruction>, units_per_ix: u32) -> Vec<Instruction> {
+    let units = (instructions.len() as u32 * units_per_ix).min(MAX_COMPUTE_UNIT_LIMIT);
+    let mut batch = Vec::with_capacity(instructions.len() + 1);
+    batch.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    batch.extend(instructions);
+    batch
+}
+
+fn fits_in_packet(payer: &Pubkey, instructions: &[Instruction]) -> bool {
+    let mut with_budget = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        MAX_COMPUTE_UNIT_LIMIT,
+    )];
+    with_budget.extend_from_slice(instructions);
+    let message = Message::new(&with_budget, Some(payer));
+    // shortvec signature count + one signature per required signer + message
+    let size = 1 + 64 * message.header.num_required_signatures as usize + message.serialize().len();
+    size <= PACKET_DATA_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::{CollectFeesParams, InstructionBuilder};
+
+    fn collect_ix(builder: &PositionInstructionBuilder, owner: Pubkey) -> Instruction {
+        builder
+            .collect_fees(
+                owner,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Some((Pubkey::new_unique(), Pubkey::new_unique())),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pack_collect_instructions_splits_on_size() {
+        let builder = PositionInstructionBuilder::new(crate::program_id());
+        let owner = Pubkey::new_unique();
+        let ixs: Vec<Instruction> = (0..10).map(|_| collect_ix(&builder, owner)).collect();
+
+        let batches = pack_collect_instructions(&owner, &ixs);
+        assert!(batches.len() > 1);
+        assert_eq!(
+            batches.iter().map(|b| b.len() - 1).sum::<usize>(),
+            ixs.len()
+        );
+        for batch in &batches {
+            assert!(fits_in_packet(&owner, &batch[1..]));
+        }
+    }
+
+    fn position(liquidity: u128, tokens_owed_0: u64, is_pomm: bool) -> Position {
+        Position {
+            nft_mint: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            tick_lower: -100,
+            tick_upper: 100,
+            liquidity,
+            fee_growth_inside_0_last_x64: 0,
+            fee_growth_inside_1_last_x64: 0,
+            tokens_owed_0,
+            tokens_owed_1: 0,
+            position_bump: 0,
+            is_pomm,
+            last_updated_slot: 0,
+            fee_growth_inside_0_last: 0,
+            fee_growth_inside_1_last: 0,
+            fees_owed_0: 0,
+            fees_owed_1: 0,
+        }
+    }
+
+    #[test]
+    fn test_dust_positions_to_close() {
+        let dust = MIN_POSITION_LIQUIDITY - 1;
+        assert!(is_dust(&position(dust, 0, false)));
+        assert!(!is_dust(&position(MIN_POSITION_LIQUIDITY, 0, false)));
+        assert!(!is_dust(&position(dust, 0, true)));
+        assert_eq!(close_skip_reason(&position(dust, 0, false), false), None);
+
+        // close_position rejects zero liquidity, even when forced
+        assert!(close_skip_reason(&position(0, 0, false), true).is_some());
+
+        // Owed fees block the close unless forced
+        assert!(close_skip_reason(&position(dust, 5, false), false).is_some());
+        assert_eq!(close_skip_reason(&position(dust, 5, false), true), None);
+    }
+
+    #[test]
+    fn test_close_position_accounts() {
+        let builder = PositionInstructionBuilder::new(crate::program_id());
+        let (owner, market, position, position_mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let (token_0, token_1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let tick_arrays = (Pubkey::new_unique(), Pubkey::new_unique());
+        let params = ClosePositionParams {
+            amount_0_min: 0,
+            amount_1_min: 0,
+            close_account: true,
+        };
+        let ix = builder
+            .close_position(
+                owner,
+                market,
+                position,
+                position_mint,
+                token_0,
+                token_1,
+                tick_arrays,
+                params.clone(),
+            )
+            .unwrap();
+
+        // Same order as the program's ClosePosition accounts
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(keys.len(), 13);
+        assert_eq!(keys[0], owner);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(keys[1], market);
+        assert_eq!(keys[2], position_mint);
+        assert_eq!(
+            keys[3],
+            spl_associated_token_account::get_associated_token_address(&owner, &position_mint)
+        );
+        assert_eq!(keys[4], position);
+        assert_eq!(
+            keys[5],
+            spl_associated_token_account::get_associated_token_address(&owner, &token_0)
+        );
+        assert_eq!((keys[10], keys[11]), tick_arrays);
+        assert_eq!(keys[12], spl_token::id());
+        assert_eq!(ix.data, params.build_data().unwrap());
+    }
+
+    fn owed(tokens_owed_0: u64, tokens_owed_1: u64) -> Position {
+        let mut position = position(1_000_000_000, tokens_owed_0, false);
+        position.tokens_owed_1 = tokens_owed_1;
+        position
+    }
+
+    fn deposit(position: &Position, liquidity: u128, sqrt_price: u128) -> (u64, u64) {
+        feels::logic::amounts_from_liquidity(
+            sqrt_price,
+            tick_to_sqrt_price(position.tick_lower).unwrap(),
+            tick_to_sqrt_price(position.tick_upper).unwrap(),
+            liquidity,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compound_liquidity_is_funded_by_owed_fees() {
+        let sqrt_price = 1u128 << 64;
+        let position = owed(1_000_000, 3_000_000);
+        let liquidity = compound_liquidity(&position, sqrt_price).unwrap();
+        assert!(liquidity > 0);
+
+        // Never more than owed, and token 0 binds at the 1:1 price
+        let (amount_0, amount_1) = deposit(&position, liquidity, sqrt_price);
+        assert!(amount_0 <= 1_000_000 && amount_1 <= 3_000_000);
+        assert!(1_000_000 - amount_0 <= 1);
+    }
+
+    #[test]
+    fn test_compound_liquidity_needs_both_tokens_in_range() {
+        let sqrt_price = 1u128 << 64;
+        assert_eq!(
+            compound_liquidity(&owed(0, 3_000_000), sqrt_price).unwrap(),
+            0
+        );
+        assert_eq!(
+            compound_liquidity(&owed(1_000_000, 0), sqrt_price).unwrap(),
+            0
+        );
+        assert_eq!(compound_liquidity(&owed(0, 0), sqrt_price).unwrap(), 0);
+
+        // Below the range only token 0 is deposited
+        let below = tick_to_sqrt_price(-500).unwrap();
+        let position = owed(1_000_000, 0);
+        let liquidity = compound_liquidity(&position, below).unwrap();
+        assert!(liquidity > 0);
+        assert_eq!(deposit(&position, liquidity, below).1, 0);
+    }
+
+    #[test]
+    fn test_collect_and_compound_ix() {
+        let program_id = crate::program_id();
+        let rpc = solana_client::nonblocking::rpc_client::RpcClient::new(
+            "http://localhost:8899".to_string(),
+        );
+        let service = PositionService::new(
+            Arc::new(BaseClient::new(Arc::new(rpc))),
+            Arc::new(PdaBuilder::new(program_id)),
+            program_id,
+        );
+        let mut market =
+            Market::try_deserialize_unchecked(&mut vec![0u8; Market::LEN].as_slice()).unwrap();
+        market.tick_spacing = 10;
+        market.sqrt_price = 1u128 << 64;
+        let owner = Pubkey::new_unique();
+        let position = owed(1_000_000, 3_000_000);
+
+        let address = Pubkey::new_unique();
+        let plan = service
+            .collect_and_compound_ix(owner, address, &position, &market)
+            .unwrap();
+        assert_eq!((plan.fees_0, plan.fees_1), (1_000_000, 3_000_000));
+        assert_eq!(
+            plan.liquidity,
+            compound_liquidity(&position, market.sqrt_price).unwrap()
+        );
+
+        // Collect first, then grow the same position with the collected fees
+        let [collect, increase] = &plan.instructions[..] else {
+            panic!("expected two instructions");
+        };
+        assert_eq!(collect.data, CollectFeesParams {}.build_data().unwrap());
+        let params = IncreaseLiquidityParams {
+            liquidity: plan.liquidity,
+            amount_0_max: 1_000_000,
+            amount_1_max: 3_000_000,
+        };
+        assert_eq!(increase.data, params.build_data().unwrap());
+        assert!(collect.accounts[0].is_signer && increase.accounts[0].is_signer);
+        assert_eq!(collect.accounts[0].pubkey, owner);
+        assert_eq!(increase.accounts[0].pubkey, owner);
+        assert_eq!(increase.accounts[3].pubkey, address);
+        assert_eq!(increase.accounts.len(), 11);
+
+        // Nothing owed, nothing to compound
+        assert!(matches!(
+            service.collect_and_compound_ix(owner, Pubkey::new_unique(), &owed(0, 0), &market),
+            Err(SdkError::InvalidParameters(_))
+        ));
+
+        // A dust position the fees cannot lift to the minimum is rejected
+        let mut dust = owed(100, 100);
+        dust.liquidity = 1;
+        assert!(compound_liquidity(&dust, market.sqrt_price).unwrap() > 0);
+        assert!(matches!(
+            service.collect_and_compound_ix(owner, address, &dust, &market),
+            Err(SdkError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_pack_collect_instructions_single() {
+        let builder = PositionInstructionBuilder::new(crate::program_id());
+        let owner = Pubkey::new_unique();
+        let batches = pack_collect_instructions(&owner, &[collect_ix(&builder, owner)]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 2);
+    }
+}
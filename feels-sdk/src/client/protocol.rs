@@ -1,36 +1,212 @@
 use std::sync::Arc;
 
 use crate::prelude::*;
+use anchor_lang::AccountDeserialize;
+use feels::state::{ProtocolConfig, SafetyController};
 use solana_sdk::instruction::Instruction;
 
 use crate::{
-    core::SdkResult,
+    core::{
+        constants::{JITO_STAKE_POOL, SPL_STAKE_POOL_PROGRAM_ID},
+        SafetyProjection, SafetyThresholds, SdkError, SdkResult, StressComponents,
+    },
     instructions::{
         InitializeHubParams, InitializeProtocolParams, ProtocolInstructionBuilder,
         UpdateProtocolParams,
     },
-    protocol::PdaBuilder,
+    protocol::{parse_stake_pool_rate, project_safety_trip, rate_to_q64, PdaBuilder},
 };
 
 use super::BaseClient;
 
+/// Protocol configuration as stored on chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolConfigView {
+    /// Address of the `ProtocolConfig` PDA
+    pub address: Pubkey,
+    /// Authority that can update the configuration
+    pub authority: Pubkey,
+    /// Account receiving protocol fees
+    pub treasury: Pubkey,
+    /// Fee for minting a new token (FeelsSOL lamports)
+    pub mint_fee: u64,
+    pub default_protocol_fee_rate: u16,
+    pub default_creator_fee_rate: u16,
+    pub max_protocol_fee_rate: u16,
+    /// Seconds a minted token has to deploy liquidity before it can be destroyed
+    pub token_expiration_seconds: i64,
+    /// Parameters `initialize_market` requires of every new market
+    pub default_base_fee_bps: u16,
+    pub default_tick_spacing: u16,
+    pub default_initial_sqrt_price: u128,
+    pub default_tick_step_size: u16,
+    /// De-peg circuit breaker hysteresis
+    pub safety_thresholds: SafetyThresholds,
+    /// Largest spot/TWAP divergence a swap may cause (bps), 0 when disabled
+    pub max_spot_twap_deviation_bps: u16,
+    /// Per-slot volume caps (FeelsSOL units), 0 when unlimited
+    pub mint_per_slot_cap_feelssol: u64,
+    pub redeem_per_slot_cap_feelssol: u64,
+    pub swap_per_slot_cap_feelssol: u64,
+    pub bonding_swap_per_slot_cap_feelssol: u64,
+    pub dex_twap_window_secs: u32,
+    pub dex_twap_stale_age_secs: u32,
+    pub dex_twap_updater: Pubkey,
+    /// Whitelisted DEX venues, without the unused slots
+    pub dex_whitelist: Vec<Pubkey>,
+    /// Address of the `SafetyController` PDA
+    pub safety_controller: Pubkey,
+    /// Whether the safety controller has paused redemptions, `None` while
+    /// the controller is not initialized
+    pub redemptions_paused: Option<bool>,
+}
+
+impl ProtocolConfigView {
+    /// Decode the protocol config at `address` and, if it exists, the safety
+    /// controller at `safety_controller`
+    pub fn from_account_data(
+        address: Pubkey,
+        data: &[u8],
+        safety_controller: Pubkey,
+        safety_controller_data: Option<&[u8]>,
+    ) -> SdkResult<Self> {
+        let config = ProtocolConfig::try_deserialize(&mut &data[..])
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        let redemptions_paused = safety_controller_data
+            .map(|data| {
+                SafetyController::try_deserialize(&mut &data[..])
+                    .map(|controller| controller.redemptions_paused)
+                    .map_err(|e| SdkError::SerializationError(e.to_string()))
+            })
+            .transpose()?;
+        let whitelist_len = (config.dex_whitelist_len as usize).min(config.dex_whitelist.len());
+
+        Ok(Self {
+            address,
+            authority: config.authority,
+            treasury: config.treasury,
+            mint_fee: config.mint_fee,
+            default_protocol_fee_rate: config.default_protocol_fee_rate,
+            default_creator_fee_rate: config.default_creator_fee_rate,
+            max_protocol_fee_rate: config.max_protocol_fee_rate,
+            token_expiration_seconds: config.token_expiration_seconds,
+            default_base_fee_bps: config.default_base_fee_bps,
+            default_tick_spacing: config.default_tick_spacing,
+            default_initial_sqrt_price: config.default_initial_sqrt_price,
+            default_tick_step_size: config.default_tick_step_size,
+            safety_thresholds: SafetyThresholds {
+                depeg_threshold_bps: config.depeg_threshold_bps,
+                depeg_required_obs: config.depeg_required_obs,
+                clear_required_obs: config.clear_required_obs,
+            },
+            max_spot_twap_deviation_bps: config.max_spot_twap_deviation_bps,
+            mint_per_slot_cap_feelssol: config.mint_per_slot_cap_feelssol,
+            redeem_per_slot_cap_feelssol: config.redeem_per_slot_cap_feelssol,
+            swap_per_slot_cap_feelssol: config.swap_per_slot_cap_feelssol,
+            bonding_swap_per_slot_cap_feelssol: config.bonding_swap_per_slot_cap_feelssol,
+            dex_twap_window_secs: config.dex_twap_window_secs,
+            dex_twap_stale_age_secs: config.dex_twap_stale_age_secs,
+            dex_twap_updater: config.dex_twap_updater,
+            dex_whitelist: config.dex_whitelist[..whitelist_len].to_vec(),
+            safety_controller,
+            redemptions_paused,
+        })
+    }
+}
+
 /// Protocol management service
 #[allow(dead_code)]
 pub struct ProtocolService {
-    _base: Arc<BaseClient>,
-    _pda: Arc<PdaBuilder>,
+    base: Arc<BaseClient>,
+    pda: Arc<PdaBuilder>,
     builder: ProtocolInstructionBuilder,
 }
 
 impl ProtocolService {
     pub fn new(base: Arc<BaseClient>, pda: Arc<PdaBuilder>, program_id: Pubkey) -> Self {
         Self {
-            _base: base,
-            _pda: pda,
+            base,
+            pda,
             builder: ProtocolInstructionBuilder::new(program_id),
         }
     }
 
+    /// Fetch the protocol configuration and the safety controller's pause state
+    ///
+    /// Both accounts are read in one call. Fails if the protocol is not
+    /// initialized; a missing safety controller only leaves
+    /// `redemptions_paused` empty.
+    pub async fn get_config(&self) -> SdkResult<ProtocolConfigView> {
+        let (config_address, _) = self.pda.protocol_config();
+        let (controller_address, _) = self.pda.safety_controller();
+        let mut accounts = self
+            .base
+            .get_multiple_accounts(&[config_address, controller_address])
+            .await?
+            .into_iter();
+        let config = accounts.next().flatten().ok_or_else(|| {
+            SdkError::InvalidParameters(format!(
+                "Protocol config {} is not initialized",
+                config_address
+            ))
+        })?;
+        let controller = accounts.next().flatten();
+
+        ProtocolConfigView::from_account_data(
+            config_address,
+            &config.data,
+            controller_address,
+            controller.as_ref().map(|account| account.data.as_slice()),
+        )
+    }
+
+    /// Project whether the given de-peg stress would trip the safety controller
+    pub async fn simulate_safety_trip(
+        &self,
+        stress: StressComponents,
+    ) -> SdkResult<SafetyProjection> {
+        let (controller_address, _) = self.pda.safety_controller();
+        let account = self.base.get_account(&controller_address).await?;
+        let controller = SafetyController::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+        let (config_address, _) = self.pda.protocol_config();
+        let account = self.base.get_account(&config_address).await?;
+        let config = ProtocolConfig::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+        let thresholds = SafetyThresholds {
+            depeg_threshold_bps: config.depeg_threshold_bps,
+            depeg_required_obs: config.depeg_required_obs,
+            clear_required_obs: config.clear_required_obs,
+        };
+
+        Ok(project_safety_trip(&controller, &thresholds, &stress))
+    }
+
+    /// Fetch the current SOL-per-JitoSOL rate from the Jito stake pool
+    ///
+    /// Returns `(total_lamports, pool_token_supply)` as numerator/denominator.
+    pub async fn fetch_jitosol_rate(&self) -> SdkResult<(u64, u64)> {
+        let pool: Pubkey = JITO_STAKE_POOL.parse().unwrap();
+        let account = self.base.get_account(&pool).await?;
+        if account.owner != SPL_STAKE_POOL_PROGRAM_ID.parse::<Pubkey>().unwrap() {
+            return Err(SdkError::InvalidParameters(
+                "Jito stake pool has unexpected owner".to_string(),
+            ));
+        }
+        parse_stake_pool_rate(&account.data)
+    }
+
+    /// Build `update_native_rate` from the live JitoSOL stake pool rate
+    pub async fn update_native_rate_from_stake_pool_ix(
+        &self,
+        authority: Pubkey,
+    ) -> SdkResult<Instruction> {
+        let (numerator, denominator) = self.fetch_jitosol_rate().await?;
+        self.update_native_rate_ix(authority, rate_to_q64(numerator, denominator)?)
+    }
+
     /// Initialize the protocol (one-time setup)
     pub fn initialize_protocol_ix(
         &self,
@@ -83,12 +259,160 @@ impl ProtocolService {
             .set_protocol_owned_override(authority, buffer, override_amount)
     }
 
-    /// Update native rate for a market
+    /// Update the FeelsSOL native reserve rate
     pub fn update_native_rate_ix(
         &self,
-        market: Pubkey,
-        feelssol_mint: Pubkey,
+        authority: Pubkey,
+        native_rate_q64: u128,
     ) -> SdkResult<Instruction> {
-        self.builder.update_native_rate(market, feelssol_mint)
+        self.builder.update_native_rate(authority, native_rate_q64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use feels::state::DegradeFlags;
+
+    /// Account data of a protocol config, as the program writes it
+    fn protocol_config_data(authority: Pubkey, treasury: Pubkey, whitelist: &[Pubkey]) -> Vec<u8> {
+        let mut dex_whitelist = [Pubkey::default(); 8];
+        dex_whitelist[..whitelist.len()].copy_from_slice(whitelist);
+        let config = ProtocolConfig {
+            authority,
+            mint_fee: 1_000_000,
+            treasury,
+            default_protocol_fee_rate: 1_000,
+            default_creator_fee_rate: 500,
+            max_protocol_fee_rate: 2_500,
+            token_expiration_seconds: 3_600,
+            depeg_threshold_bps: 75,
+            depeg_required_obs: 3,
+            clear_required_obs: 5,
+            dex_twap_window_secs: 1_800,
+            dex_twap_stale_age_secs: 600,
+            dex_twap_updater: authority,
+            dex_whitelist,
+            dex_whitelist_len: whitelist.len() as u8,
+            max_spot_twap_deviation_bps: 200,
+            _reserved: [0; 5],
+            mint_per_slot_cap_feelssol: 10_000,
+            redeem_per_slot_cap_feelssol: 20_000,
+            swap_per_slot_cap_feelssol: 0,
+            bonding_swap_per_slot_cap_feelssol: 5_000,
+            default_base_fee_bps: 30,
+            default_tick_spacing: 10,
+            default_initial_sqrt_price: 1u128 << 64,
+            default_tick_step_size: 100,
+        };
+        let mut data = Vec::with_capacity(ProtocolConfig::LEN);
+        config.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn safety_controller_data(redemptions_paused: bool) -> Vec<u8> {
+        let controller = SafetyController {
+            redemptions_paused,
+            consecutive_breaches: 0,
+            consecutive_clears: 0,
+            last_change_slot: 0,
+            mint_last_slot: 0,
+            mint_slot_amount: 0,
+            redeem_last_slot: 0,
+            redeem_slot_amount: 0,
+            last_divergence_check_slot: 0,
+            degrade_flags: DegradeFlags::default(),
+            _reserved: [0; 32],
+        };
+        let mut data = Vec::with_capacity(SafetyController::LEN);
+        controller.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_deserializes_protocol_config_fixture() {
+        let pda = PdaBuilder::new(crate::program_id());
+        let (address, _) = pda.protocol_config();
+        let (safety_controller, _) = pda.safety_controller();
+        let authority = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let venue = Pubkey::new_unique();
+
+        let data = protocol_config_data(authority, treasury, &[venue]);
+        let controller = safety_controller_data(true);
+        let view = ProtocolConfigView::from_account_data(
+            address,
+            &data,
+            safety_controller,
+            Some(&controller),
+        )
+        .unwrap();
+
+        assert_eq!(
+            view,
+            ProtocolConfigView {
+                address,
+                authority,
+                treasury,
+                mint_fee: 1_000_000,
+                default_protocol_fee_rate: 1_000,
+                default_creator_fee_rate: 500,
+                max_protocol_fee_rate: 2_500,
+                token_expiration_seconds: 3_600,
+                default_base_fee_bps: 30,
+                default_tick_spacing: 10,
+                default_initial_sqrt_price: 1u128 << 64,
+                default_tick_step_size: 100,
+                safety_thresholds: SafetyThresholds {
+                    depeg_threshold_bps: 75,
+                    depeg_required_obs: 3,
+                    clear_required_obs: 5,
+                },
+                max_spot_twap_deviation_bps: 200,
+                mint_per_slot_cap_feelssol: 10_000,
+                redeem_per_slot_cap_feelssol: 20_000,
+                swap_per_slot_cap_feelssol: 0,
+                bonding_swap_per_slot_cap_feelssol: 5_000,
+                dex_twap_window_secs: 1_800,
+                dex_twap_stale_age_secs: 600,
+                dex_twap_updater: authority,
+                dex_whitelist: vec![venue],
+                safety_controller,
+                redemptions_paused: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_safety_controller_leaves_pause_state_unknown() {
+        let data = protocol_config_data(Pubkey::new_unique(), Pubkey::new_unique(), &[]);
+        let view = ProtocolConfigView::from_account_data(
+            Pubkey::new_unique(),
+            &data,
+            Pubkey::new_unique(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(view.redemptions_paused, None);
+        assert!(view.dex_whitelist.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_other_accounts() {
+        let address = Pubkey::new_unique();
+        let controller = safety_controller_data(false);
+        let config = protocol_config_data(Pubkey::new_unique(), Pubkey::new_unique(), &[]);
+
+        // A safety controller passed as the config
+        assert!(
+            ProtocolConfigView::from_account_data(address, &controller, address, None).is_err()
+        );
+        // A config passed as the safety controller
+        assert!(
+            ProtocolConfigView::from_account_data(address, &config, address, Some(&config))
+                .is_err()
+        );
     }
 }
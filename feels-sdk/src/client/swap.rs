@@ -1,17 +1,22 @@
 use std::sync::Arc;
 
 use crate::prelude::*;
+use anchor_lang::AccountDeserialize;
 use solana_sdk::{
     account::Account,
+    instruction::Instruction,
     signature::{Keypair, Signature},
     signer::Signer,
 };
 
 use crate::{
     client::BaseClient,
-    core::{FeeEstimate, Route, SdkResult, SwapDirection, SwapSimulation},
+    core::{
+        FeeEstimate, Route, SdkError, SdkResult, SwapDirection, SwapExplanation, SwapSimulation,
+    },
     instructions::{SwapAccounts, SwapInstructionBuilder, SwapParams},
-    protocol::{calculate_swap_fees, PdaBuilder},
+    jupiter::{parse_tick_array_auto, MarketState, SwapSimulator, TickArrayLoader},
+    protocol::{calculate_swap_fees, split_fee_estimate, PdaBuilder},
 };
 
 /// Service for swap operations
@@ -19,6 +24,8 @@ pub struct SwapService {
     base: Arc<BaseClient>,
     pda: Arc<PdaBuilder>,
     swap_builder: SwapInstructionBuilder,
+    compute_unit_limit: Option<u32>,
+    lookup_tables: Vec<Pubkey>,
 }
 
 impl SwapService {
@@ -27,9 +34,82 @@ impl SwapService {
             base,
             pda,
             swap_builder: SwapInstructionBuilder::new(program_id),
+            compute_unit_limit: None,
+            lookup_tables: Vec::new(),
         }
     }
 
+    /// Send subsequent swaps as v0 transactions using these lookup tables
+    ///
+    /// Pass an empty list to go back to legacy transactions.
+    pub fn set_lookup_tables(&mut self, lookup_tables: Vec<Pubkey>) {
+        self.lookup_tables = lookup_tables;
+    }
+
+    /// Accounts shared by every swap on `market`, suitable for a lookup table
+    pub async fn market_lookup_table_addresses(&self, market: &Pubkey) -> SdkResult<Vec<Pubkey>> {
+        let market_account = self.base.get_account(market).await?;
+        let (current_tick, tick_spacing) = self.parse_market_tick_info(&market_account)?;
+        let (token_0, token_1) = self.parse_market_tokens(&market_account)?;
+
+        let (vault_0, _) = Pubkey::find_program_address(
+            &[b"vault", token_0.as_ref(), token_1.as_ref(), b"0"],
+            &self.pda.program_id,
+        );
+        let (vault_1, _) = Pubkey::find_program_address(
+            &[b"vault", token_0.as_ref(), token_1.as_ref(), b"1"],
+            &self.pda.program_id,
+        );
+
+        let mut addresses = vec![
+            *market,
+            token_0,
+            token_1,
+            vault_0,
+            vault_1,
+            self.pda.vault_authority(market).0,
+            self.pda.buffer(market).0,
+            self.pda.oracle(market).0,
+            self.pda.protocol_config().0,
+            spl_token::id(),
+            solana_program::sysvar::clock::id(),
+            self.pda.program_id,
+        ];
+        for direction in [SwapDirection::ZeroForOne, SwapDirection::OneForZero] {
+            for tick_array in self.swap_builder.derive_tick_arrays(
+                market,
+                current_tick,
+                tick_spacing,
+                direction,
+                3,
+            ) {
+                if !addresses.contains(&tick_array) {
+                    addresses.push(tick_array);
+                }
+            }
+        }
+
+        Ok(addresses)
+    }
+
+    /// Create a lookup table with a market's common swap accounts
+    pub async fn create_market_lookup_table(
+        &self,
+        authority: &Keypair,
+        market: &Pubkey,
+    ) -> SdkResult<Pubkey> {
+        let addresses = self.market_lookup_table_addresses(market).await?;
+        self.base.create_lookup_table(authority, &addresses).await
+    }
+
+    /// Override the simulated compute unit limit for subsequent swaps
+    ///
+    /// Swaps skip the simulation while a limit is set. Pass `None` to size
+    /// the limit from the simulated tick crossings.
+    pub fn set_compute_unit_limit(&mut self, units: Option<u32>) {
+        self.compute_unit_limit = units;
+    }
+
     /// Execute a swap with exact input amount
     pub async fn swap_exact_in(
         &self,
@@ -82,8 +162,12 @@ impl SwapService {
 
         let ix = self.swap_builder.swap(accounts, params)?;
 
+        let budget_ix = self
+            .compute_budget(market, amount_in, direction == SwapDirection::ZeroForOne)
+            .await?;
+
         // Execute transaction
-        let signature = self.base.send_transaction(&[ix], &[signer]).await?;
+        let signature = self.send(&[budget_ix, ix], signer).await?;
 
         Ok(SwapResult {
             signature,
@@ -154,8 +238,16 @@ impl SwapService {
 
         let ix = self.swap_builder.swap(accounts, params)?;
 
+        let budget_ix = self
+            .compute_budget(
+                market,
+                maximum_amount_in,
+                direction == SwapDirection::ZeroForOne,
+            )
+            .await?;
+
         // Execute transaction
-        let signature = self.base.send_transaction(&[ix], &[signer]).await?;
+        let signature = self.send(&[budget_ix, ix], signer).await?;
 
         Ok(SwapResult {
             signature,
@@ -169,6 +261,26 @@ impl SwapService {
         })
     }
 
+    /// Compute unit limit instruction for a swap
+    ///
+    /// Uses the limit set with `set_compute_unit_limit` when there is one,
+    /// otherwise sizes it from the simulated tick crossings.
+    async fn compute_budget(
+        &self,
+        market: Pubkey,
+        amount_in: u64,
+        is_token_0_in: bool,
+    ) -> SdkResult<Instruction> {
+        if let Some(units) = self.compute_unit_limit {
+            return Ok(self.swap_builder.compute_budget(0, Some(units)));
+        }
+
+        let simulation = self.simulate_swap(market, amount_in, is_token_0_in).await?;
+        Ok(self
+            .swap_builder
+            .compute_budget(simulation.ticks_crossed as u32, None))
+    }
+
     /// Simulate a swap without executing
     pub async fn simulate_swap(
         &self,
@@ -196,12 +308,77 @@ impl SwapService {
         })
     }
 
+    /// Walk through a simulated swap step by step, without sending anything
+    ///
+    /// Intended for support and debugging: the result records the starting
+    /// price, one step per tick crossed with its liquidity and fee, and the
+    /// final price and output. Its `Display` renders a readable narrative.
+    pub async fn explain_swap(
+        &self,
+        market: Pubkey,
+        input_mint: Pubkey,
+        amount_in: u64,
+    ) -> SdkResult<SwapExplanation> {
+        let market_account = self.base.get_account(&market).await?;
+        let market_data =
+            feels::state::Market::try_deserialize(&mut market_account.data.as_slice())
+                .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+        let direction = SwapDirection::infer_from_tokens(
+            &market,
+            &market_data.token_0,
+            &market_data.token_1,
+            &input_mint,
+        )?;
+        let market_state = MarketState::from_market(market, &market_data);
+        let tick_arrays = self.load_tick_arrays(&market_state, direction).await?;
+        SwapSimulator::new(&market_state, &tick_arrays).explain_swap(&input_mint, amount_in)
+    }
+
+    /// Fetch the tick arrays a swap on `market` would be given
+    ///
+    /// Arrays that have not been created hold no initialized ticks, so they
+    /// are left out.
+    async fn load_tick_arrays(
+        &self,
+        market: &MarketState,
+        direction: SwapDirection,
+    ) -> SdkResult<TickArrayLoader> {
+        let addresses = self.swap_builder.derive_tick_arrays(
+            &market.market_key,
+            market.current_tick,
+            market.tick_spacing,
+            direction,
+            3, // Same 3 tick arrays the swap uses
+        );
+
+        let mut tick_arrays = TickArrayLoader::new();
+        for account in self
+            .base
+            .get_multiple_accounts(&addresses)
+            .await?
+            .into_iter()
+            .flatten()
+        {
+            tick_arrays
+                .add_parsed_array(parse_tick_array_auto(&account.data, market.tick_spacing)?);
+        }
+        Ok(tick_arrays)
+    }
+
     /// Estimate fees for a swap
     pub async fn estimate_fees(&self, market: &Pubkey, amount_in: u64) -> SdkResult<FeeEstimate> {
         let market_info = self.base.get_account(market).await?;
         let (base_fee_bps, liquidity, sqrt_price) = self.parse_market_fee_info(&market_info)?;
+        let estimate = calculate_swap_fees(amount_in, base_fee_bps, liquidity, sqrt_price, true)?;
+
+        let config_account = self.base.get_account(&self.pda.protocol_config().0).await?;
+        let config =
+            feels::state::ProtocolConfig::try_deserialize(&mut config_account.data.as_slice())
+                .map_err(|e| SdkError::SerializationError(e.to_string()))?;
 
-        calculate_swap_fees(amount_in, base_fee_bps, liquidity, sqrt_price, true)
+        // Swaps built by this SDK pass no protocol token, so no creator fee is taken
+        split_fee_estimate(estimate, config.default_protocol_fee_rate, 0)
     }
 
     /// Find optimal route between two tokens
@@ -224,6 +401,16 @@ impl SwapService {
     }
 
     // Helper methods
+    async fn send(&self, instructions: &[Instruction], signer: &Keypair) -> SdkResult<Signature> {
+        if self.lookup_tables.is_empty() {
+            self.base.send_transaction(instructions, &[signer]).await
+        } else {
+            self.base
+                .send_versioned_transaction(instructions, &[signer], &self.lookup_tables)
+                .await
+        }
+    }
+
     fn parse_market_tick_info(&self, _account: &Account) -> SdkResult<(i32, u16)> {
         // Simplified - would parse actual market data
         Ok((0, 10)) // current_tick, tick_spacing
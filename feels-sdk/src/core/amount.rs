@@ -0,0 +1,141 @@
+//! Conversion between raw token amounts and decimal strings
+
+use super::{SdkError, SdkResult};
+
+/// Format a raw amount as a decimal string using the mint's decimals
+///
+/// Exact, with trailing fractional zeros trimmed: `1_500_000_000` with 9
+/// decimals is `"1.5"`, and `1` is `"0.000000001"`.
+pub fn format_amount(raw: u64, decimals: u8) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parse a decimal string into a raw amount using the mint's decimals
+///
+/// Rejects signs, exponents, separators and any fractional digit the mint
+/// cannot represent, rather than rounding it away. Trailing zeros beyond
+/// the mint's precision are accepted.
+pub fn parse_amount(s: &str, decimals: u8) -> SdkResult<u64> {
+    let s = s.trim();
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(SdkError::InvalidParameters(format!(
+            "Invalid amount '{}'",
+            s
+        )));
+    }
+
+    let decimals = decimals as usize;
+    let significant = fraction.trim_end_matches('0');
+    if significant.len() > decimals {
+        return Err(SdkError::InvalidParameters(format!(
+            "Amount '{}' has more than {} decimal places",
+            s, decimals
+        )));
+    }
+
+    let raw = format!("{}{:0<width$}", whole, significant, width = decimals);
+    let raw = raw.trim_start_matches('0');
+    if raw.is_empty() {
+        return Ok(0);
+    }
+    raw.parse().map_err(|_| SdkError::MathOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(1_500_000_000, 9), "1.5");
+        assert_eq!(format_amount(1_000_000_000, 9), "1");
+        assert_eq!(format_amount(1, 9), "0.000000001");
+        assert_eq!(format_amount(0, 9), "0");
+        assert_eq!(format_amount(123, 0), "123");
+        assert_eq!(format_amount(u64::MAX, 6), "18446744073709.551615");
+        // More decimals than u64 has digits
+        assert_eq!(format_amount(u64::MAX, 25), "0.0000018446744073709551615");
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1.5", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_amount("1", 9).unwrap(), 1_000_000_000);
+        assert_eq!(parse_amount(".5", 1).unwrap(), 5);
+        assert_eq!(parse_amount("5.", 1).unwrap(), 50);
+        assert_eq!(parse_amount(" 0.000000001 ", 9).unwrap(), 1);
+        assert_eq!(parse_amount("0", 9).unwrap(), 0);
+        assert_eq!(parse_amount("007", 0).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_round_trip_at_max_precision() {
+        for (raw, decimals) in [
+            (u64::MAX, 0),
+            (u64::MAX, 6),
+            (u64::MAX, 19),
+            (1, 25),
+            (10, 1),
+        ] {
+            assert_eq!(
+                parse_amount(&format_amount(raw, decimals), decimals).unwrap(),
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn test_over_precision_rejected_not_rounded() {
+        assert!(matches!(
+            parse_amount("1.0000000001", 9),
+            Err(SdkError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            parse_amount("0.5", 0),
+            Err(SdkError::InvalidParameters(_))
+        ));
+        // Zeros past the precision carry no value
+        assert_eq!(parse_amount("1.5000000000000", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_amount("2.000", 0).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_overflow_rejected() {
+        assert_eq!(parse_amount("18446744073709551615", 0).unwrap(), u64::MAX);
+        assert!(matches!(
+            parse_amount("18446744073709551616", 0),
+            Err(SdkError::MathOverflow)
+        ));
+        assert!(matches!(
+            parse_amount("18446744073709.551616", 6),
+            Err(SdkError::MathOverflow)
+        ));
+        assert!(matches!(parse_amount("1", 20), Err(SdkError::MathOverflow)));
+    }
+
+    #[test]
+    fn test_malformed_rejected() {
+        for input in ["", ".", "-1", "+1", "1.2.3", "1e9", "1,000", "1_000", "abc"] {
+            assert!(
+                matches!(parse_amount(input, 9), Err(SdkError::InvalidParameters(_))),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+}
@@ -8,6 +8,12 @@ pub fn program_id() -> Pubkey {
     PROGRAM_ID.parse().unwrap()
 }
 
+/// Jito stake pool backing JitoSOL
+pub const JITO_STAKE_POOL: &str = "Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb";
+
+/// SPL stake pool program that owns the Jito stake pool
+pub const SPL_STAKE_POOL_PROGRAM_ID: &str = "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy";
+
 /// Seeds for common PDAs
 pub mod seeds {
     pub const MARKET: &[u8] = b"market";
@@ -21,11 +27,17 @@ pub mod seeds {
     pub const TICK_ARRAY: &[u8] = b"tick_array";
     pub const POSITION: &[u8] = b"position";
     pub const POSITION_METADATA: &[u8] = b"position_metadata";
+    pub const POMM_POSITION: &[u8] = b"pomm_position";
+    pub const SAFETY_CONTROLLER: &[u8] = b"safety_controller";
 }
 
 /// Protocol constants
 pub const TICK_ARRAY_SIZE: i32 = 64;
 pub const MAX_TICK: i32 = 309120;
 pub const MIN_TICK: i32 = -309120;
+/// Maximum compute units a single transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 pub const MAX_SQRT_PRICE: u128 = 184467440737095516;
 pub const MIN_SQRT_PRICE: u128 = 1844674407370;
+/// Protocol-owned positions a market can have, indexed `0..MAX_POMM_POSITIONS`
+pub const MAX_POMM_POSITIONS: u8 = 8;
@@ -1,3 +1,4 @@
+use solana_sdk::pubkey::Pubkey;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,14 +21,17 @@ pub enum SdkError {
     #[error("No route found from {0} to {1}")]
     NoRouteFound(String, String),
 
-    #[error("Market not found")]
-    MarketNotFound,
+    #[error("No market for pair {token_0} / {token_1}")]
+    MarketNotFound { token_0: Pubkey, token_1: Pubkey },
 
     #[error("Invalid tick array")]
     InvalidTickArray,
 
     #[error("Simulation failed: {0}")]
     SimulationFailed(String),
+
+    #[error("Program error {code}: {message}")]
+    ProgramError { code: u32, message: String },
 }
 
 pub type SdkResult<T> = Result<T, SdkError>;
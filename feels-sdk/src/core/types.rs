@@ -1,3 +1,4 @@
+use crate::core::{SdkError, SdkResult};
 use crate::prelude::*;
 
 /// Route type for swaps
@@ -22,6 +23,44 @@ pub enum SwapDirection {
     OneForZero,
 }
 
+impl SwapDirection {
+    /// Direction of a swap paying `input_mint` into `market`
+    ///
+    /// Fails if `input_mint` is neither of the market's tokens.
+    pub fn infer(market: &MarketInfo, input_mint: &Pubkey) -> SdkResult<Self> {
+        Self::infer_from_tokens(
+            &market.address,
+            &market.token_0,
+            &market.token_1,
+            input_mint,
+        )
+    }
+
+    /// Same as [`SwapDirection::infer`], given the market's address and tokens
+    pub fn infer_from_tokens(
+        market: &Pubkey,
+        token_0: &Pubkey,
+        token_1: &Pubkey,
+        input_mint: &Pubkey,
+    ) -> SdkResult<Self> {
+        if input_mint == token_0 {
+            Ok(SwapDirection::ZeroForOne)
+        } else if input_mint == token_1 {
+            Ok(SwapDirection::OneForZero)
+        } else {
+            Err(SdkError::InvalidParameters(format!(
+                "mint {} is not in market {} ({} / {})",
+                input_mint, market, token_0, token_1
+            )))
+        }
+    }
+
+    /// Whether token 0 is the input side
+    pub fn is_token_0_in(&self) -> bool {
+        matches!(self, SwapDirection::ZeroForOne)
+    }
+}
+
 /// Market state information
 #[derive(Clone, Debug)]
 pub struct MarketInfo {
@@ -49,6 +88,51 @@ pub struct PositionInfo {
     pub tokens_owed_1: u64,
 }
 
+impl PositionInfo {
+    /// Token amounts the position's liquidity represents at `sqrt_price` (Q64)
+    pub fn value_at_price(&self, sqrt_price: u128) -> SdkResult<(u64, u64)> {
+        let sqrt_price_lower = crate::protocol::tick_to_sqrt_price(self.tick_lower)?;
+        let sqrt_price_upper = crate::protocol::tick_to_sqrt_price(self.tick_upper)?;
+        feels::logic::amounts_from_liquidity(
+            sqrt_price,
+            sqrt_price_lower,
+            sqrt_price_upper,
+            self.liquidity,
+        )
+        .map_err(|_| SdkError::MathOverflow)
+    }
+
+    /// Impermanent loss versus holding the tokens deposited at entry
+    ///
+    /// Both prices are Q64 sqrt prices. Returns the LP value relative to the
+    /// held value minus one, so -0.05 means the position is worth 5% less
+    /// than holding. Outside the range the position is single-sided and stops
+    /// rebalancing, which bounds the loss. Computed per unit of liquidity, so
+    /// the result does not depend on position size.
+    pub fn impermanent_loss(&self, entry_price: u128, current_price: u128) -> f64 {
+        const Q64: f64 = 18_446_744_073_709_551_616.0;
+        let sqrt_lower = 1.0001f64.powf(self.tick_lower as f64 / 2.0);
+        let sqrt_upper = 1.0001f64.powf(self.tick_upper as f64 / 2.0);
+
+        // Per-unit-liquidity composition with the price clamped to the range
+        let composition = |sqrt_price: f64| {
+            let s = sqrt_price.clamp(sqrt_lower, sqrt_upper);
+            (1.0 / s - 1.0 / sqrt_upper, s - sqrt_lower)
+        };
+
+        let current = current_price as f64 / Q64;
+        let price = current * current;
+        let (held_0, held_1) = composition(entry_price as f64 / Q64);
+        let (lp_0, lp_1) = composition(current);
+
+        let held_value = held_0 * price + held_1;
+        if held_value <= 0.0 {
+            return 0.0;
+        }
+        (lp_0 * price + lp_1) / held_value - 1.0
+    }
+}
+
 /// Fee estimate for a swap
 #[derive(Clone, Debug)]
 pub struct FeeEstimate {
@@ -57,6 +141,12 @@ pub struct FeeEstimate {
     pub total_fee: u64,
     pub fee_bps: u16,
     pub price_impact_bps: u16,
+    /// Portion of `total_fee` left to the market (buffer and LPs)
+    pub lp_fee: u64,
+    /// Portion of `total_fee` routed to the protocol treasury
+    pub protocol_fee: u64,
+    /// Portion of `total_fee` accrued to the token creator
+    pub creator_fee: u64,
 }
 
 /// Swap simulation result
@@ -69,3 +159,176 @@ pub struct SwapSimulation {
     pub end_tick: i32,
     pub ticks_crossed: u8,
 }
+
+/// One leg of a simulated swap, within a single liquidity range
+#[derive(Clone, Debug)]
+pub struct SwapStep {
+    /// Start index of the tick array the step trades in
+    pub tick_array_start: i32,
+    pub start_tick: i32,
+    pub end_tick: i32,
+    pub start_sqrt_price: u128,
+    pub end_sqrt_price: u128,
+    /// Active liquidity during the step
+    pub liquidity: u128,
+    /// Input consumed by the step, excluding the fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+/// Step-by-step account of a simulated swap
+#[derive(Clone, Debug)]
+pub struct SwapExplanation {
+    pub market: Pubkey,
+    pub input_mint: Pubkey,
+    pub direction: SwapDirection,
+    pub amount_in: u64,
+    pub start_tick: i32,
+    pub start_sqrt_price: u128,
+    pub steps: Vec<SwapStep>,
+    pub end_tick: i32,
+    pub end_sqrt_price: u128,
+    pub amount_out: u64,
+    pub fee_paid: u64,
+}
+
+impl std::fmt::Display for SwapExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Swap {} of {} on market {} ({:?})",
+            self.amount_in, self.input_mint, self.market, self.direction
+        )?;
+        writeln!(
+            f,
+            "Start: tick {}, sqrt_price {}",
+            self.start_tick, self.start_sqrt_price
+        )?;
+        if self.steps.is_empty() {
+            writeln!(f, "No liquidity in range, nothing traded")?;
+        }
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "Step {}: tick array {}, tick {} -> {}, liquidity {}, in {}, fee {}, out {}",
+                i + 1,
+                step.tick_array_start,
+                step.start_tick,
+                step.end_tick,
+                step.liquidity,
+                step.amount_in,
+                step.fee,
+                step.amount_out
+            )?;
+        }
+        write!(
+            f,
+            "End: tick {}, sqrt_price {}, received {}, fees {}",
+            self.end_tick, self.end_sqrt_price, self.amount_out, self.fee_paid
+        )
+    }
+}
+
+/// Projected supply and fee split for a new token launch
+#[derive(Clone, Debug)]
+pub struct MintDistribution {
+    pub total_supply: u64,
+    pub creator_allocation: u64,
+    pub liquidity_allocation: u64,
+    pub protocol_allocation: u64,
+    pub mint_fee: u64,
+    pub liquidity_feelssol: u64,
+    pub treasury_feelssol: u64,
+}
+
+/// TWAP consult result with a volatility-derived confidence band
+#[derive(Clone, Debug)]
+pub struct TwapQuote {
+    pub twap_tick: i32,
+    pub twap_sqrt_price: u128,
+    pub lower_sqrt_price: u128,
+    pub upper_sqrt_price: u128,
+    pub volatility_bps: u16,
+    pub window_secs: u32,
+    pub last_update_ts: i64,
+    pub is_stale: bool,
+}
+
+/// De-peg stress inputs for a safety-controller projection
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StressComponents {
+    pub native_rate_q64: u128,
+    pub dex_twap_rate_q64: u128,
+    pub dex_stale: bool,
+}
+
+/// Hysteresis thresholds mirrored from `ProtocolConfig`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafetyThresholds {
+    pub depeg_threshold_bps: u16,
+    pub depeg_required_obs: u8,
+    pub clear_required_obs: u8,
+}
+
+/// Projected safety-controller state after one more observation
+#[derive(Clone, Debug)]
+pub struct SafetyProjection {
+    pub divergence_bps: u16,
+    pub is_breach: bool,
+    pub counted: bool,
+    pub was_paused: bool,
+    pub redemptions_paused: bool,
+    pub consecutive_breaches: u8,
+    pub consecutive_clears: u8,
+    /// Further breach observations before a pause (`None` if already paused)
+    pub observations_until_pause: Option<u8>,
+    /// Further clear observations before resume (`None` if not paused)
+    pub observations_until_resume: Option<u8>,
+}
+
+/// TWAP reported by one FeelsSOL-quoted venue
+#[derive(Clone, Copy, Debug)]
+pub struct VenueTwap {
+    pub venue_id: Pubkey,
+    pub rate_q64: u128,
+    /// Volume traded over the window, used as the median weight
+    pub volume: u64,
+    pub obs: u16,
+}
+
+/// Venues and rejection bounds for DEX TWAP aggregation
+#[derive(Clone, Debug)]
+pub struct DexTwapSources {
+    /// Accepted venues (protocol markets and external references); empty accepts all
+    pub venues: Vec<Pubkey>,
+    /// TWAP window submitted on-chain (300..=7200 seconds)
+    pub window_secs: u32,
+    /// Maximum distance from the median before a venue is discarded
+    pub max_deviation_bps: u16,
+    /// Minimum venues that must survive outlier rejection
+    pub min_sources: usize,
+}
+
+/// Global fee growth observed at a point in time
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeGrowthSample {
+    pub timestamp: i64,
+    pub fee_growth_global_0_x64: u128,
+    pub fee_growth_global_1_x64: u128,
+    /// Market tick at `timestamp`, used for in-range time weighting
+    pub tick: i32,
+}
+
+/// Fee APR estimate for a liquidity range
+#[derive(Clone, Debug)]
+pub struct AprEstimate {
+    /// Annualized fee yield as a fraction (0.12 = 12%)
+    pub apr: f64,
+    /// Share of the window the market tick spent inside the range
+    pub in_range_fraction: f64,
+    pub window_secs: i64,
+    /// Fee growth per unit liquidity over the window (Q64)
+    pub fee_growth_0_x64: u128,
+    pub fee_growth_1_x64: u128,
+}
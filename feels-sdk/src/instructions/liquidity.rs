@@ -14,6 +14,7 @@ const EXIT_FEELSSOL_DISCRIMINATOR: [u8; 8] = [0x69, 0x76, 0xa8, 0x94, 0x3d, 0x98
 const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [0x87, 0x80, 0x2f, 0x4d, 0x0f, 0x98, 0xf0, 0x31];
 const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [0x7b, 0x86, 0x51, 0x00, 0x31, 0x44, 0x62, 0x62];
 const COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
 const INITIALIZE_MARKET_DISCRIMINATOR: [u8; 8] = [0x23, 0x23, 0xbd, 0xc1, 0x9b, 0x30, 0xaa, 0xcb];
 const MINT_TOKEN_DISCRIMINATOR: [u8; 8] = [0xac, 0x89, 0xb7, 0x0e, 0xcf, 0x6e, 0xea, 0x38];
 const DEPLOY_INITIAL_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [226, 227, 73, 75, 85, 216, 151, 217];
@@ -74,6 +75,18 @@ pub struct CollectFeesParams {}
 
 impl_instruction!(CollectFeesParams, COLLECT_FEES_DISCRIMINATOR);
 
+/// Parameters for adding liquidity to an existing position
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct IncreaseLiquidityParams {
+    pub liquidity: u128,
+    /// Maximum amount of token 0 to deposit
+    pub amount_0_max: u64,
+    /// Maximum amount of token 1 to deposit
+    pub amount_1_max: u64,
+}
+
+impl_instruction!(IncreaseLiquidityParams, INCREASE_LIQUIDITY_DISCRIMINATOR);
+
 /// Parameters for minting tokens
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct MintTokenParams {
@@ -85,9 +98,34 @@ pub struct MintTokenParams {
 impl_instruction!(MintTokenParams, MINT_TOKEN_DISCRIMINATOR);
 
 /// Parameters for deploying initial liquidity
+///
+/// Large launches deploy in several parts; each part names the amounts it
+/// deploys out of the escrow's commitment.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DeployInitialLiquidityParams {
-    pub buy_feelssol_amount: u64,
+    /// Number of ticks between each stair step (same for every part)
+    pub tick_step_size: i32,
+    /// Initial buy in FeelsSOL (0 = none), only on the part that completes the deployment
+    pub initial_buy_feelssol_amount: u64,
+    /// Protocol token amount to deploy in this part
+    pub token_amount: u64,
+    /// FeelsSOL amount to deploy in this part (both amounts 0 = everything outstanding)
+    pub feelssol_amount: u64,
+}
+
+impl DeployInitialLiquidityParams {
+    /// Deploy everything still outstanding, without an initial buy
+    ///
+    /// Finishes a partial deployment. Once the token has expired, anyone may
+    /// send this to complete a deployment the market authority left stalled.
+    pub fn remaining(tick_step_size: i32) -> Self {
+        Self {
+            tick_step_size,
+            initial_buy_feelssol_amount: 0,
+            token_amount: 0,
+            feelssol_amount: 0,
+        }
+    }
 }
 
 impl_instruction!(
@@ -307,13 +345,10 @@ impl LiquidityInstructionBuilder {
         &self,
         creator: Pubkey,
         market: Pubkey,
-        buy_feelssol_amount: u64,
+        params: DeployInitialLiquidityParams,
         feelssol_mint: Pubkey,
         other_mint: Pubkey,
     ) -> SdkResult<Instruction> {
-        let params = DeployInitialLiquidityParams {
-            buy_feelssol_amount,
-        };
         let (vault_authority, _) = self.pda.vault_authority(&market);
 
         // Derive vault addresses
@@ -66,9 +66,14 @@ impl_instruction!(
     INITIALIZE_TRANCHE_TICKS_DISCRIMINATOR
 );
 
-/// Parameters for updating DEX TWAP (no params)
+/// Parameters for updating the protocol DEX TWAP
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct UpdateDexTwapParams {}
+pub struct UpdateDexTwapParams {
+    pub dex_twap_rate_q64: u128,
+    pub window_secs: u32,
+    pub obs: u16,
+    pub venue_id: Pubkey,
+}
 
 impl_instruction!(UpdateDexTwapParams, UPDATE_DEX_TWAP_DISCRIMINATOR);
 
@@ -219,17 +224,20 @@ impl MarketInstructionBuilder {
     /// Build update DEX TWAP instruction
     pub fn update_dex_twap(
         &self,
-        market: Pubkey,
-        target_dex_pool: Pubkey,
+        updater: Pubkey,
+        params: UpdateDexTwapParams,
     ) -> SdkResult<Instruction> {
-        let (oracle, _) = self.pda.oracle(&market);
+        let (protocol_config, _) = self.pda.protocol_config();
+        let (protocol_oracle, _) = self.pda.protocol_oracle();
+        let (safety, _) = self.pda.safety_controller();
 
         Ok(FeelsInstructionBuilder::new()
-            .add_readonly(market)
-            .add_writable(oracle)
-            .add_readonly(target_dex_pool)
+            .add_signer(updater)
+            .add_readonly(protocol_config)
+            .add_writable(protocol_oracle)
+            .add_writable(safety)
             .add_readonly(solana_program::sysvar::clock::id())
-            .with_data(UpdateDexTwapParams {}.build_data()?)
+            .with_data(params.build_data()?)
             .build())
     }
 }
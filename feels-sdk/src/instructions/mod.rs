@@ -6,6 +6,7 @@ pub mod position;
 pub mod protocol;
 pub mod registry;
 pub mod swap;
+pub mod transaction;
 
 pub use builder::*;
 pub use liquidity::*;
@@ -15,3 +16,4 @@ pub use position::*;
 pub use protocol::*;
 pub use registry::*;
 pub use swap::*;
+pub use transaction::*;
@@ -4,7 +4,10 @@ use solana_sdk::instruction::Instruction;
 use crate::{
     core::SdkResult,
     impl_instruction,
-    instructions::{FeelsInstructionBuilder, InstructionBuilder},
+    instructions::{
+        ClosePositionParams, CollectFeesParams, FeelsInstructionBuilder, IncreaseLiquidityParams,
+        InstructionBuilder,
+    },
     protocol::PdaBuilder,
 };
 
@@ -194,6 +197,161 @@ impl PositionInstructionBuilder {
             .build())
     }
 
+    /// Build collect fees instruction for an NFT-backed position
+    ///
+    /// Pass `tick_arrays` (lower, upper) to calculate fees in the same
+    /// instruction; pass `None` to only transfer already-accumulated fees.
+    pub fn collect_fees(
+        &self,
+        owner: Pubkey,
+        market: Pubkey,
+        position: Pubkey,
+        position_mint: Pubkey,
+        token_0: Pubkey,
+        token_1: Pubkey,
+        tick_arrays: Option<(Pubkey, Pubkey)>,
+    ) -> SdkResult<Instruction> {
+        let position_token_account =
+            spl_associated_token_account::get_associated_token_address(&owner, &position_mint);
+        let owner_token_0 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_0);
+        let owner_token_1 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_1);
+        let (vault_0, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_0.as_ref()],
+            &self.pda.program_id,
+        );
+        let (vault_1, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_1.as_ref()],
+            &self.pda.program_id,
+        );
+        let (market_authority, _) =
+            Pubkey::find_program_address(&[b"authority", market.as_ref()], &self.pda.program_id);
+
+        let mut builder = FeelsInstructionBuilder::new()
+            .add_signer(owner)
+            .add_writable(market)
+            .add_readonly(position_mint)
+            .add_readonly(position_token_account)
+            .add_writable(position)
+            .add_writable(owner_token_0)
+            .add_writable(owner_token_1)
+            .add_writable(vault_0)
+            .add_writable(vault_1)
+            .add_readonly(market_authority)
+            .add_readonly(spl_token::id());
+
+        if let Some((lower_tick_array, upper_tick_array)) = tick_arrays {
+            builder = builder
+                .add_readonly(lower_tick_array)
+                .add_readonly(upper_tick_array);
+        }
+
+        Ok(builder
+            .with_data(CollectFeesParams {}.build_data()?)
+            .build())
+    }
+
+    /// Build close position instruction for an NFT-backed position
+    ///
+    /// Withdraws the position's liquidity and owed fees to the owner's
+    /// associated token accounts. `tick_arrays` are the (lower, upper) arrays
+    /// holding the position's ticks.
+    pub fn close_position(
+        &self,
+        owner: Pubkey,
+        market: Pubkey,
+        position: Pubkey,
+        position_mint: Pubkey,
+        token_0: Pubkey,
+        token_1: Pubkey,
+        tick_arrays: (Pubkey, Pubkey),
+        params: ClosePositionParams,
+    ) -> SdkResult<Instruction> {
+        let position_token_account =
+            spl_associated_token_account::get_associated_token_address(&owner, &position_mint);
+        let owner_token_0 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_0);
+        let owner_token_1 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_1);
+        let (vault_0, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_0.as_ref()],
+            &self.pda.program_id,
+        );
+        let (vault_1, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_1.as_ref()],
+            &self.pda.program_id,
+        );
+        let (market_authority, _) =
+            Pubkey::find_program_address(&[b"authority", market.as_ref()], &self.pda.program_id);
+        let (lower_tick_array, upper_tick_array) = tick_arrays;
+
+        Ok(FeelsInstructionBuilder::new()
+            .add_signer(owner)
+            .add_writable(market)
+            .add_writable(position_mint)
+            .add_writable(position_token_account)
+            .add_writable(position)
+            .add_writable(owner_token_0)
+            .add_writable(owner_token_1)
+            .add_writable(vault_0)
+            .add_writable(vault_1)
+            .add_readonly(market_authority)
+            .add_writable(lower_tick_array)
+            .add_writable(upper_tick_array)
+            .add_readonly(spl_token::id())
+            .with_data(params.build_data()?)
+            .build())
+    }
+
+    /// Build increase liquidity instruction for an NFT-backed position
+    ///
+    /// Deposits from the owner's associated token accounts into the
+    /// position's existing range. `tick_arrays` are the (lower, upper) arrays
+    /// holding the position's ticks.
+    pub fn increase_liquidity(
+        &self,
+        owner: Pubkey,
+        market: Pubkey,
+        position: Pubkey,
+        position_mint: Pubkey,
+        token_0: Pubkey,
+        token_1: Pubkey,
+        tick_arrays: (Pubkey, Pubkey),
+        params: IncreaseLiquidityParams,
+    ) -> SdkResult<Instruction> {
+        let position_token_account =
+            spl_associated_token_account::get_associated_token_address(&owner, &position_mint);
+        let owner_token_0 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_0);
+        let owner_token_1 =
+            spl_associated_token_account::get_associated_token_address(&owner, &token_1);
+        let (vault_0, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_0.as_ref()],
+            &self.pda.program_id,
+        );
+        let (vault_1, _) = Pubkey::find_program_address(
+            &[b"vault", market.as_ref(), token_1.as_ref()],
+            &self.pda.program_id,
+        );
+        let (lower_tick_array, upper_tick_array) = tick_arrays;
+
+        Ok(FeelsInstructionBuilder::new()
+            .add_signer(owner)
+            .add_writable(market)
+            .add_readonly(position_token_account)
+            .add_writable(position)
+            .add_writable(owner_token_0)
+            .add_writable(owner_token_1)
+            .add_writable(vault_0)
+            .add_writable(vault_1)
+            .add_writable(lower_tick_array)
+            .add_writable(upper_tick_array)
+            .add_readonly(spl_token::id())
+            .with_data(params.build_data()?)
+            .build())
+    }
+
     fn get_tick_array_for_tick(&self, market: &Pubkey, tick: i32) -> Pubkey {
         // Simplified - would need tick spacing to calculate properly
         let start_index =
@@ -65,9 +65,11 @@ impl_instruction!(
     SET_PROTOCOL_OWNED_OVERRIDE_DISCRIMINATOR
 );
 
-/// Parameters for updating native rate (no params)
+/// Parameters for updating the FeelsSOL native reserve rate
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct UpdateNativeRateParams {}
+pub struct UpdateNativeRateParams {
+    pub native_rate_q64: u128,
+}
 
 impl_instruction!(UpdateNativeRateParams, UPDATE_NATIVE_RATE_DISCRIMINATOR);
 
@@ -189,22 +191,23 @@ impl ProtocolInstructionBuilder {
             .build())
     }
 
-    /// Build update native rate instruction  
+    /// Build update native rate instruction
     pub fn update_native_rate(
         &self,
-        market: Pubkey,
-        feelssol_mint: Pubkey,
+        authority: Pubkey,
+        native_rate_q64: u128,
     ) -> SdkResult<Instruction> {
-        let (oracle, _) = self.pda.oracle(&market);
-        let (feels_hub, _) = self.pda.feels_hub();
+        let (protocol_config, _) = self.pda.protocol_config();
+        let (protocol_oracle, _) = self.pda.protocol_oracle();
+        let (safety, _) = self.pda.safety_controller();
 
         Ok(FeelsInstructionBuilder::new()
-            .add_readonly(market)
-            .add_writable(oracle)
-            .add_readonly(feelssol_mint)
-            .add_readonly(feels_hub)
+            .add_signer(authority)
+            .add_readonly(protocol_config)
+            .add_writable(protocol_oracle)
+            .add_writable(safety)
             .add_readonly(solana_program::sysvar::clock::id())
-            .with_data(UpdateNativeRateParams {}.build_data()?)
+            .with_data(UpdateNativeRateParams { native_rate_q64 }.build_data()?)
             .build())
     }
 }
@@ -29,6 +29,10 @@ pub struct RegisterPoolParams {}
 impl_instruction!(RegisterPoolParams, REGISTER_POOL_DISCRIMINATOR);
 
 /// Pool phase enum
+///
+/// Wire-compatible with the program's `PoolPhase`: `PreLaunch` is
+/// `BondingCurve`, `Live` is `SteadyState`, `PostGraduation` is `Paused` and
+/// `Expired` is `Deprecated`.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum PoolPhase {
     PreLaunch,
@@ -37,6 +41,22 @@ pub enum PoolPhase {
     Expired,
 }
 
+impl PoolPhase {
+    fn to_program(&self) -> feels::state::PoolPhase {
+        match self {
+            PoolPhase::PreLaunch => feels::state::PoolPhase::BondingCurve,
+            PoolPhase::Live => feels::state::PoolPhase::SteadyState,
+            PoolPhase::PostGraduation => feels::state::PoolPhase::Paused,
+            PoolPhase::Expired => feels::state::PoolPhase::Deprecated,
+        }
+    }
+
+    /// Whether `update_pool_phase` will accept moving from `self` to `new_phase`
+    pub fn can_transition_to(&self, new_phase: &PoolPhase) -> bool {
+        feels::state::can_transition(self.to_program(), new_phase.to_program())
+    }
+}
+
 /// Parameters for updating pool phase
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct UpdatePoolPhaseParams {
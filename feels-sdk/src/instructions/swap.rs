@@ -1,8 +1,8 @@
 use crate::prelude::*;
-use solana_sdk::instruction::Instruction;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, instruction::Instruction};
 
 use crate::{
-    core::{SdkResult, SwapDirection},
+    core::{constants::MAX_COMPUTE_UNIT_LIMIT, SdkResult, SwapDirection},
     impl_instruction,
     instructions::{FeelsInstructionBuilder, InstructionBuilder},
     protocol::PdaBuilder,
@@ -22,6 +22,19 @@ pub struct SwapParams {
 
 impl_instruction!(SwapParams, SWAP_DISCRIMINATOR);
 
+/// Baseline compute units for a swap that stays within one tick
+pub const SWAP_BASE_COMPUTE_UNITS: u32 = 100_000;
+
+/// Additional compute units budgeted per initialized tick crossed
+pub const SWAP_COMPUTE_UNITS_PER_CROSSING: u32 = 30_000;
+
+/// Compute unit limit for a swap expected to cross `ticks_crossed` ticks
+pub fn swap_compute_unit_limit(ticks_crossed: u32) -> u32 {
+    SWAP_BASE_COMPUTE_UNITS
+        .saturating_add(ticks_crossed.saturating_mul(SWAP_COMPUTE_UNITS_PER_CROSSING))
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
 /// Common swap accounts
 pub struct SwapAccounts {
     pub user: Pubkey,
@@ -109,6 +122,16 @@ impl SwapInstructionBuilder {
         Ok(builder.with_data(data).build())
     }
 
+    /// Build the compute budget instruction to prepend to a swap
+    ///
+    /// `override_units` takes precedence over the tick-crossing estimate.
+    pub fn compute_budget(&self, ticks_crossed: u32, override_units: Option<u32>) -> Instruction {
+        let units = override_units
+            .map(|units| units.min(MAX_COMPUTE_UNIT_LIMIT))
+            .unwrap_or_else(|| swap_compute_unit_limit(ticks_crossed));
+        ComputeBudgetInstruction::set_compute_unit_limit(units)
+    }
+
     /// Derive tick arrays needed for a swap
     pub fn derive_tick_arrays(
         &self,
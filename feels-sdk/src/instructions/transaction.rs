@@ -0,0 +1,120 @@
+use crate::prelude::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, hash::Hash, instruction::Instruction,
+    message::Message, signer::Signer, transaction::Transaction,
+};
+
+use crate::core::{SdkError, SdkResult};
+
+/// Accumulates built instructions into a single signed transaction
+///
+/// Multi-step flows such as initialize_market followed by
+/// deploy_initial_liquidity are assembled here instead of by hand. Signers
+/// are deduplicated by pubkey, so the same keypair may be added for every
+/// instruction it signs. The payer always signs first.
+pub struct TransactionBuilder<'a> {
+    payer: &'a dyn Signer,
+    signers: Vec<&'a dyn Signer>,
+    compute_budget: Vec<Instruction>,
+    instructions: Vec<Instruction>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    pub fn new(payer: &'a dyn Signer) -> Self {
+        Self {
+            payer,
+            signers: vec![payer],
+            compute_budget: Vec::new(),
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Set the compute unit limit, and optionally a priority fee in
+    /// micro-lamports per unit; placed ahead of every other instruction
+    pub fn with_compute_budget(mut self, units: u32, micro_lamports: Option<u64>) -> Self {
+        self.compute_budget = vec![ComputeBudgetInstruction::set_compute_unit_limit(units)];
+        if let Some(price) = micro_lamports {
+            self.compute_budget
+                .push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        self
+    }
+
+    /// Append an instruction
+    pub fn add_instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append several instructions, in order
+    pub fn add_instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Add a signer; ignored if one with the same pubkey was already added
+    pub fn add_signer(mut self, signer: &'a dyn Signer) -> Self {
+        let pubkey = signer.pubkey();
+        if !self.signers.iter().any(|s| s.pubkey() == pubkey) {
+            self.signers.push(signer);
+        }
+        self
+    }
+
+    /// Fee payer
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Every instruction in transaction order, compute budget first
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.compute_budget
+            .iter()
+            .chain(&self.instructions)
+            .cloned()
+            .collect()
+    }
+
+    /// Pubkeys of the added signers, payer first
+    pub fn signer_pubkeys(&self) -> Vec<Pubkey> {
+        self.signers.iter().map(|s| s.pubkey()).collect()
+    }
+
+    /// Compile the message against `recent_blockhash`
+    pub fn message(&self, recent_blockhash: Hash) -> Message {
+        Message::new_with_blockhash(&self.instructions(), Some(&self.payer()), &recent_blockhash)
+    }
+
+    /// Build and sign the transaction
+    ///
+    /// Fails if an instruction requires a signature from a key that was not
+    /// added, or if a signer was added that no instruction needs.
+    pub fn build(&self, recent_blockhash: Hash) -> SdkResult<Transaction> {
+        if self.instructions.is_empty() {
+            return Err(SdkError::InvalidParameters(
+                "Transaction has no instructions".to_string(),
+            ));
+        }
+
+        let message = self.message(recent_blockhash);
+        let required = &message.account_keys[..message.header.num_required_signatures as usize];
+        let provided = self.signer_pubkeys();
+        if let Some(missing) = required.iter().find(|key| !provided.contains(key)) {
+            return Err(SdkError::InvalidParameters(format!(
+                "Missing signer {}",
+                missing
+            )));
+        }
+        if let Some(extra) = provided.iter().find(|key| !required.contains(key)) {
+            return Err(SdkError::InvalidParameters(format!(
+                "Signer {} is not required by any instruction",
+                extra
+            )));
+        }
+
+        let mut tx = Transaction::new_unsigned(message);
+        tx.try_sign(&self.signers, recent_blockhash)
+            .map_err(|e| SdkError::InvalidParameters(e.to_string()))?;
+        Ok(tx)
+    }
+}
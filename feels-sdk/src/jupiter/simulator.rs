@@ -1,18 +1,30 @@
-use crate::core::SwapSimulation;
+use crate::core::{SdkError, SwapDirection, SwapExplanation, SwapSimulation, SwapStep};
 use crate::jupiter::types::*;
+use feels::constants::MAX_TICKS_CROSSED;
+use feels::logic::{
+    compute_swap_step, skip_empty_range, StepOutcome, SwapContext,
+    SwapDirection as EngineDirection, MAX_SWAP_STEPS,
+};
+use feels::utils::{apply_liquidity_net, sqrt_price_from_tick, tick_from_sqrt_price};
+use solana_program::pubkey::Pubkey;
 
 /// Swap simulator for Jupiter integration
-#[allow(dead_code)]
+///
+/// Runs the same step loop as the program's `execute_swap_steps`: each step
+/// trades up to the next initialized tick from `tick_arrays` (or the
+/// market's price bound), and crossing a tick applies its liquidity net.
+/// Quotes match on-chain execution exactly, rounding included, as long as
+/// the loaded arrays cover the price path and JIT liquidity is inactive.
 pub struct SwapSimulator<'a> {
     market_state: &'a MarketState,
-    _tick_arrays: &'a TickArrayLoader,
+    tick_arrays: &'a TickArrayLoader,
 }
 
 impl<'a> SwapSimulator<'a> {
     pub fn new(market_state: &'a MarketState, tick_arrays: &'a TickArrayLoader) -> Self {
         Self {
             market_state,
-            _tick_arrays: tick_arrays,
+            tick_arrays,
         }
     }
 
@@ -22,114 +34,157 @@ impl<'a> SwapSimulator<'a> {
         amount_in: u64,
         is_token_0_to_1: bool,
     ) -> Result<SwapSimulation, crate::core::SdkError> {
-        // Calculate fee
-        let fee_amount = self.calculate_fee(amount_in);
-        let amount_after_fee = amount_in.saturating_sub(fee_amount);
-
-        // Simulate the swap through the concentrated liquidity curve
-        let (amount_out, end_sqrt_price, end_tick, ticks_crossed) =
-            self.simulate_swap_step(amount_after_fee, is_token_0_to_1)?;
-
-        Ok(SwapSimulation {
-            amount_in,
-            amount_out,
-            fee_paid: fee_amount,
-            end_sqrt_price,
-            end_tick,
-            ticks_crossed,
-        })
-    }
-
-    /// Calculate fee amount using the same logic as on-chain
-    fn calculate_fee(&self, amount_in: u64) -> u64 {
-        // Use ceiling division to match on-chain behavior
-        let fee_bps = self.market_state.fee_bps as u128;
-        let amount_in_u128 = amount_in as u128;
-
-        // fee = ceil(amount_in * fee_bps / 10000)
-        ((amount_in_u128 * fee_bps + 9999) / 10000) as u64
+        self.simulate_swap_steps(amount_in, is_token_0_to_1)
+            .map(|(simulation, _)| simulation)
     }
 
-    /// Simulate swap through concentrated liquidity
-    fn simulate_swap_step(
+    /// Simulate a swap, also returning each step taken through the curve
+    ///
+    /// There is one step per tick crossed plus the final partial step, so a
+    /// range with no liquidity shows up as a step that trades nothing. A
+    /// market with no active liquidity quotes zero output and charges no fee.
+    pub fn simulate_swap_steps(
         &self,
-        amount_remaining: u64,
+        amount_in: u64,
         is_token_0_to_1: bool,
-    ) -> Result<(u64, u128, i32, u8), crate::core::SdkError> {
-        let mut sqrt_price = self.market_state.sqrt_price;
-        let current_tick = self.market_state.current_tick;
-        let liquidity = self.market_state.liquidity;
-        let amount_remaining = amount_remaining as u128;
-        let mut amount_out: u128;
-        let ticks_crossed = 0u8;
-
-        // Simplified simulation - just use current liquidity
-        // In a real implementation, this would:
-        // 1. Find next initialized tick
-        // 2. Swap within tick
-        // 3. Cross tick and update liquidity
-        // 4. Repeat until amount exhausted
-
-        if liquidity == 0 {
-            return Ok((0, sqrt_price, current_tick, 0));
+    ) -> Result<(SwapSimulation, Vec<SwapStep>), crate::core::SdkError> {
+        let market = self.market_state;
+        let mut simulation = SwapSimulation {
+            amount_in,
+            amount_out: 0,
+            fee_paid: 0,
+            end_sqrt_price: market.sqrt_price,
+            end_tick: market.current_tick,
+            ticks_crossed: 0,
+        };
+        let mut steps = Vec::new();
+        if market.liquidity == 0 || amount_in == 0 {
+            return Ok((simulation, steps));
         }
 
-        // Calculate output using simplified constant product approximation
-        // For production, this should use proper concentrated liquidity math
-        if is_token_0_to_1 {
-            // Token 0 -> Token 1: price decreases
-            // Simplified: use current price ratio
-            // price = (sqrt_price / 2^64)^2
-            // amount_out ≈ amount_in * price
-
-            // Avoid overflow by dividing step by step
-            let price_ratio = sqrt_price
-                .saturating_mul(sqrt_price)
-                .saturating_div(1u128 << 64);
-            amount_out = amount_remaining
-                .saturating_mul(price_ratio)
-                .saturating_div(1u128 << 64);
-
-            // Ensure we have some output for non-zero input
-            if amount_out == 0 && amount_remaining > 0 && liquidity > 0 {
-                // At least return 1 if we have liquidity
-                amount_out = 1;
+        let (direction, bound_tick) = if is_token_0_to_1 {
+            (EngineDirection::ZeroForOne, market.global_lower_tick)
+        } else {
+            (EngineDirection::OneForZero, market.global_upper_tick)
+        };
+        let bound_sqrt_price = sqrt_price_from_tick(bound_tick).map_err(step_error)?;
+
+        let mut ctx = SwapContext::new(
+            direction,
+            market.sqrt_price,
+            market.liquidity,
+            market.fee_bps,
+            market.global_lower_tick,
+            market.global_upper_tick,
+            market.tick_spacing,
+        );
+        let mut amount_remaining = amount_in;
+        let mut steps_taken = 0u16;
+
+        while amount_remaining > 0 && steps_taken < MAX_SWAP_STEPS {
+            steps_taken += 1;
+            if simulation.ticks_crossed >= MAX_TICKS_CROSSED {
+                return Err(SdkError::SimulationFailed(format!(
+                    "swap would cross more than {} ticks",
+                    MAX_TICKS_CROSSED
+                )));
             }
 
-            // Update price (decreases)
-            let delta_sqrt_price = amount_remaining
-                .saturating_mul(1u128 << 32) // Use smaller shift to avoid underflow
-                .saturating_div(liquidity);
-            sqrt_price = sqrt_price.saturating_sub(delta_sqrt_price.min(sqrt_price / 2));
-        // Don't decrease by more than 50%
-        } else {
-            // Token 1 -> Token 0: price increases
-            // Simplified: use inverse of current price ratio
-            // price = (2^64 / sqrt_price)^2
-            // amount_out ≈ amount_in / price
-
-            // Calculate with care to avoid overflow
-            if sqrt_price > 0 {
-                // amount_out = amount_in * (2^64)^2 / (sqrt_price)^2
-                // Split the calculation to avoid overflow
-                amount_out = ((amount_remaining << 32) / sqrt_price) << 32 / sqrt_price;
-
-                // Ensure we have some output for non-zero input
-                if amount_out == 0 && amount_remaining > 0 && liquidity > 0 {
-                    amount_out = 1;
-                }
+            let next_tick = self.tick_arrays.next_initialized_tick(
+                simulation.end_tick,
+                market.tick_spacing,
+                is_token_0_to_1,
+            );
+            let (target_tick, target_sqrt_price, liquidity_net) = match next_tick {
+                Some((tick, liquidity_net)) => (
+                    tick,
+                    sqrt_price_from_tick(tick).map_err(step_error)?,
+                    liquidity_net,
+                ),
+                None => (bound_tick, bound_sqrt_price, 0),
+            };
+
+            let step = if ctx.liquidity == 0 {
+                skip_empty_range(&ctx, target_sqrt_price, Some(target_tick))
             } else {
-                amount_out = 0;
+                compute_swap_step(&ctx, target_sqrt_price, Some(target_tick), amount_remaining)
+            }
+            .map_err(step_error)?;
+
+            let start_tick = simulation.end_tick;
+            amount_remaining = amount_remaining.saturating_sub(step.gross_in_used);
+            simulation.amount_out = simulation.amount_out.saturating_add(step.out);
+            simulation.fee_paid = simulation.fee_paid.saturating_add(step.fee);
+            simulation.end_sqrt_price = step.sqrt_next;
+            simulation.end_tick = match (step.outcome, step.crossed_tick) {
+                (StepOutcome::ReachedTarget, Some(crossed)) => crossed,
+                _ => tick_from_sqrt_price(step.sqrt_next).map_err(step_error)?,
+            };
+
+            steps.push(SwapStep {
+                tick_array_start: crate::protocol::get_tick_array_start_index(
+                    start_tick,
+                    market.tick_spacing.max(1),
+                ),
+                start_tick,
+                end_tick: simulation.end_tick,
+                start_sqrt_price: ctx.sqrt_price,
+                end_sqrt_price: step.sqrt_next,
+                liquidity: ctx.liquidity,
+                amount_in: step.net_in_used,
+                amount_out: step.out,
+                fee: step.fee,
+            });
+
+            ctx.sqrt_price = step.sqrt_next;
+            match step.outcome {
+                StepOutcome::ReachedTarget => {
+                    if step.crossed_tick.is_some() {
+                        simulation.ticks_crossed += 1;
+                        ctx.liquidity =
+                            apply_liquidity_net(direction, ctx.liquidity, liquidity_net)
+                                .map_err(step_error)?;
+                    }
+                }
+                StepOutcome::PartialByAmount | StepOutcome::PartialAtBound => break,
             }
-
-            // Update price (increases)
-            let delta_sqrt_price = amount_remaining
-                .saturating_mul(sqrt_price)
-                .saturating_div(liquidity)
-                .saturating_div(1u128 << 32); // Use smaller shift
-            sqrt_price = sqrt_price.saturating_add(delta_sqrt_price);
         }
 
-        Ok((amount_out as u64, sqrt_price, current_tick, ticks_crossed))
+        Ok((simulation, steps))
     }
+
+    /// Simulate paying `amount_in` of `input_mint` and describe each step
+    pub fn explain_swap(
+        &self,
+        input_mint: &Pubkey,
+        amount_in: u64,
+    ) -> Result<SwapExplanation, SdkError> {
+        let market = self.market_state;
+        let direction = SwapDirection::infer_from_tokens(
+            &market.market_key,
+            &market.token_0,
+            &market.token_1,
+            input_mint,
+        )?;
+
+        let (simulation, steps) = self.simulate_swap_steps(amount_in, direction.is_token_0_in())?;
+
+        Ok(SwapExplanation {
+            market: market.market_key,
+            input_mint: *input_mint,
+            direction,
+            amount_in,
+            start_tick: market.current_tick,
+            start_sqrt_price: market.sqrt_price,
+            steps,
+            end_tick: simulation.end_tick,
+            end_sqrt_price: simulation.end_sqrt_price,
+            amount_out: simulation.amount_out,
+            fee_paid: simulation.fee_paid,
+        })
+    }
+}
+
+fn step_error(e: anchor_lang::error::Error) -> SdkError {
+    SdkError::SimulationFailed(format!("Swap step failed: {}", e))
 }
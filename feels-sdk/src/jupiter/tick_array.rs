@@ -79,6 +79,7 @@ fn parse_tick_array_v1(
         format,
         market,
         start_tick_index,
+        tick_spacing,
         initialized_ticks,
         initialized_count: Some(initialized_count),
     })
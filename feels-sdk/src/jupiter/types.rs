@@ -1,3 +1,5 @@
+use anchor_lang::Discriminator;
+use feels::state::TICK_ARRAY_SIZE;
 use solana_program::pubkey::Pubkey;
 use std::collections::HashMap;
 
@@ -18,25 +20,47 @@ pub struct MarketState {
     pub fee_growth_global_1: u128,
 }
 
+impl MarketState {
+    /// Snapshot an on-chain market for simulation
+    pub fn from_market(market_key: Pubkey, market: &feels::state::Market) -> Self {
+        Self {
+            market_key,
+            token_0: market.token_0,
+            token_1: market.token_1,
+            sqrt_price: market.sqrt_price,
+            current_tick: market.current_tick,
+            liquidity: market.liquidity,
+            fee_bps: market.base_fee_bps,
+            tick_spacing: market.tick_spacing,
+            global_lower_tick: market.global_lower_tick,
+            global_upper_tick: market.global_upper_tick,
+            fee_growth_global_0: market.fee_growth_global_0_x64,
+            fee_growth_global_1: market.fee_growth_global_1_x64,
+        }
+    }
+}
+
 /// Tick array view for Jupiter integration
 #[derive(Clone, Debug)]
 pub struct TickArrayView {
     pub start_tick_index: i32,
+    pub tick_spacing: u16,
     pub ticks: Vec<TickData>,
     pub initialized_bitmap: Vec<bool>,
 }
 
 impl TickArrayView {
-    pub fn new(start_tick_index: i32) -> Self {
+    pub fn new(start_tick_index: i32, tick_spacing: u16) -> Self {
         Self {
             start_tick_index,
-            ticks: vec![TickData::default(); 64], // TICK_ARRAY_SIZE
-            initialized_bitmap: vec![false; 64],
+            tick_spacing,
+            ticks: vec![TickData::default(); TICK_ARRAY_SIZE],
+            initialized_bitmap: vec![false; TICK_ARRAY_SIZE],
         }
     }
 
     pub fn from(parsed: ParsedTickArray) -> Self {
-        let mut view = Self::new(parsed.start_tick_index);
+        let mut view = Self::new(parsed.start_tick_index, parsed.tick_spacing);
 
         // Convert initialized ticks to view format
         for (tick_index, liquidity_net) in parsed.initialized_ticks {
@@ -49,14 +73,35 @@ impl TickArrayView {
         view
     }
 
+    /// Tick index stored at `array_index`
+    pub fn tick_index(&self, array_index: usize) -> i32 {
+        self.start_tick_index + array_index as i32 * self.tick_spacing as i32
+    }
+
     fn get_array_index(&self, tick_index: i32) -> Option<usize> {
+        let spacing = self.tick_spacing.max(1) as i32;
         let relative_tick = tick_index - self.start_tick_index;
-        if relative_tick >= 0 && relative_tick < 64 {
-            Some(relative_tick as usize)
+        if relative_tick % spacing != 0 {
+            return None;
+        }
+        let array_index = relative_tick / spacing;
+        if array_index >= 0 && array_index < TICK_ARRAY_SIZE as i32 {
+            Some(array_index as usize)
         } else {
             None
         }
     }
+
+    /// First initialized slot scanning from `array_index` (inclusive)
+    fn scan(&self, array_index: usize, upward: bool) -> Option<usize> {
+        if upward {
+            (array_index..TICK_ARRAY_SIZE).find(|&i| self.initialized_bitmap[i])
+        } else {
+            (0..=array_index)
+                .rev()
+                .find(|&i| self.initialized_bitmap[i])
+        }
+    }
 }
 
 /// Individual tick data
@@ -88,16 +133,57 @@ impl TickArrayLoader {
 
     pub fn get_tick(&self, tick_index: i32) -> Option<&TickData> {
         // Find which array contains this tick
-        for (start_index, array) in &self.tick_arrays {
-            let relative_tick = tick_index - start_index;
-            if relative_tick >= 0 && relative_tick < 64 {
-                let array_index = relative_tick as usize;
-                if array.initialized_bitmap[array_index] {
-                    return Some(&array.ticks[array_index]);
-                }
+        self.tick_arrays.values().find_map(|array| {
+            let array_index = array.get_array_index(tick_index)?;
+            array.initialized_bitmap[array_index].then(|| &array.ticks[array_index])
+        })
+    }
+
+    /// Next initialized tick the program's swap would stop at
+    ///
+    /// Mirrors `TickArrayIterator::next_initialized_tick`: the search starts
+    /// at the next aligned tick in the swap direction and looks through the
+    /// array holding it plus up to 10 further arrays. Arrays that were not
+    /// loaded are treated as having no initialized ticks.
+    pub fn next_initialized_tick(
+        &self,
+        from_tick: i32,
+        tick_spacing: u16,
+        zero_for_one: bool,
+    ) -> Option<(i32, i128)> {
+        let spacing = tick_spacing.max(1) as i32;
+        let aligned_floor = from_tick.div_euclid(spacing) * spacing;
+        let start_tick = if zero_for_one {
+            if aligned_floor != from_tick {
+                aligned_floor
+            } else {
+                aligned_floor - spacing
             }
-        }
-        None
+        } else {
+            aligned_floor + spacing
+        };
+
+        let array_size_ticks = TICK_ARRAY_SIZE as i32 * spacing;
+        let first_start =
+            crate::protocol::get_tick_array_start_index(start_tick, tick_spacing.max(1));
+        let step = if zero_for_one {
+            -array_size_ticks
+        } else {
+            array_size_ticks
+        };
+
+        (0..=10).find_map(|i| {
+            let array = self.tick_arrays.get(&(first_start + i * step))?;
+            let array_index = if i == 0 {
+                array.get_array_index(start_tick)?
+            } else if zero_for_one {
+                TICK_ARRAY_SIZE - 1
+            } else {
+                0
+            };
+            let found = array.scan(array_index, !zero_for_one)?;
+            Some((array.tick_index(found), array.ticks[found].liquidity_net))
+        })
     }
 }
 
@@ -107,6 +193,7 @@ pub struct ParsedTickArray {
     pub format: TickArrayFormat,
     pub market: Pubkey,
     pub start_tick_index: i32,
+    pub tick_spacing: u16,
     pub initialized_ticks: HashMap<i32, i128>,
     pub initialized_count: Option<u16>,
 }
@@ -120,11 +207,11 @@ pub struct TickArrayFormat {
 }
 
 impl TickArrayFormat {
-    /// V1 format - standard 64 tick array
+    /// V1 format - standard 64 tick array, as written by the program
     pub const V1: Self = Self {
         version: 1,
         array_size: 64,
-        discriminator: [0xf0, 0x2f, 0x4e, 0xbd, 0x94, 0x8a, 0x8d, 0xd9],
+        discriminator: account_discriminator(feels::state::TickArray::DISCRIMINATOR),
     };
 
     pub fn calculate_total_size(&self) -> usize {
@@ -139,3 +226,14 @@ impl TickArrayFormat {
         header_size + (self.array_size as usize * tick_size)
     }
 }
+
+/// First eight bytes of an Anchor account discriminator, usable in consts
+const fn account_discriminator(discriminator: &[u8]) -> [u8; 8] {
+    let mut out = [0u8; 8];
+    let mut i = 0;
+    while i < 8 {
+        out[i] = discriminator[i];
+        i += 1;
+    }
+    out
+}
@@ -57,13 +57,17 @@ pub mod protocol;
 // Re-export main types and functions
 pub use client::FeelsClient;
 pub use core::{
-    constants::program_id, FeeEstimate, MarketInfo, PositionInfo, Route, SdkError, SdkResult,
-    SwapDirection, SwapSimulation,
+    constants::program_id, format_amount, parse_amount, AprEstimate, DexTwapSources, FeeEstimate,
+    FeeGrowthSample, MarketInfo, MintDistribution, PositionInfo, Route, SafetyProjection,
+    SafetyThresholds, SdkError, SdkResult, StressComponents, SwapDirection, SwapExplanation,
+    SwapSimulation, SwapStep, TwapQuote, VenueTwap,
 };
+pub use instructions::TransactionBuilder;
 pub use protocol::{
-    align_tick, calculate_fee_amount, calculate_price_impact_bps, calculate_swap_fees,
-    find_market_address, is_full_range_only, sqrt_price_to_price, sqrt_price_to_tick,
-    tick_to_sqrt_price,
+    aggregate_dex_twap, align_tick, calculate_fee_amount, calculate_fee_amount_ceil,
+    calculate_fee_amount_floor, calculate_price_impact_bps, calculate_swap_fees, consult_twap,
+    estimate_range_apr, find_market_address, is_full_range_only, sqrt_price_to_price,
+    sqrt_price_to_tick, tick_to_sqrt_price,
 };
 
 // Re-export Jupiter integration types
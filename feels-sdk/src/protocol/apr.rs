@@ -0,0 +1,184 @@
+use crate::core::{AprEstimate, FeeGrowthSample, SdkError, SdkResult};
+use crate::protocol::tick_to_sqrt_price;
+use feels::utils::fee_growth_delta;
+
+/// Seconds in a non-leap year, used to annualize fee yield
+pub const SECONDS_PER_YEAR: f64 = 31_536_000.0;
+
+const Q64_F64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Estimate the fee APR of liquidity placed in `[tick_lower, tick_upper)`
+///
+/// Fee growth is tracked per unit of in-range liquidity, so a position earns
+/// its share of fees simply by multiplying its liquidity by the growth delta;
+/// the APR is therefore independent of position size. Fees accrued over the
+/// window are valued in token_1 at the current price and divided by the value
+/// of one unit of liquidity in the range, then scaled by the fraction of the
+/// window the market tick spent inside the range.
+///
+/// `history` holds earlier fee-growth samples (e.g. indexer snapshots). The
+/// window runs from the oldest sample to `current`, and both the fee growth
+/// and the duration are measured over it. At least one sample older than
+/// `current` is required.
+pub fn estimate_range_apr(
+    history: &[FeeGrowthSample],
+    current: FeeGrowthSample,
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> SdkResult<AprEstimate> {
+    if tick_lower >= tick_upper {
+        return Err(SdkError::InvalidParameters(
+            "tick_lower must be below tick_upper".to_string(),
+        ));
+    }
+
+    let mut samples: Vec<FeeGrowthSample> = history
+        .iter()
+        .copied()
+        .filter(|s| s.timestamp < current.timestamp)
+        .collect();
+    if samples.is_empty() {
+        return Err(SdkError::InvalidParameters(
+            "Fee growth history needs a sample older than the current one".to_string(),
+        ));
+    }
+    samples.sort_by_key(|s| s.timestamp);
+    samples.push(current);
+
+    let anchor = samples[0];
+    let window_secs = current.timestamp - anchor.timestamp;
+
+    let in_range = |tick: i32| tick >= tick_lower && tick < tick_upper;
+    let in_range_secs: i64 = samples
+        .windows(2)
+        .filter(|w| in_range(w[0].tick))
+        .map(|w| w[1].timestamp - w[0].timestamp)
+        .sum();
+    let in_range_fraction = in_range_secs as f64 / window_secs as f64;
+
+    let fee_growth_0_x64 = fee_growth_delta(
+        current.fee_growth_global_0_x64,
+        anchor.fee_growth_global_0_x64,
+    );
+    let fee_growth_1_x64 = fee_growth_delta(
+        current.fee_growth_global_1_x64,
+        anchor.fee_growth_global_1_x64,
+    );
+
+    let price_sqrt = sqrt_price as f64 / Q64_F64;
+    let price = price_sqrt * price_sqrt;
+    let fees_value =
+        (fee_growth_0_x64 as f64 / Q64_F64) * price + fee_growth_1_x64 as f64 / Q64_F64;
+
+    let liquidity_value = unit_liquidity_value(price_sqrt, tick_lower, tick_upper)?;
+    let apr = if liquidity_value > 0.0 {
+        fees_value * in_range_fraction / liquidity_value * SECONDS_PER_YEAR / window_secs as f64
+    } else {
+        0.0
+    };
+
+    Ok(AprEstimate {
+        apr,
+        in_range_fraction,
+        window_secs,
+        fee_growth_0_x64,
+        fee_growth_1_x64,
+    })
+}
+
+/// Value in token_1 of one unit of liquidity spread over the range
+fn unit_liquidity_value(price_sqrt: f64, tick_lower: i32, tick_upper: i32) -> SdkResult<f64> {
+    let lower = tick_to_sqrt_price(tick_lower)? as f64 / Q64_F64;
+    let upper = tick_to_sqrt_price(tick_upper)? as f64 / Q64_F64;
+
+    let (amount_0, amount_1) = if price_sqrt <= lower {
+        (1.0 / lower - 1.0 / upper, 0.0)
+    } else if price_sqrt >= upper {
+        (0.0, upper - lower)
+    } else {
+        (1.0 / price_sqrt - 1.0 / upper, price_sqrt - lower)
+    };
+
+    Ok(amount_0 * price_sqrt * price_sqrt + amount_1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const Q64: u128 = 1u128 << 64;
+    const DAY: i64 = 86_400;
+
+    fn sample(timestamp: i64, growth_1: u128, tick: i32) -> FeeGrowthSample {
+        FeeGrowthSample {
+            timestamp,
+            fee_growth_global_0_x64: 0,
+            fee_growth_global_1_x64: growth_1,
+            tick,
+        }
+    }
+
+    #[test]
+    fn test_history_window_annualizes_growth() {
+        // 0.001 token_1 per unit liquidity over one day, always in range
+        let history = [sample(0, 0, 0)];
+        let current = sample(DAY, Q64 / 1000, 0);
+        let estimate = estimate_range_apr(&history, current, Q64, -100, 100).unwrap();
+
+        assert_eq!(estimate.window_secs, DAY);
+        assert_eq!(estimate.in_range_fraction, 1.0);
+
+        let value = unit_liquidity_value(1.0, -100, 100).unwrap();
+        let expected = 0.001 / value * 365.0;
+        assert!((estimate.apr - expected).abs() / expected < 1e-9);
+    }
+
+    #[test]
+    fn test_in_range_fraction_is_time_weighted() {
+        let history = [sample(0, 0, 0), sample(DAY, Q64 / 1000, 500)];
+        let current = sample(2 * DAY, Q64 / 1000, 0);
+        let estimate = estimate_range_apr(&history, current, Q64, -100, 100).unwrap();
+
+        assert_eq!(estimate.in_range_fraction, 0.5);
+
+        // Narrower ranges concentrate the same fees into less capital
+        let wide = estimate_range_apr(&history, current, Q64, -1000, 1000).unwrap();
+        assert!(estimate.apr > wide.apr);
+    }
+
+    #[test]
+    fn test_window_comes_from_history() {
+        // Growth before the oldest sample is not counted, and the window is
+        // the span of the samples, not the market's age
+        let history = [sample(10 * DAY, Q64, 0)];
+        let current = sample(11 * DAY, Q64 + Q64 / 1000, 0);
+        let estimate = estimate_range_apr(&history, current, Q64, -100, 100).unwrap();
+        assert_eq!(estimate.window_secs, DAY);
+        assert_eq!(estimate.fee_growth_1_x64, Q64 / 1000);
+
+        let reference = estimate_range_apr(
+            &[sample(0, 0, 0)],
+            sample(DAY, Q64 / 1000, 0),
+            Q64,
+            -100,
+            100,
+        )
+        .unwrap();
+        assert!((estimate.apr - reference.apr).abs() < 1e-12);
+
+        let out_of_range = estimate_range_apr(&history, current, Q64, 200, 400).unwrap();
+        assert_eq!(out_of_range.in_range_fraction, 0.0);
+        assert_eq!(out_of_range.apr, 0.0);
+    }
+
+    #[test]
+    fn test_requires_earlier_sample() {
+        let current = sample(DAY, Q64 / 1000, 0);
+        assert!(estimate_range_apr(&[], current, Q64, -100, 100).is_err());
+        assert!(estimate_range_apr(&[current], current, Q64, -100, 100).is_err());
+
+        let history = [sample(0, 0, 0)];
+        assert!(estimate_range_apr(&history, current, Q64, 100, -100).is_err());
+    }
+}
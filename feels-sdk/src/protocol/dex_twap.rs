@@ -0,0 +1,142 @@
+use crate::core::{DexTwapSources, SdkError, SdkResult, VenueTwap};
+use crate::instructions::UpdateDexTwapParams;
+
+/// Window bounds enforced by `update_dex_twap`
+pub const MIN_DEX_TWAP_WINDOW_SECS: u32 = 300;
+pub const MAX_DEX_TWAP_WINDOW_SECS: u32 = 7200;
+
+/// Aggregate venue TWAPs into a single `update_dex_twap` submission
+///
+/// Takes the volume-weighted median across accepted venues, drops any venue
+/// further than `max_deviation_bps` from it, and recomputes the median over
+/// the survivors so one manipulated venue cannot move the protocol oracle.
+/// The submitted `venue_id` is the venue that supplied the final median.
+pub fn aggregate_dex_twap(
+    sources: &DexTwapSources,
+    quotes: &[VenueTwap],
+) -> SdkResult<UpdateDexTwapParams> {
+    if !(MIN_DEX_TWAP_WINDOW_SECS..=MAX_DEX_TWAP_WINDOW_SECS).contains(&sources.window_secs) {
+        return Err(SdkError::InvalidParameters(format!(
+            "DEX TWAP window must be {}..={} seconds",
+            MIN_DEX_TWAP_WINDOW_SECS, MAX_DEX_TWAP_WINDOW_SECS
+        )));
+    }
+
+    let mut accepted: Vec<VenueTwap> = quotes
+        .iter()
+        .filter(|q| q.rate_q64 > 0 && q.volume > 0)
+        .filter(|q| sources.venues.is_empty() || sources.venues.contains(&q.venue_id))
+        .copied()
+        .collect();
+    let min_sources = sources.min_sources.max(1);
+    if accepted.len() < min_sources {
+        return Err(SdkError::InvalidParameters(format!(
+            "Only {} of {} required DEX TWAP sources available",
+            accepted.len(),
+            min_sources
+        )));
+    }
+
+    accepted.sort_by_key(|q| q.rate_q64);
+    let median = weighted_median(&accepted).rate_q64;
+    accepted.retain(|q| deviation_bps(q.rate_q64, median) <= sources.max_deviation_bps as u128);
+    if accepted.len() < min_sources {
+        return Err(SdkError::InvalidParameters(format!(
+            "Only {} of {} required DEX TWAP sources within {} bps of the median",
+            accepted.len(),
+            min_sources,
+            sources.max_deviation_bps
+        )));
+    }
+
+    let chosen = weighted_median(&accepted);
+    Ok(UpdateDexTwapParams {
+        dex_twap_rate_q64: chosen.rate_q64,
+        window_secs: sources.window_secs,
+        obs: accepted
+            .iter()
+            .fold(0u16, |acc, q| acc.saturating_add(q.obs)),
+        venue_id: chosen.venue_id,
+    })
+}
+
+/// Lower volume-weighted median of quotes sorted by rate
+fn weighted_median(sorted: &[VenueTwap]) -> VenueTwap {
+    let total: u128 = sorted.iter().map(|q| q.volume as u128).sum();
+    let mut cumulative = 0u128;
+    for quote in sorted {
+        cumulative += quote.volume as u128;
+        if cumulative * 2 >= total {
+            return *quote;
+        }
+    }
+    sorted[sorted.len() - 1]
+}
+
+fn deviation_bps(rate: u128, reference: u128) -> u128 {
+    rate.abs_diff(reference).saturating_mul(10_000) / reference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    const ONE_Q64: u128 = 1u128 << 64;
+
+    fn quote(rate_bps: u128, volume: u64) -> VenueTwap {
+        VenueTwap {
+            venue_id: Pubkey::new_unique(),
+            rate_q64: ONE_Q64 * rate_bps / 10_000,
+            volume,
+            obs: 10,
+        }
+    }
+
+    fn sources() -> DexTwapSources {
+        DexTwapSources {
+            venues: Vec::new(),
+            window_secs: 1800,
+            max_deviation_bps: 200,
+            min_sources: 2,
+        }
+    }
+
+    #[test]
+    fn test_volume_weighted_median() {
+        let quotes = [quote(10_000, 100), quote(10_050, 500), quote(10_100, 100)];
+        let params = aggregate_dex_twap(&sources(), &quotes).unwrap();
+        assert_eq!(params.dex_twap_rate_q64, quotes[1].rate_q64);
+        assert_eq!(params.venue_id, quotes[1].venue_id);
+        assert_eq!(params.window_secs, 1800);
+        assert_eq!(params.obs, 30);
+    }
+
+    #[test]
+    fn test_manipulated_venue_is_rejected() {
+        // A heavily traded venue far from the others still cannot drag the median
+        let quotes = [
+            quote(10_000, 100),
+            quote(10_020, 100),
+            quote(10_040, 100),
+            quote(15_000, 250),
+        ];
+        let params = aggregate_dex_twap(&sources(), &quotes).unwrap();
+        assert_eq!(params.dex_twap_rate_q64, quotes[1].rate_q64);
+        assert_eq!(params.obs, 30);
+    }
+
+    #[test]
+    fn test_unlisted_and_insufficient_sources() {
+        let quotes = [quote(10_000, 100), quote(10_010, 100)];
+        let mut config = sources();
+        config.venues = vec![quotes[0].venue_id];
+        assert!(aggregate_dex_twap(&config, &quotes).is_err());
+
+        config.venues.push(quotes[1].venue_id);
+        assert!(aggregate_dex_twap(&config, &quotes).is_ok());
+
+        config.window_secs = 60;
+        assert!(aggregate_dex_twap(&config, &quotes).is_err());
+    }
+}
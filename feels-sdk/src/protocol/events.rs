@@ -0,0 +1,202 @@
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use feels::events::{
+    FeeSplitApplied, PoolStatusChanged, PositionOperation, PositionUpdated, SwapExecuted,
+};
+use serde_json::{json, Value};
+
+use crate::core::{SdkError, SdkResult};
+
+/// Log prefix Anchor's `emit!` uses for serialized events
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Decoded Feels program event
+pub enum FeelsEvent {
+    Swap(SwapExecuted),
+    FeeSplit(FeeSplitApplied),
+    Position(PositionUpdated),
+    PoolStatus(PoolStatusChanged),
+}
+
+impl FeelsEvent {
+    /// Event struct name as declared in the program
+    pub fn name(&self) -> &'static str {
+        match self {
+            FeelsEvent::Swap(_) => "SwapExecuted",
+            FeelsEvent::FeeSplit(_) => "FeeSplitApplied",
+            FeelsEvent::Position(_) => "PositionUpdated",
+            FeelsEvent::PoolStatus(_) => "PoolStatusChanged",
+        }
+    }
+
+    /// JSON view of the event; pubkeys and u128 fields are strings
+    pub fn to_json(&self) -> Value {
+        match self {
+            FeelsEvent::Swap(e) => json!({
+                "market": e.market.to_string(),
+                "user": e.user.to_string(),
+                "token_in": e.token_in.to_string(),
+                "token_out": e.token_out.to_string(),
+                "amount_in": e.amount_in,
+                "amount_out": e.amount_out,
+                "fee_paid": e.fee_paid,
+                "base_fee_paid": e.base_fee_paid,
+                "impact_bps": e.impact_bps,
+                "sqrt_price_after": e.sqrt_price_after.to_string(),
+                "timestamp": e.timestamp,
+                "version": e.version,
+            }),
+            FeelsEvent::FeeSplit(e) => json!({
+                "market": e.market.to_string(),
+                "base_fee_bps": e.base_fee_bps,
+                "impact_fee_bps": e.impact_fee_bps,
+                "total_fee_bps": e.total_fee_bps,
+                "fee_denom_mint": e.fee_denom_mint.to_string(),
+                "fee_amount": e.fee_amount,
+                "to_buffer_amount": e.to_buffer_amount,
+                "to_treasury_amount": e.to_treasury_amount,
+                "to_creator_amount": e.to_creator_amount,
+                "jit_consumed_quote": e.jit_consumed_quote,
+                "timestamp": e.timestamp,
+            }),
+            FeelsEvent::Position(e) => json!({
+                "position": e.position.to_string(),
+                "position_mint": e.position_mint.to_string(),
+                "market": e.market.to_string(),
+                "owner": e.owner.to_string(),
+                "tick_lower": e.tick_lower,
+                "tick_upper": e.tick_upper,
+                "liquidity": e.liquidity.to_string(),
+                "amount_0": e.amount_0,
+                "amount_1": e.amount_1,
+                "fees_collected_0": e.fees_collected_0,
+                "fees_collected_1": e.fees_collected_1,
+                "operation": position_operation_name(e.operation),
+                "timestamp": e.timestamp,
+            }),
+            FeelsEvent::PoolStatus(e) => json!({
+                "market": e.market.to_string(),
+                "old_status": e.old_status,
+                "new_status": e.new_status,
+                "reason": e.reason,
+                "trigger": e.trigger,
+                "spot_stress_bps": e.stress.spot_stress_bps,
+                "time_stress_secs": e.stress.time_stress_secs,
+                "leverage_stress_bps": e.stress.leverage_stress_bps,
+                "timestamp": e.timestamp,
+                "slot": e.slot,
+            }),
+        }
+    }
+}
+
+/// Decode a discriminator-prefixed event payload
+///
+/// Returns `Ok(None)` for events this decoder does not know about.
+pub fn decode_event(data: &[u8]) -> SdkResult<Option<FeelsEvent>> {
+    if data.len() < 8 {
+        return Err(SdkError::SerializationError(
+            "Event data shorter than discriminator".to_string(),
+        ));
+    }
+    let (discriminator, mut body) = data.split_at(8);
+
+    let event = if discriminator == SwapExecuted::DISCRIMINATOR {
+        FeelsEvent::Swap(deserialize(&mut body)?)
+    } else if discriminator == FeeSplitApplied::DISCRIMINATOR {
+        FeelsEvent::FeeSplit(deserialize(&mut body)?)
+    } else if discriminator == PositionUpdated::DISCRIMINATOR {
+        FeelsEvent::Position(deserialize(&mut body)?)
+    } else if discriminator == PoolStatusChanged::DISCRIMINATOR {
+        FeelsEvent::PoolStatus(deserialize(&mut body)?)
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(event))
+}
+
+/// Decode every known Feels event from a transaction's log messages
+///
+/// Lines that are not event payloads, or belong to other programs, are skipped.
+pub fn parse_program_logs(logs: &[String]) -> Vec<FeelsEvent> {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|payload| STANDARD.decode(payload.trim()).ok())
+        .filter_map(|data| decode_event(&data).ok().flatten())
+        .collect()
+}
+
+/// Serialize an event the way `emit!` logs it (discriminator + borsh)
+pub fn encode_event<T: AnchorSerialize + Discriminator>(event: &T) -> SdkResult<Vec<u8>> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    event
+        .serialize(&mut data)
+        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    Ok(data)
+}
+
+fn deserialize<T: AnchorDeserialize>(body: &mut &[u8]) -> SdkResult<T> {
+    T::deserialize(body).map_err(|e| SdkError::SerializationError(e.to_string()))
+}
+
+fn position_operation_name(operation: PositionOperation) -> &'static str {
+    match operation {
+        PositionOperation::Open => "open",
+        PositionOperation::Close => "close",
+        PositionOperation::CollectFees => "collect_fees",
+        PositionOperation::AddLiquidity => "add_liquidity",
+        PositionOperation::RemoveLiquidity => "remove_liquidity",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn swap_event() -> SwapExecuted {
+        SwapExecuted {
+            market: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            token_in: Pubkey::new_unique(),
+            token_out: Pubkey::new_unique(),
+            amount_in: 1_000_000,
+            amount_out: 997_000,
+            fee_paid: 3_000,
+            base_fee_paid: 3_000,
+            impact_bps: 0,
+            sqrt_price_after: 1u128 << 64,
+            timestamp: 1_700_000_000,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_swap_from_logs() {
+        let event = swap_event();
+        let payload = STANDARD.encode(encode_event(&event).unwrap());
+        let logs = vec![
+            "Program Cbv2aa2zMJdwAwzLnRZuWQ8efpr6Xb9zxpJhEzLe3v6N invoke [1]".to_string(),
+            "Program log: Instruction: Swap".to_string(),
+            format!("{}{}", PROGRAM_DATA_PREFIX, payload),
+            format!("{}{}", PROGRAM_DATA_PREFIX, STANDARD.encode([7u8; 16])),
+        ];
+
+        let events = parse_program_logs(&logs);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "SwapExecuted");
+
+        let value = events[0].to_json();
+        assert_eq!(value["market"], event.market.to_string());
+        assert_eq!(value["amount_out"], 997_000);
+        assert_eq!(value["sqrt_price_after"], (1u128 << 64).to_string());
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_payload() {
+        let data = encode_event(&swap_event()).unwrap();
+        assert!(decode_event(&data[..4]).is_err());
+        assert!(decode_event(&data[..40]).is_err());
+        assert!(decode_event(&[0u8; 8]).unwrap().is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::core::{FeeEstimate, SdkError, SdkResult};
+use crate::protocol::calculate_fee_amount_ceil;
 
 /// Calculate fees for a swap based on amount and market parameters
+///
+/// Fee components round up, matching the program's swap fee calculation.
 pub fn calculate_swap_fees(
     amount_in: u64,
     base_fee_bps: u16,
@@ -9,17 +12,11 @@ pub fn calculate_swap_fees(
     is_buy: bool,
 ) -> SdkResult<FeeEstimate> {
     // Calculate base fee
-    let base_fee = amount_in
-        .checked_mul(base_fee_bps as u64)
-        .and_then(|v| v.checked_div(10000))
-        .ok_or(SdkError::MathOverflow)?;
+    let base_fee = calculate_fee_amount_ceil(amount_in, base_fee_bps)?;
 
     // Calculate price impact (simplified)
     let impact_bps = calculate_price_impact(amount_in, liquidity, sqrt_price, is_buy)?;
-    let impact_fee = amount_in
-        .checked_mul(impact_bps as u64)
-        .and_then(|v| v.checked_div(10000))
-        .ok_or(SdkError::MathOverflow)?;
+    let impact_fee = calculate_fee_amount_ceil(amount_in, impact_bps)?;
 
     let total_fee = base_fee.saturating_add(impact_fee);
     let fee_bps = ((total_fee as u128 * 10000) / amount_in as u128) as u16;
@@ -30,6 +27,34 @@ pub fn calculate_swap_fees(
         total_fee,
         fee_bps,
         price_impact_bps: impact_bps,
+        lp_fee: total_fee,
+        protocol_fee: 0,
+        creator_fee: 0,
+    })
+}
+
+/// Split an estimate's total fee between the market, treasury and creator
+///
+/// Mirrors the program's `split_and_apply_fees`: the protocol and creator
+/// shares round down and the market keeps the remainder.
+pub fn split_fee_estimate(
+    estimate: FeeEstimate,
+    protocol_fee_bps: u16,
+    creator_fee_bps: u16,
+) -> SdkResult<FeeEstimate> {
+    let distribution = calculate_fee_distribution(
+        estimate.total_fee,
+        creator_fee_bps > 0,
+        0,
+        protocol_fee_bps,
+        creator_fee_bps,
+    )?;
+
+    Ok(FeeEstimate {
+        lp_fee: distribution.lp_share,
+        protocol_fee: distribution.treasury_share,
+        creator_fee: distribution.creator_share,
+        ..estimate
     })
 }
 
@@ -122,6 +147,35 @@ mod tests {
         let estimate = calculate_swap_fees(10000, 30, 1_000_000_000, 1_000_000, true).unwrap();
         assert_eq!(estimate.base_fee, 30); // 0.3% of 10000
         assert!(estimate.total_fee >= estimate.base_fee);
+
+        // Rounds up where the program does (0.3% of 1001 = 3.003)
+        let estimate = calculate_swap_fees(1001, 30, 1_000_000_000, 0, true).unwrap();
+        assert_eq!(estimate.base_fee, 4);
+    }
+
+    #[test]
+    fn test_split_fee_estimate() {
+        let estimate = calculate_swap_fees(1_000_000, 30, 1_000_000_000, 0, true).unwrap();
+        assert_eq!(estimate.lp_fee, estimate.total_fee);
+
+        // 10% protocol fee, no creator on a plain market
+        let split = split_fee_estimate(estimate.clone(), 1000, 0).unwrap();
+        assert_eq!(split.protocol_fee, estimate.total_fee / 10);
+        assert_eq!(split.creator_fee, 0);
+        assert_eq!(split.lp_fee, estimate.total_fee - split.protocol_fee);
+
+        // Shares round down, so the market keeps the dust
+        let odd = FeeEstimate {
+            total_fee: 33,
+            ..estimate
+        };
+        let split = split_fee_estimate(odd, 1000, 500).unwrap();
+        assert_eq!(
+            (split.protocol_fee, split.creator_fee, split.lp_fee),
+            (3, 1, 29)
+        );
+
+        assert!(split_fee_estimate(split, 9000, 2000).is_err());
     }
 
     #[test]
@@ -0,0 +1,75 @@
+use feels::constants::{DEPLOYMENT_PERCENTAGE, TOTAL_SUPPLY};
+
+use crate::core::{MintDistribution, SdkError, SdkResult};
+use crate::instructions::MintTokenParams;
+
+/// Preview how `mint_token` and `deploy_initial_liquidity` split a new token
+///
+/// `mint_token` mints the full supply into the pre-launch escrow and holds the
+/// mint fee there; `deploy_initial_liquidity` later deploys
+/// `DEPLOYMENT_PERCENTAGE` of both escrow balances and sends the remaining
+/// FeelsSOL to the treasury.
+pub fn preview_mint_distribution(
+    params: &MintTokenParams,
+    mint_fee: u64,
+) -> SdkResult<MintDistribution> {
+    if params.ticker.len() > 10 {
+        return Err(SdkError::InvalidParameters(
+            "Ticker cannot exceed 10 bytes".to_string(),
+        ));
+    }
+    if params.name.len() > 32 {
+        return Err(SdkError::InvalidParameters(
+            "Name cannot exceed 32 bytes".to_string(),
+        ));
+    }
+    if params.uri.len() > 200 {
+        return Err(SdkError::InvalidParameters(
+            "URI cannot exceed 200 bytes".to_string(),
+        ));
+    }
+
+    let liquidity_allocation = (TOTAL_SUPPLY as u128 * DEPLOYMENT_PERCENTAGE as u128 / 100) as u64;
+    let liquidity_feelssol = (mint_fee as u128 * DEPLOYMENT_PERCENTAGE as u128 / 100) as u64;
+
+    Ok(MintDistribution {
+        total_supply: TOTAL_SUPPLY,
+        creator_allocation: 0,
+        liquidity_allocation,
+        protocol_allocation: TOTAL_SUPPLY - liquidity_allocation,
+        mint_fee,
+        liquidity_feelssol,
+        treasury_feelssol: mint_fee - liquidity_feelssol,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> MintTokenParams {
+        MintTokenParams {
+            ticker: "FEEL".to_string(),
+            name: "Feels Token".to_string(),
+            uri: "https://example.com/feel.json".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_full_supply_goes_to_liquidity() {
+        let dist = preview_mint_distribution(&params(), 1_000_000).unwrap();
+        assert_eq!(dist.total_supply, 1_000_000_000 * 1_000_000);
+        assert_eq!(dist.liquidity_allocation, dist.total_supply);
+        assert_eq!(dist.creator_allocation, 0);
+        assert_eq!(dist.protocol_allocation, 0);
+        assert_eq!(dist.liquidity_feelssol, 1_000_000);
+        assert_eq!(dist.treasury_feelssol, 0);
+    }
+
+    #[test]
+    fn test_rejects_oversized_metadata() {
+        let mut p = params();
+        p.ticker = "TOOLONGTICKER".to_string();
+        assert!(preview_mint_distribution(&p, 0).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{constants::TICK_ARRAY_SIZE, SdkError, SdkResult};
+use crate::core::{SdkError, SdkResult};
 
 /// Calculate amount with slippage tolerance
 pub fn calculate_amount_with_slippage(
@@ -24,12 +24,113 @@ pub fn calculate_amount_with_slippage(
         .ok_or(SdkError::MathOverflow)
 }
 
+/// Sqrt prices (Q64) of a price move of `slippage_bps` either way
+fn slippage_sqrt_price_band(sqrt_price: u128, slippage_bps: u16) -> SdkResult<(u128, u128)> {
+    if slippage_bps > 10000 {
+        return Err(SdkError::InvalidParameters(
+            "Slippage cannot exceed 100%".to_string(),
+        ));
+    }
+
+    let move_by = slippage_bps as f64 / 10000.0;
+    let lower = (sqrt_price as f64 * (1.0 - move_by).sqrt()) as u128;
+    let upper = (sqrt_price as f64 * (1.0 + move_by).sqrt()).ceil() as u128;
+    let min_sqrt_price = tick_to_sqrt_price(feels::constants::MIN_TICK)?;
+    let max_sqrt_price = tick_to_sqrt_price(feels::constants::MAX_TICK)?;
+    Ok((
+        lower.min(sqrt_price).max(min_sqrt_price),
+        upper.max(sqrt_price).min(max_sqrt_price),
+    ))
+}
+
+/// Liquidity to request from `open_position` for at most the given deposit
+///
+/// `open_position` charges whatever its liquidity costs at the execution
+/// price. The result stays within `max_amount_0` and `max_amount_1` for any
+/// price up to `slippage_bps` away from `sqrt_price`: token 0 costs most at
+/// the bottom of that band and token 1 at the top.
+pub fn liquidity_for_max_amounts(
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    max_amount_0: u64,
+    max_amount_1: u64,
+    slippage_bps: u16,
+) -> SdkResult<u128> {
+    let sqrt_price_lower = tick_to_sqrt_price(tick_lower)?;
+    let sqrt_price_upper = tick_to_sqrt_price(tick_upper)?;
+    let (band_lower, band_upper) = slippage_sqrt_price_band(sqrt_price, slippage_bps)?;
+
+    let liquidity_at = |price: u128| {
+        feels::utils::liquidity_from_amounts(
+            price,
+            sqrt_price_lower,
+            sqrt_price_upper,
+            max_amount_0,
+            max_amount_1,
+        )
+        .map_err(|_| SdkError::MathOverflow)
+    };
+    let liquidity = liquidity_at(band_lower)?.min(liquidity_at(band_upper)?);
+
+    // A zero amount bounds nothing in liquidity_from_amounts, so confirm the
+    // deposit at both ends of the band
+    for price in [band_lower, band_upper] {
+        let (amount_0, amount_1) = feels::logic::amounts_from_liquidity(
+            price,
+            sqrt_price_lower,
+            sqrt_price_upper,
+            liquidity,
+        )
+        .map_err(|_| SdkError::MathOverflow)?;
+        if amount_0 > max_amount_0 || amount_1 > max_amount_1 {
+            return Ok(0);
+        }
+    }
+    Ok(liquidity)
+}
+
+/// `amount_0_min` and `amount_1_min` for closing `liquidity`
+///
+/// The least of each token the position returns for any price up to
+/// `slippage_bps` away from `sqrt_price`: token 0 at the top of that band
+/// and token 1 at the bottom. A larger move makes `close_position` revert
+/// with `SlippageExceeded`. Owed fees are paid on top and not counted.
+pub fn close_amount_minimums(
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    slippage_bps: u16,
+) -> SdkResult<(u64, u64)> {
+    let sqrt_price_lower = tick_to_sqrt_price(tick_lower)?;
+    let sqrt_price_upper = tick_to_sqrt_price(tick_upper)?;
+    let (band_lower, band_upper) = slippage_sqrt_price_band(sqrt_price, slippage_bps)?;
+
+    let amounts_at = |price: u128| {
+        feels::logic::amounts_from_liquidity(price, sqrt_price_lower, sqrt_price_upper, liquidity)
+            .map_err(|_| SdkError::MathOverflow)
+    };
+    Ok((amounts_at(band_upper)?.0, amounts_at(band_lower)?.1))
+}
+
 /// Calculate fee amount from basis points
+///
+/// Rounds up like the program's swap fee, so quotes never undercharge by one
+/// unit. Use `calculate_fee_amount_floor` for fee splits, which round down.
 pub fn calculate_fee_amount(amount: u64, fee_bps: u16) -> SdkResult<u64> {
-    amount
-        .checked_mul(fee_bps as u64)
-        .and_then(|v| v.checked_div(10000))
-        .ok_or(SdkError::MathOverflow)
+    calculate_fee_amount_ceil(amount, fee_bps)
+}
+
+/// Fee rounded up in favor of the pool (on-chain swap fee rounding)
+pub fn calculate_fee_amount_ceil(amount: u64, fee_bps: u16) -> SdkResult<u64> {
+    // Use the feels program's implementation
+    feels::utils::calculate_fee_ceil(amount, fee_bps).map_err(|_| SdkError::MathOverflow)
+}
+
+/// Fee rounded down (on-chain treasury and creator split rounding)
+pub fn calculate_fee_amount_floor(amount: u64, fee_bps: u16) -> SdkResult<u64> {
+    u64::try_from(amount as u128 * fee_bps as u128 / 10_000).map_err(|_| SdkError::MathOverflow)
 }
 
 /// Calculate price impact in basis points
@@ -79,14 +180,10 @@ pub fn tick_to_sqrt_price(tick: i32) -> SdkResult<u128> {
     feels::utils::sqrt_price_from_tick(tick).map_err(|_| SdkError::MathOverflow)
 }
 
-/// Align tick to spacing
+/// Align tick to spacing (rounds toward negative infinity)
 pub fn align_tick(tick: i32, tick_spacing: u16) -> i32 {
     let spacing = tick_spacing as i32;
-    if tick >= 0 {
-        (tick / spacing) * spacing
-    } else {
-        ((tick - spacing + 1) / spacing) * spacing
-    }
+    tick.div_euclid(spacing) * spacing
 }
 
 /// Check if tick is initialized
@@ -96,14 +193,8 @@ pub fn is_tick_initialized(tick: i32, tick_spacing: u16) -> bool {
 
 /// Calculate the tick array start index for a given tick
 pub fn get_tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
-    let ticks_in_array = TICK_ARRAY_SIZE;
-    let tick_array_spacing = (tick_spacing as i32) * ticks_in_array;
-
-    if tick >= 0 {
-        (tick / tick_array_spacing) * tick_array_spacing
-    } else {
-        ((tick - tick_array_spacing + 1) / tick_array_spacing) * tick_array_spacing
-    }
+    // Use the feels program's implementation
+    feels::utils::get_tick_array_start_index(tick, tick_spacing)
 }
 
 /// Check if a tick spacing requires full range positions only
@@ -133,6 +224,42 @@ mod tests {
         assert!(calculate_amount_with_slippage(1000, 10001, true).is_err());
     }
 
+    #[test]
+    fn test_fee_rounding_direction() {
+        // 0.3% of 1001 is 3.003: the program charges 4
+        assert_eq!(calculate_fee_amount_floor(1001, 30).unwrap(), 3);
+        assert_eq!(calculate_fee_amount_ceil(1001, 30).unwrap(), 4);
+        assert_eq!(calculate_fee_amount(1001, 30).unwrap(), 4);
+
+        // Dust swaps still pay one unit
+        assert_eq!(calculate_fee_amount_floor(1, 1).unwrap(), 0);
+        assert_eq!(calculate_fee_amount(1, 1).unwrap(), 1);
+
+        // Exact multiples agree
+        assert_eq!(calculate_fee_amount_floor(10_000, 30).unwrap(), 30);
+        assert_eq!(calculate_fee_amount(10_000, 30).unwrap(), 30);
+        assert_eq!(calculate_fee_amount(0, 30).unwrap(), 0);
+        assert_eq!(calculate_fee_amount(1001, 0).unwrap(), 0);
+
+        // No intermediate overflow near u64::MAX
+        assert_eq!(
+            calculate_fee_amount_floor(u64::MAX, 10_000).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_fee_matches_program() {
+        for amount in [1u64, 333, 1001, 9_999, 123_456_789, u64::MAX / 10_000] {
+            for fee_bps in [1u16, 25, 30, 100, 1000] {
+                assert_eq!(
+                    calculate_fee_amount(amount, fee_bps).unwrap(),
+                    feels::utils::calculate_fee_ceil(amount, fee_bps).unwrap()
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_tick_alignment() {
         assert_eq!(align_tick(5, 10), 0);
@@ -1,7 +1,21 @@
+pub mod apr;
+pub mod dex_twap;
+pub mod events;
 pub mod fees;
+pub mod launch;
 pub mod math;
 pub mod pda;
+pub mod safety;
+pub mod stake_pool;
+pub mod twap;
 
+pub use apr::*;
+pub use dex_twap::*;
+pub use events::*;
 pub use fees::*;
+pub use launch::*;
 pub use math::*;
 pub use pda::*;
+pub use safety::*;
+pub use stake_pool::*;
+pub use twap::*;
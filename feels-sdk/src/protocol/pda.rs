@@ -123,6 +123,16 @@ impl PdaBuilder {
         })
     }
 
+    pub fn pomm_position(&self, market: &Pubkey, position_index: u8) -> (Pubkey, u8) {
+        let key = format!("pomm_position:{}:{}", market, position_index);
+        self.cache.get_or_compute(&key, || {
+            Pubkey::find_program_address(
+                &[seeds::POMM_POSITION, market.as_ref(), &[position_index]],
+                &self.program_id,
+            )
+        })
+    }
+
     pub fn protocol_config(&self) -> (Pubkey, u8) {
         let key = "protocol_config";
         self.cache.get_or_compute(key, || {
@@ -137,6 +147,13 @@ impl PdaBuilder {
         })
     }
 
+    pub fn safety_controller(&self) -> (Pubkey, u8) {
+        let key = "safety_controller";
+        self.cache.get_or_compute(key, || {
+            Pubkey::find_program_address(&[seeds::SAFETY_CONTROLLER], &self.program_id)
+        })
+    }
+
     pub fn feels_hub(&self) -> (Pubkey, u8) {
         let key = "feels_hub";
         self.cache.get_or_compute(key, || {
@@ -0,0 +1,143 @@
+use feels::state::{compute_divergence_bps, SafetyController};
+
+use crate::core::{SafetyProjection, SafetyThresholds, StressComponents};
+
+/// Project the safety controller one observation forward
+///
+/// Mirrors `SafetyController::check_and_update_divergence`: observations are
+/// only counted when the DEX TWAP is fresh and both rates are set, a breach
+/// resets the clear streak (and vice versa), and pause/resume only flip once
+/// the respective streak reaches its threshold.
+pub fn project_safety_trip(
+    controller: &SafetyController,
+    thresholds: &SafetyThresholds,
+    stress: &StressComponents,
+) -> SafetyProjection {
+    let was_paused = controller.redemptions_paused;
+    let mut paused = was_paused;
+    let mut breaches = controller.consecutive_breaches;
+    let mut clears = controller.consecutive_clears;
+
+    let counted = !stress.dex_stale && stress.native_rate_q64 > 0 && stress.dex_twap_rate_q64 > 0;
+    let divergence_bps = if counted {
+        compute_divergence_bps(stress.native_rate_q64, stress.dex_twap_rate_q64)
+    } else {
+        0
+    };
+    let is_breach = counted && divergence_bps >= thresholds.depeg_threshold_bps;
+
+    if counted {
+        if is_breach {
+            breaches = breaches.saturating_add(1);
+            clears = 0;
+            if !paused && breaches >= thresholds.depeg_required_obs {
+                paused = true;
+            }
+        } else {
+            clears = clears.saturating_add(1);
+            breaches = 0;
+            if paused && clears >= thresholds.clear_required_obs {
+                paused = false;
+            }
+        }
+    }
+
+    let (observations_until_pause, observations_until_resume) = if paused {
+        (
+            None,
+            Some(thresholds.clear_required_obs.saturating_sub(clears)),
+        )
+    } else {
+        (
+            Some(thresholds.depeg_required_obs.saturating_sub(breaches)),
+            None,
+        )
+    };
+
+    SafetyProjection {
+        divergence_bps,
+        is_breach,
+        counted,
+        was_paused,
+        redemptions_paused: paused,
+        consecutive_breaches: breaches,
+        consecutive_clears: clears,
+        observations_until_pause,
+        observations_until_resume,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feels::state::DegradeFlags;
+
+    const ONE_Q64: u128 = 1u128 << 64;
+
+    fn controller(paused: bool, breaches: u8, clears: u8) -> SafetyController {
+        SafetyController {
+            redemptions_paused: paused,
+            consecutive_breaches: breaches,
+            consecutive_clears: clears,
+            last_change_slot: 0,
+            mint_last_slot: 0,
+            mint_slot_amount: 0,
+            redeem_last_slot: 0,
+            redeem_slot_amount: 0,
+            last_divergence_check_slot: 0,
+            degrade_flags: DegradeFlags::default(),
+            _reserved: [0; 32],
+        }
+    }
+
+    fn thresholds() -> SafetyThresholds {
+        SafetyThresholds {
+            depeg_threshold_bps: 100,
+            depeg_required_obs: 3,
+            clear_required_obs: 2,
+        }
+    }
+
+    fn stress(divergence_bps: u128) -> StressComponents {
+        StressComponents {
+            native_rate_q64: ONE_Q64,
+            dex_twap_rate_q64: ONE_Q64 + ONE_Q64 * divergence_bps / 10_000,
+            dex_stale: false,
+        }
+    }
+
+    #[test]
+    fn test_breach_streak_trips_pause() {
+        let p = project_safety_trip(&controller(false, 1, 0), &thresholds(), &stress(200));
+        assert!(p.is_breach);
+        assert!(!p.redemptions_paused);
+        assert_eq!(p.observations_until_pause, Some(1));
+
+        let p = project_safety_trip(&controller(false, 2, 0), &thresholds(), &stress(200));
+        assert!(p.redemptions_paused);
+        assert_eq!(p.observations_until_resume, Some(2));
+    }
+
+    #[test]
+    fn test_clear_streak_resumes() {
+        let p = project_safety_trip(&controller(true, 0, 1), &thresholds(), &stress(10));
+        assert!(!p.is_breach);
+        assert!(!p.redemptions_paused);
+        assert_eq!(p.observations_until_pause, Some(3));
+
+        // A breach while paused resets the clear streak
+        let p = project_safety_trip(&controller(true, 0, 1), &thresholds(), &stress(200));
+        assert!(p.redemptions_paused);
+        assert_eq!(p.observations_until_resume, Some(2));
+    }
+
+    #[test]
+    fn test_stale_dex_is_not_counted() {
+        let mut s = stress(500);
+        s.dex_stale = true;
+        let p = project_safety_trip(&controller(false, 2, 0), &thresholds(), &s);
+        assert!(!p.counted);
+        assert!(!p.redemptions_paused);
+        assert_eq!(p.consecutive_breaches, 2);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::core::{SdkError, SdkResult};
+
+/// `AccountType::StakePool` discriminant in the SPL stake pool layout
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Offset of `total_lamports` in the SPL `StakePool` account
+///
+/// account_type (1) + manager, staker, stake_deposit_authority (3 * 32) +
+/// stake_withdraw_bump_seed (1) + validator_list, reserve_stake, pool_mint,
+/// manager_fee_account, token_program_id (5 * 32)
+const TOTAL_LAMPORTS_OFFSET: usize = 258;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = TOTAL_LAMPORTS_OFFSET + 8;
+
+/// Parse the SOL-per-JitoSOL rate from raw stake pool account data
+///
+/// Returns `(total_lamports, pool_token_supply)`, the numerator and
+/// denominator of the pool's redemption rate.
+pub fn parse_stake_pool_rate(data: &[u8]) -> SdkResult<(u64, u64)> {
+    if data.len() < POOL_TOKEN_SUPPLY_OFFSET + 8 {
+        return Err(SdkError::SerializationError(
+            "Stake pool account too short".to_string(),
+        ));
+    }
+    if data[0] != STAKE_POOL_ACCOUNT_TYPE {
+        return Err(SdkError::SerializationError(
+            "Account is not a stake pool".to_string(),
+        ));
+    }
+
+    let read_u64 = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    let total_lamports = read_u64(TOTAL_LAMPORTS_OFFSET);
+    let pool_token_supply = read_u64(POOL_TOKEN_SUPPLY_OFFSET);
+    if pool_token_supply == 0 {
+        return Err(SdkError::InvalidParameters(
+            "Stake pool has no token supply".to_string(),
+        ));
+    }
+
+    Ok((total_lamports, pool_token_supply))
+}
+
+/// Convert a numerator/denominator rate into the Q64 form `update_native_rate` expects
+pub fn rate_to_q64(numerator: u64, denominator: u64) -> SdkResult<u128> {
+    if denominator == 0 {
+        return Err(SdkError::MathOverflow);
+    }
+    Ok(((numerator as u128) << 64) / denominator as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stake pool account with the Jito pool's shape and a ~1.19 SOL/JitoSOL rate
+    fn fixture() -> Vec<u8> {
+        let mut data = vec![0u8; 611];
+        data[0] = STAKE_POOL_ACCOUNT_TYPE;
+        data[TOTAL_LAMPORTS_OFFSET..POOL_TOKEN_SUPPLY_OFFSET]
+            .copy_from_slice(&15_270_442_113_526_305u64.to_le_bytes());
+        data[POOL_TOKEN_SUPPLY_OFFSET..POOL_TOKEN_SUPPLY_OFFSET + 8]
+            .copy_from_slice(&12_803_391_062_315_862u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_parse_stake_pool_rate() {
+        let (lamports, supply) = parse_stake_pool_rate(&fixture()).unwrap();
+        assert_eq!(lamports, 15_270_442_113_526_305);
+        assert_eq!(supply, 12_803_391_062_315_862);
+
+        // ~1.1927 SOL per JitoSOL
+        let rate_q64 = rate_to_q64(lamports, supply).unwrap();
+        let rate_e4 = (rate_q64 * 10_000) >> 64;
+        assert_eq!(rate_e4, 11_926);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_accounts() {
+        let mut data = fixture();
+        assert!(parse_stake_pool_rate(&data[..100]).is_err());
+
+        data[0] = 2; // ValidatorList
+        assert!(parse_stake_pool_rate(&data).is_err());
+
+        let mut data = fixture();
+        data[POOL_TOKEN_SUPPLY_OFFSET..POOL_TOKEN_SUPPLY_OFFSET + 8].fill(0);
+        assert!(parse_stake_pool_rate(&data).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::core::{
+    constants::{MAX_TICK, MIN_TICK},
+    SdkError, SdkResult, TwapQuote,
+};
+use crate::protocol::tick_to_sqrt_price;
+
+/// Minimum TWAP window enforced on-chain by `OracleState::get_twap_tick`
+pub const MIN_TWAP_WINDOW_SECS: u32 = 60;
+
+/// Width of the confidence band in standard deviations (~95%)
+pub const CONFIDENCE_SIGMAS: i64 = 2;
+
+/// Consult a TWAP from `(timestamp, tick_cumulative)` observations
+///
+/// The window ends at the newest observation so a stale oracle still yields a
+/// quote; `is_stale` is set when that observation is older than
+/// `max_staleness_secs` relative to `now`. The confidence band is the TWAP tick
+/// widened by `CONFIDENCE_SIGMAS` standard deviations of the per-interval ticks.
+pub fn consult_twap(
+    observations: &[(i64, i128)],
+    now: i64,
+    window_secs: u32,
+    max_staleness_secs: i64,
+) -> SdkResult<TwapQuote> {
+    let window_secs = window_secs.max(MIN_TWAP_WINDOW_SECS);
+
+    let mut sorted: Vec<(i64, i128)> = observations.to_vec();
+    sorted.sort_by_key(|(ts, _)| *ts);
+    sorted.dedup_by_key(|(ts, _)| *ts);
+
+    let (newest_ts, newest_cum) = *sorted
+        .last()
+        .ok_or_else(|| SdkError::InvalidParameters("Oracle has no observations".to_string()))?;
+    let target_ts = newest_ts - window_secs as i64;
+
+    // Anchor on the most recent observation at or before the window start
+    let start = sorted
+        .iter()
+        .rposition(|(ts, _)| *ts <= target_ts)
+        .ok_or_else(|| {
+            SdkError::InvalidParameters("Insufficient oracle history for window".to_string())
+        })?;
+    let (anchor_ts, anchor_cum) = sorted[start];
+
+    let elapsed = newest_ts - anchor_ts;
+    let twap_tick = ((newest_cum - anchor_cum) / elapsed as i128) as i32;
+
+    // Per-interval average ticks inside the window
+    let interval_ticks: Vec<i64> = sorted[start..]
+        .windows(2)
+        .map(|w| ((w[1].1 - w[0].1) / (w[1].0 - w[0].0) as i128) as i64)
+        .collect();
+    let volatility_ticks = tick_std_dev(&interval_ticks);
+
+    let band = volatility_ticks.saturating_mul(CONFIDENCE_SIGMAS);
+    let lower_tick = (twap_tick as i64 - band).clamp(MIN_TICK as i64, MAX_TICK as i64) as i32;
+    let upper_tick = (twap_tick as i64 + band).clamp(MIN_TICK as i64, MAX_TICK as i64) as i32;
+
+    Ok(TwapQuote {
+        twap_tick,
+        twap_sqrt_price: tick_to_sqrt_price(twap_tick)?,
+        lower_sqrt_price: tick_to_sqrt_price(lower_tick)?,
+        upper_sqrt_price: tick_to_sqrt_price(upper_tick)?,
+        volatility_bps: volatility_ticks.min(10_000) as u16,
+        window_secs: elapsed as u32,
+        last_update_ts: newest_ts,
+        is_stale: now.saturating_sub(newest_ts) > max_staleness_secs,
+    })
+}
+
+/// Population standard deviation of tick samples (1 tick ≈ 1 bp)
+fn tick_std_dev(samples: &[i64]) -> i64 {
+    if samples.len() < 2 {
+        return 0;
+    }
+    let n = samples.len() as i128;
+    let mean = samples.iter().map(|s| *s as i128).sum::<i128>() / n;
+    let var = samples
+        .iter()
+        .map(|s| {
+            let d = *s as i128 - mean;
+            d * d
+        })
+        .sum::<i128>()
+        / n;
+    integer_sqrt::IntegerSquareRoot::integer_sqrt(&(var as u128)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Observations every 60s alternating between ticks 100 and 120
+    fn fixture() -> Vec<(i64, i128)> {
+        let mut obs = vec![(1_000, 0i128)];
+        for i in 0..6 {
+            let (ts, cum) = *obs.last().unwrap();
+            let tick = if i % 2 == 0 { 100 } else { 120 };
+            obs.push((ts + 60, cum + tick * 60));
+        }
+        obs
+    }
+
+    #[test]
+    fn test_consult_fresh() {
+        let obs = fixture();
+        let quote = consult_twap(&obs, 1_360 + 30, 360, 120).unwrap();
+        assert_eq!(quote.twap_tick, 110);
+        assert_eq!(quote.window_secs, 360);
+        assert_eq!(quote.volatility_bps, 10);
+        assert!(!quote.is_stale);
+        assert!(quote.lower_sqrt_price < quote.twap_sqrt_price);
+        assert!(quote.upper_sqrt_price > quote.twap_sqrt_price);
+        assert_eq!(quote.lower_sqrt_price, tick_to_sqrt_price(90).unwrap());
+        assert_eq!(quote.upper_sqrt_price, tick_to_sqrt_price(130).unwrap());
+    }
+
+    #[test]
+    fn test_consult_stale() {
+        let obs = fixture();
+        let quote = consult_twap(&obs, 1_360 + 600, 360, 120).unwrap();
+        assert_eq!(quote.twap_tick, 110);
+        assert_eq!(quote.last_update_ts, 1_360);
+        assert!(quote.is_stale);
+    }
+
+    #[test]
+    fn test_consult_insufficient_history() {
+        let obs = fixture();
+        assert!(consult_twap(&obs, 1_360, 3_600, 120).is_err());
+        assert!(consult_twap(&[], 0, 60, 120).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Test slippage-protected position sizing and close minimums
+
+#[cfg(test)]
+mod tests {
+    use feels::logic::{amounts_from_liquidity, validate_slippage};
+    use feels_sdk::protocol::{
+        close_amount_minimums, liquidity_for_max_amounts, tick_to_sqrt_price,
+    };
+
+    const TICK_LOWER: i32 = -1_000;
+    const TICK_UPPER: i32 = 1_000;
+    const SQRT_PRICE: u128 = 1u128 << 64;
+
+    /// Sqrt price after the price moves by `bps` (negative moves down)
+    fn moved(bps: i32) -> u128 {
+        (SQRT_PRICE as f64 * (1.0 + bps as f64 / 10_000.0).sqrt()) as u128
+    }
+
+    fn amounts_at(sqrt_price: u128, liquidity: u128) -> (u64, u64) {
+        amounts_from_liquidity(
+            sqrt_price,
+            tick_to_sqrt_price(TICK_LOWER).unwrap(),
+            tick_to_sqrt_price(TICK_UPPER).unwrap(),
+            liquidity,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_open_deposit_stays_within_max_amounts() {
+        let (max_0, max_1) = (1_000_000_000, 1_000_000_000);
+        let liquidity =
+            liquidity_for_max_amounts(SQRT_PRICE, TICK_LOWER, TICK_UPPER, max_0, max_1, 100)
+                .unwrap();
+        assert!(liquidity > 0);
+
+        // Anywhere within the 1% band the program charges no more than the maximums
+        for bps in [-100, -50, 0, 50, 100] {
+            let (amount_0, amount_1) = amounts_at(moved(bps), liquidity);
+            assert!(amount_0 <= max_0 && amount_1 <= max_1, "move {} bps", bps);
+        }
+
+        // Tolerating slippage costs liquidity
+        let exact =
+            liquidity_for_max_amounts(SQRT_PRICE, TICK_LOWER, TICK_UPPER, max_0, max_1, 0).unwrap();
+        assert!(exact > liquidity);
+    }
+
+    #[test]
+    fn test_open_requires_both_tokens_in_range() {
+        let liquidity =
+            liquidity_for_max_amounts(SQRT_PRICE, TICK_LOWER, TICK_UPPER, 0, 1_000_000_000, 100)
+                .unwrap();
+        assert_eq!(liquidity, 0);
+        assert!(
+            liquidity_for_max_amounts(SQRT_PRICE, TICK_LOWER, TICK_UPPER, 1, 1, 10_001).is_err()
+        );
+    }
+
+    #[test]
+    fn test_close_minimums_hold_within_tolerance() {
+        let liquidity = 1_000_000_000_000;
+        let (min_0, min_1) =
+            close_amount_minimums(SQRT_PRICE, TICK_LOWER, TICK_UPPER, liquidity, 100).unwrap();
+        let (quoted_0, quoted_1) = amounts_at(SQRT_PRICE, liquidity);
+        assert!(min_0 > 0 && min_0 < quoted_0);
+        assert!(min_1 > 0 && min_1 < quoted_1);
+
+        for bps in [-100, -50, 0, 50, 100] {
+            let (amount_0, amount_1) = amounts_at(moved(bps), liquidity);
+            assert!(
+                validate_slippage(amount_0, min_0).is_ok(),
+                "move {} bps",
+                bps
+            );
+            assert!(
+                validate_slippage(amount_1, min_1).is_ok(),
+                "move {} bps",
+                bps
+            );
+        }
+    }
+
+    #[test]
+    fn test_close_reverts_when_price_moves_past_tolerance() {
+        let liquidity = 1_000_000_000_000;
+        let (min_0, min_1) =
+            close_amount_minimums(SQRT_PRICE, TICK_LOWER, TICK_UPPER, liquidity, 100).unwrap();
+
+        // Price up 2%: the position holds less token 0 than the minimum
+        let (amount_0, _) = amounts_at(moved(200), liquidity);
+        assert!(validate_slippage(amount_0, min_0).is_err());
+
+        // Price down 2%: less token 1 than the minimum
+        let (_, amount_1) = amounts_at(moved(-200), liquidity);
+        assert!(validate_slippage(amount_1, min_1).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! Test that SDK tick math agrees with the program implementation
+
+#[cfg(test)]
+mod tests {
+    use feels::constants::{MAX_TICK, MIN_TICK};
+    use feels_sdk::{
+        align_tick,
+        protocol::{get_tick_array_start_index, sqrt_price_to_tick, tick_to_sqrt_price},
+    };
+
+    const SPACINGS: [u16; 6] = [1, 2, 10, 16, 64, 128];
+
+    fn sample_ticks() -> Vec<i32> {
+        let mut ticks = vec![MIN_TICK, MIN_TICK + 1, -1, 0, 1, MAX_TICK - 1, MAX_TICK];
+        ticks.extend((MIN_TICK..=MAX_TICK).step_by(9_973));
+        ticks
+    }
+
+    #[test]
+    fn test_tick_array_start_matches_program() {
+        for spacing in SPACINGS {
+            for tick in sample_ticks() {
+                assert_eq!(
+                    get_tick_array_start_index(tick, spacing),
+                    feels::utils::get_tick_array_start_index(tick, spacing),
+                    "tick {} spacing {}",
+                    tick,
+                    spacing
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_align_tick_is_floor() {
+        for spacing in SPACINGS {
+            let s = spacing as i32;
+            for tick in sample_ticks() {
+                assert_eq!(align_tick(tick, spacing), tick.div_euclid(s) * s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_price_conversions_match_program() {
+        for tick in sample_ticks() {
+            let sqrt_price = tick_to_sqrt_price(tick).unwrap();
+            assert_eq!(
+                sqrt_price,
+                feels::utils::sqrt_price_from_tick(tick).unwrap()
+            );
+            assert_eq!(sqrt_price_to_tick(sqrt_price).unwrap(), tick);
+            if tick == MAX_TICK {
+                continue;
+            }
+            assert_eq!(
+                sqrt_price_to_tick(sqrt_price + 1).unwrap(),
+                feels::utils::tick_from_sqrt_price(sqrt_price + 1).unwrap()
+            );
+        }
+    }
+}
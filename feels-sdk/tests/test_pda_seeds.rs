@@ -0,0 +1,50 @@
+//! Test that SDK PDA derivations agree with the program seed registry
+
+#[cfg(test)]
+mod tests {
+    use feels::utils::seeds::derive_pda;
+    use feels_sdk::{program_id, protocol::PdaBuilder};
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_sdk_pdas_match_program_registry() {
+        let pid = program_id();
+        let pda = PdaBuilder::new(pid);
+        let token_0 = Pubkey::new_unique();
+        let token_1 = Pubkey::new_unique();
+        let market = pda.market(&token_0, &token_1).0;
+
+        assert_eq!(
+            derive_pda("market", &[token_0.as_ref(), token_1.as_ref()], &pid),
+            Some(pda.market(&token_0, &token_1))
+        );
+        assert_eq!(
+            derive_pda("buffer", &[market.as_ref()], &pid),
+            Some(pda.buffer(&market))
+        );
+        assert_eq!(
+            derive_pda("oracle", &[market.as_ref()], &pid),
+            Some(pda.oracle(&market))
+        );
+        assert_eq!(
+            derive_pda(
+                "tick_array",
+                &[market.as_ref(), &(-64i32).to_le_bytes()],
+                &pid
+            ),
+            Some(pda.tick_array(&market, -64))
+        );
+        assert_eq!(
+            derive_pda("protocol_config", &[], &pid),
+            Some(pda.protocol_config())
+        );
+        assert_eq!(
+            derive_pda("protocol_oracle", &[], &pid),
+            Some(pda.protocol_oracle())
+        );
+        assert_eq!(
+            derive_pda("safety_controller", &[], &pid),
+            Some(pda.safety_controller())
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Test position impermanent loss against closed-form reference values
+
+#[cfg(test)]
+mod tests {
+    use feels_sdk::{
+        core::constants::{MAX_TICK, MIN_TICK},
+        tick_to_sqrt_price, PositionInfo,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    const Q64: u128 = 1u128 << 64;
+
+    fn position(tick_lower: i32, tick_upper: i32) -> PositionInfo {
+        PositionInfo {
+            owner: Pubkey::default(),
+            liquidity: 1_000_000_000,
+            tick_lower,
+            tick_upper,
+            fee_growth_inside_0: 0,
+            fee_growth_inside_1: 0,
+            tokens_owed_0: 0,
+            tokens_owed_1: 0,
+        }
+    }
+
+    #[test]
+    fn test_full_range_matches_constant_product() {
+        let full_range = position(MIN_TICK, MAX_TICK);
+
+        // 4x price move: 2 * sqrt(4) / (1 + 4) - 1
+        let il = full_range.impermanent_loss(Q64, 2 * Q64);
+        assert!((il - (-0.2)).abs() < 1e-6, "il = {}", il);
+
+        // Symmetric for the inverse move
+        let il = full_range.impermanent_loss(Q64, Q64 / 2);
+        assert!((il - (-0.2)).abs() < 1e-6, "il = {}", il);
+
+        assert_eq!(full_range.impermanent_loss(Q64, Q64), 0.0);
+    }
+
+    #[test]
+    fn test_narrow_range_exited_above_upper_tick() {
+        let narrow = position(-1000, 1000);
+        let current = tick_to_sqrt_price(4000).unwrap();
+
+        // Fully converted to token_1 at the upper bound:
+        // (sb - sa) / ((1 - 1/sb) * p + (1 - sa)) - 1
+        let il = narrow.impermanent_loss(Q64, current);
+        assert!((il - (-0.176_790_795)).abs() < 1e-6, "il = {}", il);
+
+        // The range amplifies loss relative to full range at the same move
+        let full_range = position(MIN_TICK, MAX_TICK).impermanent_loss(Q64, current);
+        assert!(il < full_range);
+
+        let (amount_0, amount_1) = narrow.value_at_price(current).unwrap();
+        assert_eq!(amount_0, 0);
+        assert!(amount_1 > 0);
+    }
+
+    #[test]
+    fn test_value_at_price_in_range_holds_both_tokens() {
+        let (amount_0, amount_1) = position(-1000, 1000).value_at_price(Q64).unwrap();
+        assert!(amount_0 > 0 && amount_1 > 0);
+        // Symmetric range around price 1 holds roughly equal amounts
+        assert!(amount_0.abs_diff(amount_1) <= 2);
+    }
+}
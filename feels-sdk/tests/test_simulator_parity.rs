@@ -0,0 +1,408 @@
+//! Test that SwapSimulator quotes match the program's swap execution
+//!
+//! The reference is the program's own `execute_swap_steps`, run against tick
+//! array accounts built the way the program stores them. The simulator reads
+//! the same accounts through the SDK's parser, and has to agree with the
+//! program exactly, rounding included, across initialized ticks, empty
+//! ranges between positions and the market's price bounds.
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+    use anchor_lang::Discriminator;
+    use feels::constants::MIN_TICK;
+    use feels::logic::{execute_swap_steps, SwapDirection, SwapParams, SwapState};
+    use feels::state::{Buffer, Market, TickArray, TICK_ARRAY_SIZE};
+    use feels::utils::{get_tick_array_start_index, sqrt_price_from_tick};
+    use feels_sdk::{parse_tick_array_auto, MarketState, SwapSimulator, TickArrayLoader};
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use std::sync::Once;
+
+    const TICK_SPACING: u16 = 64;
+    const LOWER_BOUND: i32 = -8000;
+    const UPPER_BOUND: i32 = 8000;
+    const FEES_BPS: [u16; 3] = [0, 30, 100];
+    const AMOUNTS: [u64; 6] = [
+        1,
+        100_000,
+        1_000_000_000,
+        50_000_000_000,
+        1_000_000_000_000,
+        1_000_000_000_000_000,
+    ];
+
+    /// Positions as (tick_lower, tick_upper, liquidity)
+    type Positions = &'static [(i32, i32, u128)];
+
+    /// Overlapping ranges around the current price
+    const NESTED: Positions = &[
+        (-6400, 6400, 1_000_000_000_000),
+        (-640, 1280, 2_000_000_000_000),
+        (-1920, -1280, 500_000_000_000),
+        (2560, 3840, 3_000_000_000_000),
+    ];
+
+    /// Two ranges with no liquidity between or beyond them
+    const GAPPED: Positions = &[
+        (-1280, 640, 1_000_000_000_000),
+        (1920, 3200, 1_000_000_000_000),
+        (-4480, -3200, 1_000_000_000_000),
+    ];
+
+    struct ClockStubs;
+
+    impl SyscallStubs for ClockStubs {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe {
+                *(var_addr as *mut Clock) = Clock::default();
+            }
+            solana_program::entrypoint::SUCCESS
+        }
+    }
+
+    /// `execute_swap_steps` reads the clock for JIT, which off-chain needs stubs
+    fn install_clock() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            set_syscall_stubs(Box::new(ClockStubs));
+        });
+    }
+
+    struct Scenario {
+        market_key: Pubkey,
+        market: Market,
+        /// Serialized tick array accounts covering both bounds
+        tick_arrays: Vec<Vec<u8>>,
+    }
+
+    impl Scenario {
+        fn new(positions: Positions, tick: i32, fee_bps: u16) -> Self {
+            let market_key = Pubkey::new_unique();
+            let spacing = TICK_SPACING as i32;
+            let array_ticks = TICK_ARRAY_SIZE as i32 * spacing;
+
+            let first = get_tick_array_start_index(LOWER_BOUND, TICK_SPACING);
+            let last = get_tick_array_start_index(UPPER_BOUND, TICK_SPACING);
+            let mut arrays: Vec<TickArray> = (first..=last)
+                .step_by(array_ticks as usize)
+                .map(|start| {
+                    let mut array: TickArray = bytemuck::Zeroable::zeroed();
+                    array.market = market_key;
+                    array.start_tick_index = start;
+                    array
+                })
+                .collect();
+
+            let mut liquidity = 0u128;
+            for &(lower, upper, amount) in positions {
+                for (index, net) in [(lower, amount as i128), (upper, -(amount as i128))] {
+                    let start = get_tick_array_start_index(index, TICK_SPACING);
+                    let array = &mut arrays[((start - first) / array_ticks) as usize];
+                    let slot = &mut array.ticks[((index - start) / spacing) as usize];
+                    slot.liquidity_net += net;
+                    slot.liquidity_gross += amount;
+                    slot.initialized = 1;
+                    array.initialized_tick_count += 1;
+                }
+                if lower <= tick && tick < upper {
+                    liquidity += amount;
+                }
+            }
+
+            let tick_arrays = arrays
+                .iter()
+                .map(|array| {
+                    let mut data = TickArray::DISCRIMINATOR.to_vec();
+                    data.extend_from_slice(bytemuck::bytes_of(array));
+                    data
+                })
+                .collect();
+
+            Self {
+                market_key,
+                market: market(tick, liquidity, fee_bps),
+                tick_arrays,
+            }
+        }
+
+        fn market_state(&self) -> MarketState {
+            MarketState::from_market(self.market_key, &self.market)
+        }
+
+        fn tick_array_loader(&self) -> TickArrayLoader {
+            let mut loader = TickArrayLoader::new();
+            for data in &self.tick_arrays {
+                loader.add_parsed_array(parse_tick_array_auto(data, TICK_SPACING).unwrap());
+            }
+            loader
+        }
+
+        /// Run the program's swap loop over the tick array accounts
+        fn on_chain_swap(&self, amount_in: u64, zero_for_one: bool) -> Option<SwapState> {
+            install_clock();
+
+            // The swap borrows its accounts for the life of the program, so
+            // leak them rather than fight the lifetimes
+            let owner: &'static Pubkey = Box::leak(Box::new(feels::ID));
+            let accounts: Vec<AccountInfo<'static>> = self
+                .tick_arrays
+                .iter()
+                .map(|data| {
+                    AccountInfo::new(
+                        Box::leak(Box::new(Pubkey::new_unique())),
+                        false,
+                        true,
+                        Box::leak(Box::new(1_000_000_000u64)),
+                        aligned_account_data(data),
+                        owner,
+                        false,
+                        0,
+                    )
+                })
+                .collect();
+            let accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.into_boxed_slice());
+
+            let params = SwapParams {
+                amount_in,
+                minimum_amount_out: 0,
+                max_ticks_crossed: 0,
+                max_total_fee_bps: 0,
+            };
+            let direction = if zero_for_one {
+                SwapDirection::ZeroForOne
+            } else {
+                SwapDirection::OneForZero
+            };
+            let state = SwapState::new(
+                amount_in,
+                self.market.sqrt_price,
+                self.market.current_tick,
+                self.market.liquidity,
+            );
+
+            execute_swap_steps(
+                accounts,
+                &self.market_key,
+                &params,
+                &self.market,
+                &mut buffer(),
+                state,
+                direction,
+                zero_for_one,
+                false,
+                &Pubkey::new_unique(),
+            )
+            .ok()
+        }
+    }
+
+    /// Leaked copy of `data` laid out as the runtime does: the zero-copy
+    /// body after the 8-byte discriminator starts 16-byte aligned
+    fn aligned_account_data(data: &[u8]) -> &'static mut [u8] {
+        let words = Box::leak(vec![0u128; (data.len() + 8).div_ceil(16)].into_boxed_slice());
+        let bytes = &mut bytemuck::cast_slice_mut::<u128, u8>(words)[8..8 + data.len()];
+        bytes.copy_from_slice(data);
+        bytes
+    }
+
+    fn market(tick: i32, liquidity: u128, fee_bps: u16) -> Market {
+        Market {
+            version: 1,
+            is_initialized: true,
+            is_paused: false,
+            token_0: Pubkey::new_unique(),
+            token_1: Pubkey::new_unique(),
+            feelssol_mint: Pubkey::new_unique(),
+            token_0_type: feels::state::TokenType::Spl,
+            token_1_type: feels::state::TokenType::Spl,
+            token_0_origin: feels::state::TokenOrigin::ProtocolMinted,
+            token_1_origin: feels::state::TokenOrigin::External,
+            sqrt_price: sqrt_price_from_tick(tick).unwrap(),
+            liquidity,
+            current_tick: tick,
+            tick_spacing: TICK_SPACING,
+            global_lower_tick: LOWER_BOUND,
+            global_upper_tick: UPPER_BOUND,
+            floor_liquidity: 0,
+            fee_growth_global_0_x64: 0,
+            fee_growth_global_1_x64: 0,
+            base_fee_bps: fee_bps,
+            buffer: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            last_epoch_update: 0,
+            epoch_number: 0,
+            oracle: Pubkey::new_unique(),
+            oracle_bump: 255,
+            policy: feels::state::PolicyV1::default(),
+            market_authority_bump: 254,
+            vault_0_bump: 253,
+            vault_1_bump: 252,
+            reentrancy_guard: false,
+            initial_liquidity_deployed: true,
+            jit_enabled: false,
+            jit_base_cap_bps: 300,
+            jit_per_slot_cap_bps: 500,
+            jit_concentration_width: 100,
+            jit_max_multiplier: 10,
+            jit_drain_protection_bps: 7000,
+            jit_circuit_breaker_bps: 3000,
+            floor_tick: MIN_TICK,
+            floor_buffer_ticks: 100,
+            last_floor_ratchet_ts: 0,
+            floor_cooldown_secs: 60,
+            steady_state_seeded: false,
+            cleanup_complete: false,
+            vault_0: Pubkey::new_unique(),
+            vault_1: Pubkey::new_unique(),
+            hub_protocol: None,
+            fee_growth_global_0: 0,
+            fee_growth_global_1: 0,
+            phase: 0,
+            phase_start_slot: 0,
+            phase_start_timestamp: 0,
+            last_phase_transition_slot: 0,
+            last_phase_trigger: 0,
+            total_volume_token_0: 0,
+            total_volume_token_1: 0,
+            rolling_buy_volume: 0,
+            rolling_sell_volume: 0,
+            rolling_total_volume: 0,
+            rolling_window_start_slot: 0,
+            tick_snapshot_1hr: 0,
+            last_snapshot_timestamp: 0,
+            swap_volume_slot: 0,
+            swap_slot_volume_feelssol: 0,
+            _reserved: [0; 1],
+        }
+    }
+
+    /// Empty buffer, so no JIT liquidity joins the swap
+    fn buffer() -> Buffer {
+        Buffer {
+            market: Pubkey::default(),
+            authority: Pubkey::default(),
+            feelssol_mint: Pubkey::default(),
+            fees_token_0: 0,
+            fees_token_1: 0,
+            tau_spot: 0,
+            tau_time: 0,
+            tau_leverage: 0,
+            floor_tick_spacing: 0,
+            floor_placement_threshold: 0,
+            last_floor_placement: 0,
+            last_rebase: 0,
+            total_distributed: 0,
+            buffer_authority_bump: 0,
+            jit_last_slot: 0,
+            jit_slot_used_q: 0,
+            jit_rolling_consumption: 0,
+            jit_rolling_window_start: 0,
+            jit_last_heavy_usage_slot: 0,
+            jit_total_consumed_epoch: 0,
+            initial_tau_spot: 0,
+            protocol_owned_override: 0,
+            pomm_position_count: 0,
+            _padding: [0; 7],
+        }
+    }
+
+    /// Compare one swap, returning how many ticks the program crossed and
+    /// whether it stopped at a bound with input left over
+    fn assert_parity(scenario: &Scenario, amount_in: u64, zero_for_one: bool) -> (u8, bool) {
+        let market = scenario.market_state();
+        let tick_arrays = scenario.tick_array_loader();
+        let simulated =
+            SwapSimulator::new(&market, &tick_arrays).simulate_swap(amount_in, zero_for_one);
+        let case = format!(
+            "tick {} liquidity {} fee {} amount {} zero_for_one {}",
+            market.current_tick, market.liquidity, market.fee_bps, amount_in, zero_for_one
+        );
+
+        let Some(expected) = scenario.on_chain_swap(amount_in, zero_for_one) else {
+            assert!(
+                simulated.is_err(),
+                "simulator quoted a failing swap: {}",
+                case
+            );
+            return (0, false);
+        };
+        let simulated = simulated.unwrap_or_else(|e| panic!("{}: {}", case, e));
+
+        assert_eq!(
+            simulated.amount_out, expected.amount_out,
+            "amount out: {}",
+            case
+        );
+        assert_eq!(simulated.fee_paid, expected.total_fee_paid, "fee: {}", case);
+        assert_eq!(
+            simulated.end_sqrt_price, expected.sqrt_price,
+            "sqrt price: {}",
+            case
+        );
+        assert_eq!(
+            simulated.end_tick, expected.current_tick,
+            "end tick: {}",
+            case
+        );
+        assert_eq!(
+            simulated.ticks_crossed, expected.ticks_crossed,
+            "ticks crossed: {}",
+            case
+        );
+
+        (expected.ticks_crossed, expected.amount_remaining > 0)
+    }
+
+    fn assert_parity_over(positions: Positions, ticks: &[i32]) -> (u8, bool) {
+        let mut most_crossed = 0;
+        let mut reached_bound = false;
+        for &tick in ticks {
+            for fee_bps in FEES_BPS {
+                let scenario = Scenario::new(positions, tick, fee_bps);
+                for amount_in in AMOUNTS {
+                    for zero_for_one in [true, false] {
+                        let (crossed, at_bound) = assert_parity(&scenario, amount_in, zero_for_one);
+                        most_crossed = most_crossed.max(crossed);
+                        reached_bound |= at_bound;
+                    }
+                }
+            }
+        }
+        (most_crossed, reached_bound)
+    }
+
+    #[test]
+    fn test_simulator_matches_program_across_initialized_ticks() {
+        // Aligned and unaligned starting ticks, on both sides of zero
+        let (most_crossed, reached_bound) = assert_parity_over(NESTED, &[0, 300, -100, -1000]);
+
+        // The larger swaps cross several ranges and run into the bounds
+        assert!(most_crossed >= 3, "crossed at most {} ticks", most_crossed);
+        assert!(reached_bound);
+    }
+
+    #[test]
+    fn test_simulator_matches_program_across_empty_ranges() {
+        let (most_crossed, reached_bound) = assert_parity_over(GAPPED, &[0, -700, 500]);
+
+        assert!(most_crossed >= 3, "crossed at most {} ticks", most_crossed);
+        assert!(reached_bound);
+    }
+
+    #[test]
+    fn test_no_output_without_liquidity() {
+        let scenario = Scenario::new(GAPPED, 1000, 30);
+        let market = scenario.market_state();
+        assert_eq!(market.liquidity, 0);
+
+        let tick_arrays = scenario.tick_array_loader();
+        let simulated = SwapSimulator::new(&market, &tick_arrays)
+            .simulate_swap(100_000, true)
+            .unwrap();
+
+        assert_eq!(simulated.amount_out, 0);
+        assert_eq!(simulated.fee_paid, 0);
+        assert_eq!(simulated.end_sqrt_price, market.sqrt_price);
+        assert_eq!(simulated.end_tick, market.current_tick);
+    }
+}
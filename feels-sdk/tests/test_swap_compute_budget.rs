@@ -0,0 +1,49 @@
+//! Test SDK swap compute budget sizing
+
+#[cfg(test)]
+mod tests {
+    use feels_sdk::{
+        core::constants::MAX_COMPUTE_UNIT_LIMIT,
+        instructions::{swap_compute_unit_limit, SwapInstructionBuilder, SWAP_BASE_COMPUTE_UNITS},
+        program_id,
+    };
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+
+    #[test]
+    fn test_deep_swap_gets_higher_limit() {
+        let shallow = swap_compute_unit_limit(1);
+        let deep = swap_compute_unit_limit(20);
+
+        assert!(shallow >= SWAP_BASE_COMPUTE_UNITS);
+        assert!(deep > shallow);
+        assert!(deep <= MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_limit_clamped_to_max() {
+        assert_eq!(swap_compute_unit_limit(u32::MAX), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_compute_budget_override() {
+        let builder = SwapInstructionBuilder::new(program_id());
+
+        let sized = builder.compute_budget(20, None);
+        assert_eq!(
+            sized,
+            ComputeBudgetInstruction::set_compute_unit_limit(swap_compute_unit_limit(20))
+        );
+
+        let overridden = builder.compute_budget(20, Some(250_000));
+        assert_eq!(
+            overridden,
+            ComputeBudgetInstruction::set_compute_unit_limit(250_000)
+        );
+
+        let clamped = builder.compute_budget(0, Some(u32::MAX));
+        assert_eq!(
+            clamped,
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT)
+        );
+    }
+}
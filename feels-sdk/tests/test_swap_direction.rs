@@ -0,0 +1,41 @@
+//! Test inferring swap direction from the input mint
+
+#[cfg(test)]
+mod tests {
+    use feels_sdk::{MarketInfo, SdkError, SwapDirection};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn market() -> MarketInfo {
+        MarketInfo {
+            address: Pubkey::new_unique(),
+            token_0: Pubkey::new_unique(),
+            token_1: Pubkey::new_unique(),
+            sqrt_price: 1u128 << 64,
+            liquidity: 0,
+            current_tick: 0,
+            base_fee_bps: 30,
+            tick_spacing: 10,
+            is_paused: false,
+        }
+    }
+
+    #[test]
+    fn test_infer_both_directions() {
+        let market = market();
+
+        let direction = SwapDirection::infer(&market, &market.token_0).unwrap();
+        assert_eq!(direction, SwapDirection::ZeroForOne);
+        assert!(direction.is_token_0_in());
+
+        let direction = SwapDirection::infer(&market, &market.token_1).unwrap();
+        assert_eq!(direction, SwapDirection::OneForZero);
+        assert!(!direction.is_token_0_in());
+    }
+
+    #[test]
+    fn test_infer_rejects_mint_outside_pair() {
+        let market = market();
+        let result = SwapDirection::infer(&market, &Pubkey::new_unique());
+        assert!(matches!(result, Err(SdkError::InvalidParameters(_))));
+    }
+}
@@ -0,0 +1,120 @@
+//! Test the step-by-step swap explanation
+
+#[cfg(test)]
+mod tests {
+    use feels_sdk::{
+        MarketState, ParsedTickArray, SdkError, SwapDirection, SwapSimulator, TickArrayFormat,
+        TickArrayLoader,
+    };
+    use solana_sdk::pubkey::Pubkey;
+    use std::collections::HashMap;
+
+    fn market(liquidity: u128) -> MarketState {
+        MarketState {
+            market_key: Pubkey::new_unique(),
+            token_0: Pubkey::new_unique(),
+            token_1: Pubkey::new_unique(),
+            sqrt_price: 1u128 << 64,
+            current_tick: 700,
+            liquidity,
+            fee_bps: 30,
+            tick_spacing: 10,
+            global_lower_tick: -443630,
+            global_upper_tick: 443630,
+            fee_growth_global_0: 0,
+            fee_growth_global_1: 0,
+        }
+    }
+
+    #[test]
+    fn test_explanation_matches_simulation() {
+        let market = market(1_000_000_000);
+        let tick_arrays = TickArrayLoader::new();
+        let simulator = SwapSimulator::new(&market, &tick_arrays);
+
+        let explanation = simulator.explain_swap(&market.token_0, 100_000).unwrap();
+        let simulation = simulator.simulate_swap(100_000, true).unwrap();
+
+        assert_eq!(explanation.direction, SwapDirection::ZeroForOne);
+        assert_eq!(explanation.start_tick, 700);
+        assert_eq!(explanation.amount_out, simulation.amount_out);
+        assert_eq!(explanation.fee_paid, simulation.fee_paid);
+        assert_eq!(explanation.end_sqrt_price, simulation.end_sqrt_price);
+
+        // Steps account for the whole input and output
+        assert!(!explanation.steps.is_empty());
+        let step_in: u64 = explanation.steps.iter().map(|s| s.amount_in + s.fee).sum();
+        let step_out: u64 = explanation.steps.iter().map(|s| s.amount_out).sum();
+        assert_eq!(step_in, 100_000);
+        assert_eq!(step_out, explanation.amount_out);
+
+        // Tick 700 at spacing 10 lives in the array starting at 640
+        assert_eq!(explanation.steps[0].tick_array_start, 640);
+        assert_eq!(explanation.steps[0].liquidity, 1_000_000_000);
+
+        let text = explanation.to_string();
+        assert!(text.contains("Start: tick 700"));
+        assert!(text.contains("Step 1: tick array 640"));
+        assert!(text.contains(&format!("received {}", explanation.amount_out)));
+    }
+
+    #[test]
+    fn test_explanation_has_a_step_per_tick_crossed() {
+        let mut market = market(1_000_000_000);
+        market.current_tick = 0;
+
+        // A position ending at -100 drops out once the price crosses it
+        let mut tick_arrays = TickArrayLoader::new();
+        tick_arrays.add_parsed_array(ParsedTickArray {
+            format: TickArrayFormat::V1,
+            market: market.market_key,
+            start_tick_index: -640,
+            tick_spacing: 10,
+            initialized_ticks: HashMap::from([(-100, 400_000_000), (-300, 600_000_000)]),
+            initialized_count: Some(2),
+        });
+        let simulator = SwapSimulator::new(&market, &tick_arrays);
+
+        let explanation = simulator.explain_swap(&market.token_0, 20_000_000).unwrap();
+        let simulation = simulator.simulate_swap(20_000_000, true).unwrap();
+        assert_eq!(simulation.ticks_crossed, 2);
+        assert_eq!(explanation.steps.len(), 3);
+
+        let ends: Vec<i32> = explanation.steps.iter().map(|s| s.end_tick).collect();
+        assert_eq!(&ends[..2], &[-100, -300]);
+        let liquidity: Vec<u128> = explanation.steps.iter().map(|s| s.liquidity).collect();
+        assert_eq!(liquidity, vec![1_000_000_000, 600_000_000, 0]);
+
+        // Each step picks up where the previous one stopped
+        for pair in explanation.steps.windows(2) {
+            assert_eq!(pair[0].end_tick, pair[1].start_tick);
+            assert_eq!(pair[0].end_sqrt_price, pair[1].start_sqrt_price);
+        }
+        let step_out: u64 = explanation.steps.iter().map(|s| s.amount_out).sum();
+        assert_eq!(step_out, explanation.amount_out);
+        assert!(explanation.to_string().contains("Step 3:"));
+    }
+
+    #[test]
+    fn test_explanation_without_liquidity() {
+        let market = market(0);
+        let tick_arrays = TickArrayLoader::new();
+        let simulator = SwapSimulator::new(&market, &tick_arrays);
+
+        let explanation = simulator.explain_swap(&market.token_1, 100_000).unwrap();
+        assert_eq!(explanation.direction, SwapDirection::OneForZero);
+        assert!(explanation.steps.is_empty());
+        assert_eq!(explanation.amount_out, 0);
+        assert!(explanation.to_string().contains("No liquidity in range"));
+    }
+
+    #[test]
+    fn test_explanation_rejects_foreign_mint() {
+        let market = market(1_000_000_000);
+        let tick_arrays = TickArrayLoader::new();
+        let simulator = SwapSimulator::new(&market, &tick_arrays);
+
+        let result = simulator.explain_swap(&Pubkey::new_unique(), 100_000);
+        assert!(matches!(result, Err(SdkError::InvalidParameters(_))));
+    }
+}
@@ -0,0 +1,61 @@
+//! Test that the tick array parser reads accounts as the program writes them
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+    use feels::state::TickArray;
+    use feels_sdk::{parse_tick_array_auto, TickArrayFormat};
+    use solana_sdk::pubkey::Pubkey;
+
+    /// Account data for `array`: discriminator followed by the zero-copy body
+    fn serialize(array: &TickArray) -> Vec<u8> {
+        let mut data = TickArray::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(array));
+        data
+    }
+
+    #[test]
+    fn test_v1_format_matches_program_discriminator() {
+        assert_eq!(TickArrayFormat::V1.discriminator, TickArray::DISCRIMINATOR);
+        assert!(TickArrayFormat::V1.calculate_total_size() <= TickArray::LEN);
+    }
+
+    #[test]
+    fn test_parses_serialized_tick_array() {
+        let market = Pubkey::new_unique();
+        let mut array: TickArray = bytemuck::Zeroable::zeroed();
+        array.market = market;
+        array.start_tick_index = -640;
+        array.ticks[0].liquidity_net = 1_000;
+        array.ticks[0].liquidity_gross = 1_000;
+        array.ticks[0].initialized = 1;
+        array.ticks[63].liquidity_net = -1_000;
+        array.ticks[63].liquidity_gross = 1_000;
+        array.ticks[63].initialized = 1;
+        // Initialized with no net liquidity still counts
+        array.ticks[7].liquidity_gross = 500;
+        array.ticks[7].initialized = 1;
+        array.initialized_tick_count = 3;
+
+        let data = serialize(&array);
+        assert_eq!(data.len(), TickArray::LEN);
+
+        let parsed = parse_tick_array_auto(&data, 10).unwrap();
+        assert_eq!(parsed.format, TickArrayFormat::V1);
+        assert_eq!(parsed.market, market);
+        assert_eq!(parsed.start_tick_index, -640);
+        assert_eq!(parsed.initialized_count, Some(3));
+        assert_eq!(parsed.initialized_ticks.len(), 3);
+        assert_eq!(parsed.initialized_ticks[&-640], 1_000);
+        assert_eq!(parsed.initialized_ticks[&-570], 0);
+        assert_eq!(parsed.initialized_ticks[&-10], -1_000);
+    }
+
+    #[test]
+    fn test_rejects_other_accounts() {
+        let array: TickArray = bytemuck::Zeroable::zeroed();
+        let mut data = serialize(&array);
+        data[..8].copy_from_slice(&feels::state::Market::DISCRIMINATOR[..8]);
+        assert!(parse_tick_array_auto(&data, 10).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Test SDK batched transaction building
+
+#[cfg(test)]
+mod tests {
+    use feels_sdk::{
+        instructions::{
+            DeployInitialLiquidityParams, InitializeMarketParams, LiquidityInstructionBuilder,
+        },
+        program_id, SdkError, TransactionBuilder,
+    };
+    use solana_sdk::{
+        compute_budget, hash::Hash, instruction::Instruction, pubkey::Pubkey, signature::Keypair,
+        signer::Signer,
+    };
+
+    fn market_flow(creator: Pubkey) -> (Instruction, Instruction) {
+        let builder = LiquidityInstructionBuilder::new(program_id());
+        let feelssol_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let (token_0, token_1) = if feelssol_mint < other_mint {
+            (feelssol_mint, other_mint)
+        } else {
+            (other_mint, feelssol_mint)
+        };
+
+        let initialize = builder
+            .initialize_market(
+                creator,
+                token_0,
+                token_1,
+                InitializeMarketParams {
+                    base_fee_bps: 30,
+                    tick_spacing: 10,
+                    initial_sqrt_price: 79228162514264337593543950336u128,
+                    initial_buy_feelssol_amount: 0,
+                },
+            )
+            .unwrap();
+        let market = initialize.accounts[1].pubkey;
+        let deploy = builder
+            .deploy_initial_liquidity(
+                creator,
+                market,
+                DeployInitialLiquidityParams::remaining(100),
+                feelssol_mint,
+                other_mint,
+            )
+            .unwrap();
+        (initialize, deploy)
+    }
+
+    #[test]
+    fn test_two_instruction_transaction() {
+        let payer = Keypair::new();
+        let creator = Keypair::new();
+        let (initialize, deploy) = market_flow(creator.pubkey());
+        let accounts: Vec<Pubkey> = initialize
+            .accounts
+            .iter()
+            .chain(&deploy.accounts)
+            .map(|meta| meta.pubkey)
+            .collect();
+
+        let tx = TransactionBuilder::new(&payer)
+            .with_compute_budget(400_000, None)
+            .add_instruction(initialize)
+            .add_signer(&creator)
+            .add_instruction(deploy)
+            // The creator signs both instructions but is only added once
+            .add_signer(&creator)
+            .build(Hash::new_unique())
+            .unwrap();
+
+        let message = &tx.message;
+        assert_eq!(message.instructions.len(), 3);
+        assert_eq!(
+            message.account_keys[message.instructions[0].program_id_index as usize],
+            compute_budget::id()
+        );
+
+        // Payer first, then the creator; nobody else signs
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.account_keys[0], payer.pubkey());
+        assert_eq!(message.account_keys[1], creator.pubkey());
+        assert_eq!(tx.signatures.len(), 2);
+        assert!(tx.verify().is_ok());
+
+        // Every account of both instructions is in the message exactly once
+        for account in &accounts {
+            assert_eq!(
+                message
+                    .account_keys
+                    .iter()
+                    .filter(|k| *k == account)
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn test_payer_can_be_the_only_signer() {
+        let creator = Keypair::new();
+        let (initialize, deploy) = market_flow(creator.pubkey());
+
+        let tx = TransactionBuilder::new(&creator)
+            .add_instructions([initialize, deploy])
+            .add_signer(&creator)
+            .build(Hash::new_unique())
+            .unwrap();
+
+        assert_eq!(tx.message.header.num_required_signatures, 1);
+        assert!(tx.verify().is_ok());
+    }
+
+    #[test]
+    fn test_missing_signer_rejected() {
+        let payer = Keypair::new();
+        let creator = Keypair::new();
+        let (initialize, _) = market_flow(creator.pubkey());
+
+        let result = TransactionBuilder::new(&payer)
+            .add_instruction(initialize)
+            .build(Hash::new_unique());
+
+        assert!(matches!(result, Err(SdkError::InvalidParameters(_))));
+    }
+}
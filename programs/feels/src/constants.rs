@@ -22,6 +22,7 @@ pub const METADATA_SEED: &[u8] = b"metadata";
 pub const TOKEN_DECIMALS: u8 = 6;
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000 * 1_000_000; // 1B tokens with 6 decimals
 pub const MIN_LAUNCH_AMOUNT: u64 = 250_000_000 * 1_000_000; // 250M tokens with 6 decimals
+pub const DEPLOYMENT_PERCENTAGE: u8 = 100; // Share of escrow deployed as initial liquidity
 
 // Fee constants
 pub const MAX_FEE_BPS: u16 = 1000; // 10%
@@ -46,6 +47,11 @@ pub const BASIS_POINTS_DIVISOR: u16 = 10000;
 /// but still consume on-chain resources. Set to 1000 units.
 pub const MIN_LIQUIDITY: u128 = 1000;
 
+/// Minimum liquidity for a user position
+/// Every position initializes two ticks that swaps have to walk, so user
+/// positions must be large enough to be worth that cost.
+pub const MIN_POSITION_LIQUIDITY: u128 = 100_000;
+
 /// Maximum liquidity allowed per position
 /// This prevents overflow in liquidity calculations.
 pub const MAX_LIQUIDITY: u128 = u128::MAX / 2;
@@ -55,12 +61,125 @@ pub const MAX_LIQUIDITY: u128 = u128::MAX / 2;
 /// This prevents griefing attacks where attackers create many empty ticks
 pub const MAX_TICKS_CROSSED: u8 = 200;
 
-/// Size of tick array - matches Orca Whirlpool standard
-pub const TICK_ARRAY_SIZE: i32 = 88;
+/// Number of ticks per tick array account (see `state::TICK_ARRAY_SIZE`)
+pub const TICK_ARRAY_SIZE: i32 = crate::state::TICK_ARRAY_SIZE as i32;
 
 // Protocol token registry
 pub const PROTOCOL_TOKEN_SEED: &[u8] = b"protocol_token";
 
+// PDA seed registry
+/// One component of a PDA seed pattern
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedComponent {
+    /// Fixed byte string
+    Literal(&'static [u8]),
+    /// 32-byte public key supplied by the caller
+    Pubkey(&'static str),
+    /// Little-endian i32 supplied by the caller
+    I32(&'static str),
+}
+
+/// Named seed pattern for a program PDA
+#[derive(Clone, Copy, Debug)]
+pub struct PdaSeeds {
+    pub name: &'static str,
+    pub components: &'static [SeedComponent],
+}
+
+/// Every PDA seed pattern used by the program
+///
+/// Authoritative list for off-chain clients; `utils::derive_pda` derives any
+/// entry by name from its dynamic inputs.
+pub const PDA_SEEDS: &[PdaSeeds] = {
+    use SeedComponent::{Literal, Pubkey, I32};
+    &[
+        PdaSeeds {
+            name: "market",
+            components: &[Literal(MARKET_SEED), Pubkey("token_0"), Pubkey("token_1")],
+        },
+        PdaSeeds {
+            name: "market_authority",
+            components: &[Literal(MARKET_AUTHORITY_SEED), Pubkey("market")],
+        },
+        PdaSeeds {
+            name: "vault",
+            components: &[Literal(VAULT_SEED), Pubkey("market"), Pubkey("mint")],
+        },
+        PdaSeeds {
+            name: "buffer",
+            components: &[Literal(BUFFER_SEED), Pubkey("market")],
+        },
+        PdaSeeds {
+            name: "oracle",
+            components: &[Literal(b"oracle"), Pubkey("market")],
+        },
+        PdaSeeds {
+            name: "tick_array",
+            components: &[
+                Literal(TICK_ARRAY_SEED),
+                Pubkey("market"),
+                I32("start_tick_index"),
+            ],
+        },
+        PdaSeeds {
+            name: "position",
+            components: &[Literal(POSITION_SEED), Pubkey("position_mint")],
+        },
+        PdaSeeds {
+            name: "epoch_params",
+            components: &[Literal(EPOCH_PARAMS_SEED), Pubkey("market")],
+        },
+        PdaSeeds {
+            name: "tranche_plan",
+            components: &[Literal(crate::state::TranchePlan::SEED), Pubkey("market")],
+        },
+        PdaSeeds {
+            name: "escrow",
+            components: &[Literal(ESCROW_SEED), Pubkey("token_mint")],
+        },
+        PdaSeeds {
+            name: "escrow_authority",
+            components: &[Literal(ESCROW_AUTHORITY_SEED), Pubkey("escrow")],
+        },
+        PdaSeeds {
+            name: "protocol_token",
+            components: &[Literal(PROTOCOL_TOKEN_SEED), Pubkey("token_mint")],
+        },
+        PdaSeeds {
+            name: "feels_hub",
+            components: &[Literal(FEELS_HUB_SEED), Pubkey("feelssol_mint")],
+        },
+        PdaSeeds {
+            name: "jitosol_vault",
+            components: &[Literal(JITOSOL_VAULT_SEED), Pubkey("feelssol_mint")],
+        },
+        PdaSeeds {
+            name: "vault_authority",
+            components: &[Literal(VAULT_AUTHORITY_SEED), Pubkey("feelssol_mint")],
+        },
+        PdaSeeds {
+            name: "mint_authority",
+            components: &[Literal(MINT_AUTHORITY_SEED), Pubkey("feelssol_mint")],
+        },
+        PdaSeeds {
+            name: "protocol_config",
+            components: &[Literal(crate::state::ProtocolConfig::SEED)],
+        },
+        PdaSeeds {
+            name: "protocol_oracle",
+            components: &[Literal(crate::state::ProtocolOracle::SEED)],
+        },
+        PdaSeeds {
+            name: "safety_controller",
+            components: &[Literal(crate::state::SafetyController::SEED)],
+        },
+        PdaSeeds {
+            name: "pool_registry",
+            components: &[Literal(crate::state::PoolRegistry::SEED)],
+        },
+    ]
+};
+
 // Floor liquidity constants
 /// Minimum threshold for floor liquidity placement (100 tokens with 6 decimals)
 /// This prevents griefing by requiring economically significant amounts
@@ -85,6 +204,10 @@ pub const POMM_MIN_WIDTH: i32 = 10;
 /// POMM maximum tick width
 pub const POMM_MAX_WIDTH: i32 = 2000;
 
+// Swap circuit breaker constants
+/// TWAP window the swap circuit breaker compares spot against, in seconds
+pub const SWAP_TWAP_SECONDS: u32 = 300;
+
 // Metaplex Token Metadata Program ID
 // For localnet testing, use a default ID that can be overridden
 #[cfg(feature = "localnet")]
@@ -303,4 +303,28 @@ pub enum FeelsError {
 
     #[msg("Invalid update")]
     InvalidUpdate,
+
+    #[msg("Invalid deployment amount")]
+    InvalidDeploymentAmount,
+
+    #[msg("Deployment exceeds liquidity commitment")]
+    DeploymentExceedsCommitment,
+
+    #[msg("Reserves do not back the circulating supply at the floor price")]
+    FloorBackingViolated,
+
+    #[msg("Account uses an older layout and must be migrated")]
+    AccountNeedsMigration,
+
+    #[msg("Swap would move the price too far from the oracle TWAP")]
+    SpotTwapDeviationExceeded,
+
+    #[msg("Swap volume cap for this slot exceeded")]
+    SlotSwapVolumeCapExceeded,
+
+    #[msg("Position deposit rounds to zero on one side")]
+    PositionAmountRoundsToZero,
+
+    #[msg("Initial liquidity has not been fully deployed")]
+    InitialLiquidityNotDeployed,
 }
@@ -224,6 +224,7 @@ pub struct TokenMinted {
     pub creator: Pubkey,
     pub ticker: String,
     pub name: String,
+    pub decimals: u8,
     pub total_supply: u64,
     pub buffer_amount: u64,
     pub creator_amount: u64,
@@ -324,9 +325,8 @@ pub struct TokenDestroyed {
     pub destroyer: Pubkey,
     pub created_at: i64,
     pub destroyed_at: i64,
+    pub creator: Pubkey,
     pub mint_fee_returned: u64,
-    pub destroyer_reward: u64,
-    pub treasury_amount: u64,
 }
 
 /// Event emitted when a pool is registered
@@ -376,3 +376,37 @@ pub struct MarketPhaseTransitioned {
     pub timestamp: i64,
     pub slot: u64,
 }
+
+/// Pool trading status changed (Normal <-> Disabled)
+#[event]
+pub struct PoolStatusChanged {
+    pub market: Pubkey,
+    pub old_status: u8,
+    pub new_status: u8,
+    pub reason: u8,
+    pub trigger: u8,
+    pub stress: crate::state::PoolStressComponents,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Program account grown to its current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Unreferenced ticks cleared from a tick array by the cleanup crank
+#[event]
+pub struct TickArrayCleaned {
+    pub market: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+    pub ticks_cleared: u16,
+    pub crank: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,52 @@
+//! Permissionless tick array cleanup crank
+//!
+//! Closing positions leaves their boundary ticks initialized with zero
+//! liquidity, and swaps still stop at (and pay CU for) every one of them.
+//! This crank deinitializes ticks no open position references.
+//!
+//! The array itself always stays open: swaps need every array along the
+//! price path, and there is no permissionless way to recreate one.
+
+use crate::{
+    error::FeelsError,
+    events::TickArrayCleaned,
+    state::{Market, TickArray},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CleanupTickArray<'info> {
+    /// Anyone may crank
+    pub crank: Signer<'info>,
+
+    #[account(constraint = market.is_initialized @ FeelsError::MarketNotInitialized)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = tick_array.load()?.market == market.key() @ FeelsError::InvalidTickArray,
+    )]
+    pub tick_array: AccountLoader<'info, TickArray>,
+}
+
+pub fn cleanup_tick_array(ctx: Context<CleanupTickArray>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(!market.reentrancy_guard, FeelsError::ReentrancyDetected);
+
+    let (start_tick_index, ticks_cleared) = {
+        let mut tick_array = ctx.accounts.tick_array.load_mut()?;
+        let cleared = tick_array.clear_unreferenced_ticks();
+        (tick_array.start_tick_index, cleared)
+    };
+
+    emit!(TickArrayCleaned {
+        market: market.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index,
+        ticks_cleared,
+        crank: ctx.accounts.crank.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -46,7 +46,7 @@ use crate::{
     error::FeelsError,
     events::{PositionOperation, PositionUpdated},
     state::{Market, Position},
-    utils::transfer_from_vault_to_user_unchecked,
+    utils::{fee_growth_delta, transfer_from_vault_to_user_unchecked},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
@@ -211,12 +211,14 @@ fn process_fee_calculation(
 
     // For now, just use the market's global fee growth as a fallback
     // In a real implementation, you'd want to load the tick arrays more efficiently
-    let fee_growth_0_increment = market
-        .fee_growth_global_0_x64
-        .saturating_sub(position.fee_growth_inside_0_last_x64);
-    let fee_growth_1_increment = market
-        .fee_growth_global_1_x64
-        .saturating_sub(position.fee_growth_inside_1_last_x64);
+    let fee_growth_0_increment = fee_growth_delta(
+        market.fee_growth_global_0_x64,
+        position.fee_growth_inside_0_last_x64,
+    );
+    let fee_growth_1_increment = fee_growth_delta(
+        market.fee_growth_global_1_x64,
+        position.fee_growth_inside_1_last_x64,
+    );
 
     // Calculate fees owed increment (simplified calculation)
     let liquidity = position.liquidity;
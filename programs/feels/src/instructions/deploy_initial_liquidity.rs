@@ -3,11 +3,19 @@
 //! Deploys protocol escrow liquidity in an escalating stair pattern (100% of escrow).
 //! Optionally allows the deployer to execute an initial buy at the best price
 //! by including FeelsSOL with the instruction.
+//!
+//! Large launches can deploy in several parts. The first part fixes the
+//! commitment from the escrow balances and the stair layout from the current
+//! tick; each part is reconciled against them in the escrow's
+//! `DeploymentProgress`, and the market only goes live (and opens to swaps)
+//! once the full commitment has been deployed. If the market authority stops
+//! partway, anyone may deploy the outstanding remainder once the token has
+//! expired.
 
 use crate::{
-    constants::{ESCROW_AUTHORITY_SEED, MARKET_AUTHORITY_SEED, VAULT_SEED},
+    constants::{DEPLOYMENT_PERCENTAGE, ESCROW_AUTHORITY_SEED, MARKET_AUTHORITY_SEED, VAULT_SEED},
     error::FeelsError,
-    state::{Market, PreLaunchEscrow, TrancheEntry, TranchePlan},
+    state::{DeploymentProgress, Market, PreLaunchEscrow, TrancheEntry, TranchePlan},
     utils::{liquidity_from_amounts, sqrt_price_from_tick, transfer_from_user_to_vault_unchecked},
 };
 use anchor_lang::prelude::*;
@@ -18,16 +26,19 @@ use spl_token::state::Account as TokenAccountState;
 /// Number of steps in the stair pattern
 const STAIR_STEPS: usize = 10;
 
-/// Percentage of escrow tokens to deploy (100%)
-const DEPLOYMENT_PERCENTAGE: u8 = 100;
-
 /// Deploy initial liquidity parameters
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct DeployInitialLiquidityParams {
-    /// Number of ticks between each stair step
+    /// Number of ticks between each stair step (fixed by the first part)
     pub tick_step_size: i32,
     /// Optional initial buy amount in FeelsSOL (0 = no initial buy)
+    /// Only allowed on the part that completes the deployment
     pub initial_buy_feelssol_amount: u64,
+    /// Protocol token amount to deploy in this part
+    pub token_amount: u64,
+    /// FeelsSOL amount to deploy in this part
+    /// (both amounts 0 = deploy everything still outstanding)
+    pub feelssol_amount: u64,
 }
 
 /// Deploy initial liquidity accounts
@@ -135,9 +146,9 @@ pub struct DeployInitialLiquidity<'info> {
 
     // Remaining accounts: expected TickArray PDAs for any tick ranges touched.
     // The handler will initialize any uninitialized arrays it needs from these.
-    /// Tranche plan PDA (initialized here)
+    /// Tranche plan PDA (initialized by the first part, extended by later parts)
     #[account(
-        init,
+        init_if_needed,
         payer = deployer,
         space = TranchePlan::space_for(STAIR_STEPS),
         seeds = [b"tranche_plan".as_ref(), market.key().as_ref()],
@@ -154,8 +165,23 @@ pub fn deploy_initial_liquidity<'info>(
     // Early validation - fail fast before any state changes
 
     // Validate constraints (moved from struct to save stack space)
+    // Only the market authority deploys, except that a stalled multi-part
+    // deployment can be finished by anyone once the token has expired
+    let is_market_authority = ctx.accounts.deployer.key() == ctx.accounts.market.authority;
+    let expiration_time = ctx
+        .accounts
+        .escrow
+        .created_at
+        .checked_add(ctx.accounts.protocol_config.token_expiration_seconds)
+        .ok_or(FeelsError::MathOverflow)?;
+    let resuming_stalled = !is_market_authority
+        && ctx
+            .accounts
+            .escrow
+            .deployment
+            .is_stalled(Clock::get()?.unix_timestamp, expiration_time);
     require!(
-        ctx.accounts.deployer.key() == ctx.accounts.market.authority,
+        is_market_authority || resuming_stalled,
         FeelsError::UnauthorizedSigner
     );
     require!(
@@ -194,8 +220,15 @@ pub fn deploy_initial_liquidity<'info>(
     let protocol_config = &ctx.accounts.protocol_config;
     let is_protocol_authority = ctx.accounts.deployer.key() == protocol_config.authority;
 
-    // During memecoin phase, only protocol authority can override default tick_step_size
-    if !is_protocol_authority {
+    // During memecoin phase, only protocol authority can override default tick_step_size.
+    // Later parts must repeat the step size recorded by the first part instead.
+    let deployment_started = ctx.accounts.escrow.deployment.is_started();
+    if deployment_started {
+        require!(
+            params.tick_step_size == ctx.accounts.escrow.deployment.tick_step_size,
+            FeelsError::InvalidTickRange
+        );
+    } else if !is_protocol_authority {
         require!(
             params.tick_step_size == protocol_config.default_tick_step_size as i32,
             FeelsError::UnauthorizedSigner
@@ -204,6 +237,8 @@ pub fn deploy_initial_liquidity<'info>(
 
     // Validate initial buy amount if specified
     if params.initial_buy_feelssol_amount > 0 {
+        // The initial buy is reserved for the market authority
+        require!(!resuming_stalled, FeelsError::UnauthorizedSigner);
         crate::utils::validate_swap_amount(params.initial_buy_feelssol_amount, false)?;
     }
 
@@ -219,14 +254,48 @@ pub fn deploy_initial_liquidity<'info>(
         FeelsError::InvalidBuffer
     );
 
-    // 4. Validate sufficient tokens in escrow for deployment
-    let deploy_token_amount =
-        (ctx.accounts.escrow_token_vault.amount * DEPLOYMENT_PERCENTAGE as u64) / 100;
-    let deploy_feelssol_amount =
-        (ctx.accounts.escrow_feelssol_vault.amount * DEPLOYMENT_PERCENTAGE as u64) / 100;
+    // 4. Reconcile this part against the liquidity commitment. The first part
+    // fixes the commitment from the escrow balances and anchors the stair at
+    // the current tick, so every later part lays out the same ranges.
+    if !deployment_started {
+        let committed_token = (ctx.accounts.escrow_token_vault.amount as u128
+            * DEPLOYMENT_PERCENTAGE as u128
+            / 100) as u64;
+        let committed_feelssol = (ctx.accounts.escrow_feelssol_vault.amount as u128
+            * DEPLOYMENT_PERCENTAGE as u128
+            / 100) as u64;
+
+        require!(committed_token > 0, FeelsError::InsufficientBalance);
+        require!(committed_feelssol > 0, FeelsError::InsufficientBalance);
+
+        ctx.accounts.escrow.deployment = DeploymentProgress::new(
+            committed_token,
+            committed_feelssol,
+            ctx.accounts.market.current_tick,
+            params.tick_step_size,
+        );
+    }
+
+    let (deploy_token_amount, deploy_feelssol_amount) = ctx
+        .accounts
+        .escrow
+        .deployment
+        .part_amounts(params.token_amount, params.feelssol_amount);
+    require!(
+        deploy_token_amount > 0 && deploy_feelssol_amount > 0,
+        FeelsError::InvalidDeploymentAmount
+    );
+    let deployment_complete = ctx
+        .accounts
+        .escrow
+        .deployment
+        .record(deploy_token_amount, deploy_feelssol_amount)?;
 
-    require!(deploy_token_amount > 0, FeelsError::InsufficientBalance);
-    require!(deploy_feelssol_amount > 0, FeelsError::InsufficientBalance);
+    // The initial buy trades against the full stair, so it waits for the last part
+    require!(
+        params.initial_buy_feelssol_amount == 0 || deployment_complete,
+        FeelsError::InvalidDeploymentAmount
+    );
 
     // 5. Early validation: If initial buy requested, validate deployer has sufficient balance
     if params.initial_buy_feelssol_amount > 0 {
@@ -264,7 +333,8 @@ pub fn deploy_initial_liquidity<'info>(
         &ctx.accounts.escrow_token_vault,
         &ctx.accounts.escrow_feelssol_vault,
         &ctx.accounts.escrow_authority,
-        params.tick_step_size,
+        deploy_token_amount,
+        deploy_feelssol_amount,
         &ctx.accounts.deployer,
         &ctx.accounts.system_program,
         ctx.remaining_accounts,
@@ -478,6 +548,28 @@ pub fn deploy_initial_liquidity<'info>(
         msg!("Market state updated after initial buy");
     }
 
+    // Initialize TranchePlan PDA with computed ranges + liquidity for crank usage,
+    // or add this part's liquidity to the ranges recorded by earlier parts
+    let tranche_plan = &mut ctx.accounts.tranche_plan;
+    if tranche_plan.entries.is_empty() {
+        tranche_plan.market = ctx.accounts.market.key();
+        tranche_plan.applied = false;
+        tranche_plan.count = STAIR_STEPS as u8;
+        tranche_plan.entries = tranche_entries;
+    } else {
+        merge_tranche_entries(&mut tranche_plan.entries, &tranche_entries)?;
+    }
+
+    if !deployment_complete {
+        let (remaining_token, remaining_feelssol) = ctx.accounts.escrow.deployment.remaining();
+        msg!(
+            "Partial deployment recorded; outstanding: {} token, {} FeelsSOL",
+            remaining_token,
+            remaining_feelssol
+        );
+        return Ok(());
+    }
+
     // Update market to reflect deployment
     let market = &mut ctx.accounts.market;
     market.initial_liquidity_deployed = true;
@@ -515,14 +607,25 @@ pub fn deploy_initial_liquidity<'info>(
         msg!("Mint fee transferred to treasury successfully");
     }
 
-    // Initialize TranchePlan PDA with computed ranges + liquidity for crank usage
-    // Account creation handled by Anchor's init constraint
-    let tranche_plan = &mut ctx.accounts.tranche_plan;
-    tranche_plan.market = ctx.accounts.market.key();
-    tranche_plan.applied = false;
-    tranche_plan.count = STAIR_STEPS as u8;
-    tranche_plan.entries = tranche_entries;
+    Ok(())
+}
 
+/// Add one deployment part's tranche liquidity to the plan recorded so far
+///
+/// Every part must produce the same tick ranges, so a part deployed with a
+/// different step size is rejected rather than silently mixed in.
+pub fn merge_tranche_entries(existing: &mut [TrancheEntry], part: &[TrancheEntry]) -> Result<()> {
+    require!(existing.len() == part.len(), FeelsError::InvalidTickRange);
+    for (entry, added) in existing.iter_mut().zip(part) {
+        require!(
+            entry.tick_lower == added.tick_lower && entry.tick_upper == added.tick_upper,
+            FeelsError::InvalidTickRange
+        );
+        entry.liquidity = entry
+            .liquidity
+            .checked_add(added.liquidity)
+            .ok_or(FeelsError::LiquidityOverflow)?;
+    }
     Ok(())
 }
 
@@ -538,35 +641,25 @@ fn deploy_protocol_stair_pattern<'info>(
     escrow_token_vault: &Account<'info, TokenAccount>,
     escrow_feelssol_vault: &Account<'info, TokenAccount>,
     escrow_authority: &AccountInfo<'info>,
-    tick_step_size: i32,
+    deploy_token_amount: u64,
+    deploy_feelssol_amount: u64,
     _payer: &Signer<'info>,
     _system_program: &Program<'info, System>,
     _remaining_accounts: &'info [AccountInfo<'info>],
     _market_key: Pubkey,
     entries_out: &mut Vec<TrancheEntry>,
 ) -> Result<()> {
-    // Parameters already validated in main handler
-
-    // Get current balances
-    let escrow_token_balance = escrow_token_vault.amount;
-    let escrow_feelssol_balance = escrow_feelssol_vault.amount;
-
-    // Calculate 100% of each token to deploy
-    let deploy_token_amount =
-        (escrow_token_balance as u128 * DEPLOYMENT_PERCENTAGE as u128 / 100) as u64;
-    let deploy_feelssol_amount =
-        (escrow_feelssol_balance as u128 * DEPLOYMENT_PERCENTAGE as u128 / 100) as u64;
-
+    // Parameters already validated in main handler and reconciled against the commitment
     msg!("Deploying protocol liquidity in stair pattern:");
     msg!(
-        "  Token amount: {} (100% of {})",
+        "  Token amount: {} ({} committed)",
         deploy_token_amount,
-        escrow_token_balance
+        escrow.deployment.committed_token
     );
     msg!(
-        "  FeelsSOL amount: {} (100% of {})",
+        "  FeelsSOL amount: {} ({} committed)",
         deploy_feelssol_amount,
-        escrow_feelssol_balance
+        escrow.deployment.committed_feelssol
     );
 
     // Determine if FeelsSOL is token_0 or token_1
@@ -619,8 +712,9 @@ fn deploy_protocol_stair_pattern<'info>(
         transfer_amount,
     )?;
 
-    // Calculate stair pattern positions
-    let current_tick = market.current_tick;
+    // Calculate stair pattern positions from the layout fixed by the first part
+    let anchor_tick = escrow.deployment.anchor_tick;
+    let tick_step_size = escrow.deployment.tick_step_size;
     let tick_spacing = market.tick_spacing as i32;
 
     // Create stair steps above current price
@@ -631,12 +725,12 @@ fn deploy_protocol_stair_pattern<'info>(
     msg!(
         "Creating {} stair steps starting from tick {}",
         STAIR_STEPS,
-        current_tick
+        anchor_tick
     );
 
     for step in 0..STAIR_STEPS {
         // Calculate tick range for this step
-        let tick_lower = current_tick + (step as i32 * tick_step_size);
+        let tick_lower = anchor_tick + (step as i32 * tick_step_size);
         let tick_upper = tick_lower + tick_step_size;
 
         // Ensure ticks are aligned to spacing
@@ -711,7 +805,6 @@ fn deploy_protocol_stair_pattern<'info>(
 
     // Update market liquidity
     market.liquidity = market.liquidity.saturating_add(total_liquidity_added);
-    market.initial_liquidity_deployed = true;
 
     // Note: Buffer updates would need to be done outside this function
     // since buffer is passed as immutable reference
@@ -1,6 +1,10 @@
 //! Destroy expired token instruction
 //!
-//! Allows anyone to destroy an expired token that hasn't had liquidity deployed
+//! Allows anyone to destroy an expired token that hasn't had liquidity deployed.
+//! The escrowed mint fee is always refunded in full to the token creator's
+//! FeelsSOL ATA (created on the spot if the creator closed it), the unsold
+//! escrow tokens are burned, and the destroyer is compensated with the rent
+//! of the closed accounts.
 
 use crate::{
     constants::{ESCROW_AUTHORITY_SEED, ESCROW_SEED, MARKET_SEED, PROTOCOL_TOKEN_SEED},
@@ -9,7 +13,10 @@ use crate::{
     state::{Market, PreLaunchEscrow, ProtocolConfig, ProtocolToken},
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
 
 /// Destroy expired token accounts
 #[derive(Accounts)]
@@ -20,6 +27,7 @@ pub struct DestroyExpiredToken<'info> {
 
     /// Token mint to destroy
     /// CHECK: We verify this is expired through protocol_token
+    #[account(mut)]
     pub token_mint: AccountInfo<'info>,
 
     /// Protocol token registry entry
@@ -41,20 +49,19 @@ pub struct DestroyExpiredToken<'info> {
     )]
     pub escrow: Box<Account<'info, PreLaunchEscrow>>,
 
-    /// Escrow's token vault
+    /// Escrow's token vault (burned and closed by the handler)
     #[account(
         mut,
         associated_token::mint = token_mint,
         associated_token::authority = escrow_authority,
-        close = destroyer, // Return rent to destroyer
     )]
     pub escrow_token_vault: Box<Account<'info, TokenAccount>>,
 
-    /// Escrow's FeelsSOL vault (contains mint fee)
+    /// Escrow's FeelsSOL vault (contains mint fee, closed by the handler)
     #[account(
         mut,
-        constraint = escrow_feelssol_vault.mint == escrow.feelssol_mint @ FeelsError::InvalidMint,
-        close = destroyer, // Return rent to destroyer
+        associated_token::mint = feelssol_mint,
+        associated_token::authority = escrow_authority,
     )]
     pub escrow_feelssol_vault: Box<Account<'info, TokenAccount>>,
 
@@ -73,28 +80,36 @@ pub struct DestroyExpiredToken<'info> {
     )]
     pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
-    /// Treasury to receive 50% of mint fee
+    /// Token creator, owner of the refund ATA
+    /// CHECK: Only used as the ATA authority, pinned to the escrow's creator
+    #[account(address = escrow.creator @ FeelsError::InvalidAuthority)]
+    pub creator: AccountInfo<'info>,
+
+    /// FeelsSOL mint
+    #[account(address = escrow.feelssol_mint @ FeelsError::InvalidMint)]
+    pub feelssol_mint: Box<Account<'info, Mint>>,
+
+    /// Creator's FeelsSOL ATA to receive the mint fee refund
     #[account(
-        mut,
-        constraint = treasury.key() == protocol_config.treasury @ FeelsError::InvalidAuthority,
+        init_if_needed,
+        payer = destroyer,
+        associated_token::mint = feelssol_mint,
+        associated_token::authority = creator,
     )]
-    pub treasury: Box<Account<'info, TokenAccount>>,
+    pub creator_feelssol: Box<Account<'info, TokenAccount>>,
 
-    /// Destroyer's FeelsSOL account to receive 50% of mint fee
+    /// Market PDA for this token, which may not have been created yet
+    /// CHECK: Always required so its deployment status cannot be hidden;
+    /// parsed in the handler only if it is a program-owned account
     #[account(
         mut,
-        constraint = destroyer_feelssol.owner == destroyer.key() @ FeelsError::InvalidAuthority,
-        constraint = destroyer_feelssol.mint == escrow.feelssol_mint @ FeelsError::InvalidMint,
+        seeds = [MARKET_SEED, escrow.feelssol_mint.as_ref(), token_mint.key().as_ref()],
+        bump,
     )]
-    pub destroyer_feelssol: Box<Account<'info, TokenAccount>>,
-
-    /// Optional: Market account if it was created
-    /// CHECK: Market may or may not exist
-    #[account(mut)]
-    pub market: Option<AccountInfo<'info>>,
+    pub market: AccountInfo<'info>,
 
     /// Associated token program
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// Token program
     pub token_program: Program<'info, Token>,
@@ -108,7 +123,7 @@ pub fn destroy_expired_token(ctx: Context<DestroyExpiredToken>) -> Result<()> {
     let clock = Clock::get()?;
     let protocol_token = &ctx.accounts.protocol_token;
     let protocol_config = &ctx.accounts.protocol_config;
-    let escrow_feelssol_vault = &ctx.accounts.escrow_feelssol_vault;
+    let market_info = &ctx.accounts.market;
 
     // Check if token has expired
     let expiration_time = protocol_token
@@ -116,108 +131,99 @@ pub fn destroy_expired_token(ctx: Context<DestroyExpiredToken>) -> Result<()> {
         .checked_add(protocol_config.token_expiration_seconds)
         .ok_or(FeelsError::MathOverflow)?;
 
-    require!(
-        clock.unix_timestamp > expiration_time,
-        FeelsError::TokenNotExpired
-    );
-
-    // If market was created, verify it hasn't had liquidity deployed
-    if let Some(market_info) = &ctx.accounts.market {
-        // Derive expected market PDA
-        let (expected_market, _bump) = Pubkey::find_program_address(
-            &[
-                MARKET_SEED,
-                ctx.accounts.token_mint.key().as_ref(),
-                ctx.accounts.escrow.feelssol_mint.as_ref(),
-            ],
-            ctx.program_id,
-        );
-
-        // If this is indeed the market account
-        if market_info.key() == expected_market {
-            // Try to deserialize as Market
-            if let Ok(market_data) = market_info.try_borrow_data() {
-                if market_data.len() >= 8 + Market::LEN {
-                    let market: Market = Market::try_from_slice(&market_data[8..])?;
-
-                    // Ensure no liquidity was deployed
-                    require!(
-                        !market.initial_liquidity_deployed,
-                        FeelsError::MarketAlreadyActive
-                    );
-
-                    // Close the market account
-                    let dest_starting_lamports = ctx.accounts.destroyer.lamports();
-                    **ctx.accounts.destroyer.lamports.borrow_mut() = dest_starting_lamports
-                        .checked_add(market_info.lamports())
-                        .ok_or(FeelsError::MathOverflow)?;
-                    **market_info.lamports.borrow_mut() = 0;
-
-                    // Clear data
-                    let mut data = market_info.try_borrow_mut_data()?;
-                    data.fill(0);
-                }
-            }
-        }
+    // A partially deployed launch already has escrow funds in the market vaults
+    let mut liquidity_deployed = ctx.accounts.escrow.deployment.is_started();
+
+    // If the market was created, verify it hasn't had liquidity deployed. An
+    // account that doesn't parse as a market is treated as deployed.
+    let market_exists = market_info.owner == ctx.program_id && !market_info.data_is_empty();
+    if market_exists {
+        let market_data = market_info.try_borrow_data()?;
+        liquidity_deployed |= Market::try_deserialize(&mut &market_data[..])
+            .map(|market| market.initial_liquidity_deployed)
+            .unwrap_or(true);
     }
 
-    // Calculate mint fee split (50% to destroyer, 50% to treasury)
-    let mint_fee = escrow_feelssol_vault.amount;
-    let destroyer_reward = mint_fee / 2;
-    let treasury_amount = mint_fee - destroyer_reward; // Ensures no dust
+    validate_escrow_unwind(clock.unix_timestamp, expiration_time, liquidity_deployed)?;
+
+    if market_exists {
+        // Close the market account
+        let dest_starting_lamports = ctx.accounts.destroyer.lamports();
+        **ctx.accounts.destroyer.lamports.borrow_mut() = dest_starting_lamports
+            .checked_add(market_info.lamports())
+            .ok_or(FeelsError::MathOverflow)?;
+        **market_info.lamports.borrow_mut() = 0;
+
+        // Clear data
+        let mut data = market_info.try_borrow_mut_data()?;
+        data.fill(0);
+    }
+
+    // The mint fee belongs to the creator regardless of who cranks
+    let mint_fee = ctx.accounts.escrow_feelssol_vault.amount;
+    let unsold_tokens = ctx.accounts.escrow_token_vault.amount;
 
     msg!("Destroying expired token:");
     msg!("  Token: {}", ctx.accounts.token_mint.key());
     msg!("  Created at: {}", protocol_token.created_at);
     msg!("  Expired at: {}", expiration_time);
-    msg!("  Mint fee: {}", mint_fee);
-    msg!("  Destroyer reward: {}", destroyer_reward);
-    msg!("  Treasury amount: {}", treasury_amount);
+    msg!("  Mint fee refunded: {}", mint_fee);
+
+    let escrow_key = ctx.accounts.escrow.key();
+    let escrow_authority_seeds = &[
+        ESCROW_AUTHORITY_SEED,
+        escrow_key.as_ref(),
+        &[ctx.accounts.escrow.escrow_authority_bump],
+    ];
+    let signer_seeds = &[&escrow_authority_seeds[..]];
 
-    // Transfer rewards if there's a mint fee
     if mint_fee > 0 {
-        let escrow_key = ctx.accounts.escrow.key();
-        let escrow_authority_seeds = &[
-            ESCROW_AUTHORITY_SEED,
-            escrow_key.as_ref(),
-            &[ctx.accounts.escrow.escrow_authority_bump],
-        ];
-
-        // Transfer 50% to destroyer
-        if destroyer_reward > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    token::Transfer {
-                        from: ctx.accounts.escrow_feelssol_vault.to_account_info(),
-                        to: ctx.accounts.destroyer_feelssol.to_account_info(),
-                        authority: ctx.accounts.escrow_authority.to_account_info(),
-                    },
-                    &[escrow_authority_seeds],
-                ),
-                destroyer_reward,
-            )?;
-        }
-
-        // Transfer remaining to treasury
-        if treasury_amount > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    token::Transfer {
-                        from: ctx.accounts.escrow_feelssol_vault.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                        authority: ctx.accounts.escrow_authority.to_account_info(),
-                    },
-                    &[escrow_authority_seeds],
-                ),
-                treasury_amount,
-            )?;
-        }
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.escrow_feelssol_vault.to_account_info(),
+                    to: ctx.accounts.creator_feelssol.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            mint_fee,
+        )?;
     }
 
-    // Token vaults and accounts will be closed automatically by anchor's close constraint
-    // This returns rent to the destroyer as incentive
+    // Token accounts can only be closed empty, so burn what the escrow still holds
+    if unsold_tokens > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.escrow_token_vault.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            unsold_tokens,
+        )?;
+    }
+
+    // The vaults are owned by the token program, so they are closed through it
+    // rather than with anchor's close constraint. Rent goes to the destroyer.
+    for vault in [
+        ctx.accounts.escrow_token_vault.to_account_info(),
+        ctx.accounts.escrow_feelssol_vault.to_account_info(),
+    ] {
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: vault,
+                destination: ctx.accounts.destroyer.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
 
     // Emit event
     emit!(TokenDestroyed {
@@ -225,10 +231,23 @@ pub fn destroy_expired_token(ctx: Context<DestroyExpiredToken>) -> Result<()> {
         destroyer: ctx.accounts.destroyer.key(),
         created_at: protocol_token.created_at,
         destroyed_at: clock.unix_timestamp,
+        creator: ctx.accounts.escrow.creator,
         mint_fee_returned: mint_fee,
-        destroyer_reward,
-        treasury_amount,
     });
 
     Ok(())
 }
+
+/// Check that an expired token's escrow may be unwound
+///
+/// The token must be past its expiration and its market, if any, must never
+/// have received the initial liquidity deployment.
+pub fn validate_escrow_unwind(
+    now: i64,
+    expiration_time: i64,
+    liquidity_deployed: bool,
+) -> Result<()> {
+    require!(now > expiration_time, FeelsError::TokenNotExpired);
+    require!(!liquidity_deployed, FeelsError::MarketAlreadyActive);
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Increase liquidity instruction (core logic)
+//!
+//! Adds liquidity to an existing position over its current range. Fees
+//! accrued on the old liquidity are checkpointed into `tokens_owed_*` first,
+//! so the added liquidity does not earn fees retroactively.
+
+use crate::{
+    constants::{MAX_LIQUIDITY, POSITION_SEED, VAULT_SEED},
+    error::FeelsError,
+    events::{PositionOperation, PositionUpdated},
+    logic::{amounts_from_liquidity, calculate_position_fee_accrual},
+    state::{Market, Position, TickArray},
+    utils::{add_liquidity, sqrt_price_from_tick, transfer_from_user_to_vault_unchecked},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Increase liquidity parameters
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct IncreaseLiquidityParams {
+    /// Liquidity to add to the position
+    pub liquidity: u128,
+    /// Maximum amount of token 0 to deposit
+    pub amount_0_max: u64,
+    /// Maximum amount of token 1 to deposit
+    pub amount_1_max: u64,
+}
+
+/// Increase liquidity accounts
+#[derive(Accounts)]
+pub struct IncreaseLiquidity<'info> {
+    /// Position owner
+    /// SECURITY: Must be a system account to prevent PDA identity confusion
+    #[account(
+        mut,
+        constraint = owner.owner == &System::id() @ FeelsError::InvalidAuthority
+    )]
+    pub owner: Signer<'info>,
+
+    /// Market state
+    #[account(
+        mut,
+        constraint = market.is_initialized @ FeelsError::MarketNotInitialized,
+        constraint = !market.is_paused @ FeelsError::MarketPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Position token account (must hold the position token)
+    #[account(
+        constraint = position_token_account.mint == position.nft_mint @ FeelsError::InvalidMint,
+        constraint = position_token_account.owner == owner.key() @ FeelsError::InvalidAuthority,
+        constraint = position_token_account.amount == 1 @ FeelsError::InvalidPosition,
+    )]
+    pub position_token_account: Account<'info, TokenAccount>,
+
+    /// Position account (PDA)
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, position.nft_mint.as_ref()],
+        bump = position.position_bump,
+        constraint = position.market == market.key() @ FeelsError::InvalidMarket,
+        constraint = position.owner == owner.key() @ FeelsError::InvalidAuthority,
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Owner's token account for token 0
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub owner_token_0: UncheckedAccount<'info>,
+
+    /// Owner's token account for token 1
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub owner_token_1: UncheckedAccount<'info>,
+
+    /// Market vault for token 0 - derived from market and token_0
+    /// CHECK: Validated as PDA in constraints
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, market.key().as_ref(), market.token_0.as_ref()],
+        bump,
+    )]
+    pub vault_0: UncheckedAccount<'info>,
+
+    /// Market vault for token 1 - derived from market and token_1
+    /// CHECK: Validated as PDA in constraints
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, market.key().as_ref(), market.token_1.as_ref()],
+        bump,
+    )]
+    pub vault_1: UncheckedAccount<'info>,
+
+    /// Tick array containing the lower tick
+    #[account(
+        mut,
+        constraint = lower_tick_array.load()?.market == market.key() @ FeelsError::InvalidTickArray,
+    )]
+    pub lower_tick_array: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing the upper tick
+    #[account(
+        mut,
+        constraint = upper_tick_array.load()?.market == market.key() @ FeelsError::InvalidTickArray,
+    )]
+    pub upper_tick_array: AccountLoader<'info, TickArray>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Increase liquidity handler
+pub fn increase_liquidity(
+    ctx: Context<IncreaseLiquidity>,
+    params: IncreaseLiquidityParams,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    require!(params.liquidity > 0, FeelsError::ZeroLiquidity);
+    crate::utils::validate_liquidity_amount(params.liquidity)?;
+
+    // Manually deserialize and validate owner token accounts
+    let owner_token_0 =
+        TokenAccount::try_deserialize(&mut &ctx.accounts.owner_token_0.data.borrow()[..])?;
+    let owner_token_1 =
+        TokenAccount::try_deserialize(&mut &ctx.accounts.owner_token_1.data.borrow()[..])?;
+    require!(
+        owner_token_0.owner == ctx.accounts.owner.key(),
+        FeelsError::InvalidAuthority
+    );
+    require!(
+        owner_token_1.owner == ctx.accounts.owner.key(),
+        FeelsError::InvalidAuthority
+    );
+    require!(
+        owner_token_0.mint == market.token_0,
+        FeelsError::InvalidMint
+    );
+    require!(
+        owner_token_1.mint == market.token_1,
+        FeelsError::InvalidMint
+    );
+
+    let tick_lower = position.tick_lower;
+    let tick_upper = position.tick_upper;
+
+    // Validate that tick arrays match the position's ticks
+    {
+        let lower_array = ctx.accounts.lower_tick_array.load()?;
+        let upper_array = ctx.accounts.upper_tick_array.load()?;
+        crate::utils::validate_tick_array_for_tick(&lower_array, tick_lower, market.tick_spacing)?;
+        crate::utils::validate_tick_array_for_tick(&upper_array, tick_upper, market.tick_spacing)?;
+    }
+
+    // Checkpoint fees earned by the existing liquidity before it changes
+    {
+        let lower_array = ctx.accounts.lower_tick_array.load()?;
+        let upper_array = ctx.accounts.upper_tick_array.load()?;
+        let lower_tick = lower_array.get_tick(tick_lower, market.tick_spacing)?;
+        let upper_tick = upper_array.get_tick(tick_upper, market.tick_spacing)?;
+
+        let fee_accrual = calculate_position_fee_accrual(
+            market.current_tick,
+            tick_lower,
+            tick_upper,
+            position.liquidity,
+            market.fee_growth_global_0_x64,
+            market.fee_growth_global_1_x64,
+            lower_tick,
+            upper_tick,
+            position.fee_growth_inside_0_last_x64,
+            position.fee_growth_inside_1_last_x64,
+        )?;
+
+        position.tokens_owed_0 = position
+            .tokens_owed_0
+            .saturating_add(fee_accrual.tokens_owed_0_increment);
+        position.tokens_owed_1 = position
+            .tokens_owed_1
+            .saturating_add(fee_accrual.tokens_owed_1_increment);
+        position.fee_growth_inside_0_last_x64 = fee_accrual.fee_growth_inside_0;
+        position.fee_growth_inside_1_last_x64 = fee_accrual.fee_growth_inside_1;
+    }
+
+    // Calculate the deposit for the added liquidity
+    let sqrt_price_lower = sqrt_price_from_tick(tick_lower)?;
+    let sqrt_price_upper = sqrt_price_from_tick(tick_upper)?;
+    let (amount_0, amount_1) = amounts_from_liquidity(
+        market.sqrt_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        params.liquidity,
+    )?;
+    require!(
+        amount_0 <= params.amount_0_max && amount_1 <= params.amount_1_max,
+        FeelsError::SlippageExceeded
+    );
+
+    let new_liquidity = position
+        .liquidity
+        .checked_add(params.liquidity)
+        .ok_or(FeelsError::MathOverflow)?;
+    require!(
+        new_liquidity <= MAX_LIQUIDITY,
+        FeelsError::LiquidityOverflow
+    );
+
+    // The grown position must still clear the minimum, and the added
+    // liquidity must not round to a free deposit
+    crate::utils::validate_position_size(
+        new_liquidity,
+        market.sqrt_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        amount_0,
+        amount_1,
+    )?;
+
+    {
+        let mut lower_array = ctx.accounts.lower_tick_array.load_mut()?;
+        lower_array.update_liquidity(
+            tick_lower,
+            market.tick_spacing,
+            params.liquidity as i128,
+            false,
+        )?;
+    }
+    {
+        let mut upper_array = ctx.accounts.upper_tick_array.load_mut()?;
+        upper_array.update_liquidity(
+            tick_upper,
+            market.tick_spacing,
+            params.liquidity as i128,
+            true,
+        )?;
+    }
+
+    position.liquidity = new_liquidity;
+
+    // Update market liquidity if position is in range
+    if market.current_tick >= tick_lower && market.current_tick < tick_upper {
+        market.liquidity = add_liquidity(market.liquidity, params.liquidity)?;
+    }
+
+    // Transfer tokens from owner to vaults
+    if amount_0 > 0 {
+        transfer_from_user_to_vault_unchecked(
+            &ctx.accounts.owner_token_0.to_account_info(),
+            &ctx.accounts.vault_0.to_account_info(),
+            &ctx.accounts.owner,
+            &ctx.accounts.token_program,
+            amount_0,
+        )?;
+    }
+
+    if amount_1 > 0 {
+        transfer_from_user_to_vault_unchecked(
+            &ctx.accounts.owner_token_1.to_account_info(),
+            &ctx.accounts.vault_1.to_account_info(),
+            &ctx.accounts.owner,
+            &ctx.accounts.token_program,
+            amount_1,
+        )?;
+    }
+
+    // Emit unified event
+    emit!(PositionUpdated {
+        position: position.key(),
+        position_mint: position.nft_mint,
+        market: market.key(),
+        owner: ctx.accounts.owner.key(),
+        tick_lower,
+        tick_upper,
+        liquidity: new_liquidity,
+        amount_0,
+        amount_1,
+        fees_collected_0: 0,
+        fees_collected_1: 0,
+        operation: PositionOperation::AddLiquidity,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ use crate::{
     error::FeelsError,
     events::MarketInitialized,
     state::{Buffer, Market, MarketPhase, OracleState, PolicyV1, ProtocolConfig},
-    utils::tick_from_sqrt_price,
+    utils::align_initial_sqrt_price,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
@@ -145,8 +145,9 @@ pub fn initialize_market(
         FeelsError::InvalidParameter
     );
 
-    // Compute current tick from sqrt price
-    let mut current_tick = tick_from_sqrt_price(params.initial_sqrt_price)?;
+    // Snap the initial price onto a tick aligned to the market's spacing
+    let (initial_sqrt_price, mut current_tick) =
+        align_initial_sqrt_price(params.initial_sqrt_price, params.tick_spacing)?;
     let tick_spacing_i32 = params.tick_spacing as i32;
 
     // Align global tick bounds to spacing while clamping within protocol limits
@@ -232,7 +233,7 @@ pub fn initialize_market(
         ..PolicyV1::default()
     };
     market.tick_spacing = params.tick_spacing;
-    market.sqrt_price = params.initial_sqrt_price;
+    market.sqrt_price = initial_sqrt_price;
     market.current_tick = current_tick;
     market.liquidity = 0;
     market.global_lower_tick = global_lower_tick;
@@ -323,7 +324,7 @@ pub fn initialize_market(
         buffer: ctx.accounts.buffer.key(),
         base_fee_bps: params.base_fee_bps,
         tick_spacing: params.tick_spacing,
-        initial_sqrt_price: params.initial_sqrt_price,
+        initial_sqrt_price,
         timestamp: clock.unix_timestamp,
         version: 1,
     });
@@ -111,22 +111,11 @@ pub fn initialize_protocol(
     let max_protocol_fee = params.max_protocol_fee_rate.unwrap_or(1000); // Default max 10%
 
     // Enforce reasonable fee bounds
-    require!(
-        default_protocol_fee <= 1000, // Max 10%
-        FeelsError::InvalidMarket
-    );
-    require!(
-        default_creator_fee <= 500, // Max 5%
-        FeelsError::InvalidMarket
-    );
-    require!(
-        max_protocol_fee <= 1000 && max_protocol_fee >= default_protocol_fee, // Max 10% and must be >= default
-        FeelsError::InvalidMarket
-    );
-    require!(
-        default_protocol_fee + default_creator_fee <= 1000, // Combined max 10%
-        FeelsError::InvalidMarket
-    );
+    crate::utils::validate_protocol_fee_rates(
+        default_protocol_fee,
+        default_creator_fee,
+        max_protocol_fee,
+    )?;
 
     let config = &mut ctx.accounts.protocol_config;
 
@@ -145,7 +134,7 @@ pub fn initialize_protocol(
     config.clear_required_obs = params.clear_required_obs;
     config.dex_twap_window_secs = params.dex_twap_window_secs;
     config.dex_twap_stale_age_secs = params.dex_twap_stale_age_secs;
-    config._reserved = [0; 7];
+    config._reserved = [0; 5];
     // Initialize DEX whitelist (truncate to fit)
     config.dex_whitelist = [Pubkey::default(); 8];
     let mut i = 0usize;
@@ -157,6 +146,8 @@ pub fn initialize_protocol(
     // Per-slot caps default to 0 (unlimited) in MVP; adjustable via update_protocol
     config.mint_per_slot_cap_feelssol = 0;
     config.redeem_per_slot_cap_feelssol = 0;
+    config.swap_per_slot_cap_feelssol = 0;
+    config.bonding_swap_per_slot_cap_feelssol = 0;
 
     // Set default AMM parameters (admin-controlled during memecoin phase)
     config.default_base_fee_bps = 30; // 0.3%
@@ -245,6 +236,10 @@ pub struct UpdateProtocolParams {
     /// Optional: per-slot caps
     pub mint_per_slot_cap_feelssol: Option<u64>,
     pub redeem_per_slot_cap_feelssol: Option<u64>,
+    pub swap_per_slot_cap_feelssol: Option<u64>,
+    pub bonding_swap_per_slot_cap_feelssol: Option<u64>,
+    /// Optional: swap circuit breaker threshold (0 disables it)
+    pub max_spot_twap_deviation_bps: Option<u16>,
 }
 
 /// Update protocol accounts
@@ -281,42 +276,38 @@ pub fn update_protocol(ctx: Context<UpdateProtocol>, params: UpdateProtocolParam
         msg!("Updated treasury to: {}", treasury);
     }
 
-    if let Some(protocol_fee_rate) = params.default_protocol_fee_rate {
-        require!(
-            protocol_fee_rate <= config.max_protocol_fee_rate,
-            FeelsError::InvalidMarket
-        );
-        config.default_protocol_fee_rate = protocol_fee_rate;
-        msg!(
-            "Updated default protocol fee rate to: {} bps",
-            protocol_fee_rate
-        );
-    }
+    // Validate the fee rates this update produces together, so the outcome
+    // does not depend on which of them is applied first
+    if params.default_protocol_fee_rate.is_some()
+        || params.default_creator_fee_rate.is_some()
+        || params.max_protocol_fee_rate.is_some()
+    {
+        let protocol_fee_rate = params
+            .default_protocol_fee_rate
+            .unwrap_or(config.default_protocol_fee_rate);
+        let creator_fee_rate = params
+            .default_creator_fee_rate
+            .unwrap_or(config.default_creator_fee_rate);
+        let max_fee_rate = params
+            .max_protocol_fee_rate
+            .unwrap_or(config.max_protocol_fee_rate);
+        crate::utils::validate_protocol_fee_rates(
+            protocol_fee_rate,
+            creator_fee_rate,
+            max_fee_rate,
+        )?;
 
-    if let Some(creator_fee_rate) = params.default_creator_fee_rate {
-        require!(creator_fee_rate <= 500, FeelsError::InvalidMarket); // Max 5% creator fee
-                                                                      // Ensure combined fees don't exceed 10%
-        require!(
-            config.default_protocol_fee_rate + creator_fee_rate <= 1000,
-            FeelsError::InvalidMarket
-        );
+        config.default_protocol_fee_rate = protocol_fee_rate;
         config.default_creator_fee_rate = creator_fee_rate;
+        config.max_protocol_fee_rate = max_fee_rate;
         msg!(
-            "Updated default creator fee rate to: {} bps",
-            creator_fee_rate
+            "Updated fee rates: protocol {} bps, creator {} bps, max protocol {} bps",
+            protocol_fee_rate,
+            creator_fee_rate,
+            max_fee_rate
         );
     }
 
-    if let Some(max_fee_rate) = params.max_protocol_fee_rate {
-        require!(max_fee_rate <= 1000, FeelsError::InvalidMarket); // Max 10% total protocol fee
-        require!(
-            max_fee_rate >= config.default_protocol_fee_rate,
-            FeelsError::InvalidMarket
-        );
-        config.max_protocol_fee_rate = max_fee_rate;
-        msg!("Updated max protocol fee rate to: {} bps", max_fee_rate);
-    }
-
     if let Some(authority) = params.authority {
         config.authority = authority;
         msg!("Updated authority to: {}", authority);
@@ -359,6 +350,16 @@ pub fn update_protocol(ctx: Context<UpdateProtocol>, params: UpdateProtocolParam
     if let Some(x) = params.redeem_per_slot_cap_feelssol {
         config.redeem_per_slot_cap_feelssol = x;
     }
+    if let Some(x) = params.swap_per_slot_cap_feelssol {
+        config.swap_per_slot_cap_feelssol = x;
+    }
+    if let Some(x) = params.bonding_swap_per_slot_cap_feelssol {
+        config.bonding_swap_per_slot_cap_feelssol = x;
+    }
+    if let Some(x) = params.max_spot_twap_deviation_bps {
+        require!(x <= 10_000, FeelsError::InvalidMarket);
+        config.max_spot_twap_deviation_bps = x;
+    }
 
     emit!(crate::events::ProtocolParamsUpdated {
         authority: config.authority,
@@ -106,8 +106,10 @@ pub fn initialize_tranche_ticks<'info>(
         AccountLoader::<TickArray>::try_from(info)
     };
 
-    // Iterate tranche entries; optionally apply liquidity nets if not yet applied
-    let apply_liq = !ctx.accounts.tranche_plan.applied;
+    // Iterate tranche entries; optionally apply liquidity nets if not yet applied.
+    // Nets are only applied once every part of the initial deployment has landed,
+    // otherwise later parts would never reach the tick arrays.
+    let apply_liq = !ctx.accounts.tranche_plan.applied && market.initial_liquidity_deployed;
     let total = core::cmp::min(
         params.num_steps as usize,
         ctx.accounts.tranche_plan.entries.len(),
@@ -0,0 +1,69 @@
+//! Account layout migration
+//!
+//! Grows a program account created under an older, smaller layout to its
+//! current `LEN`. The newly exposed bytes are zeroed, so fields added since
+//! the account was created read as zero. Permissionless: the payer only
+//! covers the extra rent.
+
+use crate::{
+    error::FeelsError,
+    events::AccountMigrated,
+    state::{current_account_len, needs_migration},
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// Account to grow
+    /// CHECK: Owned by this program; its type is read from the discriminator
+    #[account(mut, owner = crate::ID @ FeelsError::InvalidAccountOwner)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let old_len = account.data_len();
+    let new_len =
+        current_account_len(&account.try_borrow_data()?).ok_or(FeelsError::InvalidAccount)?;
+
+    // Only ever grow; an account at or past its current size is left alone
+    if !needs_migration(old_len, new_len) {
+        return Ok(());
+    }
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+
+    account.realloc(new_len, true)?;
+    account.try_borrow_mut_data()?[old_len..].fill(0);
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        old_len: old_len as u32,
+        new_len: new_len as u32,
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
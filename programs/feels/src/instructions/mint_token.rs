@@ -6,7 +6,7 @@ use crate::{
     constants::{ESCROW_AUTHORITY_SEED, ESCROW_SEED, TOKEN_DECIMALS, TOTAL_SUPPLY},
     error::FeelsError,
     events::TokenMinted,
-    state::{PreLaunchEscrow, ProtocolConfig},
+    state::{DeploymentProgress, PreLaunchEscrow, ProtocolConfig},
 };
 use anchor_lang::prelude::borsh;
 use anchor_lang::prelude::*;
@@ -193,7 +193,8 @@ pub fn mint_token(ctx: Context<MintToken>, params: MintTokenParams) -> Result<()
     escrow.created_at = clock.unix_timestamp;
     escrow.market = Pubkey::default(); // Will be set when market is initialized
     escrow.escrow_authority_bump = ctx.bumps.escrow_authority;
-    escrow._reserved = [0; 128];
+    escrow.deployment = DeploymentProgress::default();
+    escrow._reserved = [0; 88];
 
     // Create token metadata
     let metadata_data = DataV2 {
@@ -299,6 +300,7 @@ pub fn mint_token(ctx: Context<MintToken>, params: MintTokenParams) -> Result<()
         creator: ctx.accounts.creator.key(),
         ticker: params.ticker,
         name: params.name,
+        decimals: TOKEN_DECIMALS,
         total_supply: TOTAL_SUPPLY,
         buffer_amount: escrow_amount,
         creator_amount: 0,
@@ -19,6 +19,9 @@ pub use open_position::*;
 pub mod close_position;
 pub use close_position::*;
 
+pub mod increase_liquidity;
+pub use increase_liquidity::*;
+
 pub mod collect_fees;
 pub use collect_fees::*;
 
@@ -88,6 +91,12 @@ pub use manage_pomm_position::*;
 pub mod transition_market_phase;
 pub use transition_market_phase::*;
 
+pub mod migrate_account;
+pub use migrate_account::*;
+
+pub mod cleanup_tick_array;
+pub use cleanup_tick_array::*;
+
 // Additional specific exports for Anchor
 pub use update_protocol_oracle::{
     update_dex_twap, update_native_rate, UpdateDexTwap, UpdateDexTwapParams, UpdateNativeRate,
@@ -1,7 +1,7 @@
 //! Open position instruction (core logic)
 
 use crate::{
-    constants::{MIN_LIQUIDITY, POSITION_SEED},
+    constants::POSITION_SEED,
     error::FeelsError,
     events::{PositionOperation, PositionUpdated},
     logic::{amounts_from_liquidity, calculate_position_fee_accrual},
@@ -176,12 +176,6 @@ pub fn open_position(
 
     require!(liquidity_amount > 0, FeelsError::ZeroLiquidity);
 
-    // Check against minimum liquidity to prevent dust positions
-    require!(
-        liquidity_amount >= MIN_LIQUIDITY,
-        FeelsError::LiquidityBelowMinimum
-    );
-
     // Use the new parameter validation for liquidity amount
     crate::utils::validate_liquidity_amount(liquidity_amount)?;
 
@@ -207,6 +201,16 @@ pub fn open_position(
         liquidity_amount,
     )?;
 
+    // Reject dust positions and deposits that round to nothing on one side
+    crate::utils::validate_position_size(
+        liquidity_amount,
+        sqrt_price_current,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        amount_0,
+        amount_1,
+    )?;
+
     // Mint position token to provider (before mutating position)
     let position_bump = ctx.bumps.position;
     let position_mint_key = ctx.accounts.position_mint.key();
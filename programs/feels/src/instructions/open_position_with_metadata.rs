@@ -174,12 +174,6 @@ pub fn open_position_with_metadata(
     crate::utils::validate_tick_range(tick_lower, tick_upper, market.tick_spacing)?;
     require!(liquidity_amount > 0, FeelsError::ZeroLiquidity);
 
-    // Check against minimum liquidity to prevent dust positions
-    require!(
-        liquidity_amount >= crate::constants::MIN_LIQUIDITY,
-        FeelsError::LiquidityBelowMinimum
-    );
-
     // Validate that tick arrays match the expected ticks
     {
         let lower_array = ctx.accounts.lower_tick_array.load()?;
@@ -229,6 +223,16 @@ pub fn open_position_with_metadata(
         liquidity_amount,
     )?;
 
+    // Reject dust positions and deposits that round to nothing on one side
+    crate::utils::validate_position_size(
+        liquidity_amount,
+        sqrt_price_current,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        amount_0,
+        amount_1,
+    )?;
+
     // Initialize position state
     position.nft_mint = ctx.accounts.position_mint.key();
     position.market = market.key();
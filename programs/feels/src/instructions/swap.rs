@@ -12,13 +12,15 @@ use crate::{
     events::{FeeSplitApplied, SwapExecuted},
     logic::{
         execute_swap_steps, SwapParams, SwapState, SwapExecutionResult,
-        finalize_fee_state, split_and_apply_fees,
+        finalize_fee_state, split_and_apply_fees, spot_twap_reference_tick,
+        validate_spot_twap_deviation,
+        record_slot_swap_volume, swap_per_slot_cap,
         SwapDirection,
     },
     state::{Buffer, Market, OracleState, ProtocolConfig, ProtocolToken},
     utils::{
         transfer_from_user_to_vault_unchecked, transfer_from_vault_to_user_unchecked,
-        validate_amount, validate_slippage, validate_swap_route,
+        sqrt_price_from_tick, validate_amount, validate_slippage, validate_swap_route,
     },
 };
 use anchor_lang::prelude::*;
@@ -62,12 +64,14 @@ pub struct Swap<'info> {
     pub user_token_account_out: Account<'info, TokenAccount>,
 
     /// The market account containing trading pair configuration and state
-    /// All swaps occur within a specific market context
+    /// All swaps occur within a specific market context. Trading only opens once
+    /// the launch stair is complete, so nobody can trade between deployment parts
     #[account(
         mut,
         has_one = token_0,
         has_one = token_1,
-        constraint = !market.reentrancy_guard @ FeelsError::ReentrancyDetected
+        constraint = !market.reentrancy_guard @ FeelsError::ReentrancyDetected,
+        constraint = market.initial_liquidity_deployed @ FeelsError::InitialLiquidityNotDeployed
     )]
     pub market: Account<'info, Market>,
 
@@ -258,14 +262,14 @@ pub fn swap<'info>(
 
     // Load the unchecked accounts manually to avoid lifetime issues
     let buffer_data = ctx.accounts.buffer.try_borrow_data()?;
-    let mut buffer: Buffer = Buffer::try_deserialize(&mut &buffer_data[8..])?;
+    let mut buffer: Buffer = Buffer::try_deserialize(&mut &buffer_data[..])?;
 
     let oracle_data = ctx.accounts.oracle.try_borrow_data()?;
-    let mut oracle: OracleState = OracleState::try_deserialize(&mut &oracle_data[8..])?;
+    let mut oracle: OracleState = OracleState::try_deserialize(&mut &oracle_data[..])?;
 
     let protocol_config_data = ctx.accounts.protocol_config.try_borrow_data()?;
     let protocol_config: ProtocolConfig =
-        ProtocolConfig::try_deserialize(&mut &protocol_config_data[8..])?;
+        ProtocolConfig::try_deserialize(&mut &protocol_config_data[..])?;
 
     // Set reentrancy guard to prevent recursive calls
     ctx.accounts.market.reentrancy_guard = true;
@@ -307,6 +311,20 @@ pub fn swap<'info>(
         }
     }
 
+    // Circuit breaker reference, resolved up front so an oracle that cannot
+    // produce a TWAP rejects the swap instead of disabling the check
+    let twap_sqrt_price = if protocol_config.max_spot_twap_deviation_bps > 0 {
+        spot_twap_reference_tick(
+            &oracle,
+            ctx.accounts.clock.unix_timestamp,
+            ctx.accounts.market.current_tick,
+        )?
+        .map(sqrt_price_from_tick)
+        .transpose()?
+    } else {
+        None
+    };
+
     // --- SWAP EXECUTION ---
 
     // Initialize swap state tracking
@@ -340,6 +358,30 @@ pub fn swap<'info>(
 
     let swap_execution_result = final_state.to_result(ctx.accounts.market.current_tick, params.amount_in);
 
+    // Circuit breaker: refuse swaps that push spot too far from the oracle TWAP
+    if let Some(twap_sqrt_price) = twap_sqrt_price {
+        validate_spot_twap_deviation(
+            ctx.accounts.market.sqrt_price,
+            swap_execution_result.final_sqrt_price,
+            twap_sqrt_price,
+            protocol_config.max_spot_twap_deviation_bps,
+        )?;
+    }
+
+    // Per-slot volume cap, measured on the FeelsSOL side of the swap
+    let volume_feelssol = if token_in == ctx.accounts.market.feelssol_mint {
+        amount_in_used
+    } else {
+        swap_execution_result.amount_out
+    };
+    let slot_cap = swap_per_slot_cap(&protocol_config, ctx.accounts.market.phase);
+    record_slot_swap_volume(
+        &mut ctx.accounts.market,
+        volume_feelssol,
+        slot_cap,
+        ctx.accounts.clock.slot,
+    )?;
+
     // --- FEE PROCESSING ---
 
     // Split and apply fees (extracted to logic module)
@@ -418,6 +460,28 @@ pub fn swap<'info>(
         &ctx.accounts.clock,
     )?;
 
+    // Verification builds re-check that the reserves still back the floor
+    #[cfg(feature = "verify-invariants")]
+    {
+        ctx.accounts.vault_0.reload()?;
+        ctx.accounts.vault_1.reload()?;
+        let (feels_vault, project_vault, project_mint) =
+            if ctx.accounts.market.token_0 == ctx.accounts.market.feelssol_mint {
+                (&ctx.accounts.vault_0, &ctx.accounts.vault_1, &ctx.accounts.token_1)
+            } else {
+                (&ctx.accounts.vault_1, &ctx.accounts.vault_0, &ctx.accounts.token_0)
+            };
+        let project_mint =
+            anchor_spl::token::Mint::try_deserialize(&mut &project_mint.try_borrow_data()?[..])?;
+        crate::logic::verify_floor_backing(
+            ctx.accounts.market.floor_tick,
+            buffer.tau_spot.saturating_add(feels_vault.amount as u128),
+            project_mint.supply,
+            project_vault.amount,
+            buffer.protocol_owned_override,
+        )?;
+    }
+
     // Clear reentrancy guard
     ctx.accounts.market.reentrancy_guard = false;
 
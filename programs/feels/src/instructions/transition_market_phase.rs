@@ -2,7 +2,14 @@
 //!
 //! Manages the lifecycle of markets through different phases
 
-use crate::{error::FeelsError, events::MarketPhaseTransitioned, state::*};
+use crate::{
+    constants::SWAP_TWAP_SECONDS,
+    error::FeelsError,
+    events::{MarketPhaseTransitioned, PoolStatusChanged},
+    logic::spot_twap_deviation_bps,
+    state::*,
+    utils::sqrt_price_from_tick,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -38,7 +45,7 @@ pub struct TransitionMarketPhase<'info> {
 pub struct TransitionPhaseParams {
     /// Target phase to transition to
     pub target_phase: MarketPhase,
-    /// Force transition even if criteria not met (governance only)
+    /// Force transition even if graduation criteria are not met (governance only)
     pub force: bool,
 }
 
@@ -47,7 +54,7 @@ pub fn transition_market_phase(
     params: TransitionPhaseParams,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
-    let _oracle = &ctx.accounts.oracle;
+    let oracle = &ctx.accounts.oracle;
     let _buffer = &ctx.accounts.buffer;
 
     let clock = Clock::get()?;
@@ -66,8 +73,9 @@ pub fn transition_market_phase(
         _ => return Err(FeelsError::InvalidPhase.into()),
     };
 
-    // Validate transition
-    if !params.force && !current_phase.can_transition_to(params.target_phase) {
+    // Validate transition; `force` only waives the graduation criteria below,
+    // never the legality of the transition itself
+    if !current_phase.can_transition_to(params.target_phase) {
         return Err(FeelsError::InvalidPhaseTransition.into());
     }
 
@@ -113,6 +121,23 @@ pub fn transition_market_phase(
         PhaseTrigger::Governance
     };
 
+    let was_paused = market.is_paused;
+
+    // Stress readings for the status-change event, taken before the phase
+    // clock is reset. Markets without oracle history report no spot stress.
+    let spot_stress_bps = match oracle.get_twap_tick(current_timestamp, SWAP_TWAP_SECONDS) {
+        Ok(twap_tick) => {
+            spot_twap_deviation_bps(market.sqrt_price, sqrt_price_from_tick(twap_tick)?)
+                .min(u64::MAX as u128) as u64
+        }
+        Err(_) => 0,
+    };
+    let stress = PoolStressComponents {
+        spot_stress_bps,
+        time_stress_secs: current_timestamp.saturating_sub(market.phase_start_timestamp),
+        leverage_stress_bps: 0,
+    };
+
     // Execute phase-specific transitions
     match (current_phase, params.target_phase) {
         (MarketPhase::Created, MarketPhase::BondingCurve) => {
@@ -170,9 +195,51 @@ pub fn transition_market_phase(
         slot: current_slot,
     });
 
+    if let Some(event) = pool_status_change(
+        market.key(),
+        was_paused,
+        market.is_paused,
+        trigger,
+        stress,
+        current_timestamp,
+        current_slot,
+    ) {
+        emit!(event);
+    }
+
     Ok(())
 }
 
+/// Build the status-change event if the pool's paused flag flipped
+pub fn pool_status_change(
+    market: Pubkey,
+    was_paused: bool,
+    is_paused: bool,
+    trigger: PhaseTrigger,
+    stress: PoolStressComponents,
+    timestamp: i64,
+    slot: u64,
+) -> Option<PoolStatusChanged> {
+    if was_paused == is_paused {
+        return None;
+    }
+
+    let old_status = PoolStatus::from_paused(was_paused);
+    let new_status = PoolStatus::from_paused(is_paused);
+    let reason = PoolStatusReason::from_trigger(new_status, trigger);
+
+    Some(PoolStatusChanged {
+        market,
+        old_status: old_status as u8,
+        new_status: new_status as u8,
+        reason: reason as u8,
+        trigger: trigger as u8,
+        stress,
+        timestamp,
+        slot,
+    })
+}
+
 // Graduation thresholds
 const GRADUATION_VOLUME_THRESHOLD: u64 = 1_000_000_000_000; // 1M tokens
 const GRADUATION_LIQUIDITY_THRESHOLD: u128 = 100_000_000_000; // 100k tokens
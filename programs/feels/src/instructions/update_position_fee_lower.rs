@@ -8,6 +8,7 @@ use crate::{
     constants::POSITION_SEED,
     error::FeelsError,
     state::{Market, Position, TickArray},
+    utils::fee_growth_delta,
 };
 use anchor_lang::prelude::*;
 
@@ -124,10 +125,11 @@ fn calculate_partial_fees(
 
     // For wide positions, we use a simplified calculation
     // that avoids requiring both ticks in the same transaction
-    let fee_growth_delta = fee_growth_global
-        .wrapping_sub(fee_growth_outside)
-        .wrapping_sub(last_fee_growth);
+    let delta = fee_growth_delta(
+        fee_growth_global.wrapping_sub(fee_growth_outside),
+        last_fee_growth,
+    );
 
     // Calculate fees with proper scaling
-    ((liquidity.saturating_mul(fee_growth_delta)) >> 64) as u64
+    ((liquidity.saturating_mul(delta)) >> 64) as u64
 }
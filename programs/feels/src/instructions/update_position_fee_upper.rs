@@ -8,6 +8,7 @@ use crate::{
     constants::POSITION_SEED,
     error::FeelsError,
     state::{Market, Position, TickArray},
+    utils::fee_growth_delta,
 };
 use anchor_lang::prelude::*;
 
@@ -93,9 +94,9 @@ pub fn update_position_fee_upper(ctx: Context<UpdatePositionFeeUpper>) -> Result
 
     // Calculate fees owed
     let fee_growth_delta_0 =
-        fee_growth_inside_0.wrapping_sub(position.fee_growth_inside_0_last_x64);
+        fee_growth_delta(fee_growth_inside_0, position.fee_growth_inside_0_last_x64);
     let fee_growth_delta_1 =
-        fee_growth_inside_1.wrapping_sub(position.fee_growth_inside_1_last_x64);
+        fee_growth_delta(fee_growth_inside_1, position.fee_growth_inside_1_last_x64);
 
     let fees_owed_0 = (position.liquidity.saturating_mul(fee_growth_delta_0) >> 64) as u64;
     let fees_owed_1 = (position.liquidity.saturating_mul(fee_growth_delta_1) >> 64) as u64;
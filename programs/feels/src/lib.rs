@@ -69,6 +69,11 @@ pub mod feels {
         instructions::register_pool(ctx)
     }
 
+    /// Grow an account created under an older layout to its current size
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_account(ctx)
+    }
+
     /// Update pool phase in registry
     pub fn update_pool_phase(ctx: Context<UpdatePoolPhase>, new_phase: PoolPhase) -> Result<()> {
         instructions::update_pool_phase(ctx, new_phase)
@@ -149,6 +154,14 @@ pub mod feels {
         instructions::close_position(ctx, params)
     }
 
+    /// Add liquidity to an existing position over its range
+    pub fn increase_liquidity(
+        ctx: Context<IncreaseLiquidity>,
+        params: IncreaseLiquidityParams,
+    ) -> Result<()> {
+        instructions::increase_liquidity(ctx, params)
+    }
+
     /// Collect fees from a position - smart single entry point
     /// Automatically handles normal positions, wide positions, and accumulated fees
     pub fn collect_fees<'info>(
@@ -197,6 +210,11 @@ pub mod feels {
         instructions::cleanup_bonding_curve(ctx)
     }
 
+    /// Permissionless crank to clear unreferenced ticks from a TickArray
+    pub fn cleanup_tick_array(ctx: Context<CleanupTickArray>) -> Result<()> {
+        instructions::cleanup_tick_array(ctx)
+    }
+
     /// Open a position with NFT metadata
     pub fn open_position_with_metadata(
         ctx: Context<OpenPositionWithMetadata>,
@@ -369,6 +369,48 @@ pub fn compute_swap_step(
     })
 }
 
+/// Move across a range with no active liquidity
+///
+/// Nothing can be swapped inside an empty range, so the price jumps straight
+/// to the target without consuming input. The outcome mirrors
+/// `compute_swap_step` so callers handle both the same way.
+pub fn skip_empty_range(
+    ctx: &SwapContext,
+    target_sqrt_price: u128,
+    target_tick: Option<i32>,
+) -> Result<StepResult> {
+    let at_bound = match ctx.direction {
+        SwapDirection::ZeroForOne => target_tick.is_some_and(|t| t <= ctx.global_lower_tick),
+        SwapDirection::OneForZero => target_tick.is_some_and(|t| t >= ctx.global_upper_tick),
+    };
+
+    if at_bound {
+        let bound_tick = match ctx.direction {
+            SwapDirection::ZeroForOne => ctx.global_lower_tick,
+            SwapDirection::OneForZero => ctx.global_upper_tick,
+        };
+        return Ok(StepResult {
+            gross_in_used: 0,
+            net_in_used: 0,
+            out: 0,
+            fee: 0,
+            sqrt_next: sqrt_price_from_tick(bound_tick)?,
+            crossed_tick: None,
+            outcome: StepOutcome::PartialAtBound,
+        });
+    }
+
+    Ok(StepResult {
+        gross_in_used: 0,
+        net_in_used: 0,
+        out: 0,
+        fee: 0,
+        sqrt_next: target_sqrt_price,
+        crossed_tick: target_tick,
+        outcome: StepOutcome::ReachedTarget,
+    })
+}
+
 use std::collections::HashMap;
 
 /// Generic tick array iterator for finding next initialized tick
@@ -498,8 +540,15 @@ impl<'info> TickArrayIterator<'info> {
             SwapDirection::OneForZero => 1,  // Search upward
         };
 
-        // Start from the next tick in search direction
-        let start_tick = from_tick + search_direction * self.tick_spacing as i32;
+        // Start from the next aligned tick in search direction. After a
+        // partial step the current tick is usually not aligned, and the
+        // aligned tick just below it has not been crossed yet.
+        let spacing = self.tick_spacing as i32;
+        let aligned_floor = from_tick.div_euclid(spacing) * spacing;
+        let start_tick = match self.direction {
+            SwapDirection::ZeroForOne if aligned_floor != from_tick => aligned_floor,
+            _ => aligned_floor + search_direction * spacing,
+        };
 
         // First, find the array containing the start tick
         if let Some(array_loader) = self.find_loader_for_tick_index(start_tick) {
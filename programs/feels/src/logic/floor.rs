@@ -1,10 +1,44 @@
 //! Floor helpers (MVP)
 
+use crate::{error::FeelsError, utils::sqrt_price_from_tick};
+use anchor_lang::prelude::*;
+use ethnum::U256;
+
 /// Compute candidate floor tick given current tick and buffer
 pub fn candidate_floor_tick(current_tick: i32, floor_buffer_ticks: i32) -> i32 {
     current_tick.saturating_sub(floor_buffer_ticks)
 }
 
+/// Post-swap invariant: FeelsSOL reserves back the circulating supply at the floor
+///
+/// This is the solvency condition update_floor searches on, evaluated at the
+/// market's floor: `price(floor_tick) * circulating <= reserve`, compared in
+/// Q128 like the search. Circulating supply is the project mint supply less
+/// the pool's own inventory, or less `protocol_owned_override` when set.
+pub fn verify_floor_backing(
+    floor_tick: i32,
+    feelssol_reserve: u128,
+    total_supply: u64,
+    pool_inventory: u64,
+    protocol_owned_override: u64,
+) -> Result<()> {
+    let non_circulating = if protocol_owned_override > 0 {
+        protocol_owned_override
+    } else {
+        pool_inventory
+    };
+    let circulating = total_supply.saturating_sub(non_circulating);
+
+    // A Q128 price below 2^192 times a u64 supply always fits in 256 bits
+    let sqrt_price = U256::from(sqrt_price_from_tick(floor_tick)?);
+    let floor_value = sqrt_price * sqrt_price * U256::from(circulating);
+    require!(
+        floor_value <= U256::from(feelssol_reserve) << 128,
+        FeelsError::FloorBackingViolated
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,6 +467,8 @@ mod tests {
             rolling_window_start_slot: 0,
             tick_snapshot_1hr: 0,
             last_snapshot_timestamp: 0,
+            swap_volume_slot: 0,
+            swap_slot_volume_feelssol: 0,
             _reserved: [0; 1],
         }
     }
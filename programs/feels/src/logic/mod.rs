@@ -29,6 +29,8 @@ pub use swap_common::{
     validate_swap_params, execute_swap_transfers, distribute_swap_fees, 
     update_market_state, update_oracle_state, emit_swap_event, 
     validate_slippage_exact_out, validate_slippage, validate_fee_cap, 
+    spot_twap_deviation_bps, spot_twap_reference_tick, validate_spot_twap_deviation,
+    swap_per_slot_cap, record_slot_swap_volume,
     get_swap_accounts, SwapAccounts, SwapResult
 };
 // Export specific items from swap_execution to avoid conflicts
@@ -4,6 +4,7 @@
 
 use crate::error::FeelsError;
 use crate::state::Tick;
+use crate::utils::fee_growth_delta;
 
 /// Position fee accrual result
 #[derive(Debug, Clone, Copy)]
@@ -76,17 +77,17 @@ pub fn calculate_position_fee_accrual(
     };
 
     // Calculate incremental fees owed since last update
-    // NOTE: We use wrapping_sub here because fee growth can legitimately wrap around u128
-    // over the lifetime of a pool. The delta calculation handles this correctly.
+    // NOTE: fee growth can legitimately wrap around u128 over the lifetime of a
+    // pool, so deltas go through `fee_growth_delta` (wrapping subtraction).
     let tokens_owed_0_increment = if position_liquidity > 0 {
-        let fee_growth_delta_0 = fee_growth_inside_0.wrapping_sub(last_fee_growth_inside_0);
+        let fee_growth_delta_0 = fee_growth_delta(fee_growth_inside_0, last_fee_growth_inside_0);
         (fee_growth_delta_0.saturating_mul(position_liquidity) >> 64) as u64
     } else {
         0
     };
 
     let tokens_owed_1_increment = if position_liquidity > 0 {
-        let fee_growth_delta_1 = fee_growth_inside_1.wrapping_sub(last_fee_growth_inside_1);
+        let fee_growth_delta_1 = fee_growth_delta(fee_growth_inside_1, last_fee_growth_inside_1);
         (fee_growth_delta_1.saturating_mul(position_liquidity) >> 64) as u64
     } else {
         0
@@ -4,10 +4,10 @@
 //! update logic used by both swap and swap_exact_out instructions.
 
 use crate::{
-    constants::{BASIS_POINTS_DIVISOR, MARKET_AUTHORITY_SEED},
+    constants::{BASIS_POINTS_DIVISOR, MARKET_AUTHORITY_SEED, SWAP_TWAP_SECONDS},
     error::FeelsError,
     events::SwapExecuted,
-    state::{Buffer, Market, OracleState},
+    state::{Buffer, Market, MarketPhase, OracleState, ProtocolConfig},
     utils::{
         transfer_from_user_to_vault_unchecked, transfer_from_vault_to_user_unchecked,
         validate_amount,
@@ -15,6 +15,7 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
+use ethnum::U256;
 
 /// Common result structure for swap operations
 #[derive(Debug, Clone)]
@@ -262,6 +263,109 @@ pub fn validate_fee_cap(fee_amount: u64, swap_amount: u64, max_total_fee_bps: u1
     Ok(())
 }
 
+/// Divergence of `sqrt_price` from `twap_sqrt_price`, in basis points of price
+pub fn spot_twap_deviation_bps(sqrt_price: u128, twap_sqrt_price: u128) -> u128 {
+    let spot = U256::from(sqrt_price);
+    let twap = U256::from(twap_sqrt_price);
+    let (spot_price, twap_price) = (spot * spot, twap * twap);
+    if twap_price == U256::ZERO {
+        return u128::MAX;
+    }
+    let diff = if spot_price > twap_price {
+        spot_price - twap_price
+    } else {
+        twap_price - spot_price
+    };
+    (diff * U256::from(BASIS_POINTS_DIVISOR) / twap_price)
+        .min(U256::from(u128::MAX))
+        .as_u128()
+}
+
+/// Circuit breaker against single-block price manipulation
+///
+/// Rejects a swap that leaves spot more than `max_deviation_bps` away from
+/// the oracle TWAP. A swap that moves an already-deviated price back towards
+/// the TWAP is still allowed, so the pool can always be arbitraged back into
+/// the band. A threshold of 0 disables the check.
+pub fn validate_spot_twap_deviation(
+    sqrt_price_before: u128,
+    sqrt_price_after: u128,
+    twap_sqrt_price: u128,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    if max_deviation_bps == 0 {
+        return Ok(());
+    }
+    let deviation_after = spot_twap_deviation_bps(sqrt_price_after, twap_sqrt_price);
+    require!(
+        deviation_after <= max_deviation_bps as u128
+            || deviation_after <= spot_twap_deviation_bps(sqrt_price_before, twap_sqrt_price),
+        FeelsError::SpotTwapDeviationExceeded
+    );
+    Ok(())
+}
+
+/// Oracle TWAP tick the spot/TWAP circuit breaker compares a swap against
+///
+/// The TWAP covers `SWAP_TWAP_SECONDS` up to `now` (less while the oracle's
+/// history is shorter), with the time since the last observation counted at
+/// `current_tick`. `None` is the bootstrap exemption: the oracle has nothing
+/// observed before `now`, as in the block the market was created, so there is
+/// no TWAP yet and the breaker is skipped. Any other oracle failure is
+/// returned, so the swap is rejected rather than let through unchecked.
+pub fn spot_twap_reference_tick(
+    oracle: &OracleState,
+    now: i64,
+    current_tick: i32,
+) -> Result<Option<i32>> {
+    match oracle.get_twap_tick_at(now, current_tick, SWAP_TWAP_SECONDS) {
+        Ok(twap_tick) => Ok(Some(twap_tick)),
+        Err(Error::AnchorError(e))
+            if e.error_code_number == u32::from(FeelsError::OracleInsufficientData) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Per-slot swap volume cap for a market in `phase`, in FeelsSOL (0 = unlimited)
+///
+/// Bonding-curve markets get their own, typically tighter, cap to blunt
+/// launch sniping.
+pub fn swap_per_slot_cap(config: &ProtocolConfig, phase: u8) -> u64 {
+    if phase == MarketPhase::BondingCurve as u8 {
+        config.bonding_swap_per_slot_cap_feelssol
+    } else {
+        config.swap_per_slot_cap_feelssol
+    }
+}
+
+/// Add a swap's FeelsSOL volume to the market's running total for `slot`
+///
+/// The total resets when the slot advances. Rejects the swap if it would take
+/// the slot's volume past `cap`; with a cap of 0 the volume is only tracked.
+pub fn record_slot_swap_volume(
+    market: &mut Market,
+    volume_feelssol: u64,
+    cap: u64,
+    slot: u64,
+) -> Result<()> {
+    if market.swap_volume_slot != slot {
+        market.swap_volume_slot = slot;
+        market.swap_slot_volume_feelssol = 0;
+    }
+    let total = market
+        .swap_slot_volume_feelssol
+        .saturating_add(volume_feelssol);
+    require!(
+        cap == 0 || total <= cap,
+        FeelsError::SlotSwapVolumeCapExceeded
+    );
+    market.swap_slot_volume_feelssol = total;
+    Ok(())
+}
+
 /// Common swap account getter to reduce duplication
 pub struct SwapAccounts<'info> {
     pub user_src: &'info Account<'info, TokenAccount>,
@@ -359,4 +463,112 @@ mod tests {
         // No cap (0 = unlimited)
         assert!(validate_fee_cap(1000, 10000, 0).is_ok());
     }
+
+    #[test]
+    fn test_spot_twap_deviation_bps() {
+        let twap = crate::utils::sqrt_price_from_tick(0).unwrap();
+        let up = crate::utils::sqrt_price_from_tick(100).unwrap();
+        let down = crate::utils::sqrt_price_from_tick(-100).unwrap();
+
+        assert_eq!(spot_twap_deviation_bps(twap, twap), 0);
+        // 1.0001^100 ~ 1.01005 and 1.0001^-100 ~ 0.99005
+        assert_eq!(spot_twap_deviation_bps(up, twap), 100);
+        assert_eq!(spot_twap_deviation_bps(down, twap), 99);
+    }
+
+    #[test]
+    fn test_spot_twap_circuit_breaker() {
+        let tick = |t| crate::utils::sqrt_price_from_tick(t).unwrap();
+        let twap = tick(0);
+
+        // Swap within a 100 bps band succeeds
+        assert!(validate_spot_twap_deviation(twap, tick(50), twap, 100).is_ok());
+        assert!(validate_spot_twap_deviation(twap, tick(-90), twap, 100).is_ok());
+
+        // Swap that would push spot out of the band reverts
+        assert!(validate_spot_twap_deviation(twap, tick(200), twap, 100).is_err());
+        assert!(validate_spot_twap_deviation(tick(80), tick(-300), twap, 100).is_err());
+
+        // Already outside the band: moving back towards the TWAP is allowed,
+        // moving further away is not
+        assert!(validate_spot_twap_deviation(tick(500), tick(300), twap, 100).is_ok());
+        assert!(validate_spot_twap_deviation(tick(500), tick(600), twap, 100).is_err());
+
+        // 0 disables the breaker
+        assert!(validate_spot_twap_deviation(twap, tick(5_000), twap, 0).is_ok());
+    }
+
+    #[test]
+    fn test_spot_twap_reference_tick() {
+        let mut oracle = OracleState::default();
+        oracle.initialize(Pubkey::default(), 0, 0, 1_000).unwrap();
+
+        // Bootstrap: nothing observed before the block the market was created in
+        assert_eq!(spot_twap_reference_tick(&oracle, 1_000, 0).unwrap(), None);
+
+        // An idle oracle is averaged up to now at the current tick
+        assert_eq!(
+            spot_twap_reference_tick(&oracle, 1_600, 100).unwrap(),
+            Some(100)
+        );
+
+        // History shorter than the window starts at the oldest observation:
+        // 50s at tick 300 up to the last update, then 100s at tick 400
+        oracle.update(200, 1_100).unwrap();
+        oracle.update(300, 1_150).unwrap();
+        assert_eq!(
+            spot_twap_reference_tick(&oracle, 1_250, 400).unwrap(),
+            Some(366)
+        );
+
+        // A corrupted oracle rejects the swap instead of skipping the breaker
+        let latest = oracle.observation_index as usize;
+        oracle.observations[latest].block_timestamp = 5_000;
+        assert!(spot_twap_reference_tick(&oracle, 1_250, 400).is_err());
+    }
+
+    /// All-zero market, as freshly allocated
+    fn zeroed_market() -> Market {
+        Market::try_deserialize_unchecked(&mut &[0u8; Market::LEN][..]).unwrap()
+    }
+
+    #[test]
+    fn test_slot_swap_volume_cap() {
+        let mut market = zeroed_market();
+
+        // Swaps under the cap in one slot succeed
+        assert!(record_slot_swap_volume(&mut market, 400, 1_000, 7).is_ok());
+        assert!(record_slot_swap_volume(&mut market, 600, 1_000, 7).is_ok());
+        assert_eq!(market.swap_slot_volume_feelssol, 1_000);
+
+        // The one that breaches reverts and is not counted
+        assert!(record_slot_swap_volume(&mut market, 1, 1_000, 7).is_err());
+        assert_eq!(market.swap_slot_volume_feelssol, 1_000);
+
+        // The cap resets in the next slot
+        assert!(record_slot_swap_volume(&mut market, 900, 1_000, 8).is_ok());
+        assert_eq!(market.swap_volume_slot, 8);
+        assert_eq!(market.swap_slot_volume_feelssol, 900);
+
+        // A cap of 0 only tracks
+        assert!(record_slot_swap_volume(&mut market, u64::MAX, 0, 8).is_ok());
+    }
+
+    #[test]
+    fn test_swap_cap_is_tighter_while_bonding() {
+        let mut config =
+            ProtocolConfig::try_deserialize_unchecked(&mut &[0u8; ProtocolConfig::LEN][..])
+                .unwrap();
+        config.swap_per_slot_cap_feelssol = 1_000_000;
+        config.bonding_swap_per_slot_cap_feelssol = 10_000;
+
+        assert_eq!(
+            swap_per_slot_cap(&config, MarketPhase::BondingCurve as u8),
+            10_000
+        );
+        assert_eq!(
+            swap_per_slot_cap(&config, MarketPhase::SteadyState as u8),
+            1_000_000
+        );
+    }
 }
@@ -7,17 +7,19 @@
 //! - Price impact and step outcome handling
 
 use crate::{
-    constants::{MAX_TICKS_CROSSED, TICK_ARRAY_SIZE},
+    constants::MAX_TICKS_CROSSED,
     error::FeelsError,
     logic::jit_safety::{
         calculate_safe_jit_allowance, update_directional_volume, update_price_snapshot, JitBudget,
     },
     logic::{
-        compute_swap_step, maybe_pomm_add_liquidity, update_fee_growth_segment, StepOutcome,
-        SwapContext, SwapDirection, TickArrayIterator, MAX_SWAP_STEPS,
+        compute_swap_step, maybe_pomm_add_liquidity, skip_empty_range, update_fee_growth_segment,
+        StepOutcome, SwapContext, SwapDirection, TickArrayIterator, MAX_SWAP_STEPS,
     },
     state::{Buffer, Market},
-    utils::{apply_liquidity_net, sqrt_price_from_tick, tick_from_sqrt_price},
+    utils::{
+        apply_liquidity_net, get_tick_array_start_index, sqrt_price_from_tick, tick_from_sqrt_price,
+    },
 };
 use anchor_lang::prelude::*;
 
@@ -265,25 +267,19 @@ pub fn execute_swap_steps<'info>(
 
         // Find next initialized tick and precompute target sqrt price
         let next_tick_result = tick_arrays.next_initialized_tick(swap_state.current_tick)?;
-        let (target_tick_opt, target_sqrt_price) = match next_tick_result {
-            Some((tick, _array)) => {
+        let (target_tick_opt, target_sqrt_price, target_array) = match next_tick_result {
+            Some((tick, array)) => {
                 let target_sqrt = sqrt_price_from_tick(tick)?;
-                (Some(tick), target_sqrt)
+                (Some(tick), target_sqrt, Some(array))
             }
             None => {
                 // No more initialized ticks found - check for missing coverage
                 let expected_array_start = match direction {
                     SwapDirection::ZeroForOne => {
-                        ((swap_state.current_tick - 1)
-                            / (TICK_ARRAY_SIZE as i32 * market.tick_spacing as i32))
-                            * TICK_ARRAY_SIZE as i32
-                            * market.tick_spacing as i32
+                        get_tick_array_start_index(swap_state.current_tick - 1, market.tick_spacing)
                     }
                     SwapDirection::OneForZero => {
-                        ((swap_state.current_tick + 1)
-                            / (TICK_ARRAY_SIZE as i32 * market.tick_spacing as i32))
-                            * TICK_ARRAY_SIZE as i32
-                            * market.tick_spacing as i32
+                        get_tick_array_start_index(swap_state.current_tick + 1, market.tick_spacing)
                     }
                 };
 
@@ -310,21 +306,31 @@ pub fn execute_swap_steps<'info>(
                     return Err(FeelsError::MissingTickArrayCoverage.into());
                 }
 
-                // Use precomputed boundary prices
+                // Target the bound itself so the step stops there instead of
+                // retrying from the bound on the next iteration
                 match direction {
-                    SwapDirection::ZeroForOne => (None, floor_lower_sqrt),
-                    SwapDirection::OneForZero => (None, floor_upper_sqrt),
+                    SwapDirection::ZeroForOne => {
+                        (Some(market.global_lower_tick), floor_lower_sqrt, None)
+                    }
+                    SwapDirection::OneForZero => {
+                        (Some(market.global_upper_tick), floor_upper_sqrt, None)
+                    }
                 }
             }
         };
 
-        // Compute swap step with bound awareness
-        let step = compute_swap_step(
-            &swap_ctx,
-            target_sqrt_price,
-            target_tick_opt,
-            swap_state.amount_remaining,
-        )?;
+        // Compute swap step with bound awareness; empty ranges between
+        // positions are crossed without consuming input
+        let step = if swap_ctx.liquidity == 0 {
+            skip_empty_range(&swap_ctx, target_sqrt_price, target_tick_opt)?
+        } else {
+            compute_swap_step(
+                &swap_ctx,
+                target_sqrt_price,
+                target_tick_opt,
+                swap_state.amount_remaining,
+            )?
+        };
 
         // Update swap state
         swap_state.amount_remaining = swap_state
@@ -369,9 +375,14 @@ pub fn execute_swap_steps<'info>(
                     swap_state.ticks_crossed += 1;
 
                     // Apply liquidity net change at crossed tick
-                    // For now, skip liquidity update to fix compilation
-                    // In production, we'd get the tick's liquidity_net from the tick array
-                    // swap_state.liquidity = apply_liquidity_net(...)?;
+                    if let Some(array) = target_array {
+                        let liquidity_net = array
+                            .load()?
+                            .get_tick(crossed_tick_idx, market.tick_spacing)?
+                            .liquidity_net;
+                        swap_state.liquidity =
+                            apply_liquidity_net(direction, swap_state.liquidity, liquidity_net)?;
+                    }
                     swap_ctx.liquidity = swap_state.liquidity;
                 }
             }
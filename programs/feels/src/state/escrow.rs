@@ -3,6 +3,7 @@
 //! Temporary escrow that holds newly minted tokens and mint fees
 //! before market initialization
 
+use super::DeploymentProgress;
 use anchor_lang::prelude::*;
 
 /// Pre-launch escrow account for newly minted tokens
@@ -27,8 +28,11 @@ pub struct PreLaunchEscrow {
     /// Canonical bump for escrow authority PDA
     pub escrow_authority_bump: u8,
 
+    /// Initial liquidity deployed from this escrow against its commitment
+    pub deployment: DeploymentProgress,
+
     /// Reserved space for future expansion
-    pub _reserved: [u8; 128],
+    pub _reserved: [u8; 88],
 }
 
 impl PreLaunchEscrow {
@@ -39,6 +43,7 @@ impl PreLaunchEscrow {
         8 +  // created_at
         32 + // market
         1 +  // escrow_authority_bump
-        128 + // _reserved
+        DeploymentProgress::SIZE + // deployment
+        88 + // _reserved
         7; // padding added by Rust compiler for alignment
 }
@@ -45,3 +45,107 @@ impl InitialLiquidityCommitment {
         (num_positions * (4 + 4 + 16 + 32)) // positions
     }
 }
+
+/// Cumulative progress of an initial liquidity deployment against its commitment
+///
+/// Large launches may deploy the committed escrow across several
+/// transactions. The commitment and the stair layout (anchor tick and step
+/// size) are fixed by the first part; every part adds to the deployed
+/// totals over the same ranges, and the deployment is only complete once
+/// both sides have been deployed in full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DeploymentProgress {
+    /// Protocol token amount committed to initial liquidity
+    pub committed_token: u64,
+    /// FeelsSOL amount committed to initial liquidity
+    pub committed_feelssol: u64,
+    /// Protocol token amount deployed so far
+    pub deployed_token: u64,
+    /// FeelsSOL amount deployed so far
+    pub deployed_feelssol: u64,
+    /// Market tick the stair was laid out from by the first part
+    pub anchor_tick: i32,
+    /// Ticks between stair steps, fixed by the first part
+    pub tick_step_size: i32,
+}
+
+impl DeploymentProgress {
+    pub const SIZE: usize = 8 * 4 + 4 * 2;
+
+    /// Start tracking a deployment of the given committed amounts, laid out
+    /// from `anchor_tick` in steps of `tick_step_size`
+    pub fn new(
+        committed_token: u64,
+        committed_feelssol: u64,
+        anchor_tick: i32,
+        tick_step_size: i32,
+    ) -> Self {
+        Self {
+            committed_token,
+            committed_feelssol,
+            deployed_token: 0,
+            deployed_feelssol: 0,
+            anchor_tick,
+            tick_step_size,
+        }
+    }
+
+    /// Whether a commitment has been recorded yet
+    pub fn is_started(&self) -> bool {
+        self.committed_token > 0 || self.committed_feelssol > 0
+    }
+
+    /// Whether the full committed amounts have been deployed
+    pub fn is_complete(&self) -> bool {
+        self.is_started()
+            && self.deployed_token == self.committed_token
+            && self.deployed_feelssol == self.committed_feelssol
+    }
+
+    /// Amounts still to be deployed (token, FeelsSOL)
+    pub fn remaining(&self) -> (u64, u64) {
+        (
+            self.committed_token.saturating_sub(self.deployed_token),
+            self.committed_feelssol
+                .saturating_sub(self.deployed_feelssol),
+        )
+    }
+
+    /// Resolve the amounts for the next part
+    ///
+    /// Zero for both sides means "everything still outstanding".
+    pub fn part_amounts(&self, token_amount: u64, feelssol_amount: u64) -> (u64, u64) {
+        if token_amount == 0 && feelssol_amount == 0 {
+            self.remaining()
+        } else {
+            (token_amount, feelssol_amount)
+        }
+    }
+
+    /// Record one deployed part, returning whether the deployment is now complete
+    ///
+    /// Rejects any part that would push either side past its commitment.
+    pub fn record(&mut self, token_amount: u64, feelssol_amount: u64) -> Result<bool> {
+        require!(
+            self.is_started(),
+            crate::error::FeelsError::InvalidDeploymentAmount
+        );
+        let (remaining_token, remaining_feelssol) = self.remaining();
+        require!(
+            token_amount <= remaining_token && feelssol_amount <= remaining_feelssol,
+            crate::error::FeelsError::DeploymentExceedsCommitment
+        );
+
+        self.deployed_token += token_amount;
+        self.deployed_feelssol += feelssol_amount;
+        Ok(self.is_complete())
+    }
+
+    /// Whether a started deployment was left unfinished past `expiration_time`
+    ///
+    /// A stalled deployment may be resumed by anyone, so the escrow is not
+    /// stranded if the market authority never sends the remaining parts.
+    pub fn is_stalled(&self, now: i64, expiration_time: i64) -> bool {
+        self.is_started() && !self.is_complete() && now > expiration_time
+    }
+}
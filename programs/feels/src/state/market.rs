@@ -181,6 +181,10 @@ pub struct Market {
     pub tick_snapshot_1hr: i32,
     pub last_snapshot_timestamp: i64,
 
+    /// Per-slot swap volume cap tracking (FeelsSOL side of each swap)
+    pub swap_volume_slot: u64,
+    pub swap_slot_volume_feelssol: u64,
+
     /// Reserved space for future expansion
     pub _reserved: [u8; 1], // Reduced for new fields
 }
@@ -250,6 +254,8 @@ impl Market {
         8 + // rolling_window_start_slot
         4 + // tick_snapshot_1hr
         8 + // last_snapshot_timestamp
+        8 + // swap_volume_slot
+        8 + // swap_slot_volume_feelssol
         1 + // _reserved
         10 + // padding added by Rust compiler for alignment
         6; // Additional alignment padding (increased from 3 to match actual size)
@@ -36,6 +36,67 @@ pub use tick::*;
 pub use token_metadata::*;
 pub use tranche_plan::*;
 
+/// Whether an account was allocated for an older, smaller layout
+///
+/// Accounts are created at their struct's `LEN`. When a struct grows past its
+/// `_reserved` padding, accounts created before the change keep their shorter
+/// data and fail to deserialize as the new layout. Instructions that load such
+/// an account should check it first and either grow it with Anchor's `realloc`
+/// constraint (as `register_pool` does for the pool registry) or reject it
+/// with [`require_current_layout`]. Anyone can also bring an account up to
+/// date with the `migrate_account` instruction.
+///
+/// `expected` is the full size including the discriminator, i.e. `LEN`.
+pub fn needs_migration(account_data_len: usize, expected: usize) -> bool {
+    account_data_len < expected
+}
+
+/// Current `LEN` of each fixed-size program account, keyed by discriminator
+///
+/// `PoolRegistry` and `TranchePlan` hold vectors and are sized by their
+/// contents, so they have no single current size.
+const ACCOUNT_LENS: &[(&[u8], usize)] = {
+    use anchor_lang::Discriminator;
+    &[
+        (Market::DISCRIMINATOR, Market::LEN),
+        (Position::DISCRIMINATOR, Position::LEN),
+        (Buffer::DISCRIMINATOR, Buffer::LEN),
+        (PreLaunchEscrow::DISCRIMINATOR, PreLaunchEscrow::LEN),
+        (SafetyController::DISCRIMINATOR, SafetyController::LEN),
+        (EpochParams::DISCRIMINATOR, EpochParams::LEN),
+        (FeelsHub::DISCRIMINATOR, FeelsHub::LEN),
+        (ProtocolToken::DISCRIMINATOR, ProtocolToken::LEN),
+        (ProtocolConfig::DISCRIMINATOR, ProtocolConfig::LEN),
+        (ProtocolOracle::DISCRIMINATOR, ProtocolOracle::LEN),
+        (OracleState::DISCRIMINATOR, OracleState::LEN),
+        (TickArray::DISCRIMINATOR, TickArray::LEN),
+    ]
+};
+
+/// Current size of the program account whose data starts with `data`
+///
+/// The account type is read from the discriminator. Returns `None` for
+/// unknown and variable-size accounts.
+pub fn current_account_len(data: &[u8]) -> Option<usize> {
+    let discriminator = data.get(..8)?;
+    ACCOUNT_LENS
+        .iter()
+        .find(|(d, _)| *d == discriminator)
+        .map(|(_, len)| *len)
+}
+
+/// Reject an account allocated for an older layout
+pub fn require_current_layout(
+    account: &anchor_lang::prelude::AccountInfo,
+    expected: usize,
+) -> anchor_lang::Result<()> {
+    anchor_lang::require!(
+        !needs_migration(account.data_len(), expected),
+        crate::error::FeelsError::AccountNeedsMigration
+    );
+    Ok(())
+}
+
 // Compile-time assertions for account struct sizes
 // These ensure our structs maintain expected memory layout
 #[cfg(test)]
@@ -124,3 +185,41 @@ mod size_assertions {
         assert_eq!(std::mem::size_of::<TickArray>() + 8, TickArray::LEN);
     }
 }
+
+#[cfg(test)]
+mod migration_tests {
+    use super::*;
+    use anchor_lang::prelude::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_undersized_account_needs_migration() {
+        // Account created before the last 32 bytes were added to Market
+        let mut data = vec![0u8; Market::LEN - 32];
+        data[..8].copy_from_slice(Market::DISCRIMINATOR);
+        assert!(needs_migration(data.len(), Market::LEN));
+        assert!(Market::try_deserialize(&mut data.as_slice()).is_err());
+
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let err = require_current_layout(&info, Market::LEN).unwrap_err();
+        assert_eq!(err, crate::error::FeelsError::AccountNeedsMigration.into());
+    }
+
+    #[test]
+    fn test_current_or_larger_account_is_accepted() {
+        assert!(!needs_migration(Market::LEN, Market::LEN));
+        assert!(!needs_migration(Market::LEN + 64, Market::LEN));
+    }
+}
@@ -204,6 +204,64 @@ impl OracleState {
 
         Ok(avg_tick)
     }
+
+    /// Calculate the TWAP tick over a period ending at `current_timestamp`
+    ///
+    /// Unlike `get_twap_tick`, which ends at the latest observation, the time
+    /// since that observation is counted at `current_tick`, so an oracle that
+    /// hasn't been written to for a while still averages up to now. When no
+    /// observation is as old as the period, the average starts at the oldest
+    /// one instead. Fails with `OracleInsufficientData` only if nothing was
+    /// observed before `current_timestamp`.
+    pub fn get_twap_tick_at(
+        &self,
+        current_timestamp: i64,
+        current_tick: i32,
+        seconds_ago: u32,
+    ) -> Result<i32> {
+        let target_timestamp = current_timestamp
+            .checked_sub(seconds_ago as i64)
+            .ok_or(FeelsError::MathOverflow)?;
+        let initialized = || {
+            self.observations[..self.observation_cardinality as usize]
+                .iter()
+                .filter(|obs| obs.initialized)
+        };
+
+        // Newest observation at or before the start of the period, falling
+        // back to the oldest one for a history shorter than the period
+        let start_observation = initialized()
+            .filter(|obs| obs.block_timestamp <= target_timestamp)
+            .max_by_key(|obs| obs.block_timestamp)
+            .or_else(|| initialized().min_by_key(|obs| obs.block_timestamp))
+            .ok_or(FeelsError::OracleInsufficientData)?;
+        let latest_observation = &self.observations[self.observation_index as usize];
+
+        // Observations from the future can only come from a corrupted oracle
+        require!(
+            latest_observation.initialized
+                && latest_observation.block_timestamp <= current_timestamp
+                && start_observation.block_timestamp <= latest_observation.block_timestamp,
+            FeelsError::InvalidTimestamp
+        );
+
+        let time_delta = current_timestamp - start_observation.block_timestamp;
+        require!(time_delta > 0, FeelsError::OracleInsufficientData);
+
+        let tick_cumulative_now = latest_observation
+            .tick_cumulative
+            .checked_add(
+                (current_tick as i128)
+                    .checked_mul((current_timestamp - latest_observation.block_timestamp) as i128)
+                    .ok_or(FeelsError::MathOverflow)?,
+            )
+            .ok_or(FeelsError::MathOverflow)?;
+        let tick_delta = tick_cumulative_now
+            .checked_sub(start_observation.tick_cumulative)
+            .ok_or(FeelsError::MathOverflow)?;
+
+        Ok((tick_delta / time_delta as i128) as i32)
+    }
 }
 
 #[cfg(test)]
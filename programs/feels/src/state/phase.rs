@@ -2,6 +2,7 @@
 //!
 //! Defines the lifecycle phases of a market from launch to steady state
 
+use crate::state::PoolPhase;
 use anchor_lang::prelude::*;
 
 /// Market lifecycle phase
@@ -56,7 +57,12 @@ impl MarketPhase {
     }
 
     /// Validate phase transition
+    ///
+    /// Staying in the same phase is not a transition and is rejected.
     pub fn can_transition_to(&self, new_phase: MarketPhase) -> bool {
+        if *self == new_phase {
+            return false;
+        }
         match (self, new_phase) {
             // Creation flow
             (MarketPhase::Created, MarketPhase::BondingCurve) => true,
@@ -79,6 +85,21 @@ impl MarketPhase {
     }
 }
 
+/// Validate a pool registry phase transition
+///
+/// Pools graduate from bonding to steady state, may be paused and resumed
+/// while live, and may be deprecated from any phase. Deprecation is final.
+pub fn can_transition(from: PoolPhase, to: PoolPhase) -> bool {
+    match (from, to) {
+        (PoolPhase::BondingCurve, PoolPhase::SteadyState) => true,
+        (PoolPhase::BondingCurve | PoolPhase::SteadyState, PoolPhase::Paused) => true,
+        (PoolPhase::Paused, PoolPhase::BondingCurve | PoolPhase::SteadyState) => true,
+        (PoolPhase::Deprecated, _) => false,
+        (_, PoolPhase::Deprecated) => true,
+        _ => false,
+    }
+}
+
 /// Phase transition event data
 #[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize)]
 pub struct PhaseTransition {
@@ -111,6 +132,72 @@ pub enum PhaseTrigger {
     Creator,
 }
 
+/// Trading status of a pool, mirrored by `Market::is_paused`
+#[derive(Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+#[repr(u8)]
+pub enum PoolStatus {
+    /// Trading enabled
+    Normal = 0,
+
+    /// Trading disabled
+    Disabled = 1,
+}
+
+impl PoolStatus {
+    pub fn from_paused(is_paused: bool) -> Self {
+        if is_paused {
+            PoolStatus::Disabled
+        } else {
+            PoolStatus::Normal
+        }
+    }
+}
+
+/// Why a pool's trading status changed
+#[derive(Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+#[repr(u8)]
+pub enum PoolStatusReason {
+    /// Spot price stress (safety circuit breaker)
+    SpotStress = 0,
+
+    /// Time-based stress (stale or expired market)
+    TimeStress = 1,
+
+    /// Leverage stress (reserved for leveraged markets)
+    LeverageStress = 2,
+
+    /// Manual disable by creator or governance
+    ManualDisable = 3,
+
+    /// Re-enabled after the disable condition cleared
+    Cooldown = 4,
+}
+
+impl PoolStatusReason {
+    /// Classify a status change from the trigger that caused it
+    pub fn from_trigger(new_status: PoolStatus, trigger: PhaseTrigger) -> Self {
+        match (new_status, trigger) {
+            (PoolStatus::Normal, _) => PoolStatusReason::Cooldown,
+            (PoolStatus::Disabled, PhaseTrigger::SafetyTrigger) => PoolStatusReason::SpotStress,
+            (PoolStatus::Disabled, PhaseTrigger::TimeElapsed) => PoolStatusReason::TimeStress,
+            (PoolStatus::Disabled, _) => PoolStatusReason::ManualDisable,
+        }
+    }
+}
+
+/// Stress readings at the moment a pool's trading status changes
+#[derive(Clone, Copy, Debug, Default, PartialEq, AnchorSerialize, AnchorDeserialize)]
+pub struct PoolStressComponents {
+    /// Spot deviation from the oracle TWAP in bps (0 without TWAP history)
+    pub spot_stress_bps: u64,
+
+    /// Seconds spent in the phase being left
+    pub time_stress_secs: i64,
+
+    /// Leverage stress in bps (always 0 until leveraged markets exist)
+    pub leverage_stress_bps: u64,
+}
+
 /// Phase-specific parameters
 #[derive(Clone, Debug, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct PhaseParams {
@@ -133,6 +133,11 @@ impl PoolRegistry {
             .find(|p| p.market == *market)
             .ok_or(crate::error::FeelsError::PoolNotFound)?;
 
+        require!(
+            crate::state::can_transition(pool.phase, new_phase),
+            crate::error::FeelsError::InvalidPhaseTransition
+        );
+
         pool.phase = new_phase;
         pool.updated_at = timestamp;
         Ok(())
@@ -41,8 +41,11 @@ pub struct ProtocolConfig {
     /// DEX whitelist (venues/pools) - fixed size for MVP
     pub dex_whitelist: [Pubkey; 8],
     pub dex_whitelist_len: u8,
+    /// Swap circuit breaker: max divergence of spot from the oracle TWAP a
+    /// swap may cause (bps of price). 0 = disabled
+    pub max_spot_twap_deviation_bps: u16,
     /// Reserved for future protocol parameters
-    pub _reserved: [u8; 7],
+    pub _reserved: [u8; 5],
     /// Optional per-slot caps for mint/redeem (FeelsSOL units). 0 = unlimited.
     pub mint_per_slot_cap_feelssol: u64,
     pub redeem_per_slot_cap_feelssol: u64,
@@ -56,6 +59,13 @@ pub struct ProtocolConfig {
     pub default_initial_sqrt_price: u128,
     /// Default tick step size for bonding curve deployment
     pub default_tick_step_size: u16,
+
+    /// Per-slot swap volume caps per market (FeelsSOL units). 0 = unlimited.
+    /// Markets still on their bonding curve use the bonding cap.
+    /// Appended after the defaults so configs migrated from the previous
+    /// layout keep their offsets and read zero (no cap) here.
+    pub swap_per_slot_cap_feelssol: u64,
+    pub bonding_swap_per_slot_cap_feelssol: u64,
 }
 
 impl ProtocolConfig {
@@ -75,13 +85,16 @@ impl ProtocolConfig {
         32 + // dex_twap_updater
         (32*8) + // dex_whitelist
         1 + // dex_whitelist_len
-        7 +  // _reserved
+        2 +  // max_spot_twap_deviation_bps
+        5 +  // _reserved
         8 +  // mint_per_slot_cap_feelssol
         8 + // redeem_per_slot_cap_feelssol
         2 +  // default_base_fee_bps
         2 +  // default_tick_spacing
         16 + // default_initial_sqrt_price (u128)
-        2; // default_tick_step_size
+        2 + // default_tick_step_size
+        8 + // swap_per_slot_cap_feelssol
+        8; // bonding_swap_per_slot_cap_feelssol
 
     /// Seed for deriving the protocol config PDA
     pub const SEED: &'static [u8] = b"protocol_config";
@@ -160,6 +160,23 @@ impl TickArray {
         t.fee_growth_outside_1_x64 = fee_growth_global_1_x64.wrapping_sub(prev1);
         Ok(())
     }
+
+    /// Deinitialize every tick no position references any more.
+    /// A tick with zero gross liquidity is not a boundary of any open
+    /// position (and so also has zero net), so clearing it cannot change
+    /// fee accounting; it is re-initialized when a position next uses it.
+    /// Returns the number of ticks cleared.
+    pub fn clear_unreferenced_ticks(&mut self) -> u16 {
+        let mut cleared = 0u16;
+        for t in self.ticks.iter_mut() {
+            if t.initialized == 1 && t.liquidity_gross == 0 {
+                *t = Tick::default();
+                cleared += 1;
+            }
+        }
+        self.initialized_tick_count = self.initialized_tick_count.saturating_sub(cleared);
+        cleared
+    }
 }
 
 // Compile-time size assertions guarantee zero_copy layout stays stable
@@ -65,6 +65,17 @@ pub fn calculate_fee_ceil(amount: u64, fee_bps: u16) -> Result<u64> {
     mul_div_ceil_u64(amount, fee_bps as u64, 10000)
 }
 
+/// Fee growth accrued between two readings of a fee growth accumulator
+///
+/// Fee growth accumulators (global, outside and inside) are allowed to wrap
+/// around u128 over a market's lifetime. Only differences are meaningful, and
+/// they stay correct under wrapping as long as less than one full u128 of
+/// growth accrues between readings. A checked or saturating subtraction
+/// would instead report zero fees once the accumulator wraps.
+pub fn fee_growth_delta(current: u128, last: u128) -> u128 {
+    current.wrapping_sub(last)
+}
+
 /// Simple square root for u128 (Newton's method)
 #[allow(dead_code)]
 fn sqrt_u128(n: u128) -> Result<u128> {
@@ -3,7 +3,11 @@
 //! Provides comprehensive validation for instruction parameters to prevent
 //! malicious or invalid inputs that could compromise market integrity.
 
-use crate::{constants::*, error::FeelsError};
+use crate::{
+    constants::*,
+    error::FeelsError,
+    utils::{sqrt_price_from_tick, tick_from_sqrt_price, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+};
 use anchor_lang::prelude::*;
 
 /// Validate base fee in basis points
@@ -11,10 +15,8 @@ pub fn validate_base_fee_bps(fee_bps: u16) -> Result<()> {
     // Minimum fee to prevent zero-fee exploitation
     const MIN_BASE_FEE_BPS: u16 = 1; // 0.01%
 
-    require!(
-        (MIN_BASE_FEE_BPS..=MAX_FEE_BPS).contains(&fee_bps),
-        FeelsError::InvalidPrice
-    );
+    require!(fee_bps >= MIN_BASE_FEE_BPS, FeelsError::InvalidParameter);
+    require!(fee_bps <= MAX_FEE_BPS, FeelsError::FeeTooHigh);
 
     // Warn if fee is unusually high
     if fee_bps > 100 {
@@ -25,6 +27,35 @@ pub fn validate_base_fee_bps(fee_bps: u16) -> Result<()> {
     Ok(())
 }
 
+/// Validate protocol-level fee rates (basis points of collected fees)
+///
+/// The protocol share may not exceed its configured maximum, and the protocol
+/// and creator shares together may not exceed `MAX_FEE_BPS`.
+pub fn validate_protocol_fee_rates(
+    protocol_fee_rate: u16,
+    creator_fee_rate: u16,
+    max_protocol_fee_rate: u16,
+) -> Result<()> {
+    // Maximum creator share of collected fees: 5%
+    const MAX_CREATOR_FEE_RATE: u16 = 500;
+
+    require!(max_protocol_fee_rate <= MAX_FEE_BPS, FeelsError::FeeTooHigh);
+    require!(
+        protocol_fee_rate <= max_protocol_fee_rate,
+        FeelsError::FeeTooHigh
+    );
+    require!(
+        creator_fee_rate <= MAX_CREATOR_FEE_RATE,
+        FeelsError::FeeTooHigh
+    );
+    require!(
+        protocol_fee_rate as u32 + creator_fee_rate as u32 <= MAX_FEE_BPS as u32,
+        FeelsError::FeeTooHigh
+    );
+
+    Ok(())
+}
+
 /// Validate tick spacing parameters
 pub fn validate_tick_spacing_param(tick_spacing: u16) -> Result<()> {
     // Valid tick spacings are powers of 2 for efficiency
@@ -40,11 +71,6 @@ pub fn validate_tick_spacing_param(tick_spacing: u16) -> Result<()> {
 
 /// Validate initial sqrt price
 pub fn validate_initial_sqrt_price(sqrt_price: u128) -> Result<()> {
-    // Minimum sqrt price (prevents extreme prices)
-    const MIN_SQRT_PRICE: u128 = 4295048016; // ~1e-9 price
-                                             // Maximum sqrt price (prevents overflow)
-    const MAX_SQRT_PRICE: u128 = 79226673515401279992447579055; // ~1e9 price
-
     require!(
         (MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price),
         FeelsError::InvalidPrice
@@ -53,6 +79,29 @@ pub fn validate_initial_sqrt_price(sqrt_price: u128) -> Result<()> {
     Ok(())
 }
 
+/// Validate the initial sqrt price and align its tick to `tick_spacing`
+///
+/// Returns the sqrt price to store and its tick. A price whose tick is not a
+/// multiple of `tick_spacing` is snapped to the nearest aligned tick inside the
+/// valid tick range.
+pub fn align_initial_sqrt_price(sqrt_price: u128, tick_spacing: u16) -> Result<(u128, i32)> {
+    validate_initial_sqrt_price(sqrt_price)?;
+    require!(tick_spacing > 0, FeelsError::InvalidTickSpacing);
+
+    let spacing = tick_spacing as i32;
+    let tick = tick_from_sqrt_price(sqrt_price)?;
+    if tick % spacing == 0 {
+        return Ok((sqrt_price, tick));
+    }
+
+    let min_aligned = -((-MIN_TICK) / spacing) * spacing;
+    let max_aligned = (MAX_TICK / spacing) * spacing;
+    let aligned =
+        ((tick + spacing / 2).div_euclid(spacing) * spacing).clamp(min_aligned, max_aligned);
+
+    Ok((sqrt_price_from_tick(aligned)?, aligned))
+}
+
 /// Validate tick range parameters
 pub fn validate_tick_range_params(
     tick_lower: i32,
@@ -110,6 +159,35 @@ pub fn validate_liquidity_amount(liquidity: u128) -> Result<()> {
     Ok(())
 }
 
+/// Validate the size of a new liquidity position
+///
+/// Rejects liquidity below `MIN_POSITION_LIQUIDITY`, and deposits that round
+/// to zero on a side the position needs: both sides while the price is inside
+/// the range, otherwise the single side it is made of.
+pub fn validate_position_size(
+    liquidity: u128,
+    sqrt_price: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<()> {
+    require!(
+        liquidity >= MIN_POSITION_LIQUIDITY,
+        FeelsError::LiquidityBelowMinimum
+    );
+
+    let in_range = sqrt_price > sqrt_price_lower && sqrt_price < sqrt_price_upper;
+    let funded = if in_range {
+        amount_0 > 0 && amount_1 > 0
+    } else {
+        amount_0 > 0 || amount_1 > 0
+    };
+    require!(funded, FeelsError::PositionAmountRoundsToZero);
+
+    Ok(())
+}
+
 /// Validate swap amount
 pub fn validate_swap_amount(amount: u64, is_exact_out: bool) -> Result<()> {
     require!(amount > 0, FeelsError::ZeroAmount);
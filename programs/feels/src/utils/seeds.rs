@@ -78,3 +78,32 @@ pub fn derive_metadata(mint: &Pubkey, metadata_program_id: &Pubkey) -> (Pubkey,
         metadata_program_id,
     )
 }
+
+/// Derive any PDA listed in `PDA_SEEDS` by name
+///
+/// `inputs` supplies the dynamic components in order (32-byte keys, 4-byte
+/// little-endian ticks). Returns `None` for an unknown name or malformed inputs.
+pub fn derive_pda(name: &str, inputs: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    let pattern = PDA_SEEDS.iter().find(|p| p.name == name)?;
+    let mut inputs = inputs.iter();
+    let mut seeds: Vec<&[u8]> = Vec::with_capacity(pattern.components.len());
+
+    for component in pattern.components {
+        match component {
+            SeedComponent::Literal(bytes) => seeds.push(*bytes),
+            SeedComponent::Pubkey(_) => {
+                let input = inputs.next().filter(|i| i.len() == 32)?;
+                seeds.push(*input);
+            }
+            SeedComponent::I32(_) => {
+                let input = inputs.next().filter(|i| i.len() == 4)?;
+                seeds.push(*input);
+            }
+        }
+    }
+
+    if inputs.next().is_some() {
+        return None;
+    }
+    Some(Pubkey::find_program_address(&seeds, program_id))
+}
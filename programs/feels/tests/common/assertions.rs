@@ -190,6 +190,25 @@ impl SwapAssertions for SwapResult {
     }
 }
 
+/// Assert that a transaction failed with the given program error
+pub fn assert_feels_error<T>(result: TestResult<T>, expected: feels::error::FeelsError) {
+    let name = format!("{:?}", expected);
+    let code = u32::from(expected);
+    match result {
+        Ok(_) => panic!("Expected {} ({:#x}), but the transaction succeeded", name, code),
+        Err(e) => {
+            let message = e.to_string();
+            assert!(
+                message.contains(&format!("custom program error: {:#x}", code)),
+                "Expected {} ({:#x}), got: {}",
+                name,
+                code,
+                message
+            );
+        }
+    }
+}
+
 // Helper macro for common test assertions
 #[macro_export]
 macro_rules! assert_tx_success {
@@ -1,9 +1,10 @@
 //! Unified test client interface that works with both ProgramTest and RPC
 
+use super::time::TimeUtils;
 use super::*;
 use anchor_lang::AccountDeserialize;
 use solana_program::program_pack::Pack;
-use solana_program_test::{BanksClient, ProgramTest};
+use solana_program_test::{BanksClient, BanksClientError, ProgramTest, ProgramTestContext};
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::transaction::Transaction;
 use spl_token::state::Account as TokenAccount;
@@ -27,6 +28,46 @@ impl TestClient {
         }
     }
 
+    /// Process a single instruction, returning the program logs with the result
+    pub async fn process_instruction_with_logs(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (TestResult<()>, Vec<String>) {
+        match self {
+            TestClient::InMemory(client) => {
+                let (result, logs) = client
+                    .process_instruction_with_logs(instruction, signers)
+                    .await;
+                (result.map_err(Into::into), logs)
+            }
+            TestClient::Devnet(client) => (
+                client.process_instruction(instruction, signers).await,
+                Vec::new(),
+            ),
+        }
+    }
+
+    /// Process a single instruction, returning the compute units it consumed
+    pub async fn process_instruction_measuring_cu(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (TestResult<()>, u64) {
+        match self {
+            TestClient::InMemory(client) => {
+                let (result, units) = client
+                    .process_instruction_measuring_cu(instruction, signers)
+                    .await;
+                (result.map_err(Into::into), units)
+            }
+            TestClient::Devnet(client) => (
+                client.process_instruction(instruction, signers).await,
+                0,
+            ),
+        }
+    }
+
     /// Process multiple instructions in a single transaction
     pub async fn process_transaction(
         &mut self,
@@ -59,13 +100,26 @@ impl TestClient {
     }
 
     /// Set account data directly (for testing)
-    pub fn set_account_data(&mut self, address: &Pubkey, data: Vec<u8>) -> TestResult<()> {
+    pub async fn set_account_data(&mut self, address: &Pubkey, data: Vec<u8>) -> TestResult<()> {
         match self {
-            TestClient::InMemory(client) => client.set_account_data(address, data),
+            TestClient::InMemory(client) => client.set_account_data(address, data).await,
             TestClient::Devnet(_) => Err("Cannot set account data in devnet tests".into()),
         }
     }
 
+    /// Create or overwrite an account owned by `owner` (for testing)
+    pub async fn set_account(
+        &mut self,
+        address: &Pubkey,
+        owner: &Pubkey,
+        data: Vec<u8>,
+    ) -> TestResult<()> {
+        match self {
+            TestClient::InMemory(client) => client.set_account(address, owner, data).await,
+            TestClient::Devnet(_) => Err("Cannot set accounts in devnet tests".into()),
+        }
+    }
+
     /// Get token account balance
     pub async fn get_token_balance(&mut self, address: &Pubkey) -> TestResult<u64> {
         match self {
@@ -98,6 +152,14 @@ impl TestClient {
         }
     }
 
+    /// Advance the clock by `slots`, moving the timestamp forward with them
+    pub async fn warp_forward_slots(&mut self, slots: u64) -> TestResult<()> {
+        match self {
+            TestClient::InMemory(client) => client.warp_forward_slots(slots).await,
+            TestClient::Devnet(client) => client.warp_forward_slots(slots).await,
+        }
+    }
+
     /// Get current slot
     pub async fn get_slot(&mut self) -> TestResult<u64> {
         match self {
@@ -106,6 +168,22 @@ impl TestClient {
         }
     }
 
+    /// Slot from the Clock sysvar
+    pub async fn current_slot(&mut self) -> TestResult<u64> {
+        match self {
+            TestClient::InMemory(client) => client.current_slot().await,
+            TestClient::Devnet(client) => client.current_slot().await,
+        }
+    }
+
+    /// Unix timestamp from the Clock sysvar
+    pub async fn current_unix_timestamp(&mut self) -> TestResult<i64> {
+        match self {
+            TestClient::InMemory(client) => client.current_unix_timestamp().await,
+            TestClient::Devnet(client) => client.current_unix_timestamp().await,
+        }
+    }
+
     /// Get the payer pubkey
     pub fn payer(&self) -> Pubkey {
         match self {
@@ -128,6 +206,8 @@ pub struct InMemoryClient {
     pub banks_client: BanksClient,
     pub payer: Keypair,
     pub last_blockhash: Hash,
+    /// Handle for warping the bank's clock
+    context: ProgramTestContext,
 }
 
 impl InMemoryClient {
@@ -223,7 +303,10 @@ impl InMemoryClient {
         // Increase compute units for complex operations
         program_test.set_compute_max_units(2_000_000);
 
-        let (banks_client, payer, recent_blockhash) = program_test.start().await;
+        let context = program_test.start_with_context().await;
+        let banks_client = context.banks_client.clone();
+        let payer = context.payer.insecure_clone();
+        let recent_blockhash = context.last_blockhash;
 
         // Fund the payer account with SOL for transaction fees
         // The payer should already have SOL from ProgramTest, but let's check
@@ -240,6 +323,7 @@ impl InMemoryClient {
             banks_client,
             payer,
             last_blockhash: recent_blockhash,
+            context,
         })
     }
 }
@@ -258,6 +342,63 @@ impl InMemoryClient {
         instructions: &[Instruction],
         signers: &[&Keypair],
     ) -> TestResult<()> {
+        let tx = self.signed_transaction(instructions, signers).await?;
+        self.banks_client.process_transaction(tx).await?;
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+
+    /// Process a single instruction and capture the program logs
+    ///
+    /// Logs are returned whether or not the transaction succeeds, so failing
+    /// tests can show what the program printed before it errored.
+    pub async fn process_instruction_with_logs(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (std::result::Result<(), BanksClientError>, Vec<String>) {
+        let (result, logs, _) = self.process_with_metadata(instruction, signers).await;
+        (result, logs)
+    }
+
+    /// Process a single instruction and report the compute units it consumed
+    pub async fn process_instruction_measuring_cu(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (std::result::Result<(), BanksClientError>, u64) {
+        let (result, _, units) = self.process_with_metadata(instruction, signers).await;
+        (result, units)
+    }
+
+    /// Process an instruction, returning its logs and compute units consumed
+    async fn process_with_metadata(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (std::result::Result<(), BanksClientError>, Vec<String>, u64) {
+        let tx = match self.signed_transaction(&[instruction], signers).await {
+            Ok(tx) => tx,
+            Err(e) => return (Err(e), Vec::new(), 0),
+        };
+
+        match self.banks_client.process_transaction_with_metadata(tx).await {
+            Ok(outcome) => {
+                let (logs, units) = outcome
+                    .metadata
+                    .map(|metadata| (metadata.log_messages, metadata.compute_units_consumed))
+                    .unwrap_or_default();
+                (outcome.result.map_err(Into::into), logs, units)
+            }
+            Err(e) => (Err(e), Vec::new(), 0),
+        }
+    }
+
+    /// Sign `instructions` with a fresh blockhash, adding the payer if needed
+    async fn signed_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<Transaction, BanksClientError> {
         // Update blockhash
         self.last_blockhash = self.banks_client.get_latest_blockhash().await?;
 
@@ -272,15 +413,12 @@ impl InMemoryClient {
         }
         all_signers.extend(signers);
 
-        let tx = Transaction::new_signed_with_payer(
+        Ok(Transaction::new_signed_with_payer(
             instructions,
             Some(&payer_pubkey),
             &all_signers,
             self.last_blockhash,
-        );
-
-        self.banks_client.process_transaction(tx).await?;
-        Ok::<(), Box<dyn std::error::Error>>(())
+        ))
     }
 
     pub async fn get_account<T: AccountDeserialize>(
@@ -310,12 +448,37 @@ impl InMemoryClient {
         }
     }
 
-    /// Set account data directly (for testing)
-    /// Note: This is not feasible with BanksClient - account data must be set through transactions
-    pub fn set_account_data(&mut self, _address: &Pubkey, _data: Vec<u8>) -> TestResult<()> {
-        // BanksClient doesn't support direct account data setting
-        // Account data can only be modified through program instructions
-        Err("Direct account data setting not supported in BanksClient tests".into())
+    /// Set account data directly (for testing), keeping the account's owner
+    pub async fn set_account_data(&mut self, address: &Pubkey, data: Vec<u8>) -> TestResult<()> {
+        let owner = self
+            .banks_client
+            .get_account(*address)
+            .await?
+            .ok_or("Cannot set data on a missing account")?
+            .owner;
+        self.set_account(address, &owner, data).await
+    }
+
+    /// Create or overwrite an account, funded to be rent exempt
+    ///
+    /// Lets tests start from program state that cannot be reached through
+    /// instructions in this environment (e.g. launches that need Metaplex).
+    pub async fn set_account(
+        &mut self,
+        address: &Pubkey,
+        owner: &Pubkey,
+        data: Vec<u8>,
+    ) -> TestResult<()> {
+        let rent = self.banks_client.get_rent().await?;
+        let account = solana_sdk::account::Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: *owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.context.set_account(address, &account.into());
+        Ok(())
     }
 
     pub async fn get_token_balance(&mut self, address: &Pubkey) -> TestResult<u64> {
@@ -343,29 +506,40 @@ impl InMemoryClient {
     }
 
     pub async fn advance_time(&mut self, seconds: i64) -> TestResult<()> {
-        use solana_program_test::tokio::time::{sleep, Duration};
-
-        // Get current slot
-        let current_slot = self.banks_client.get_root_slot().await?;
+        self.warp(TimeUtils::seconds_to_slots(seconds), seconds).await
+    }
 
-        // Advance slots (assuming ~400ms per slot)
-        let slots_to_advance = (seconds * 1000 / 400).max(1) as u64;
-        let target_slot = current_slot + slots_to_advance;
+    pub async fn warp_forward_slots(&mut self, slots: u64) -> TestResult<()> {
+        self.warp(slots, TimeUtils::slots_to_seconds(slots)).await
+    }
 
-        // Note: warp_to_slot is not available in current BanksClient
-        // For now, we'll just sleep to simulate time passing
+    /// Warp at least one slot ahead and set the clock `seconds` later
+    ///
+    /// `warp_to_slot` alone leaves the timestamp wherever the bank puts it,
+    /// so it is pinned afterwards to keep slot- and time-based logic in step.
+    async fn warp(&mut self, slots: u64, seconds: i64) -> TestResult<()> {
+        let start: solana_program::clock::Clock = self.banks_client.get_sysvar().await?;
+        let target_slot = start.slot + slots.max(1);
+        self.context.warp_to_slot(target_slot)?;
 
-        // Also update clock sysvar
         let mut clock: solana_program::clock::Clock = self.banks_client.get_sysvar().await?;
-        clock.unix_timestamp += seconds;
-        clock.slot = target_slot;
-
-        // Small delay to ensure state updates
-        sleep(Duration::from_millis(10)).await;
+        clock.unix_timestamp = start.unix_timestamp + seconds;
+        self.context.set_sysvar(&clock);
 
+        self.last_blockhash = self.banks_client.get_latest_blockhash().await?;
         Ok::<(), Box<dyn std::error::Error>>(())
     }
 
+    pub async fn current_slot(&mut self) -> TestResult<u64> {
+        let clock: solana_program::clock::Clock = self.banks_client.get_sysvar().await?;
+        Ok(clock.slot)
+    }
+
+    pub async fn current_unix_timestamp(&mut self) -> TestResult<i64> {
+        let clock: solana_program::clock::Clock = self.banks_client.get_sysvar().await?;
+        Ok(clock.unix_timestamp)
+    }
+
     pub async fn get_slot(&mut self) -> TestResult<u64> {
         Ok(self.banks_client.get_root_slot().await?)
     }
@@ -457,10 +631,22 @@ impl DevnetClient {
         Err("DevnetClient is a stub - use InMemoryClient for unit tests".into())
     }
 
+    pub async fn warp_forward_slots(&mut self, _slots: u64) -> TestResult<()> {
+        Err("DevnetClient is a stub - use InMemoryClient for unit tests".into())
+    }
+
     pub async fn get_slot(&mut self) -> TestResult<u64> {
         Err("DevnetClient is a stub - use InMemoryClient for unit tests".into())
     }
 
+    pub async fn current_slot(&mut self) -> TestResult<u64> {
+        Err("DevnetClient is a stub - use InMemoryClient for unit tests".into())
+    }
+
+    pub async fn current_unix_timestamp(&mut self) -> TestResult<i64> {
+        Err("DevnetClient is a stub - use InMemoryClient for unit tests".into())
+    }
+
     pub fn payer(&self) -> Pubkey {
         self.payer.pubkey()
     }
@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::common::client::{DevnetClient, InMemoryClient};
+use crate::common::keys::KeypairSource;
 use anchor_lang::prelude::*;
 use solana_program::program_pack::Pack;
 use solana_sdk::signature::{Keypair, Signer};
@@ -29,6 +30,115 @@ impl TestAccounts {
             protocol_treasury: Keypair::new(),
         }
     }
+
+    /// Accounts drawn from `keys`, reproducible from its seed
+    pub fn from_source(keys: &mut KeypairSource) -> Self {
+        Self {
+            alice: keys.next_keypair(),
+            bob: keys.next_keypair(),
+            charlie: keys.next_keypair(),
+            market_creator: keys.next_keypair(),
+            fee_collector: keys.next_keypair(),
+            protocol_treasury: keys.next_keypair(),
+        }
+    }
+}
+
+/// Fee settings the protocol is initialized with
+#[derive(Clone, Debug)]
+pub struct ProtocolFees {
+    pub mint_fee: u64,
+    pub default_protocol_fee_rate: u16,
+    pub default_creator_fee_rate: u16,
+    pub max_protocol_fee_rate: u16,
+}
+
+impl Default for ProtocolFees {
+    fn default() -> Self {
+        Self {
+            mint_fee: 0,                    // No fee for testing
+            default_protocol_fee_rate: 30,  // 0.3% for testing
+            default_creator_fee_rate: 70,   // 0.7% for testing
+            max_protocol_fee_rate: 100,     // 1% max for testing
+        }
+    }
+}
+
+/// Protocol-level accounts created by the setup sequence
+#[derive(Clone, Debug)]
+pub struct ProtocolAccounts {
+    /// Protocol authority, treasury and DEX TWAP updater (the payer)
+    pub authority: Pubkey,
+    pub protocol_config: Pubkey,
+    pub protocol_oracle: Pubkey,
+    pub safety_controller: Pubkey,
+    pub feels_hub: Pubkey,
+}
+
+/// A launched token whose escrow is funded and linked to its market
+#[derive(Clone, Debug)]
+pub struct PendingLaunch {
+    pub token_mint: Pubkey,
+    pub market: Pubkey,
+    pub escrow: Pubkey,
+    pub escrow_authority: Pubkey,
+    pub escrow_token_vault: Pubkey,
+    pub escrow_feelssol_vault: Pubkey,
+    pub protocol_token: Pubkey,
+}
+
+/// Builds a `TestContext` with non-default protocol settings
+///
+/// `TestContext::new` is this builder with every default.
+pub struct TestContextBuilder {
+    environment: TestEnvironment,
+    protocol_fees: ProtocolFees,
+    seed: Option<u64>,
+}
+
+impl TestContextBuilder {
+    pub fn new(environment: TestEnvironment) -> Self {
+        Self {
+            environment,
+            protocol_fees: ProtocolFees::default(),
+            seed: None,
+        }
+    }
+
+    /// Seed the harness keypairs; defaults to `FEELS_TEST_SEED` or a random seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn mint_fee(mut self, mint_fee: u64) -> Self {
+        self.protocol_fees.mint_fee = mint_fee;
+        self
+    }
+
+    pub fn protocol_fee_rate(mut self, bps: u16) -> Self {
+        self.protocol_fees.default_protocol_fee_rate = bps;
+        self
+    }
+
+    pub fn creator_fee_rate(mut self, bps: u16) -> Self {
+        self.protocol_fees.default_creator_fee_rate = bps;
+        self
+    }
+
+    pub fn max_protocol_fee_rate(mut self, bps: u16) -> Self {
+        self.protocol_fees.max_protocol_fee_rate = bps;
+        self
+    }
+
+    /// Fund test accounts, create the mints, and initialize the protocol and FeelsHub
+    pub async fn build(self) -> TestResult<TestContext> {
+        let keys = match self.seed {
+            Some(seed) => KeypairSource::new(seed),
+            None => KeypairSource::from_env_or_random(),
+        };
+        TestContext::with_settings(self.environment, self.protocol_fees, keys).await
+    }
 }
 
 /// Main test context that provides all test functionality
@@ -40,17 +150,21 @@ pub struct TestContext {
     pub jitosol_mint: Pubkey,
     pub feelssol_authority: Keypair,
     pub jitosol_authority: Keypair,
+    pub protocol_fees: ProtocolFees,
+    /// Seed of every keypair the harness generated; report it to reproduce a failure
+    pub seed: u64,
+    keys: Arc<std::sync::Mutex<KeypairSource>>,
 }
 
 impl TestContext {
     /// Generate a keypair with a low pubkey value
     /// This is useful for creating FeelsSOL mints that can always be token_0
-    fn generate_low_pubkey_keypair(max_attempts: usize) -> Keypair {
-        let mut best_kp = Keypair::new();
+    fn generate_low_pubkey_keypair(keys: &mut KeypairSource, max_attempts: usize) -> Keypair {
+        let mut best_kp = keys.next_keypair();
         let mut best_first_bytes = best_kp.pubkey().to_bytes()[0..2].to_vec();
 
         for i in 0..max_attempts {
-            let kp = Keypair::new();
+            let kp = keys.next_keypair();
             let bytes = kp.pubkey().to_bytes();
 
             if bytes[0] < best_first_bytes[0]
@@ -83,9 +197,43 @@ impl TestContext {
 
     /// Create a new test context for the given environment
     pub async fn new(environment: TestEnvironment) -> TestResult<Self> {
+        TestContextBuilder::new(environment).build().await
+    }
+
+    /// Create a test context whose keypairs are all derived from `seed`
+    pub async fn new_deterministic(environment: TestEnvironment, seed: u64) -> TestResult<Self> {
+        TestContextBuilder::new(environment)
+            .seed(seed)
+            .build()
+            .await
+    }
+
+    /// Start building a test context with non-default protocol settings
+    pub fn builder(environment: TestEnvironment) -> TestContextBuilder {
+        TestContextBuilder::new(environment)
+    }
+
+    /// Next keypair from the context's seeded source
+    pub fn next_keypair(&self) -> Keypair {
+        self.keys.lock().unwrap().next_keypair()
+    }
+
+    async fn with_settings(
+        environment: TestEnvironment,
+        protocol_fees: ProtocolFees,
+        mut keys: KeypairSource,
+    ) -> TestResult<Self> {
         // Initialize tracing to suppress OpenTelemetry warnings
         crate::common::tracing::init_test_tracing();
 
+        // Captured output is only shown for failing tests, which is when the seed matters
+        println!(
+            "Test keypair seed: {} (reproduce with {}={})",
+            keys.seed(),
+            crate::common::keys::TEST_SEED_ENV,
+            keys.seed()
+        );
+
         let client = match &environment {
             TestEnvironment::InMemory => TestClient::InMemory(InMemoryClient::new().await?),
             TestEnvironment::Devnet {
@@ -103,13 +251,14 @@ impl TestContext {
         };
 
         // Create test token mints
-        let jitosol_authority = Keypair::new();
-        let feelssol_authority = Keypair::new();
+        let accounts = TestAccounts::from_source(&mut keys);
+        let jitosol_authority = keys.next_keypair();
+        let feelssol_authority = keys.next_keypair();
 
         // For in-memory tests and localnet, create a mock JitoSOL mint
         let (jitosol_mint, jitosol_mint_keypair) = match &environment {
             TestEnvironment::InMemory | TestEnvironment::Localnet { .. } => {
-                let mint = Keypair::new();
+                let mint = keys.next_keypair();
                 (mint.pubkey(), Some(mint))
             }
             _ => {
@@ -121,20 +270,23 @@ impl TestContext {
         // Generate a FeelsSOL mint with a very low pubkey to ensure it can be token_0
         let feelssol_mint = if matches!(&environment, TestEnvironment::InMemory) {
             // For in-memory tests, use the helper to find a keypair with low pubkey
-            Self::generate_low_pubkey_keypair(1000)
+            Self::generate_low_pubkey_keypair(&mut keys, 1000)
         } else {
             // For other environments, use a regular keypair
-            Keypair::new()
+            keys.next_keypair()
         };
 
         let mut ctx = Self {
             client: Arc::new(Mutex::new(client)),
-            accounts: TestAccounts::new(),
+            accounts,
             environment,
             feelssol_mint: feelssol_mint.pubkey(),
             jitosol_mint,
             feelssol_authority,
             jitosol_authority,
+            protocol_fees,
+            seed: keys.seed(),
+            keys: Arc::new(std::sync::Mutex::new(keys)),
         };
 
         // Fund test accounts
@@ -446,13 +598,14 @@ impl TestContext {
         let payer_pubkey = self.payer().await;
         println!("Using payer as authority: {}", payer_pubkey);
 
-        // Create initialization parameters with 0 mint fee for testing and sane oracle/safety defaults
+        // Create initialization parameters with the context's fees and sane oracle/safety defaults
+        let fees = &self.protocol_fees;
         let params = InitializeProtocolParams {
-            mint_fee: 0,                         // No fee for testing
-            treasury: payer_pubkey,              // Use payer as treasury for simplicity
-            default_protocol_fee_rate: Some(30), // 0.3% for testing
-            default_creator_fee_rate: Some(70),  // 0.7% for testing
-            max_protocol_fee_rate: Some(100),    // 1% max for testing
+            mint_fee: fees.mint_fee,
+            treasury: payer_pubkey, // Use payer as treasury for simplicity
+            default_protocol_fee_rate: Some(fees.default_protocol_fee_rate),
+            default_creator_fee_rate: Some(fees.default_creator_fee_rate),
+            max_protocol_fee_rate: Some(fees.max_protocol_fee_rate),
             dex_twap_updater: payer_pubkey,
             depeg_threshold_bps: 500, // 5%
             depeg_required_obs: 2,
@@ -536,6 +689,21 @@ impl TestContext {
         }
     }
 
+    /// Protocol-level PDAs and the authority that initialized them
+    pub async fn protocol_accounts(&self) -> ProtocolAccounts {
+        use feels::constants::FEELS_HUB_SEED;
+        use feels::state::{ProtocolConfig, ProtocolOracle, SafetyController};
+
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &PROGRAM_ID).0;
+        ProtocolAccounts {
+            authority: self.payer().await,
+            protocol_config: pda(&[ProtocolConfig::SEED]),
+            protocol_oracle: pda(&[ProtocolOracle::SEED]),
+            safety_controller: pda(&[SafetyController::SEED]),
+            feels_hub: pda(&[FEELS_HUB_SEED, self.feelssol_mint.as_ref()]),
+        }
+    }
+
     /// Get the payer pubkey
     pub async fn payer(&self) -> Pubkey {
         self.client.lock().await.payer()
@@ -554,6 +722,55 @@ impl TestContext {
             .await
     }
 
+    /// Process an instruction, returning the program logs with the result
+    pub async fn process_instruction_with_logs(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (TestResult<()>, Vec<String>) {
+        self.client
+            .lock()
+            .await
+            .process_instruction_with_logs(instruction, signers)
+            .await
+    }
+
+    /// Process an instruction, returning the compute units it consumed
+    pub async fn process_instruction_measuring_cu(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> (TestResult<()>, u64) {
+        self.client
+            .lock()
+            .await
+            .process_instruction_measuring_cu(instruction, signers)
+            .await
+    }
+
+    /// Process an instruction, failing unless it succeeds under `limit` units
+    ///
+    /// Returns the units consumed so tests can report headroom.
+    pub async fn assert_cu_below(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+        limit: u64,
+    ) -> TestResult<u64> {
+        let (result, units) = self
+            .process_instruction_measuring_cu(instruction, signers)
+            .await;
+        result?;
+        if units >= limit {
+            return Err(format!(
+                "Instruction used {} compute units, limit is {}",
+                units, limit
+            )
+            .into());
+        }
+        Ok(units)
+    }
+
     /// Process multiple instructions
     pub async fn process_transaction(
         &self,
@@ -590,14 +807,29 @@ impl TestContext {
         self.client.lock().await.advance_time(seconds).await
     }
 
+    /// Advance the clock by `slots`, moving the timestamp forward with them
+    pub async fn warp_forward_slots(&self, slots: u64) -> TestResult<()> {
+        self.client.lock().await.warp_forward_slots(slots).await
+    }
+
     /// Get current slot
     pub async fn get_slot(&self) -> TestResult<u64> {
         self.client.lock().await.get_slot().await
     }
 
+    /// Slot from the Clock sysvar
+    pub async fn current_slot(&self) -> TestResult<u64> {
+        self.client.lock().await.current_slot().await
+    }
+
+    /// Unix timestamp from the Clock sysvar
+    pub async fn current_unix_timestamp(&self) -> TestResult<i64> {
+        self.client.lock().await.current_unix_timestamp().await
+    }
+
     /// Create a new SPL token mint
     pub async fn create_mint(&self, authority: &Pubkey, decimals: u8) -> TestResult<Keypair> {
-        let mint = Keypair::new();
+        let mint = self.next_keypair();
         let payer_pubkey = self.payer().await;
 
         // Calculate rent for mint account
@@ -650,7 +882,7 @@ impl TestContext {
     ) -> TestResult<Keypair> {
         let max_attempts = 1000; // Prevent infinite loop
         for _ in 0..max_attempts {
-            let mint = Keypair::new();
+            let mint = self.next_keypair();
 
             // Check if this mint satisfies the ordering constraint
             // We need mint.pubkey() > reference_pubkey for proper token ordering
@@ -843,6 +1075,66 @@ impl TestContext {
         }
     }
 
+    /// Write a program-owned Anchor account, zero-padded to `space` bytes
+    pub async fn set_program_account<T: AccountSerialize>(
+        &self,
+        address: &Pubkey,
+        account: &T,
+        space: usize,
+    ) -> TestResult<()> {
+        let mut data = Vec::with_capacity(space);
+        account.try_serialize(&mut data)?;
+        data.resize(space.max(data.len()), 0);
+        self.client
+            .lock()
+            .await
+            .set_account(address, &PROGRAM_ID, data)
+            .await
+    }
+
+    /// Modify a program-owned Anchor account in place, keeping its size
+    pub async fn update_program_account<T, F>(&self, address: &Pubkey, update: F) -> TestResult<()>
+    where
+        T: AccountSerialize + AccountDeserialize,
+        F: FnOnce(&mut T),
+    {
+        let data = self
+            .client
+            .lock()
+            .await
+            .get_account_data(address)
+            .await?
+            .ok_or("Account not found")?;
+        let mut account = T::try_deserialize(&mut data.as_slice())?;
+        update(&mut account);
+        self.set_program_account(address, &account, data.len())
+            .await
+    }
+
+    /// Create or overwrite an initialized token account holding `amount`
+    pub async fn set_token_account(
+        &self,
+        address: &Pubkey,
+        mint: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> TestResult<()> {
+        let account = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(account, &mut data)?;
+        self.client
+            .lock()
+            .await
+            .set_account(address, &spl_token::id(), data)
+            .await
+    }
+
     /// Get SOL balance for an account
     pub async fn get_balance(&self, address: &Pubkey) -> TestResult<u64> {
         self.client.lock().await.get_balance(address).await
@@ -1017,6 +1309,117 @@ impl TestContext {
         Ok(tick_array)
     }
 
+    /// Set up a launch that is ready for deploy_initial_liquidity
+    ///
+    /// mint_token needs Metaplex, so the escrow and registry entry it would
+    /// leave behind are written directly, with the escrowed tokens minted for
+    /// real; the market itself goes through initialize_market with the
+    /// protocol defaults, created by `market_creator`. The protocol
+    /// treasury is pointed at a FeelsSOL token account so the launch can pay
+    /// its mint fee.
+    pub async fn create_pending_launch(
+        &self,
+        token_amount: u64,
+        feelssol_amount: u64,
+    ) -> TestResult<PendingLaunch> {
+        use feels::constants::{ESCROW_AUTHORITY_SEED, ESCROW_SEED, PROTOCOL_TOKEN_SEED};
+        use feels::state::{
+            DeploymentProgress, PreLaunchEscrow, ProtocolConfig, ProtocolToken, TokenType,
+        };
+
+        let creator = &self.accounts.market_creator;
+        let token_mint = self
+            .create_mint_with_ordering_constraint(&creator.pubkey(), 6, &self.feelssol_mint)
+            .await?
+            .pubkey();
+
+        let protocol_config = self.protocol_accounts().await.protocol_config;
+        let config = self
+            .get_account::<ProtocolConfig>(&protocol_config)
+            .await?
+            .ok_or("Protocol config not initialized")?;
+        let market = self
+            .initialize_market(
+                creator,
+                &self.feelssol_mint,
+                &token_mint,
+                config.default_base_fee_bps,
+                config.default_tick_spacing,
+                config.default_initial_sqrt_price,
+                0,
+            )
+            .await?;
+
+        let (escrow, _) =
+            Pubkey::find_program_address(&[ESCROW_SEED, token_mint.as_ref()], &PROGRAM_ID);
+        let (escrow_authority, escrow_authority_bump) =
+            Pubkey::find_program_address(&[ESCROW_AUTHORITY_SEED, escrow.as_ref()], &PROGRAM_ID);
+        let escrow_token_vault = spl_associated_token_account::get_associated_token_address(
+            &escrow_authority,
+            &token_mint,
+        );
+        let escrow_feelssol_vault = spl_associated_token_account::get_associated_token_address(
+            &escrow_authority,
+            &self.feelssol_mint,
+        );
+        self.set_token_account(&escrow_token_vault, &token_mint, &escrow_authority, 0)
+            .await?;
+        self.mint_to(&token_mint, &escrow_token_vault, creator, token_amount)
+            .await?;
+        self.set_token_account(
+            &escrow_feelssol_vault,
+            &self.feelssol_mint,
+            &escrow_authority,
+            feelssol_amount,
+        )
+        .await?;
+
+        let created_at = self.current_unix_timestamp().await?;
+        let escrow_state = PreLaunchEscrow {
+            token_mint,
+            creator: creator.pubkey(),
+            feelssol_mint: self.feelssol_mint,
+            created_at,
+            market,
+            escrow_authority_bump,
+            deployment: DeploymentProgress::default(),
+            _reserved: [0; 88],
+        };
+        self.set_program_account(&escrow, &escrow_state, PreLaunchEscrow::LEN)
+            .await?;
+
+        let (protocol_token, _) =
+            Pubkey::find_program_address(&[PROTOCOL_TOKEN_SEED, token_mint.as_ref()], &PROGRAM_ID);
+        let registry_entry = ProtocolToken {
+            mint: token_mint,
+            creator: creator.pubkey(),
+            token_type: TokenType::Spl,
+            created_at,
+            can_create_markets: true,
+            _reserved: [0; 32],
+        };
+        self.set_program_account(&protocol_token, &registry_entry, ProtocolToken::LEN)
+            .await?;
+
+        let treasury = self.next_keypair().pubkey();
+        self.set_token_account(&treasury, &self.feelssol_mint, &self.payer().await, 0)
+            .await?;
+        self.update_program_account::<ProtocolConfig, _>(&protocol_config, |config| {
+            config.treasury = treasury;
+        })
+        .await?;
+
+        Ok(PendingLaunch {
+            token_mint,
+            market,
+            escrow,
+            escrow_authority,
+            escrow_token_vault,
+            escrow_feelssol_vault,
+            protocol_token,
+        })
+    }
+
     /// Initialize a market
     pub async fn initialize_market(
         &self,
@@ -1124,6 +1527,9 @@ impl Clone for TestContext {
             jitosol_mint: self.jitosol_mint,
             feelssol_authority: Keypair::new(), // Create new keypair
             jitosol_authority: Keypair::new(),  // Create new keypair
+            protocol_fees: self.protocol_fees.clone(),
+            seed: self.seed,
+            keys: self.keys.clone(),
         }
     }
 }
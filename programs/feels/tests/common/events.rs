@@ -0,0 +1,77 @@
+//! Decoding of Anchor events from transaction logs
+//!
+//! `emit!` writes each event as a `Program data: <base64>` log line holding the
+//! event discriminator followed by its Borsh encoding. Pair these helpers with
+//! `process_instruction_with_logs` to assert on what an instruction emitted.
+
+use anchor_lang::{Discriminator, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Every `E` event found in `logs`, in emission order
+pub fn decode_events<E: Event>(logs: &[String]) -> Vec<E> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|encoded| STANDARD.decode(encoded).ok())
+        .filter_map(|data| {
+            let payload = data.strip_prefix(E::DISCRIMINATOR)?;
+            E::deserialize(&mut &payload[..]).ok()
+        })
+        .collect()
+}
+
+/// The first `E` event in `logs`, panicking if none was emitted
+pub fn assert_event<E: Event>(logs: &[String]) -> E {
+    decode_events(logs).into_iter().next().unwrap_or_else(|| {
+        panic!(
+            "Expected event {} in logs:\n{}",
+            std::any::type_name::<E>(),
+            logs.join("\n")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feels::events::{AccountMigrated, FloorRatcheted};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn program_data(event: &impl Event) -> String {
+        format!("{}{}", PROGRAM_DATA_PREFIX, STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn test_assert_event_decodes_emitted_event() {
+        let event = AccountMigrated {
+            account: Pubkey::new_unique(),
+            old_len: 100,
+            new_len: 132,
+            payer: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", feels::ID),
+            "Program log: Instruction: MigrateAccount".to_string(),
+            program_data(&event),
+            format!("Program {} success", feels::ID),
+        ];
+
+        let decoded: AccountMigrated = assert_event(&logs);
+        assert_eq!(decoded.account, event.account);
+        assert_eq!(decoded.old_len, 100);
+        assert_eq!(decoded.new_len, 132);
+        assert_eq!(decoded.timestamp, 1_700_000_000);
+
+        // Other event types in the same logs are ignored
+        assert!(decode_events::<FloorRatcheted>(&logs).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected event")]
+    fn test_assert_event_fails_when_absent() {
+        let logs = vec!["Program log: nothing emitted".to_string()];
+        let _: AccountMigrated = assert_event(&logs);
+    }
+}
@@ -209,6 +209,14 @@ impl MarketHelper {
                 // The initialize_market will get the system program as protocol_token accounts
                 let market_id = self.create_feelssol_market(&token_mint.pubkey()).await?;
 
+                // Skipping the escrow launch leaves the market closed to swaps, so
+                // open it the way a completed deploy_initial_liquidity would
+                self.ctx
+                    .update_program_account::<Market, _>(&market_id, |market| {
+                        market.initial_liquidity_deployed = true;
+                    })
+                    .await?;
+
                 // Determine token ordering
                 let (token_0, token_1) = if self.ctx.feelssol_mint < token_mint.pubkey() {
                     (self.ctx.feelssol_mint, token_mint.pubkey())
@@ -283,6 +291,8 @@ impl MarketHelper {
                 let deploy_params = feels::instructions::DeployInitialLiquidityParams {
                     initial_buy_feelssol_amount: 0,
                     tick_step_size: 100,
+                    token_amount: 0,
+                    feelssol_amount: 0,
                 };
                 let ix_deploy = sdk_compat::instructions::deploy_initial_liquidity(
                     creator.pubkey(),
@@ -430,6 +440,8 @@ impl MarketHelper {
         let deploy_params = feels::instructions::DeployInitialLiquidityParams {
             tick_step_size: 100,
             initial_buy_feelssol_amount: 0,
+            token_amount: 0,
+            feelssol_amount: 0,
         };
         let ix_deploy =
             sdk_compat::deploy_initial_liquidity(creator.pubkey(), market_id, deploy_params);
@@ -417,24 +417,84 @@ impl PositionHelper {
     /// Add liquidity to existing position
     pub async fn add_liquidity(
         &self,
-        _position_id: &Pubkey,
-        _owner: &Keypair,
-        _liquidity_delta: u128,
+        position_id: &Pubkey,
+        owner: &Keypair,
+        liquidity_delta: u128,
     ) -> TestResult<()> {
-        // The Feels protocol currently does not support modifying liquidity in existing positions.
-        // This is a deliberate design choice to keep the protocol simpler and more efficient.
-        //
-        // To add liquidity at the same price range:
-        // 1. Open a new position with the additional liquidity
-        // 2. Keep track of multiple position NFTs
-        //
-        // This approach:
-        // - Simplifies the protocol implementation
-        // - Makes fee accounting cleaner (each position tracks its own fees)
-        // - Allows for more granular position management
-        // - Enables easier position transfers (NFT-based)
+        // Get position and market state
+        let position = self
+            .ctx
+            .get_account::<Position>(position_id)
+            .await?
+            .unwrap();
+        let market_state = self
+            .ctx
+            .get_account::<Market>(&position.market)
+            .await?
+            .unwrap();
 
-        Err("Adding liquidity to existing positions is not supported. Please open a new position instead.".into())
+        let position_token_account = spl_associated_token_account::get_associated_token_address(
+            &owner.pubkey(),
+            &position.nft_mint,
+        );
+
+        // Create owner token accounts if they don't exist
+        let owner_token_0 = self
+            .ctx
+            .create_ata(&owner.pubkey(), &market_state.token_0)
+            .await?;
+        let owner_token_1 = self
+            .ctx
+            .create_ata(&owner.pubkey(), &market_state.token_1)
+            .await?;
+
+        // Derive vaults
+        let (vault_0, _) = sdk_compat::find_vault_address(&position.market, &market_state.token_0);
+        let (vault_1, _) = sdk_compat::find_vault_address(&position.market, &market_state.token_1);
+
+        // Calculate tick array addresses
+        let lower_array_start =
+            utils::get_tick_array_start_index(position.tick_lower, market_state.tick_spacing);
+        let upper_array_start =
+            utils::get_tick_array_start_index(position.tick_upper, market_state.tick_spacing);
+        let (lower_tick_array, _) =
+            utils::find_tick_array_address(&position.market, lower_array_start);
+        let (upper_tick_array, _) =
+            utils::find_tick_array_address(&position.market, upper_array_start);
+
+        // Build instruction manually; sdk_compat has no builder for it
+        let discriminator: [u8; 8] =
+            anchor_lang::solana_program::hash::hash(b"global:increase_liquidity").to_bytes()[..8]
+                .try_into()
+                .unwrap();
+        let mut data: Vec<u8> = discriminator.to_vec();
+        data.extend_from_slice(&liquidity_delta.to_le_bytes());
+        data.extend_from_slice(&u64::MAX.to_le_bytes()); // amount_0_max
+        data.extend_from_slice(&u64::MAX.to_le_bytes()); // amount_1_max
+
+        let accounts = vec![
+            AccountMeta::new(owner.pubkey(), true),
+            AccountMeta::new(position.market, false),
+            AccountMeta::new_readonly(position_token_account, false),
+            AccountMeta::new(*position_id, false),
+            AccountMeta::new(owner_token_0, false),
+            AccountMeta::new(owner_token_1, false),
+            AccountMeta::new(vault_0, false),
+            AccountMeta::new(vault_1, false),
+            AccountMeta::new(lower_tick_array, false),
+            AccountMeta::new(upper_tick_array, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        let ix = Instruction {
+            program_id: sdk_compat::program_id(),
+            accounts,
+            data,
+        };
+
+        self.ctx.process_instruction(ix, &[owner]).await?;
+
+        Ok(())
     }
 
     /// Remove liquidity from position
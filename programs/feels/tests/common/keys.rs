@@ -0,0 +1,75 @@
+//! Reproducible keypairs for tests
+//!
+//! Pubkeys decide token ordering and PDA addresses, so a test that only
+//! fails for some random keys is flaky. Every `TestContext` draws its
+//! keypairs from a seeded `KeypairSource` and reports the seed when a test
+//! fails. Setting `FEELS_TEST_SEED` to that seed replays the same keys.
+//!
+//! The ProgramTest payer is generated by `solana-program-test` itself and
+//! is not covered by the seed.
+
+use solana_sdk::hash::hashv;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::keypair::keypair_from_seed;
+
+/// Environment variable that fixes the seed of every `TestContext`
+pub const TEST_SEED_ENV: &str = "FEELS_TEST_SEED";
+
+/// Keypair derived only from `seed`
+pub fn deterministic_keypair(seed: u64) -> Keypair {
+    keypair_from_hash(&[b"feels-test-keypair", &seed.to_le_bytes()])
+}
+
+/// Seed from `FEELS_TEST_SEED`, if set
+///
+/// Panics on a value that is not a u64, rather than silently running with
+/// a random seed the caller did not ask for.
+pub fn seed_from_env() -> Option<u64> {
+    let value = std::env::var(TEST_SEED_ENV).ok()?;
+    Some(
+        value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{}={} is not a u64 seed", TEST_SEED_ENV, value)),
+    )
+}
+
+fn keypair_from_hash(parts: &[&[u8]]) -> Keypair {
+    keypair_from_seed(hashv(parts).as_ref()).expect("32-byte seed")
+}
+
+/// Sequence of keypairs determined by a seed
+#[derive(Clone, Debug)]
+pub struct KeypairSource {
+    seed: u64,
+    next_index: u64,
+}
+
+impl KeypairSource {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            next_index: 0,
+        }
+    }
+
+    /// Seed from `FEELS_TEST_SEED`, or a fresh random one
+    pub fn from_env_or_random() -> Self {
+        Self::new(seed_from_env().unwrap_or_else(rand::random))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Next keypair in the sequence
+    pub fn next_keypair(&mut self) -> Keypair {
+        let index = self.next_index;
+        self.next_index += 1;
+        keypair_from_hash(&[
+            b"feels-test-keypair-source",
+            &self.seed.to_le_bytes(),
+            &index.to_le_bytes(),
+        ])
+    }
+}
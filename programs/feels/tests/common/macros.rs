@@ -14,7 +14,13 @@ macro_rules! test_all_environments {
                     .await
                     .expect("Failed to create in-memory test context");
 
-                ($test_fn)(ctx).await.expect("In-memory test failed");
+                let seed = ctx.seed;
+                ($test_fn)(ctx).await.unwrap_or_else(|e| {
+                    panic!(
+                        "In-memory test failed (reproduce with FEELS_TEST_SEED={}): {}",
+                        seed, e
+                    )
+                });
             }
 
             #[tokio::test]
@@ -28,7 +34,13 @@ macro_rules! test_all_environments {
                     .await
                     .expect("Failed to create devnet test context");
 
-                ($test_fn)(ctx).await.expect("Devnet test failed");
+                let seed = ctx.seed;
+                ($test_fn)(ctx).await.unwrap_or_else(|e| {
+                    panic!(
+                        "Devnet test failed (reproduce with FEELS_TEST_SEED={}): {}",
+                        seed, e
+                    )
+                });
             }
 
             #[tokio::test]
@@ -42,7 +54,13 @@ macro_rules! test_all_environments {
                     .await
                     .expect("Failed to create localnet test context");
 
-                ($test_fn)(ctx).await.expect("Localnet test failed");
+                let seed = ctx.seed;
+                ($test_fn)(ctx).await.unwrap_or_else(|e| {
+                    panic!(
+                        "Localnet test failed (reproduce with FEELS_TEST_SEED={}): {}",
+                        seed, e
+                    )
+                });
             }
         }
     };
@@ -59,7 +77,13 @@ macro_rules! test_in_memory {
                 .await
                 .expect("Failed to create test context");
 
-            ($test_fn)(ctx).await.expect("Test failed");
+            let seed = ctx.seed;
+            ($test_fn)(ctx).await.unwrap_or_else(|e| {
+                panic!(
+                    "Test failed (reproduce with FEELS_TEST_SEED={}): {}",
+                    seed, e
+                )
+            });
         }
     };
 }
@@ -79,7 +103,13 @@ macro_rules! test_devnet {
                 .await
                 .expect("Failed to create test context");
 
-            ($test_fn)(ctx).await.expect("Test failed");
+            let seed = ctx.seed;
+            ($test_fn)(ctx).await.unwrap_or_else(|e| {
+                panic!(
+                    "Test failed (reproduce with FEELS_TEST_SEED={}): {}",
+                    seed, e
+                )
+            });
         }
     };
 }
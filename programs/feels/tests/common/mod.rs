@@ -7,9 +7,11 @@ pub mod builders;
 pub mod client;
 pub mod context;
 pub mod environment;
+pub mod events;
 pub mod fixtures;
 pub mod helpers;
 pub mod jito;
+pub mod keys;
 pub mod prelude;
 pub mod sdk_compat;
 pub mod time;
@@ -26,15 +28,19 @@ pub use std::error::Error;
 // Re-export core types
 pub use builders::MarketBuilder;
 pub use client::TestClient;
-pub use context::TestContext;
+pub use context::{
+    PendingLaunch, ProtocolAccounts, ProtocolFees, TestContext, TestContextBuilder,
+};
 pub use environment::{should_run_devnet_tests, should_run_localnet_tests, TestEnvironment};
+pub use events::{assert_event, decode_events};
 pub use helpers::{MarketHelper, SwapHelper};
+pub use keys::{deterministic_keypair, KeypairSource, TEST_SEED_ENV};
 pub use sdk_compat::{TestMarketSetup, SwapResult, PositionInfo, CollectFeesResult};
 
 // Re-export assertion utilities
 pub use assertions::{
-    MarketAssertions, MarketTestData, PositionAssertions, ProtocolInvariants, SwapAssertions,
-    SwapResult as AssertionSwapResult, TickArrayAssertions,
+    assert_feels_error, MarketAssertions, MarketTestData, PositionAssertions, ProtocolInvariants,
+    SwapAssertions, SwapResult as AssertionSwapResult, TickArrayAssertions,
 };
 
 // Note: Macros assert_tx_success, assert_error, assert_balance_change are available
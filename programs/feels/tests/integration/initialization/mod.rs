@@ -1,4 +1,6 @@
 // Test modules for initialization
 
 pub mod test_basic_market;
+pub mod test_deterministic_seed;
 pub mod test_edge_cases;
+pub mod test_protocol_setup;
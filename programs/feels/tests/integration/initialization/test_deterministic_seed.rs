@@ -0,0 +1,53 @@
+//! Test that a seeded TestContext reproduces its keypairs
+
+use crate::common::*;
+
+#[test]
+fn test_keypairs_follow_the_seed() {
+    assert_eq!(
+        deterministic_keypair(7).pubkey(),
+        deterministic_keypair(7).pubkey()
+    );
+    assert_ne!(
+        deterministic_keypair(7).pubkey(),
+        deterministic_keypair(8).pubkey()
+    );
+
+    let mut first = KeypairSource::new(42);
+    let mut second = KeypairSource::new(42);
+    let mut other = KeypairSource::new(43);
+    for _ in 0..8 {
+        let key = first.next_keypair().pubkey();
+        assert_eq!(key, second.next_keypair().pubkey());
+        assert_ne!(key, other.next_keypair().pubkey());
+    }
+}
+
+#[tokio::test]
+async fn test_same_seed_same_context() -> TestResult<()> {
+    let first = TestContext::new_deterministic(TestEnvironment::in_memory(), 1705).await?;
+    let second = TestContext::new_deterministic(TestEnvironment::in_memory(), 1705).await?;
+    assert_eq!(first.seed, 1705);
+
+    assert_eq!(
+        first.accounts.alice.pubkey(),
+        second.accounts.alice.pubkey()
+    );
+    assert_eq!(
+        first.accounts.market_creator.pubkey(),
+        second.accounts.market_creator.pubkey()
+    );
+    assert_eq!(first.feelssol_mint, second.feelssol_mint);
+    assert_eq!(first.jitosol_mint, second.jitosol_mint);
+
+    // Keys generated after setup continue the same sequence
+    let authority = first.accounts.market_creator.pubkey();
+    let mint_a = first.create_mint(&authority, 6).await?;
+    let mint_b = second.create_mint(&authority, 6).await?;
+    assert_eq!(mint_a.pubkey(), mint_b.pubkey());
+
+    let other = TestContext::new_deterministic(TestEnvironment::in_memory(), 1706).await?;
+    assert_ne!(first.accounts.alice.pubkey(), other.accounts.alice.pubkey());
+
+    Ok(())
+}
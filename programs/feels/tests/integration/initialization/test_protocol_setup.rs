@@ -0,0 +1,62 @@
+//! Smoke test for the shared protocol setup in `TestContextBuilder`
+
+use crate::common::*;
+use feels::state::{FeelsHub, Market, ProtocolConfig};
+
+#[tokio::test]
+async fn test_custom_fee_setup_can_create_a_market() -> TestResult<()> {
+    let ctx = TestContext::builder(TestEnvironment::in_memory())
+        .protocol_fee_rate(50)
+        .creator_fee_rate(25)
+        .max_protocol_fee_rate(200)
+        .build()
+        .await?;
+    let accounts = ctx.protocol_accounts().await;
+
+    // Protocol config carries the overridden fees and the payer as authority
+    let config = ctx
+        .get_account::<ProtocolConfig>(&accounts.protocol_config)
+        .await?
+        .expect("protocol config initialized");
+    assert_eq!(config.authority, accounts.authority);
+    assert_eq!(config.default_protocol_fee_rate, 50);
+    assert_eq!(config.default_creator_fee_rate, 25);
+    assert_eq!(config.max_protocol_fee_rate, 200);
+    assert_eq!(config.mint_fee, 0);
+
+    let hub = ctx
+        .get_account::<FeelsHub>(&accounts.feels_hub)
+        .await?
+        .expect("FeelsHub initialized");
+    assert_eq!(hub.feelssol_mint, ctx.feelssol_mint);
+    for pda in [accounts.protocol_oracle, accounts.safety_controller] {
+        assert!(ctx.get_account_raw(&pda).await.is_ok());
+    }
+
+    // Nothing else is needed before a market can be created
+    let setup = ctx.create_test_market(6).await?;
+    let market = ctx
+        .get_account::<Market>(&setup.market)
+        .await?
+        .expect("market created");
+    assert_eq!(market.feelssol_mint, ctx.feelssol_mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_default_setup_matches_builder_defaults() -> TestResult<()> {
+    let ctx = TestContext::new(TestEnvironment::in_memory()).await?;
+    let accounts = ctx.protocol_accounts().await;
+    let defaults = ProtocolFees::default();
+
+    let config = ctx
+        .get_account::<ProtocolConfig>(&accounts.protocol_config)
+        .await?
+        .expect("protocol config initialized");
+    assert_eq!(config.default_protocol_fee_rate, defaults.default_protocol_fee_rate);
+    assert_eq!(config.default_creator_fee_rate, defaults.default_creator_fee_rate);
+    assert_eq!(config.max_protocol_fee_rate, defaults.max_protocol_fee_rate);
+
+    Ok(())
+}
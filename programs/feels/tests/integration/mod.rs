@@ -8,8 +8,13 @@ pub mod test_basic_operations;
 pub mod test_creator_launch_flow;
 pub mod test_creator_market_launch;
 pub mod test_debug_exit;
+pub mod test_deploy_initial_liquidity_parts;
+pub mod test_destroy_expired_token;
 pub mod test_exact_output_swap;
 pub mod test_exact_output_swap_all_scenarios;
+pub mod test_floor_backing_swap;
+pub mod test_increase_liquidity;
 pub mod test_jito_integration;
 pub mod test_oracle_safety;
+pub mod test_swap_circuit_breaker;
 pub mod test_token_ordering;
@@ -43,6 +43,60 @@ test_in_memory!(test_can_airdrop_sol, |ctx: TestContext| async move {
     Ok::<(), Box<dyn std::error::Error>>(())
 });
 
+test_in_memory!(test_warp_advances_clock, |ctx: TestContext| async move {
+    let start_slot = ctx.current_slot().await?;
+    let start_time = ctx.current_unix_timestamp().await?;
+
+    // Warping by time moves the slot forward too
+    ctx.advance_time(60).await?;
+    assert_eq!(ctx.current_unix_timestamp().await?, start_time + 60);
+    let slot = ctx.current_slot().await?;
+    assert!(slot > start_slot, "warping 60s should advance the slot");
+
+    // Warping by slots moves the timestamp by their expected duration
+    ctx.warp_forward_slots(150).await?;
+    assert_eq!(ctx.current_slot().await?, slot + 150);
+    assert_eq!(ctx.current_unix_timestamp().await?, start_time + 60 + 60);
+
+    Ok::<(), Box<dyn std::error::Error>>(())
+});
+
+test_in_memory!(test_instruction_logs_are_captured, |ctx: TestContext| async move {
+    let payer = ctx.payer().await;
+    let recipient = Keypair::new();
+    let system_program = solana_sdk::system_program::id().to_string();
+
+    let ix = solana_sdk::system_instruction::transfer(&payer, &recipient.pubkey(), 1_000_000);
+    let (result, logs) = ctx.process_instruction_with_logs(ix, &[]).await;
+    assert!(result.is_ok());
+    assert!(logs.iter().any(|log| log.contains(&system_program) && log.contains("success")));
+
+    // Logs are still returned when the transaction fails
+    let ix = solana_sdk::system_instruction::transfer(&recipient.pubkey(), &payer, 2_000_000);
+    let (result, logs) = ctx.process_instruction_with_logs(ix, &[&recipient]).await;
+    assert!(result.is_err());
+    assert!(logs.iter().any(|log| log.contains(&system_program) && log.contains("failed")));
+
+    Ok::<(), Box<dyn std::error::Error>>(())
+});
+
+test_in_memory!(test_compute_units_are_measured, |ctx: TestContext| async move {
+    let payer = ctx.payer().await;
+    let transfer = || {
+        solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000_000)
+    };
+
+    let (result, units) = ctx.process_instruction_measuring_cu(transfer(), &[]).await;
+    result?;
+    assert!(units > 0);
+
+    // A system transfer costs 150 units
+    assert_eq!(ctx.assert_cu_below(transfer(), &[], 1_000).await?, units);
+    assert!(ctx.assert_cu_below(transfer(), &[], units).await.is_err());
+
+    Ok::<(), Box<dyn std::error::Error>>(())
+});
+
 test_all_environments!(test_create_and_use_token, |ctx: TestContext| async move {
     // Create a new token mint
     let mint = ctx
@@ -0,0 +1,245 @@
+//! Test that a multi-part initial liquidity deployment keeps one stair layout
+//!
+//! The first part adds in-range liquidity, so without a guard the market could
+//! be traded (and its tick moved) before the remaining parts land on top.
+
+use crate::common::*;
+use anchor_lang::InstructionData;
+use feels::constants::MARKET_AUTHORITY_SEED;
+use feels::error::FeelsError;
+use feels::logic::SwapParams;
+use feels::state::{Market, PreLaunchEscrow, ProtocolConfig, TranchePlan};
+
+const ESCROW_TOKEN: u64 = 1_000_000_000_000;
+const ESCROW_FEELSSOL: u64 = 10_000_000_000;
+
+pub(crate) fn deploy_ix(
+    launch: &PendingLaunch,
+    market: &Market,
+    deployer: &Keypair,
+    deployer_feelssol: Pubkey,
+    deployer_token_out: Pubkey,
+    treasury: Pubkey,
+    params: DeployInitialLiquidityParams,
+) -> Instruction {
+    let (market_authority, _) = Pubkey::find_program_address(
+        &[MARKET_AUTHORITY_SEED, launch.market.as_ref()],
+        &PROGRAM_ID,
+    );
+    let (protocol_config, _) = Pubkey::find_program_address(&[ProtocolConfig::SEED], &PROGRAM_ID);
+    let (tranche_plan, _) =
+        Pubkey::find_program_address(&[TranchePlan::SEED, launch.market.as_ref()], &PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: feels::accounts::DeployInitialLiquidity {
+            deployer: deployer.pubkey(),
+            market: launch.market,
+            token_0_mint: market.token_0,
+            token_1_mint: market.token_1,
+            deployer_feelssol,
+            deployer_token_out,
+            vault_0: market.vault_0,
+            vault_1: market.vault_1,
+            market_authority,
+            buffer: market.buffer,
+            oracle: market.oracle,
+            escrow: launch.escrow,
+            escrow_token_vault: launch.escrow_token_vault,
+            escrow_feelssol_vault: launch.escrow_feelssol_vault,
+            escrow_authority: launch.escrow_authority,
+            protocol_config,
+            treasury,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+            tranche_plan,
+        }
+        .to_account_metas(None),
+        data: feels::instruction::DeployInitialLiquidity { params }.data(),
+    }
+}
+
+pub(crate) fn swap_ix(
+    market_id: Pubkey,
+    market: &Market,
+    user: &Keypair,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    params: SwapParams,
+) -> Instruction {
+    let (market_authority, _) =
+        Pubkey::find_program_address(&[MARKET_AUTHORITY_SEED, market_id.as_ref()], &PROGRAM_ID);
+    let (protocol_config, _) = Pubkey::find_program_address(&[ProtocolConfig::SEED], &PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: feels::accounts::Swap {
+            user: user.pubkey(),
+            user_token_account_in: user_token_in,
+            user_token_account_out: user_token_out,
+            market: market_id,
+            vault_0: market.vault_0,
+            vault_1: market.vault_1,
+            buffer: market.buffer,
+            oracle: market.oracle,
+            protocol_config,
+            clock: solana_sdk::sysvar::clock::id(),
+            token_0: market.token_0,
+            token_1: market.token_1,
+            token_in: market.token_0,
+            token_out: market.token_1,
+            market_authority,
+            token_program: spl_token::id(),
+            treasury: None,
+            protocol_token: None,
+            creator_token_account: None,
+        }
+        .to_account_metas(None),
+        data: feels::instruction::Swap { params }.data(),
+    }
+}
+
+test_in_memory!(
+    test_two_part_deploy_with_swap_in_between,
+    |ctx: TestContext| async move {
+        let launch = ctx
+            .create_pending_launch(ESCROW_TOKEN, ESCROW_FEELSSOL)
+            .await?;
+        let creator = &ctx.accounts.market_creator;
+        let alice = &ctx.accounts.alice;
+
+        let protocol_config = ctx.protocol_accounts().await.protocol_config;
+        let config = ctx
+            .get_account::<ProtocolConfig>(&protocol_config)
+            .await?
+            .unwrap();
+        let step = config.default_tick_step_size as i32;
+        let market_before = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+
+        let creator_feelssol = ctx
+            .create_ata(&creator.pubkey(), &market_before.token_0)
+            .await?;
+        let creator_token = ctx
+            .create_ata(&creator.pubkey(), &market_before.token_1)
+            .await?;
+        let deploy = |params| {
+            deploy_ix(
+                &launch,
+                &market_before,
+                creator,
+                creator_feelssol,
+                creator_token,
+                config.treasury,
+                params,
+            )
+        };
+
+        // Part 1: half of each side
+        ctx.process_instruction(
+            deploy(DeployInitialLiquidityParams {
+                tick_step_size: step,
+                initial_buy_feelssol_amount: 0,
+                token_amount: ESCROW_TOKEN / 2,
+                feelssol_amount: ESCROW_FEELSSOL / 2,
+            }),
+            &[creator],
+        )
+        .await?;
+
+        let escrow = ctx
+            .get_account::<PreLaunchEscrow>(&launch.escrow)
+            .await?
+            .unwrap();
+        assert!(escrow.deployment.is_started());
+        assert!(!escrow.deployment.is_complete());
+        assert_eq!(escrow.deployment.anchor_tick, market_before.current_tick);
+        assert_eq!(escrow.deployment.tick_step_size, step);
+
+        // The first part already put liquidity in range, but the market stays closed
+        let market_mid = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+        assert!(market_mid.liquidity > 0);
+        assert!(!market_mid.initial_liquidity_deployed);
+
+        let (tranche_plan, _) =
+            Pubkey::find_program_address(&[TranchePlan::SEED, launch.market.as_ref()], &PROGRAM_ID);
+        let plan_mid = ctx
+            .get_account::<TranchePlan>(&tranche_plan)
+            .await?
+            .unwrap();
+
+        // A swap between the parts is rejected before it can move the tick
+        let alice_feelssol = ctx.create_ata(&alice.pubkey(), &market_mid.token_0).await?;
+        let alice_token = ctx.create_ata(&alice.pubkey(), &market_mid.token_1).await?;
+        let result = ctx
+            .process_instruction(
+                swap_ix(
+                    launch.market,
+                    &market_mid,
+                    alice,
+                    alice_feelssol,
+                    alice_token,
+                    SwapParams {
+                        amount_in: 1_000_000,
+                        minimum_amount_out: 0,
+                        max_ticks_crossed: 0,
+                        max_total_fee_bps: 0,
+                    },
+                ),
+                &[alice],
+            )
+            .await;
+        assert_feels_error(result, FeelsError::InitialLiquidityNotDeployed);
+
+        let market_after_swap = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+        assert_eq!(market_after_swap.current_tick, market_before.current_tick);
+
+        // Later parts cannot change the step size fixed by the first
+        let result = ctx
+            .process_instruction(
+                deploy(DeployInitialLiquidityParams {
+                    tick_step_size: step * 2,
+                    initial_buy_feelssol_amount: 0,
+                    token_amount: 0,
+                    feelssol_amount: 0,
+                }),
+                &[creator],
+            )
+            .await;
+        assert_feels_error(result, FeelsError::InvalidTickRange);
+
+        // Part 2: everything still outstanding, over the same ranges
+        ctx.process_instruction(
+            deploy(DeployInitialLiquidityParams {
+                tick_step_size: step,
+                initial_buy_feelssol_amount: 0,
+                token_amount: 0,
+                feelssol_amount: 0,
+            }),
+            &[creator],
+        )
+        .await?;
+
+        let escrow = ctx
+            .get_account::<PreLaunchEscrow>(&launch.escrow)
+            .await?
+            .unwrap();
+        assert!(escrow.deployment.is_complete());
+
+        let market_after = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+        assert!(market_after.initial_liquidity_deployed);
+        assert!(market_after.liquidity > market_mid.liquidity);
+
+        let plan_after = ctx
+            .get_account::<TranchePlan>(&tranche_plan)
+            .await?
+            .unwrap();
+        assert_eq!(plan_after.entries.len(), plan_mid.entries.len());
+        for (mid, after) in plan_mid.entries.iter().zip(&plan_after.entries) {
+            assert_eq!(after.tick_lower, mid.tick_lower);
+            assert_eq!(after.tick_upper, mid.tick_upper);
+            assert!(after.liquidity >= mid.liquidity);
+        }
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
@@ -0,0 +1,147 @@
+//! Test that expired launches can be unwound only while no liquidity is live
+//!
+//! Drives destroy_expired_token end to end: the mint fee goes back to the
+//! creator's FeelsSOL ATA (created if missing), and deployed or unexpired
+//! launches are rejected.
+
+use crate::common::*;
+use anchor_lang::InstructionData;
+use feels::error::FeelsError;
+use feels::state::{Market, ProtocolConfig};
+
+const ESCROW_TOKEN: u64 = 1_000_000_000_000;
+const MINT_FEE: u64 = 10_000_000_000;
+
+fn destroy_ix(
+    launch: &PendingLaunch,
+    destroyer: &Keypair,
+    creator: Pubkey,
+    feelssol_mint: Pubkey,
+) -> Instruction {
+    let (protocol_config, _) = Pubkey::find_program_address(&[ProtocolConfig::SEED], &PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: feels::accounts::DestroyExpiredToken {
+            destroyer: destroyer.pubkey(),
+            token_mint: launch.token_mint,
+            protocol_token: launch.protocol_token,
+            escrow: launch.escrow,
+            escrow_token_vault: launch.escrow_token_vault,
+            escrow_feelssol_vault: launch.escrow_feelssol_vault,
+            escrow_authority: launch.escrow_authority,
+            protocol_config,
+            creator,
+            feelssol_mint,
+            creator_feelssol: spl_associated_token_account::get_associated_token_address(
+                &creator,
+                &feelssol_mint,
+            ),
+            market: launch.market,
+            associated_token_program: spl_associated_token_account::id(),
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: feels::instruction::DestroyExpiredToken {}.data(),
+    }
+}
+
+async fn expire(ctx: &TestContext) -> TestResult<()> {
+    let protocol_config = ctx.protocol_accounts().await.protocol_config;
+    let config = ctx
+        .get_account::<ProtocolConfig>(&protocol_config)
+        .await?
+        .unwrap();
+    ctx.advance_time(config.token_expiration_seconds + 1).await
+}
+
+test_in_memory!(
+    test_destroy_refunds_creator_after_expiry,
+    |ctx: TestContext| async move {
+        let launch = ctx.create_pending_launch(ESCROW_TOKEN, MINT_FEE).await?;
+        let creator = ctx.accounts.market_creator.pubkey();
+        let destroyer = &ctx.accounts.alice;
+        let creator_feelssol = spl_associated_token_account::get_associated_token_address(
+            &creator,
+            &ctx.feelssol_mint,
+        );
+        // The creator closed (or never opened) their FeelsSOL ATA
+        assert_eq!(ctx.get_balance(&creator_feelssol).await?, 0);
+
+        expire(&ctx).await?;
+        ctx.process_instruction(
+            destroy_ix(&launch, destroyer, creator, ctx.feelssol_mint),
+            &[destroyer],
+        )
+        .await?;
+
+        assert_eq!(ctx.get_token_balance(&creator_feelssol).await?, MINT_FEE);
+        assert_eq!(ctx.get_mint(&launch.token_mint).await?.supply, 0);
+        for closed in [
+            launch.escrow,
+            launch.protocol_token,
+            launch.escrow_token_vault,
+            launch.escrow_feelssol_vault,
+            launch.market,
+        ] {
+            assert_eq!(ctx.get_balance(&closed).await?, 0);
+        }
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
+
+test_in_memory!(
+    test_destroy_rejects_deployed_market,
+    |ctx: TestContext| async move {
+        let launch = ctx.create_pending_launch(ESCROW_TOKEN, MINT_FEE).await?;
+        let creator = ctx.accounts.market_creator.pubkey();
+        let destroyer = &ctx.accounts.alice;
+        ctx.update_program_account::<Market, _>(&launch.market, |market| {
+            market.initial_liquidity_deployed = true;
+        })
+        .await?;
+
+        expire(&ctx).await?;
+        let result = ctx
+            .process_instruction(
+                destroy_ix(&launch, destroyer, creator, ctx.feelssol_mint),
+                &[destroyer],
+            )
+            .await;
+        assert_feels_error(result, FeelsError::MarketAlreadyActive);
+
+        assert!(ctx.get_account::<Market>(&launch.market).await?.is_some());
+        assert_eq!(
+            ctx.get_token_balance(&launch.escrow_feelssol_vault).await?,
+            MINT_FEE
+        );
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
+
+test_in_memory!(
+    test_destroy_rejects_before_expiry,
+    |ctx: TestContext| async move {
+        let launch = ctx.create_pending_launch(ESCROW_TOKEN, MINT_FEE).await?;
+        let creator = ctx.accounts.market_creator.pubkey();
+        let destroyer = &ctx.accounts.alice;
+
+        let result = ctx
+            .process_instruction(
+                destroy_ix(&launch, destroyer, creator, ctx.feelssol_mint),
+                &[destroyer],
+            )
+            .await;
+        assert_feels_error(result, FeelsError::TokenNotExpired);
+
+        assert_eq!(
+            ctx.get_token_balance(&launch.escrow_feelssol_vault).await?,
+            MINT_FEE
+        );
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
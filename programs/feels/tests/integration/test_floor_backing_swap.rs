@@ -0,0 +1,221 @@
+//! Test that swaps keep the FeelsSOL reserves backing the floor
+//!
+//! Builds verify-invariants re-check after every swap that the reserves cover
+//! the circulating supply at the floor price. A launched market must pass, and
+//! one whose floor claims more backing than the vaults hold must be rejected.
+
+use crate::common::*;
+use crate::integration::test_deploy_initial_liquidity_parts::{deploy_ix, swap_ix};
+use anchor_lang::InstructionData;
+use feels::logic::{verify_floor_backing, SwapParams};
+use feels::state::{Buffer, Market, ProtocolConfig, TranchePlan};
+use feels::utils::{derive_tick_array, get_tick_array_start_index};
+use solana_sdk::instruction::AccountMeta;
+
+const ESCROW_TOKEN: u64 = 1_000_000_000_000;
+const ESCROW_FEELSSOL: u64 = 10_000_000_000;
+const SWAP_AMOUNT: u64 = 1_000_000;
+
+/// A launched market with its tranche ticks in place, ready to trade
+struct LaunchedMarket {
+    market_id: Pubkey,
+    tick_arrays: Vec<Pubkey>,
+}
+
+async fn launch_market(ctx: &TestContext) -> TestResult<LaunchedMarket> {
+    let launch = ctx
+        .create_pending_launch(ESCROW_TOKEN, ESCROW_FEELSSOL)
+        .await?;
+    let creator = &ctx.accounts.market_creator;
+
+    let protocol_config = ctx.protocol_accounts().await.protocol_config;
+    let config = ctx
+        .get_account::<ProtocolConfig>(&protocol_config)
+        .await?
+        .unwrap();
+    let step = config.default_tick_step_size as i32;
+    let market = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+
+    let creator_feelssol = ctx.create_ata(&creator.pubkey(), &market.token_0).await?;
+    let creator_token = ctx.create_ata(&creator.pubkey(), &market.token_1).await?;
+    ctx.process_instruction(
+        deploy_ix(
+            &launch,
+            &market,
+            creator,
+            creator_feelssol,
+            creator_token,
+            config.treasury,
+            DeployInitialLiquidityParams {
+                tick_step_size: step,
+                initial_buy_feelssol_amount: 0,
+                token_amount: 0,
+                feelssol_amount: 0,
+            },
+        ),
+        &[creator],
+    )
+    .await?;
+
+    // Create the tick arrays for every tranche boundary and apply the nets
+    let (tranche_plan, _) =
+        Pubkey::find_program_address(&[TranchePlan::SEED, launch.market.as_ref()], &PROGRAM_ID);
+    let plan = ctx
+        .get_account::<TranchePlan>(&tranche_plan)
+        .await?
+        .unwrap();
+    let mut tick_arrays = Vec::new();
+    for entry in &plan.entries {
+        for tick in [entry.tick_lower, entry.tick_upper] {
+            let start = get_tick_array_start_index(tick, market.tick_spacing);
+            let (tick_array, _) = derive_tick_array(&launch.market, start, &PROGRAM_ID);
+            if !tick_arrays.contains(&tick_array) {
+                tick_arrays.push(tick_array);
+            }
+        }
+    }
+
+    let mut ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: feels::accounts::InitializeTrancheTicks {
+            crank: creator.pubkey(),
+            market: launch.market,
+            tranche_plan,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: feels::instruction::InitializeTrancheTicks {
+            params: InitializeTrancheTicksParams {
+                tick_step_size: step,
+                num_steps: plan.entries.len() as u8,
+            },
+        }
+        .data(),
+    };
+    ix.accounts.extend(
+        tick_arrays
+            .iter()
+            .map(|tick_array| AccountMeta::new(*tick_array, false)),
+    );
+    ctx.process_instruction(ix, &[creator]).await?;
+
+    Ok(LaunchedMarket {
+        market_id: launch.market,
+        tick_arrays,
+    })
+}
+
+/// Buy the project token with FeelsSOL as alice
+async fn buy(ctx: &TestContext, launched: &LaunchedMarket) -> TestResult<TestResult<()>> {
+    let alice = &ctx.accounts.alice;
+    let market = ctx
+        .get_account::<Market>(&launched.market_id)
+        .await?
+        .unwrap();
+
+    let alice_feelssol = spl_associated_token_account::get_associated_token_address(
+        &alice.pubkey(),
+        &market.token_0,
+    );
+    ctx.set_token_account(
+        &alice_feelssol,
+        &market.token_0,
+        &alice.pubkey(),
+        SWAP_AMOUNT,
+    )
+    .await?;
+    let alice_token = ctx.create_ata(&alice.pubkey(), &market.token_1).await?;
+
+    let mut ix = swap_ix(
+        launched.market_id,
+        &market,
+        alice,
+        alice_feelssol,
+        alice_token,
+        SwapParams {
+            amount_in: SWAP_AMOUNT,
+            minimum_amount_out: SWAP_AMOUNT,
+            max_ticks_crossed: 0,
+            max_total_fee_bps: 0,
+        },
+    );
+    // FeelsSOL is token_0, so the buy swaps zero for one; pass the tranche
+    // arrays along that path
+    let path = ctx
+        .derive_tick_arrays(
+            &launched.market_id,
+            market.current_tick,
+            market.tick_spacing,
+            true,
+        )
+        .await?;
+    ix.accounts.extend(
+        path.iter()
+            .filter(|tick_array| launched.tick_arrays.contains(tick_array))
+            .map(|tick_array| AccountMeta::new(*tick_array, false)),
+    );
+    Ok(ctx.process_instruction(ix, &[alice]).await)
+}
+
+test_in_memory!(test_buy_keeps_floor_backed, |ctx: TestContext| async move {
+    let launched = launch_market(&ctx).await?;
+    buy(&ctx, &launched).await??;
+
+    let market = ctx
+        .get_account::<Market>(&launched.market_id)
+        .await?
+        .unwrap();
+    let alice_token = spl_associated_token_account::get_associated_token_address(
+        &ctx.accounts.alice.pubkey(),
+        &market.token_1,
+    );
+    assert!(ctx.get_token_balance(&alice_token).await? > 0);
+
+    // The same check the program runs in verify-invariants builds
+    let buffer = ctx.get_account::<Buffer>(&market.buffer).await?.unwrap();
+    let feelssol_reserve = buffer
+        .tau_spot
+        .saturating_add(ctx.get_token_balance(&market.vault_0).await? as u128);
+    assert!(verify_floor_backing(
+        market.floor_tick,
+        feelssol_reserve,
+        ctx.get_mint(&market.token_1).await?.supply,
+        ctx.get_token_balance(&market.vault_1).await?,
+        buffer.protocol_owned_override,
+    )
+    .is_ok());
+
+    Ok::<(), Box<dyn std::error::Error>>(())
+});
+
+#[cfg(feature = "verify-invariants")]
+test_in_memory!(
+    test_swap_rejected_when_reserves_miss_floor,
+    |ctx: TestContext| async move {
+        use feels::error::FeelsError;
+
+        let launched = launch_market(&ctx).await?;
+
+        // A floor at the top of the range values the bought tokens far above
+        // anything the FeelsSOL vault holds
+        ctx.update_program_account::<Market, _>(&launched.market_id, |market| {
+            market.floor_tick = market.global_upper_tick;
+        })
+        .await?;
+        let market_before = ctx
+            .get_account::<Market>(&launched.market_id)
+            .await?
+            .unwrap();
+
+        let result = buy(&ctx, &launched).await?;
+        assert_feels_error(result, FeelsError::FloorBackingViolated);
+
+        let market_after = ctx
+            .get_account::<Market>(&launched.market_id)
+            .await?
+            .unwrap();
+        assert_eq!(market_after.current_tick, market_before.current_tick);
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
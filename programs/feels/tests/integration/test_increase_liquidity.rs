@@ -0,0 +1,105 @@
+//! Test that increase_liquidity grows an existing position in place
+
+use crate::common::*;
+use feels::state::{Market, Position};
+
+#[tokio::test]
+async fn test_increase_liquidity_grows_position() {
+    // Skip in-memory tests since they require protocol tokens
+    if std::env::var("RUN_DEVNET_TESTS").is_err() && std::env::var("RUN_LOCALNET_TESTS").is_err() {
+        println!("Skipping test_increase_liquidity_grows_position - requires devnet or localnet");
+        return;
+    }
+
+    let env = if std::env::var("RUN_LOCALNET_TESTS").is_ok() {
+        TestEnvironment::localnet()
+    } else {
+        TestEnvironment::devnet()
+    };
+
+    let ctx = TestContext::new(env).await.unwrap();
+    let alice = &ctx.accounts.alice;
+    let bob = &ctx.accounts.bob;
+
+    let setup = ctx
+        .market_helper()
+        .create_test_market_with_liquidity(6, alice, -10000, 10000, 1_000_000_000_000)
+        .await
+        .unwrap();
+
+    // Fund bob with both tokens to provide liquidity and swap
+    for mint in [&setup.token_0, &setup.token_1] {
+        let account = ctx.create_ata(&bob.pubkey(), mint).await.unwrap();
+        let mint_authority = if *mint == ctx.feelssol_mint {
+            &ctx.feelssol_authority
+        } else {
+            &ctx.accounts.market_creator
+        };
+        ctx.mint_to(mint, &account, mint_authority, 1_000_000_000_000)
+            .await
+            .unwrap();
+    }
+
+    let position_helper = ctx.position_helper();
+    let position_info = position_helper
+        .open_position_with_metadata(&setup.market_id, bob, -1280, 1280, 1_000_000_000)
+        .await
+        .unwrap();
+
+    // Generate fees inside the position's range
+    ctx.swap_helper()
+        .swap(
+            &setup.market_id,
+            &setup.token_1,
+            &setup.token_0,
+            1_000_000,
+            bob,
+        )
+        .await
+        .unwrap();
+
+    let position_before = ctx
+        .get_account::<Position>(&position_info.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let market_before = ctx
+        .get_account::<Market>(&setup.market_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let delta = 500_000_000u128;
+    position_helper
+        .add_liquidity(&position_info.pubkey, bob, delta)
+        .await
+        .unwrap();
+
+    let position_after = ctx
+        .get_account::<Position>(&position_info.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let market_after = ctx
+        .get_account::<Market>(&setup.market_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Same position, same range, more liquidity
+    assert_eq!(position_after.nft_mint, position_info.mint);
+    assert_eq!(position_after.tick_lower, position_before.tick_lower);
+    assert_eq!(position_after.tick_upper, position_before.tick_upper);
+    assert_eq!(position_after.liquidity, position_before.liquidity + delta);
+
+    // The range covers the current tick, so active liquidity grows too
+    assert!(
+        market_after.current_tick >= -1280 && market_after.current_tick < 1280,
+        "position should be in range"
+    );
+    assert_eq!(market_after.liquidity, market_before.liquidity + delta);
+
+    // Fees earned before the increase are checkpointed, not lost
+    assert!(position_after.tokens_owed_0 >= position_before.tokens_owed_0);
+    assert!(position_after.tokens_owed_1 >= position_before.tokens_owed_1);
+}
@@ -0,0 +1,100 @@
+//! Test that the spot/TWAP circuit breaker fails closed in the swap instruction
+//!
+//! With `max_spot_twap_deviation_bps` set, an oracle that cannot produce a
+//! TWAP must reject the swap rather than quietly switch the breaker off.
+
+use crate::common::*;
+use crate::integration::test_deploy_initial_liquidity_parts::{deploy_ix, swap_ix};
+use feels::error::FeelsError;
+use feels::logic::SwapParams;
+use feels::state::{Market, OracleState, ProtocolConfig};
+
+const ESCROW_TOKEN: u64 = 1_000_000_000_000;
+const ESCROW_FEELSSOL: u64 = 10_000_000_000;
+const MAX_DEVIATION_BPS: u16 = 500;
+
+test_in_memory!(
+    test_swap_rejected_when_oracle_cannot_produce_twap,
+    |ctx: TestContext| async move {
+        let launch = ctx
+            .create_pending_launch(ESCROW_TOKEN, ESCROW_FEELSSOL)
+            .await?;
+        let creator = &ctx.accounts.market_creator;
+        let alice = &ctx.accounts.alice;
+
+        let protocol_config = ctx.protocol_accounts().await.protocol_config;
+        let config = ctx
+            .get_account::<ProtocolConfig>(&protocol_config)
+            .await?
+            .unwrap();
+        let market = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+
+        // Deploy the whole escrow in one part so the market opens for trading
+        let creator_feelssol = ctx.create_ata(&creator.pubkey(), &market.token_0).await?;
+        let creator_token = ctx.create_ata(&creator.pubkey(), &market.token_1).await?;
+        ctx.process_instruction(
+            deploy_ix(
+                &launch,
+                &market,
+                creator,
+                creator_feelssol,
+                creator_token,
+                config.treasury,
+                DeployInitialLiquidityParams {
+                    tick_step_size: config.default_tick_step_size as i32,
+                    initial_buy_feelssol_amount: 0,
+                    token_amount: 0,
+                    feelssol_amount: 0,
+                },
+            ),
+            &[creator],
+        )
+        .await?;
+
+        ctx.update_program_account::<ProtocolConfig, _>(&protocol_config, |config| {
+            config.max_spot_twap_deviation_bps = MAX_DEVIATION_BPS;
+        })
+        .await?;
+
+        // A manipulated oracle whose latest observation claims to be from the future
+        let now = ctx.current_unix_timestamp().await?;
+        ctx.update_program_account::<OracleState, _>(&market.oracle, |oracle| {
+            let latest = oracle.observation_index as usize;
+            oracle.observations[latest].block_timestamp = now + 3_600;
+        })
+        .await?;
+
+        let market_before = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+        let alice_feelssol = ctx
+            .create_ata(&alice.pubkey(), &market_before.token_0)
+            .await?;
+        let alice_token = ctx
+            .create_ata(&alice.pubkey(), &market_before.token_1)
+            .await?;
+        let result = ctx
+            .process_instruction(
+                swap_ix(
+                    launch.market,
+                    &market_before,
+                    alice,
+                    alice_feelssol,
+                    alice_token,
+                    SwapParams {
+                        amount_in: 1_000_000,
+                        minimum_amount_out: 1_000_000,
+                        max_ticks_crossed: 0,
+                        max_total_fee_bps: 0,
+                    },
+                ),
+                &[alice],
+            )
+            .await;
+        assert_feels_error(result, FeelsError::InvalidTimestamp);
+
+        let market_after = ctx.get_account::<Market>(&launch.market).await?.unwrap();
+        assert_eq!(market_after.current_tick, market_before.current_tick);
+        assert_eq!(market_after.sqrt_price, market_before.sqrt_price);
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
@@ -1,5 +1,6 @@
 //! Integration tests for mint_token instruction
 use crate::common::*;
+use feels::events::TokenMinted;
 use feels::state::{PreLaunchEscrow, ProtocolToken};
 use solana_sdk::signature::Keypair;
 
@@ -85,7 +86,10 @@ async fn test_mint_token_basic() -> std::result::Result<(), Box<dyn std::error::
 
     // Process the instruction
     println!("About to process mint_token instruction...");
-    match ctx.process_instruction(ix, &[&creator, &token_mint]).await {
+    let (result, logs) = ctx
+        .process_instruction_with_logs(ix, &[&creator, &token_mint])
+        .await;
+    match result {
         Ok(_) => println!("[OK] mint_token instruction executed successfully"),
         Err(e) => {
             println!("[ERROR] mint_token instruction failed: {:?}", e);
@@ -96,6 +100,14 @@ async fn test_mint_token_basic() -> std::result::Result<(), Box<dyn std::error::
     // Verify the token mint was created
     let mint_info = ctx.get_mint(&token_mint.pubkey()).await?;
     assert_eq!(mint_info.decimals, 6, "Token should have 6 decimals");
+
+    // The TokenMinted event carries the mint's decimals
+    let minted: TokenMinted = assert_event(&logs);
+    assert_eq!(minted.token_mint, token_mint.pubkey());
+    assert_eq!(
+        minted.decimals, mint_info.decimals,
+        "TokenMinted should carry the mint decimals"
+    );
     assert_eq!(
         mint_info.supply, 1_000_000_000_000_000,
         "Total supply should be 1B tokens"
@@ -1,8 +1,11 @@
+pub mod test_cleanup_tick_array;
 pub mod test_close_position;
 pub mod test_collect_fees;
+pub mod test_deploy_initial_liquidity;
 pub mod test_initialize_hub;
 pub mod test_initialize_market;
 pub mod test_initialize_protocol;
+pub mod test_migrate_account;
 pub mod test_open_position;
 pub mod test_set_protocol_owned_override;
 pub mod test_swap;
@@ -0,0 +1,82 @@
+//! Unit tests for the cleanup_tick_array crank
+
+use feels::state::{Tick, TickArray, TICK_ARRAY_SIZE};
+
+const TICK_SPACING: u16 = 10;
+
+fn empty_tick_array() -> Box<TickArray> {
+    Box::new(TickArray {
+        market: Default::default(),
+        start_tick_index: 0,
+        _pad0: [0; 12],
+        ticks: [Tick::default(); TICK_ARRAY_SIZE],
+        initialized_tick_count: 0,
+        _pad1: [0; 14],
+        _reserved: [0; 32],
+    })
+}
+
+/// Add (or with a negative delta, remove) a position's liquidity at its ticks,
+/// as open_position and close_position do
+fn apply_position(array: &mut TickArray, lower: i32, upper: i32, delta: i128) {
+    for tick in [lower, upper] {
+        array.init_tick(tick, TICK_SPACING, 0, 0, 0).unwrap();
+    }
+    array.update_liquidity(lower, TICK_SPACING, delta, false).unwrap();
+    array.update_liquidity(upper, TICK_SPACING, delta, true).unwrap();
+}
+
+#[test]
+fn test_closed_position_ticks_are_deinitialized() {
+    let mut array = empty_tick_array();
+    apply_position(&mut array, 100, 200, 1_000_000);
+    apply_position(&mut array, 100, 200, -1_000_000);
+
+    // Still initialized after the close, with nothing left on them
+    assert_eq!(array.initialized_tick_count, 2);
+    assert_eq!(array.get_tick(100, TICK_SPACING).unwrap().liquidity_net, 0);
+
+    assert_eq!(array.clear_unreferenced_ticks(), 2);
+
+    assert_eq!(array.initialized_tick_count, 0);
+    for tick in [100, 200] {
+        let t = array.get_tick(tick, TICK_SPACING).unwrap();
+        assert_eq!(t.initialized, 0);
+        assert_eq!(t.fee_growth_outside_0_x64, 0);
+    }
+}
+
+#[test]
+fn test_referenced_tick_is_left_alone() {
+    let mut array = empty_tick_array();
+    // Two positions share the lower tick; the second is still open
+    apply_position(&mut array, 100, 200, 1_000_000);
+    apply_position(&mut array, 100, 300, 500_000);
+    apply_position(&mut array, 100, 200, -1_000_000);
+
+    assert_eq!(array.clear_unreferenced_ticks(), 1);
+
+    let shared = array.get_tick(100, TICK_SPACING).unwrap();
+    assert_eq!(shared.initialized, 1);
+    assert_eq!(shared.liquidity_gross, 500_000);
+    assert_eq!(shared.liquidity_net, 500_000);
+    assert_eq!(array.get_tick(200, TICK_SPACING).unwrap().initialized, 0);
+    assert_eq!(array.get_tick(300, TICK_SPACING).unwrap().initialized, 1);
+    assert_eq!(array.initialized_tick_count, 2);
+}
+
+#[test]
+fn test_tick_with_zero_net_but_open_positions_is_kept() {
+    let mut array = empty_tick_array();
+    // One position ends at 200 and another starts there: net cancels out but
+    // both still reference the tick
+    apply_position(&mut array, 100, 200, 1_000_000);
+    apply_position(&mut array, 200, 300, 1_000_000);
+
+    let shared = array.get_tick(200, TICK_SPACING).unwrap();
+    assert_eq!(shared.liquidity_net, 0);
+
+    assert_eq!(array.clear_unreferenced_ticks(), 0);
+    assert_eq!(array.get_tick(200, TICK_SPACING).unwrap().initialized, 1);
+    assert_eq!(array.initialized_tick_count, 3);
+}
@@ -0,0 +1,123 @@
+//! Tests for reconciling multi-part initial liquidity deployments
+
+use feels::error::FeelsError;
+use feels::instructions::merge_tranche_entries;
+use feels::state::{DeploymentProgress, TrancheEntry};
+
+const COMMITTED_TOKEN: u64 = 1_000_000_000;
+const COMMITTED_FEELSSOL: u64 = 50_000_000;
+const ANCHOR_TICK: i32 = -1280;
+const TICK_STEP_SIZE: i32 = 128;
+
+fn committed_progress() -> DeploymentProgress {
+    DeploymentProgress::new(
+        COMMITTED_TOKEN,
+        COMMITTED_FEELSSOL,
+        ANCHOR_TICK,
+        TICK_STEP_SIZE,
+    )
+}
+
+fn entry(tick_lower: i32, liquidity: u128) -> TrancheEntry {
+    TrancheEntry {
+        tick_lower,
+        tick_upper: tick_lower + 100,
+        liquidity,
+    }
+}
+
+#[test]
+fn test_two_part_deploy_completes_commitment() {
+    let mut progress = committed_progress();
+    assert!(progress.is_started());
+
+    let complete = progress
+        .record(COMMITTED_TOKEN / 4, COMMITTED_FEELSSOL / 2)
+        .unwrap();
+    assert!(!complete);
+    assert_eq!(
+        progress.remaining(),
+        (COMMITTED_TOKEN * 3 / 4, COMMITTED_FEELSSOL / 2)
+    );
+
+    // Zero amounts deploy whatever is still outstanding
+    let (token, feelssol) = progress.part_amounts(0, 0);
+    assert_eq!((token, feelssol), progress.remaining());
+
+    let complete = progress.record(token, feelssol).unwrap();
+    assert!(complete);
+    assert!(progress.is_complete());
+    assert_eq!(progress.deployed_token, COMMITTED_TOKEN);
+    assert_eq!(progress.deployed_feelssol, COMMITTED_FEELSSOL);
+
+    // Every part is laid out from the stair fixed by the first
+    assert_eq!(progress.anchor_tick, ANCHOR_TICK);
+    assert_eq!(progress.tick_step_size, TICK_STEP_SIZE);
+}
+
+#[test]
+fn test_over_deploy_rejected() {
+    let mut progress = committed_progress();
+    progress
+        .record(COMMITTED_TOKEN / 2, COMMITTED_FEELSSOL / 2)
+        .unwrap();
+
+    assert_eq!(
+        progress
+            .record(COMMITTED_TOKEN / 2 + 1, COMMITTED_FEELSSOL / 2)
+            .unwrap_err(),
+        FeelsError::DeploymentExceedsCommitment.into()
+    );
+    assert_eq!(
+        progress
+            .record(COMMITTED_TOKEN / 2, COMMITTED_FEELSSOL / 2 + 1)
+            .unwrap_err(),
+        FeelsError::DeploymentExceedsCommitment.into()
+    );
+
+    // The rejected parts left the totals untouched
+    assert!(!progress.is_complete());
+    assert_eq!(
+        progress.remaining(),
+        (COMMITTED_TOKEN / 2, COMMITTED_FEELSSOL / 2)
+    );
+
+    // Nothing can be recorded before a commitment exists
+    assert_eq!(
+        DeploymentProgress::default().record(1, 1).unwrap_err(),
+        FeelsError::InvalidDeploymentAmount.into()
+    );
+}
+
+#[test]
+fn test_tranche_entries_accumulate_across_parts() {
+    let mut plan = vec![entry(0, 10), entry(100, 20)];
+    merge_tranche_entries(&mut plan, &[entry(0, 5), entry(100, 7)]).unwrap();
+    assert_eq!(plan[0].liquidity, 15);
+    assert_eq!(plan[1].liquidity, 27);
+
+    // A part laid out with a different step size does not line up
+    assert_eq!(
+        merge_tranche_entries(&mut plan, &[entry(0, 5), entry(200, 7)]).unwrap_err(),
+        FeelsError::InvalidTickRange.into()
+    );
+}
+
+#[test]
+fn test_partial_deploy_stalls_after_expiration() {
+    let expiration = 1_000;
+    let mut progress = committed_progress();
+    progress
+        .record(COMMITTED_TOKEN / 2, COMMITTED_FEELSSOL / 2)
+        .unwrap();
+
+    // Unfinished, but the market authority still has until expiration
+    assert!(!progress.is_stalled(expiration, expiration));
+    assert!(progress.is_stalled(expiration + 1, expiration));
+
+    // Neither a deployment that never started nor a finished one can stall
+    assert!(!DeploymentProgress::default().is_stalled(expiration + 1, expiration));
+    let (token, feelssol) = progress.remaining();
+    progress.record(token, feelssol).unwrap();
+    assert!(!progress.is_stalled(expiration + 1, expiration));
+}
@@ -197,6 +197,8 @@ fn test_market_state_initialization() {
         rolling_window_start_slot: 0,
         tick_snapshot_1hr: 0,
         last_snapshot_timestamp: 0,
+        swap_volume_slot: 0,
+        swap_slot_volume_feelssol: 0,
         _reserved: [0; 1],
     };
 
@@ -0,0 +1,117 @@
+//! Tests for migrate_account layout detection
+
+#[cfg(test)]
+mod test_migrate_account {
+    use crate::unit::test_helpers::create_test_market;
+    use anchor_lang::prelude::*;
+    use anchor_lang::Discriminator;
+    use feels::state::{
+        current_account_len, needs_migration, Market, PoolRegistry, ProtocolConfig,
+    };
+
+    /// Bytes of the fields added to `Market` after the simulated old layout:
+    /// `tick_snapshot_1hr`, `last_snapshot_timestamp` and `_reserved`
+    const ADDED_BYTES: usize = 4 + 8 + 1;
+
+    fn serialized_market(market: &Market) -> Vec<u8> {
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_old_sized_market_migrates_with_zeroed_fields() {
+        let mut market = create_test_market();
+        market.tick_snapshot_1hr = 1234;
+        market.last_snapshot_timestamp = 1_700_000_000;
+        let full = serialized_market(&market);
+
+        // An account created before the trailing fields existed
+        let mut data = full[..full.len() - ADDED_BYTES].to_vec();
+        assert!(Market::try_deserialize(&mut data.as_slice()).is_err());
+
+        let new_len = current_account_len(&data).unwrap();
+        assert_eq!(new_len, Market::LEN);
+        assert!(needs_migration(data.len(), new_len));
+
+        // What migrate_account does after realloc: zero the exposed bytes
+        let old_len = data.len();
+        data.resize(new_len, 0xff);
+        data[old_len..].fill(0);
+
+        let migrated = Market::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.token_1, market.token_1);
+        assert_eq!(migrated.liquidity, market.liquidity);
+        assert_eq!(migrated.tick_snapshot_1hr, 0);
+        assert_eq!(migrated.last_snapshot_timestamp, 0);
+        assert_eq!(migrated._reserved, [0; 1]);
+    }
+
+    #[test]
+    fn test_protocol_config_swap_caps_migrate_as_uncapped() {
+        let config = ProtocolConfig {
+            authority: Pubkey::new_unique(),
+            mint_fee: 1_000,
+            treasury: Pubkey::new_unique(),
+            default_protocol_fee_rate: 1_000,
+            default_creator_fee_rate: 500,
+            max_protocol_fee_rate: 2_500,
+            token_expiration_seconds: 3_600,
+            depeg_threshold_bps: 100,
+            depeg_required_obs: 3,
+            clear_required_obs: 5,
+            dex_twap_window_secs: 300,
+            dex_twap_stale_age_secs: 600,
+            dex_twap_updater: Pubkey::new_unique(),
+            dex_whitelist: [Pubkey::default(); 8],
+            dex_whitelist_len: 0,
+            max_spot_twap_deviation_bps: 0,
+            _reserved: [0; 5],
+            mint_per_slot_cap_feelssol: 0,
+            redeem_per_slot_cap_feelssol: 0,
+            default_base_fee_bps: 30,
+            default_tick_spacing: 64,
+            default_initial_sqrt_price: 5825507814218144,
+            default_tick_step_size: 128,
+            swap_per_slot_cap_feelssol: 7,
+            bonding_swap_per_slot_cap_feelssol: 9,
+        };
+        let mut full = Vec::new();
+        config.try_serialize(&mut full).unwrap();
+
+        // A config created before the two swap caps existed
+        let mut data = full[..full.len() - 16].to_vec();
+        let old_len = data.len();
+        data.resize(current_account_len(&data).unwrap(), 0xff);
+        data[old_len..].fill(0);
+
+        // The market defaults keep their offsets; the caps read as unlimited
+        let migrated = ProtocolConfig::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.default_base_fee_bps, 30);
+        assert_eq!(migrated.default_tick_spacing, 64);
+        assert_eq!(migrated.default_initial_sqrt_price, 5825507814218144);
+        assert_eq!(migrated.default_tick_step_size, 128);
+        assert_eq!(migrated.swap_per_slot_cap_feelssol, 0);
+        assert_eq!(migrated.bonding_swap_per_slot_cap_feelssol, 0);
+    }
+
+    #[test]
+    fn test_current_market_is_left_alone() {
+        let mut data = serialized_market(&create_test_market());
+        data.resize(Market::LEN, 0);
+        assert!(!needs_migration(
+            data.len(),
+            current_account_len(&data).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_unknown_and_variable_size_accounts_are_rejected() {
+        assert_eq!(current_account_len(&[0u8; 64]), None);
+        assert_eq!(current_account_len(&[1u8; 4]), None);
+
+        let mut registry = PoolRegistry::DISCRIMINATOR.to_vec();
+        registry.resize(PoolRegistry::LEN, 0);
+        assert_eq!(current_account_len(&registry), None);
+    }
+}
@@ -22,13 +22,16 @@ mod test_set_protocol_owned_override {
             dex_twap_updater: Pubkey::new_unique(),
             dex_whitelist: [Pubkey::default(); 8],
             dex_whitelist_len: 0,
-            _reserved: [0; 7],
+            max_spot_twap_deviation_bps: 0,
+            _reserved: [0; 5],
             mint_per_slot_cap_feelssol: 0,
             redeem_per_slot_cap_feelssol: 0,
             default_base_fee_bps: 30,
             default_tick_spacing: 64,
             default_initial_sqrt_price: 5825507814218144,
             default_tick_step_size: 128,
+            swap_per_slot_cap_feelssol: 0,
+            bonding_swap_per_slot_cap_feelssol: 0,
         }
     }
 
@@ -4,6 +4,7 @@
 
 use crate::common::*;
 use feels::logic::position_fees::calculate_position_fee_accrual;
+use feels::utils::fee_growth_delta;
 use feels::state::Tick;
 
 fn create_tick(fee_growth_0: u128, fee_growth_1: u128) -> Tick {
@@ -253,3 +254,46 @@ test_in_memory!(
         Ok::<(), Box<dyn std::error::Error>>(())
     }
 );
+
+test_in_memory!(test_fee_growth_delta_across_wrap, |ctx: TestContext| async move {
+    // No wrap: plain difference
+    assert_eq!(fee_growth_delta(1_000, 400), 600);
+    assert_eq!(fee_growth_delta(u128::MAX, u128::MAX), 0);
+
+    // Accumulator wrapped between readings
+    assert_eq!(fee_growth_delta(0, u128::MAX), 1);
+    assert_eq!(fee_growth_delta(349, u128::MAX - 100), 450);
+    assert_eq!(fee_growth_delta(u128::MAX / 2, u128::MAX / 2 + 1), u128::MAX);
+
+    Ok::<(), Box<dyn std::error::Error>>(())
+});
+
+test_in_memory!(
+    test_accrual_after_global_wrap,
+    |ctx: TestContext| async move {
+        // Inside growth wrapped from just below u128::MAX to 350
+        let lower_tick = create_tick(100, 100);
+        let upper_tick = create_tick(50, 50);
+
+        let result = calculate_position_fee_accrual(
+            100,
+            50,
+            150,
+            1 << 64,
+            500,
+            500,
+            &lower_tick,
+            &upper_tick,
+            u128::MAX - 100,
+            u128::MAX - 100,
+        )
+        .unwrap();
+
+        // Delta: 350 - (MAX - 100) = 451, liquidity 2^64 cancels the Q64 shift
+        assert_eq!(result.fee_growth_inside_0, 350);
+        assert_eq!(result.tokens_owed_0_increment, 451);
+        assert_eq!(result.tokens_owed_1_increment, 451);
+
+        Ok::<(), Box<dyn std::error::Error>>(())
+    }
+);
@@ -23,6 +23,12 @@ pub mod test_simple_example;
 // Token ordering validation tests
 pub mod test_token_ordering;
 
+// Pool status change event tests
+pub mod test_pool_status;
+
+// Phase transition table tests
+pub mod test_phase_transitions;
+
 // Test helpers
 pub mod test_helpers;
 
@@ -7,6 +7,8 @@ pub mod test_tick_array_griefing;
 pub mod test_update_floor_validation;
 
 // New critical security tests
+pub mod test_escrow_refund;
+pub mod test_floor_backing;
 pub mod test_floor_monotonicity;
 pub mod test_mint_authority_revocation;
 pub mod test_safety_controller;
@@ -0,0 +1,29 @@
+//! Tests for unwinding the pre-launch escrow of an expired token
+
+use feels::error::FeelsError;
+use feels::instructions::validate_escrow_unwind;
+
+const EXPIRATION: i64 = 1_000;
+
+#[test]
+fn test_refund_after_expiry_without_deployment() {
+    assert!(validate_escrow_unwind(EXPIRATION + 1, EXPIRATION, false).is_ok());
+}
+
+#[test]
+fn test_refund_rejected_after_deployment() {
+    assert_eq!(
+        validate_escrow_unwind(EXPIRATION + 1, EXPIRATION, true).unwrap_err(),
+        FeelsError::MarketAlreadyActive.into()
+    );
+}
+
+#[test]
+fn test_refund_rejected_before_expiry() {
+    for now in [EXPIRATION - 1, EXPIRATION] {
+        assert_eq!(
+            validate_escrow_unwind(now, EXPIRATION, false).unwrap_err(),
+            FeelsError::TokenNotExpired.into()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Tests for the post-swap floor backing invariant
+
+use feels::constants::MAX_TICK;
+use feels::error::FeelsError;
+use feels::logic::verify_floor_backing;
+
+/// Price 1 at tick 0, so the floor value is just the circulating supply
+const UNIT_PRICE_TICK: i32 = 0;
+
+#[test]
+fn test_reserves_covering_circulating_supply_pass() {
+    // 5_000 minted, 4_000 in the pool: 1_000 circulating against 1_000 reserve
+    assert!(verify_floor_backing(UNIT_PRICE_TICK, 1_000, 5_000, 4_000, 0).is_ok());
+}
+
+#[test]
+fn test_shortfall_trips_invariant() {
+    assert_eq!(
+        verify_floor_backing(UNIT_PRICE_TICK, 1_000, 5_000, 3_999, 0).unwrap_err(),
+        FeelsError::FloorBackingViolated.into()
+    );
+}
+
+#[test]
+fn test_protocol_owned_override_replaces_pool_inventory() {
+    assert!(verify_floor_backing(UNIT_PRICE_TICK, 1_000, 5_000, 0, 4_000).is_ok());
+    assert_eq!(
+        verify_floor_backing(UNIT_PRICE_TICK, 1_000, 5_000, 5_000, 3_000).unwrap_err(),
+        FeelsError::FloorBackingViolated.into()
+    );
+}
+
+#[test]
+fn test_launch_floor_is_backed() {
+    // ~1e-7 FeelsSOL per token: 1e12 circulating needs ~1e5 of reserve
+    assert!(verify_floor_backing(-161_216, 100_000, 1_000_000_000_000, 0, 0).is_ok());
+    assert!(verify_floor_backing(-161_216, 90_000, 1_000_000_000_000, 0, 0).is_err());
+}
+
+#[test]
+fn test_nothing_circulating_needs_no_reserve() {
+    assert!(verify_floor_backing(MAX_TICK, 0, 5_000, 5_000, 0).is_ok());
+}
+
+#[test]
+fn test_largest_floor_value_is_compared_without_overflow() {
+    assert!(verify_floor_backing(MAX_TICK, u128::MAX, u64::MAX, 0, 0).is_ok());
+    assert_eq!(
+        verify_floor_backing(MAX_TICK, u64::MAX as u128, u64::MAX, 0, 0).unwrap_err(),
+        FeelsError::FloorBackingViolated.into()
+    );
+}
@@ -0,0 +1,83 @@
+//! Test the market and pool phase transition tables
+
+use feels::state::{can_transition, MarketPhase, PoolPhase};
+
+const MARKET_PHASES: [MarketPhase; 7] = [
+    MarketPhase::Created,
+    MarketPhase::BondingCurve,
+    MarketPhase::Transitioning,
+    MarketPhase::SteadyState,
+    MarketPhase::Graduated,
+    MarketPhase::Paused,
+    MarketPhase::Deprecated,
+];
+
+const POOL_PHASES: [PoolPhase; 4] = [
+    PoolPhase::BondingCurve,
+    PoolPhase::SteadyState,
+    PoolPhase::Paused,
+    PoolPhase::Deprecated,
+];
+
+#[test]
+fn test_market_phase_transition_table() {
+    use MarketPhase::*;
+    let allowed = [
+        (Created, BondingCurve),
+        (Created, SteadyState),
+        (Created, Deprecated),
+        (BondingCurve, Transitioning),
+        (BondingCurve, Paused),
+        (BondingCurve, Deprecated),
+        (Transitioning, SteadyState),
+        (Transitioning, Paused),
+        (Transitioning, Deprecated),
+        (SteadyState, Graduated),
+        (SteadyState, Paused),
+        (SteadyState, Deprecated),
+        (Graduated, Deprecated),
+        (Paused, BondingCurve),
+        (Paused, Transitioning),
+        (Paused, SteadyState),
+        (Paused, Deprecated),
+    ];
+
+    for from in MARKET_PHASES {
+        for to in MARKET_PHASES {
+            assert_eq!(
+                from.can_transition_to(to),
+                allowed.contains(&(from, to)),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pool_phase_transition_table() {
+    use PoolPhase::*;
+    let allowed = [
+        (BondingCurve, SteadyState),
+        (BondingCurve, Paused),
+        (BondingCurve, Deprecated),
+        (SteadyState, Paused),
+        (SteadyState, Deprecated),
+        (Paused, BondingCurve),
+        (Paused, SteadyState),
+        (Paused, Deprecated),
+    ];
+
+    for from in POOL_PHASES {
+        for to in POOL_PHASES {
+            assert_eq!(
+                can_transition(from, to),
+                allowed.contains(&(from, to)),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
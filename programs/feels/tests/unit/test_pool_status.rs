@@ -0,0 +1,79 @@
+//! Test pool status change events emitted on pause/unpause
+
+use crate::common::*;
+use feels::instructions::pool_status_change;
+use feels::state::{PhaseTrigger, PoolStatus, PoolStatusReason, PoolStressComponents};
+
+#[test]
+fn test_status_change_fires_on_stress_disable() {
+    let market = Pubkey::new_unique();
+
+    let stress = PoolStressComponents {
+        spot_stress_bps: 1_250,
+        time_stress_secs: 3_600,
+        leverage_stress_bps: 0,
+    };
+
+    // Safety circuit breaker moves the market into Paused
+    let event = pool_status_change(
+        market,
+        false,
+        true,
+        PhaseTrigger::SafetyTrigger,
+        stress,
+        1_000,
+        42,
+    )
+    .expect("stress-induced disable should emit PoolStatusChanged");
+
+    assert_eq!(event.market, market);
+    assert_eq!(event.old_status, PoolStatus::Normal as u8);
+    assert_eq!(event.new_status, PoolStatus::Disabled as u8);
+    assert_eq!(event.reason, PoolStatusReason::SpotStress as u8);
+    assert_eq!(event.trigger, PhaseTrigger::SafetyTrigger as u8);
+    assert_eq!(event.stress, stress);
+    assert_eq!(event.timestamp, 1_000);
+    assert_eq!(event.slot, 42);
+}
+
+#[test]
+fn test_status_change_reasons() {
+    let market = Pubkey::new_unique();
+
+    let manual = pool_status_change(
+        market,
+        false,
+        true,
+        PhaseTrigger::Governance,
+        PoolStressComponents::default(),
+        0,
+        0,
+    )
+    .unwrap();
+    assert_eq!(manual.reason, PoolStatusReason::ManualDisable as u8);
+
+    let resumed = pool_status_change(
+        market,
+        true,
+        false,
+        PhaseTrigger::Creator,
+        PoolStressComponents::default(),
+        0,
+        0,
+    )
+    .unwrap();
+    assert_eq!(resumed.new_status, PoolStatus::Normal as u8);
+    assert_eq!(resumed.reason, PoolStatusReason::Cooldown as u8);
+
+    // No event when the paused flag does not change
+    assert!(pool_status_change(
+        market,
+        false,
+        false,
+        PhaseTrigger::Creator,
+        PoolStressComponents::default(),
+        0,
+        0
+    )
+    .is_none());
+}
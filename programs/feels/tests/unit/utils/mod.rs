@@ -1,3 +1,5 @@
 // Utils test modules
 
 pub mod test_validations;
+pub mod test_pda_seeds;
+pub mod test_parameter_validation;
@@ -0,0 +1,159 @@
+//! Tests for instruction parameter validation
+
+#[cfg(test)]
+mod test_parameter_validation {
+    use feels::{
+        constants::{MAX_FEE_BPS, MAX_TICK, MIN_POSITION_LIQUIDITY, MIN_TICK},
+        error::FeelsError,
+        utils::*,
+    };
+
+    fn assert_err(result: anchor_lang::Result<()>, expected: FeelsError) {
+        assert_eq!(result.unwrap_err(), expected.into());
+    }
+
+    #[test]
+    fn test_base_fee_bounds() {
+        assert!(validate_base_fee_bps(1).is_ok());
+        assert!(validate_base_fee_bps(MAX_FEE_BPS).is_ok());
+
+        assert_err(validate_base_fee_bps(0), FeelsError::InvalidParameter);
+        assert_err(
+            validate_base_fee_bps(MAX_FEE_BPS + 1),
+            FeelsError::FeeTooHigh,
+        );
+    }
+
+    #[test]
+    fn test_tick_spacing_allowed_values() {
+        for spacing in [1, 2, 4, 6, 8, 10, 16, 32, 64] {
+            assert!(validate_tick_spacing_param(spacing).is_ok());
+        }
+
+        assert_err(
+            validate_tick_spacing_param(0),
+            FeelsError::InvalidTickSpacing,
+        );
+        assert_err(
+            validate_tick_spacing_param(3),
+            FeelsError::InvalidTickSpacing,
+        );
+        assert_err(
+            validate_tick_spacing_param(128),
+            FeelsError::InvalidTickSpacing,
+        );
+    }
+
+    #[test]
+    fn test_protocol_fee_rates() {
+        assert!(validate_protocol_fee_rates(100, 50, 1000).is_ok());
+        assert!(validate_protocol_fee_rates(500, 500, 500).is_ok());
+
+        // Protocol share above its configured maximum
+        assert_err(
+            validate_protocol_fee_rates(600, 0, 500),
+            FeelsError::FeeTooHigh,
+        );
+        // Maximum itself above the protocol cap
+        assert_err(
+            validate_protocol_fee_rates(100, 0, MAX_FEE_BPS + 1),
+            FeelsError::FeeTooHigh,
+        );
+        // Creator share above 5%
+        assert_err(
+            validate_protocol_fee_rates(100, 501, 1000),
+            FeelsError::FeeTooHigh,
+        );
+        // Combined shares above the cap
+        assert_err(
+            validate_protocol_fee_rates(800, 300, 1000),
+            FeelsError::FeeTooHigh,
+        );
+    }
+
+    #[test]
+    fn test_initial_price_out_of_range() {
+        assert_err(
+            align_initial_sqrt_price(MIN_SQRT_PRICE - 1, 64).map(|_| ()),
+            FeelsError::InvalidPrice,
+        );
+        assert_err(
+            align_initial_sqrt_price(MAX_SQRT_PRICE + 1, 64).map(|_| ()),
+            FeelsError::InvalidPrice,
+        );
+
+        // Range endpoints snap inward onto aligned ticks
+        let (_, tick) = align_initial_sqrt_price(MIN_SQRT_PRICE, 64).unwrap();
+        assert!(tick >= MIN_TICK && tick % 64 == 0);
+        let (_, tick) = align_initial_sqrt_price(MAX_SQRT_PRICE, 64).unwrap();
+        assert!(tick <= MAX_TICK && tick % 64 == 0);
+    }
+
+    #[test]
+    fn test_initial_price_alignment() {
+        // Already aligned: price is kept as-is
+        let aligned = sqrt_price_from_tick(-128).unwrap();
+        assert_eq!(
+            align_initial_sqrt_price(aligned, 64).unwrap(),
+            (aligned, -128)
+        );
+
+        // Misaligned: snapped to the nearest multiple of the spacing
+        let misaligned = sqrt_price_from_tick(-100).unwrap();
+        let (sqrt_price, tick) = align_initial_sqrt_price(misaligned, 64).unwrap();
+        assert_eq!(tick, -128);
+        assert_eq!(sqrt_price, sqrt_price_from_tick(-128).unwrap());
+
+        let misaligned = sqrt_price_from_tick(90).unwrap();
+        let (_, tick) = align_initial_sqrt_price(misaligned, 64).unwrap();
+        assert_eq!(tick, 64);
+
+        assert_err(
+            align_initial_sqrt_price(aligned, 0).map(|_| ()),
+            FeelsError::InvalidTickSpacing,
+        );
+    }
+
+    /// Amounts `open_position` would charge for `liquidity` at `sqrt_price`
+    fn position_size(
+        liquidity: u128,
+        sqrt_price: u128,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> anchor_lang::Result<()> {
+        let lower = sqrt_price_from_tick(tick_lower).unwrap();
+        let upper = sqrt_price_from_tick(tick_upper).unwrap();
+        let (amount_0, amount_1) =
+            feels::logic::amounts_from_liquidity(sqrt_price, lower, upper, liquidity).unwrap();
+        validate_position_size(liquidity, sqrt_price, lower, upper, amount_0, amount_1)
+    }
+
+    #[test]
+    fn test_dust_position_rejected() {
+        let price = sqrt_price_from_tick(0).unwrap();
+
+        assert_err(
+            position_size(MIN_POSITION_LIQUIDITY - 1, price, -100, 100),
+            FeelsError::LiquidityBelowMinimum,
+        );
+
+        // In range, but the price sits a hair above the lower tick, so the
+        // token_1 side of the deposit rounds to zero
+        let lower = sqrt_price_from_tick(-100).unwrap();
+        assert_err(
+            position_size(MIN_POSITION_LIQUIDITY, lower + 1, -100, 100),
+            FeelsError::PositionAmountRoundsToZero,
+        );
+    }
+
+    #[test]
+    fn test_minimal_position_accepted() {
+        let price = sqrt_price_from_tick(0).unwrap();
+
+        // In range: both sides funded
+        assert!(position_size(MIN_POSITION_LIQUIDITY, price, -100, 100).is_ok());
+        // Out of range: single-sided is expected
+        assert!(position_size(MIN_POSITION_LIQUIDITY, price, 100, 200).is_ok());
+        assert!(position_size(MIN_POSITION_LIQUIDITY, price, -200, -100).is_ok());
+    }
+}
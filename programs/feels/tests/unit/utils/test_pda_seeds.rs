@@ -0,0 +1,83 @@
+//! Tests for the PDA seed registry
+
+#[cfg(test)]
+mod test_pda_seeds {
+    use feels::{
+        constants::{SeedComponent, PDA_SEEDS},
+        utils::seeds::*,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_registry_names_are_unique() {
+        let mut names: Vec<&str> = PDA_SEEDS.iter().map(|p| p.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), PDA_SEEDS.len());
+
+        // Every pattern starts with a literal prefix
+        for pattern in PDA_SEEDS {
+            assert!(matches!(
+                pattern.components.first(),
+                Some(SeedComponent::Literal(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_derive_pda_matches_helpers() {
+        let program_id = feels::ID;
+        let market = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            derive_pda("vault", &[market.as_ref(), mint.as_ref()], &program_id),
+            Some(derive_vault(&market, &mint, &program_id))
+        );
+        assert_eq!(
+            derive_pda("market_authority", &[market.as_ref()], &program_id),
+            Some(derive_market_authority(&market, &program_id))
+        );
+        assert_eq!(
+            derive_pda("oracle", &[market.as_ref()], &program_id),
+            Some(derive_oracle(&market, &program_id))
+        );
+        assert_eq!(
+            derive_pda("epoch_params", &[market.as_ref()], &program_id),
+            Some(derive_epoch_params(&market, &program_id))
+        );
+        assert_eq!(
+            derive_pda(
+                "tick_array",
+                &[market.as_ref(), &(-88i32).to_le_bytes()],
+                &program_id
+            ),
+            Some(derive_tick_array(&market, -88, &program_id))
+        );
+        assert_eq!(
+            derive_pda("position", &[mint.as_ref()], &program_id),
+            Some(derive_position(&mint, &program_id))
+        );
+        assert_eq!(
+            derive_pda("mint_authority", &[mint.as_ref()], &program_id),
+            Some(derive_mint_authority(&mint, &program_id))
+        );
+        assert_eq!(
+            derive_pda("jitosol_vault", &[mint.as_ref()], &program_id),
+            Some(derive_jitosol_vault(&mint, &program_id))
+        );
+    }
+
+    #[test]
+    fn test_derive_pda_rejects_bad_inputs() {
+        let program_id = feels::ID;
+        let market = Pubkey::new_unique();
+
+        assert!(derive_pda("unknown", &[], &program_id).is_none());
+        assert!(derive_pda("oracle", &[], &program_id).is_none());
+        assert!(derive_pda("oracle", &[&[0u8; 8]], &program_id).is_none());
+        assert!(derive_pda("oracle", &[market.as_ref(), market.as_ref()], &program_id).is_none());
+        assert!(derive_pda("tick_array", &[market.as_ref(), &[0u8; 8]], &program_id).is_none());
+        assert!(derive_pda("protocol_config", &[], &program_id).is_some());
+    }
+}
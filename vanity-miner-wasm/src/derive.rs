@@ -0,0 +1,26 @@
+// Batched Ed25519 public key derivation
+//
+// Deriving a public key is SHA-512 of the secret, a fixed-base scalar
+// multiplication and a point compression. Calling curve25519-dalek directly
+// skips the SigningKey and VerifyingKey that ed25519-dalek builds (and
+// zeroizes) for every attempt; the multiplication still runs on dalek's
+// constant-time ED25519_BASEPOINT_TABLE, since the scalars are wallet secrets.
+//
+// Each key is compressed on its own. Sharing one field inversion across a
+// batch needs the points' projective coordinates, which curve25519-dalek 4
+// keeps private.
+
+use curve25519_dalek::EdwardsPoint;
+use sha2::{Digest, Sha512};
+
+// Derive the Ed25519 public key for each secret
+pub(crate) fn derive_public_keys(secrets: &[[u8; 32]], public_keys: &mut [[u8; 32]]) {
+    for (secret, public_key) in secrets.iter().zip(public_keys.iter_mut()) {
+        let hash = Sha512::digest(secret);
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&hash[..32]);
+        *public_key = EdwardsPoint::mul_base_clamped(scalar_bytes)
+            .compress()
+            .to_bytes();
+    }
+}
@@ -6,12 +6,16 @@ use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(feature = "parallel")]
 use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
 use wasm_bindgen::prelude::*;
 
+mod derive;
+use derive::derive_public_keys;
+
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 #[cfg(feature = "parallel")]
-use std::sync::{Arc, OnceLock};
+use std::sync::OnceLock;
 
 // Ed25519 key length in bytes
 const SECRET_LEN: usize = 32;
@@ -19,6 +23,8 @@ const SECRET_LEN: usize = 32;
 const ENTROPY_CHUNKS: usize = 256;
 // Total entropy buffer size (32 bytes × 256 = 8KB)
 const ENTROPY_BUFFER_LEN: usize = SECRET_LEN * ENTROPY_CHUNKS;
+// Number of keys derived per inner-loop step (divides ENTROPY_CHUNKS)
+const DERIVE_BATCH: usize = 8;
 // Maximum encoded base58 public key length
 const BASE58_BUFFER_LEN: usize = 64;
 // Threshold for modulus optimization (prevents overflow in suffix matching)
@@ -28,6 +34,8 @@ const BASE58_ALPHABET: &[u8; 58] =
     b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 // Default suffix if user provides empty string
 const DEFAULT_SUFFIX: &str = "FEEL";
+// Upper bound on keypairs returned by a single generate_random_keypairs call
+const MAX_RANDOM_KEYPAIRS: u32 = 10_000;
 
 // Global flag indicating if Rayon thread pool is initialized
 #[cfg(feature = "parallel")]
@@ -73,6 +81,15 @@ struct MiningStats {
     found: Option<FoundKeypair>, // Match if found
 }
 
+// Throughput report returned by benchmark_multi_batch
+#[derive(Serialize)]
+struct BenchmarkStats {
+    batch_size: u32,         // Batch size that was measured
+    attempts: u64,           // Total attempts in this run
+    elapsed_ms: f64,         // Total time elapsed
+    attempts_per_sec: f64,   // Measured mining rate
+}
+
 // Result of a mining run (internal use)
 struct RunOutcome {
     attempts: u32,               // Number of keys tried
@@ -92,19 +109,73 @@ pub struct VanityMiner {
     suffix: String,                                      // Original suffix (canonical uppercase)
     suffix_bytes: Vec<u8>,                               // Suffix as bytes (must be uppercase)
     suffix_params: Option<SuffixParams>,                 // Precomputed params for fast filtering
-    is_running: AtomicBool,                              // Mining state flag (atomic for thread safety)
+    is_running: Arc<AtomicBool>,                         // Mining state flag (shared with StopHandles)
     rng: ChaCha20Rng,                                    // Fast CSPRNG
     entropy_buffer: Box<[u8; ENTROPY_BUFFER_LEN]>,       // Bulk entropy buffer (reduces RNG calls)
     entropy_offset: usize,                               // Current position in entropy buffer
     secret_buffer: Box<[u8; SECRET_LEN]>,                // Reusable secret key buffer
     public_key_buffer: Box<[u8; SECRET_LEN]>,            // Reusable public key buffer
     encoding_buffer: Box<[u8; BASE58_BUFFER_LEN]>,       // Reusable base58 encoding buffer
+    alphabet: [u8; 58],                                  // Base58 digit characters, in order
+    bs58_alphabet: bs58::Alphabet,                       // Same alphabet, prepared for encoding
+}
+
+// Stop flag of a VanityMiner, detached from the miner so it can be used
+// while a mining call holds the miner
+#[wasm_bindgen]
+pub struct StopHandle {
+    is_running: Arc<AtomicBool>, // Same flag as the miner's is_running
+}
+
+#[wasm_bindgen]
+impl StopHandle {
+    // Stop the miner's current call at its next flag check
+    pub fn stop(&self) {
+        self.is_running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
 }
 
 #[wasm_bindgen]
 impl VanityMiner {
     #[wasm_bindgen(constructor)]
-    pub fn new(mut suffix: String) -> Self {
+    pub fn new(suffix: String) -> Self {
+        Self::from_parts(suffix, *BASE58_ALPHABET, random_rng())
+    }
+
+    // Miner for a non-Solana base58 variant (alphabet must be 58 distinct ASCII characters)
+    pub fn with_alphabet(suffix: String, alphabet: String) -> Result<VanityMiner, JsValue> {
+        let alphabet = parse_alphabet(&alphabet).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Self::from_parts(suffix, alphabet, random_rng()))
+    }
+
+    // Miner for one of `worker_count` coordinated workers sharing a 32-byte seed.
+    // Each worker reads its own ChaCha20 stream of that seed, so the workers'
+    // candidate keys are disjoint instead of overlapping. Secret keys are only
+    // as unpredictable as the seed: the coordinator must draw it from
+    // crypto.getRandomValues() per session and never persist or log it.
+    pub fn with_worker(
+        suffix: String,
+        worker_id: u32,
+        worker_count: u32,
+        seed: Vec<u8>,
+    ) -> Result<VanityMiner, JsValue> {
+        let rng = worker_rng(&seed, worker_id, worker_count).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Self::from_parts(suffix, *BASE58_ALPHABET, rng))
+    }
+
+    pub fn get_suffix(&self) -> String {
+        self.suffix.clone()
+    }
+
+    pub fn get_alphabet(&self) -> String {
+        String::from_utf8(self.alphabet.to_vec()).unwrap()
+    }
+
+    fn from_parts(mut suffix: String, alphabet: [u8; 58], rng: ChaCha20Rng) -> Self {
         #[cfg(feature = "console_error_panic_hook")]
         console_error_panic_hook::set_once();
 
@@ -117,32 +188,26 @@ impl VanityMiner {
         let canonical_suffix = canonicalize_suffix(&suffix);
         let suffix_bytes = canonical_suffix.as_bytes().to_vec();
         // Precompute modular arithmetic parameters for fast filtering
-        let suffix_params = compute_suffix_params(&suffix_bytes);
-
-        // Initialize RNG with crypto-secure entropy
-        let mut seed = [0u8; SECRET_LEN];
-        getrandom::getrandom(&mut seed).unwrap();
-        let rng = ChaCha20Rng::from_seed(seed);
+        let suffix_params = compute_suffix_params(&suffix_bytes, &alphabet);
 
         VanityMiner {
             suffix: canonical_suffix,
             suffix_bytes,
             suffix_params,
-            is_running: AtomicBool::new(false),
+            is_running: Arc::new(AtomicBool::new(false)),
             rng,
             entropy_buffer: Box::new([0u8; ENTROPY_BUFFER_LEN]),
             entropy_offset: ENTROPY_BUFFER_LEN, // Force initial fill
             secret_buffer: Box::new([0u8; SECRET_LEN]),
             public_key_buffer: Box::new([0u8; SECRET_LEN]),
             encoding_buffer: Box::new([0u8; BASE58_BUFFER_LEN]),
+            alphabet,
+            bs58_alphabet: bs58::Alphabet::new(&alphabet).expect("alphabet validated"),
         }
     }
 
-    pub fn get_suffix(&self) -> String {
-        self.suffix.clone()
-    }
-
     // Mine synchronously up to max_attempts (returns found keypair or NULL)
+    // Blocks the calling thread; see stop_handle() for interrupting it
     pub fn mine_sync(&mut self, max_attempts: u64) -> JsValue {
         self.mine_with_limit(max_attempts)
     }
@@ -152,6 +217,13 @@ impl VanityMiner {
         self.mine_with_limit(max_attempts)
     }
 
+    // Same run as mine_sync, but returns MiningStats so a stopped run still
+    // reports how many attempts it made
+    pub fn mine_with_stats(&mut self, max_attempts: u64) -> JsValue {
+        let stats = self.run_with_limit(max_attempts);
+        serde_wasm_bindgen::to_value(&stats).unwrap()
+    }
+
     // Mine a single batch (64-bit wrapper for mine_batch32)
     pub fn mine_batch(&mut self, batch_size: u64) -> JsValue {
         let size = batch_size.min(u32::MAX as u64) as u32;
@@ -179,21 +251,96 @@ impl VanityMiner {
     // Mine multiple batches in a single WASM call (reduces JS/WASM boundary crossings)
     // Returns MiningStats with total attempts, elapsed time, and found keypair if any
     pub fn mine_multi_batch32(&mut self, batch_size: u32, batch_count: u32) -> JsValue {
+        let stats = self.run_multi_batch(batch_size, batch_count);
+        serde_wasm_bindgen::to_value(&stats).unwrap()
+    }
+
+    // Benchmark the real mining loop (same path as mine_multi_batch32) and report throughput
+    // Lets the front end compare batch sizes on the current device before mining
+    pub fn benchmark_multi_batch(&mut self, batch_size: u32, batch_count: u32) -> JsValue {
+        let stats = self.run_multi_batch(batch_size, batch_count);
+        let attempts_per_sec = if stats.elapsed_ms > 0.0 {
+            stats.attempts as f64 * 1000.0 / stats.elapsed_ms
+        } else {
+            0.0
+        };
+        let benchmark = BenchmarkStats {
+            batch_size,
+            attempts: stats.attempts,
+            elapsed_ms: stats.elapsed_ms,
+            attempts_per_sec,
+        };
+
+        serde_wasm_bindgen::to_value(&benchmark).unwrap()
+    }
+
+    // Stop mining (sets atomic flag checked by worker loops)
+    // Every mining loop checks the flag at least once per DERIVE_BATCH attempts.
+    // A synchronous call (mine_sync, mine_until_found, mine_batch32, ...) holds
+    // the thread it runs on, so stop() on the same miner from that thread only
+    // lands between calls. To interrupt a call in progress, stop it through a
+    // stop_handle() held by another thread, or mine in short
+    // mine_multi_batch32 slices and call stop() between them.
+    pub fn stop(&self) {
+        self.is_running.store(false, Ordering::SeqCst);
+    }
+
+    // Handle sharing this miner's stop flag, usable while a mining call runs.
+    // Each mining call sets the flag when it starts, so a stop issued before
+    // the call begins does not carry over to it.
+    pub fn stop_handle(&self) -> StopHandle {
+        StopHandle {
+            is_running: Arc::clone(&self.is_running),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    // Same check as the free verify_keypair, in this miner's alphabet
+    pub fn verify_keypair(&self, secret_key: &[u8], expected_public: &str) -> bool {
+        keypair_matches(secret_key, expected_public, &self.bs58_alphabet)
+    }
+}
+
+impl VanityMiner {
+    // Internal: run up to batch_count batches, stopping early on match or stop()
+    // is_running is cleared on every exit path so an interrupted run can be restarted
+    fn run_multi_batch(&mut self, batch_size: u32, batch_count: u32) -> MiningStats {
         if batch_size == 0 || batch_count == 0 {
-            let stats = MiningStats {
+            return MiningStats {
                 attempts: 0,
                 elapsed_ms: 0.0,
                 found: None,
             };
-            return serde_wasm_bindgen::to_value(&stats).unwrap();
         }
 
         self.is_running.store(true, Ordering::SeqCst);
         let start = js_sys::Date::now();
+        let (attempts_run, found) = self.run_batches(batch_size, batch_count, start);
+        self.is_running.store(false, Ordering::SeqCst);
+
+        let elapsed = js_sys::Date::now() - start;
+        MiningStats {
+            attempts: attempts_run,
+            elapsed_ms: found
+                .as_ref()
+                .map(|f| f.elapsed_ms)
+                .unwrap_or(elapsed),
+            found,
+        }
+    }
+
+    // Run batches until found, stopped, or batch_count exhausted
+    fn run_batches(
+        &mut self,
+        batch_size: u32,
+        batch_count: u32,
+        start: f64,
+    ) -> (u64, Option<FoundKeypair>) {
         let mut attempts_run = 0u64;
-        let mut found: Option<FoundKeypair> = None;
 
-        // Run batches until found, stopped, or batch_count exhausted
         for _ in 0..batch_count {
             if !self.is_running.load(Ordering::Relaxed) {
                 break;
@@ -202,9 +349,8 @@ impl VanityMiner {
             let outcome = self.run_attempts(batch_size, start, attempts_run);
             attempts_run += outcome.attempts as u64;
 
-            if let Some(result) = outcome.found {
-                found = Some(result);
-                break;
+            if outcome.found.is_some() {
+                return (attempts_run, outcome.found);
             }
 
             // Early exit if run was interrupted
@@ -213,51 +359,57 @@ impl VanityMiner {
             }
         }
 
+        (attempts_run, None)
+    }
+
+    // Internal: mine with a maximum attempt limit (returns found keypair or NULL)
+    fn mine_with_limit(&mut self, max_attempts: u64) -> JsValue {
+        match self.run_with_limit(max_attempts).found {
+            Some(found) => serde_wasm_bindgen::to_value(&found).unwrap(),
+            None => JsValue::NULL,
+        }
+    }
+
+    // Internal: run up to max_attempts, stopping early on match or stop()
+    // is_running is cleared on every exit path so an interrupted run can be restarted
+    fn run_with_limit(&mut self, max_attempts: u64) -> MiningStats {
+        if max_attempts == 0 {
+            return MiningStats {
+                attempts: 0,
+                elapsed_ms: 0.0,
+                found: None,
+            };
+        }
+
+        self.is_running.store(true, Ordering::SeqCst);
+        let start = js_sys::Date::now();
+        let (attempts_run, found) = self.run_chunks(max_attempts, start);
         self.is_running.store(false, Ordering::SeqCst);
+
         let elapsed = js_sys::Date::now() - start;
-        let stats = MiningStats {
+        MiningStats {
             attempts: attempts_run,
             elapsed_ms: found
                 .as_ref()
                 .map(|f| f.elapsed_ms)
                 .unwrap_or(elapsed),
             found,
-        };
-
-        serde_wasm_bindgen::to_value(&stats).unwrap()
-    }
-
-    // Stop mining (sets atomic flag checked by worker loops)
-    pub fn stop(&self) {
-        self.is_running.store(false, Ordering::SeqCst);
-    }
-
-    pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::SeqCst)
-    }
-}
-
-impl VanityMiner {
-    // Internal: mine with a maximum attempt limit (handles >u32::MAX by chunking)
-    fn mine_with_limit(&mut self, max_attempts: u64) -> JsValue {
-        if max_attempts == 0 {
-            return JsValue::NULL;
         }
+    }
 
-        self.is_running.store(true, Ordering::SeqCst);
-        let start = js_sys::Date::now();
-        let mut attempts_offset = 0u64;
+    // Run max_attempts in u32 chunks (required by run_attempts signature)
+    // until found, stopped, or exhausted
+    fn run_chunks(&mut self, max_attempts: u64, start: f64) -> (u64, Option<FoundKeypair>) {
+        let mut attempts_run = 0u64;
         let mut remaining = max_attempts;
 
-        // Process in u32 chunks (required by run_attempts signature)
         while remaining > 0 && self.is_running.load(Ordering::Relaxed) {
             let chunk = remaining.min(u32::MAX as u64) as u32;
-            let outcome = self.run_attempts(chunk, start, attempts_offset);
-            attempts_offset += outcome.attempts as u64;
+            let outcome = self.run_attempts(chunk, start, attempts_run);
+            attempts_run += outcome.attempts as u64;
 
-            if let Some(found) = outcome.found {
-                self.is_running.store(false, Ordering::SeqCst);
-                return serde_wasm_bindgen::to_value(&found).unwrap();
+            if outcome.found.is_some() {
+                return (attempts_run, outcome.found);
             }
 
             // Early exit if interrupted
@@ -268,8 +420,7 @@ impl VanityMiner {
             remaining -= chunk as u64;
         }
 
-        self.is_running.store(false, Ordering::SeqCst);
-        JsValue::NULL
+        (attempts_run, None)
     }
 
     // Dispatch to parallel or sequential implementation based on feature flag
@@ -280,7 +431,7 @@ impl VanityMiner {
         self.run_attempts_sequential(attempts, start, attempt_offset)
     }
 
-    // Sequential hot loop: generate keypair, check suffix match, repeat
+    // Sequential hot loop: derive keys DERIVE_BATCH at a time, then filter each for the suffix
     fn run_attempts_sequential(
         &mut self,
         attempts: u32,
@@ -288,37 +439,41 @@ impl VanityMiner {
         attempt_offset: u64,
     ) -> RunOutcome {
         let mut completed = 0u32;
+        let mut secrets = [[0u8; SECRET_LEN]; DERIVE_BATCH];
+        let mut public_keys = [[0u8; SECRET_LEN]; DERIVE_BATCH];
 
-        for attempt_index in 0..attempts {
+        while completed < attempts {
             // Check if stop() was called
             if !self.is_running.load(Ordering::Relaxed) {
-                return RunOutcome {
-                    attempts: completed,
-                    found: None,
-                };
+                break;
             }
 
-            // Generate next secret key from entropy buffer
-            self.next_secret();
-
-            // Derive public key from secret
-            let signing_key = SigningKey::from_bytes(self.secret_buffer.as_ref());
-            let verifying_key = signing_key.verifying_key();
-            let public_key_bytes = verifying_key.to_bytes();
-            self.public_key_buffer.copy_from_slice(&public_key_bytes);
-
-            // Check if suffix matches (modular arithmetic + base58 encoding)
-            if self.try_match_suffix(&public_key_bytes).is_some() {
-                let attempts_total = attempt_offset + attempt_index as u64 + 1;
-                let found =
-                    self.build_found_keypair_from_public_key(&public_key_bytes, attempts_total, start);
-                return RunOutcome {
-                    attempts: attempt_index + 1,
-                    found: Some(found),
-                };
+            // Generate the next batch of secrets and derive their public keys
+            let lanes = ((attempts - completed) as usize).min(DERIVE_BATCH);
+            self.fill_secrets(&mut secrets[..lanes]);
+            derive_public_keys(&secrets[..lanes], &mut public_keys[..lanes]);
+
+            // Check each lane (modular prefilter before base58 encoding)
+            for lane in 0..lanes {
+                if self.try_match_suffix(&public_keys[lane]).is_some() {
+                    self.secret_buffer.copy_from_slice(&secrets[lane]);
+                    self.public_key_buffer.copy_from_slice(&public_keys[lane]);
+
+                    let attempts_run = completed + lane as u32 + 1;
+                    let attempts_total = attempt_offset + attempts_run as u64;
+                    let found = self.build_found_keypair_from_public_key(
+                        &public_keys[lane],
+                        attempts_total,
+                        start,
+                    );
+                    return RunOutcome {
+                        attempts: attempts_run,
+                        found: Some(found),
+                    };
+                }
             }
 
-            completed += 1;
+            completed += lanes as u32;
         }
 
         RunOutcome {
@@ -362,15 +517,16 @@ impl VanityMiner {
         // Clone data needed by parallel workers
         let suffix_bytes = self.suffix_bytes.clone();
         let suffix_params = self.suffix_params;
+        let alphabet = &self.bs58_alphabet;
         let found_index = AtomicUsize::new(usize::MAX);
         let candidate_slot: Arc<OnceLock<ParallelCandidate>> = Arc::new(OnceLock::new());
-        let running_flag = &self.is_running;
+        let running_flag = self.is_running.as_ref();
 
-        // Parallel verification: each worker checks its own secret
+        // Parallel verification: each worker derives and checks DERIVE_BATCH secrets at a time
         secrets
-            .par_iter()
+            .par_chunks(DERIVE_BATCH)
             .enumerate()
-            .for_each(|(idx, secret)| {
+            .for_each(|(chunk, batch)| {
                 // Early exit if stopped or already found
                 if !running_flag.load(Ordering::Relaxed) {
                     return;
@@ -379,42 +535,49 @@ impl VanityMiner {
                     return;
                 }
 
-                // Derive public key
-                let signing_key = SigningKey::from_bytes(secret);
-                let public_key = signing_key.verifying_key().to_bytes();
+                // Derive public keys for the whole chunk
+                let mut public_keys = [[0u8; SECRET_LEN]; DERIVE_BATCH];
+                derive_public_keys(batch, &mut public_keys[..batch.len()]);
 
-                // Fast path: modular arithmetic prefilter (~98% rejection)
-                if let Some(params) = suffix_params {
-                    if !matches_suffix_mod_bytes(&public_key, &params) {
-                        return;
+                for (lane, (secret, public_key)) in batch.iter().zip(public_keys).enumerate() {
+                    // Fast path: modular arithmetic prefilter (~98% rejection)
+                    if let Some(params) = suffix_params {
+                        if !matches_suffix_mod_bytes(&public_key, &params) {
+                            continue;
+                        }
                     }
-                }
 
-                // Full check: base58 encode and compare suffix
-                let mut buffer = [0u8; BASE58_BUFFER_LEN];
-                let Ok(encoded_len) = bs58::encode(public_key).onto(&mut buffer[..]) else {
-                    return;
-                };
-                if !suffix_matches_exact(&buffer[..encoded_len], &suffix_bytes) {
-                    return;
-                }
+                    // Full check: base58 encode and compare suffix
+                    let mut buffer = [0u8; BASE58_BUFFER_LEN];
+                    let Ok(encoded_len) = bs58::encode(public_key)
+                        .with_alphabet(alphabet)
+                        .onto(&mut buffer[..])
+                    else {
+                        continue;
+                    };
+                    if !suffix_matches_exact(&buffer[..encoded_len], &suffix_bytes) {
+                        continue;
+                    }
 
-                // Atomically claim first match (race condition handled by compare_exchange)
-                if found_index
-                    .compare_exchange(
-                        usize::MAX,
-                        idx,
-                        Ordering::SeqCst,
-                        Ordering::Relaxed,
-                    )
-                    .is_ok()
-                {
-                    let _ = candidate_slot.set(ParallelCandidate {
-                        index: idx,
-                        secret: *secret,
-                        public_key,
-                    });
-                    running_flag.store(false, Ordering::SeqCst);
+                    // Atomically claim first match (race condition handled by compare_exchange)
+                    let idx = chunk * DERIVE_BATCH + lane;
+                    if found_index
+                        .compare_exchange(
+                            usize::MAX,
+                            idx,
+                            Ordering::SeqCst,
+                            Ordering::Relaxed,
+                        )
+                        .is_ok()
+                    {
+                        let _ = candidate_slot.set(ParallelCandidate {
+                            index: idx,
+                            secret: *secret,
+                            public_key,
+                        });
+                        running_flag.store(false, Ordering::SeqCst);
+                    }
+                    return;
                 }
             });
 
@@ -467,6 +630,14 @@ impl VanityMiner {
         self.entropy_offset = end;
     }
 
+    // Fill a batch of secrets from the entropy buffer
+    fn fill_secrets(&mut self, secrets: &mut [[u8; SECRET_LEN]]) {
+        for secret in secrets.iter_mut() {
+            self.next_secret();
+            secret.copy_from_slice(self.secret_buffer.as_ref());
+        }
+    }
+
     // Construct FoundKeypair result from matched public key
     fn build_found_keypair_from_public_key(
         &mut self,
@@ -523,6 +694,7 @@ impl VanityMiner {
     // Base58 encode public key into reusable buffer
     fn encode_into_buffer(&mut self, public_key: &[u8; SECRET_LEN]) -> usize {
         bs58::encode(public_key)
+            .with_alphabet(&self.bs58_alphabet)
             .onto(&mut self.encoding_buffer[..])
             .expect("encoding buffer too small")
     }
@@ -568,7 +740,7 @@ fn matches_suffix_mod_bytes(public_key: &[u8; SECRET_LEN], params: &SuffixParams
 }
 
 // Precompute modular arithmetic parameters for suffix (returns None if suffix too long)
-fn compute_suffix_params(suffix_bytes: &[u8]) -> Option<SuffixParams> {
+fn compute_suffix_params(suffix_bytes: &[u8], alphabet: &[u8; 58]) -> Option<SuffixParams> {
     if suffix_bytes.is_empty() {
         return Some(SuffixParams {
             modulus: 1,
@@ -581,7 +753,7 @@ fn compute_suffix_params(suffix_bytes: &[u8]) -> Option<SuffixParams> {
 
     // Convert base58 suffix to numeric modulus and value
     for &byte in suffix_bytes {
-        let digit = decode_base58_byte(byte)?;
+        let digit = decode_base58_byte(byte, alphabet)?;
         modulus = modulus.checked_mul(58)?;
         // Abort if modulus would overflow (suffix too long for optimization)
         if modulus > BASE58_THRESHOLD_U64 {
@@ -594,13 +766,55 @@ fn compute_suffix_params(suffix_bytes: &[u8]) -> Option<SuffixParams> {
 }
 
 // Decode single base58 character to digit value (0-57)
-fn decode_base58_byte(byte: u8) -> Option<u8> {
-    BASE58_ALPHABET
+fn decode_base58_byte(byte: u8, alphabet: &[u8; 58]) -> Option<u8> {
+    alphabet
         .iter()
         .position(|&ch| ch == byte)
         .map(|idx| idx as u8)
 }
 
+// RNG seeded with crypto-secure entropy
+fn random_rng() -> ChaCha20Rng {
+    let mut seed = [0u8; SECRET_LEN];
+    getrandom::getrandom(&mut seed).unwrap();
+    ChaCha20Rng::from_seed(seed)
+}
+
+// RNG for one coordinated worker: the shared seed on a stream of its own
+fn worker_rng(seed: &[u8], worker_id: u32, worker_count: u32) -> Result<ChaCha20Rng, String> {
+    let seed: [u8; SECRET_LEN] = seed
+        .try_into()
+        .map_err(|_| format!("Worker seed must be {} bytes, got {}", SECRET_LEN, seed.len()))?;
+    if seed.iter().all(|&byte| byte == 0) {
+        return Err("Worker seed must not be all zeros".to_string());
+    }
+    if worker_id >= worker_count {
+        return Err(format!(
+            "Worker id {} out of range for {} workers",
+            worker_id, worker_count
+        ));
+    }
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    rng.set_stream(worker_id as u64);
+    Ok(rng)
+}
+
+// Validate a custom base58 alphabet: exactly 58 distinct ASCII characters
+fn parse_alphabet(input: &str) -> Result<[u8; 58], String> {
+    let alphabet: [u8; 58] = input.as_bytes().try_into().map_err(|_| {
+        format!("Alphabet must be 58 characters, got {}", input.len())
+    })?;
+    if let Some(&byte) = alphabet.iter().find(|byte| !byte.is_ascii()) {
+        return Err(format!("Alphabet contains non-ASCII byte 0x{:02x}", byte));
+    }
+    for (i, byte) in alphabet.iter().enumerate() {
+        if alphabet[..i].contains(byte) {
+            return Err(format!("Alphabet repeats character '{}'", *byte as char));
+        }
+    }
+    Ok(alphabet)
+}
+
 // Check if Rayon thread pool is initialized and ready
 fn parallel_enabled() -> bool {
     #[cfg(feature = "parallel")]
@@ -638,6 +852,70 @@ pub fn generate_random_keypair() -> JsValue {
     serde_wasm_bindgen::to_value(&result).unwrap()
 }
 
+// Utility: generate `count` random Solana keypairs in one call (no vanity matching)
+// Returns a JSON array of FoundKeypair; throws if count is 0 or above MAX_RANDOM_KEYPAIRS
+#[wasm_bindgen]
+pub fn generate_random_keypairs(count: u32) -> Result<JsValue, JsValue> {
+    if count == 0 || count > MAX_RANDOM_KEYPAIRS {
+        return Err(JsValue::from_str(&format!(
+            "count must be between 1 and {}",
+            MAX_RANDOM_KEYPAIRS
+        )));
+    }
+
+    let mut seed = [0u8; SECRET_LEN];
+    getrandom::getrandom(&mut seed).unwrap();
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    let keypairs = random_keypairs(&mut rng, count as usize);
+    serde_wasm_bindgen::to_value(&keypairs).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Generate keypairs from one RNG, drawing secrets from a shared entropy buffer
+fn random_keypairs(rng: &mut ChaCha20Rng, count: usize) -> Vec<FoundKeypair> {
+    let mut entropy = [0u8; ENTROPY_BUFFER_LEN];
+    let mut keypairs = Vec::with_capacity(count);
+
+    while keypairs.len() < count {
+        let chunk = (count - keypairs.len()).min(ENTROPY_CHUNKS);
+        let bytes = &mut entropy[..chunk * SECRET_LEN];
+        rng.fill_bytes(bytes);
+
+        for secret in bytes.chunks_exact(SECRET_LEN) {
+            let secret: [u8; SECRET_LEN] = secret.try_into().unwrap();
+            let public_key = SigningKey::from_bytes(&secret).verifying_key();
+            keypairs.push(FoundKeypair {
+                public_key: bs58::encode(public_key.to_bytes()).into_string(),
+                secret_key: secret.to_vec(),
+                attempts: 1,
+                elapsed_ms: 0.0,
+            });
+        }
+    }
+
+    keypairs
+}
+
+// Utility: check that expected_public is the base58 public key of secret_key
+// Lets callers confirm a FoundKeypair survived serialization intact before using it.
+// Only knows the Solana alphabet; use VanityMiner.verify_keypair for custom alphabets.
+#[wasm_bindgen]
+pub fn verify_keypair(secret_key: &[u8], expected_public: &str) -> bool {
+    keypair_matches(secret_key, expected_public, bs58::Alphabet::DEFAULT)
+}
+
+// Re-derive the public key and compare its encoding (false for a malformed secret)
+fn keypair_matches(secret_key: &[u8], expected_public: &str, alphabet: &bs58::Alphabet) -> bool {
+    let Ok(secret) = <[u8; SECRET_LEN]>::try_from(secret_key) else {
+        return false;
+    };
+    let public_key = SigningKey::from_bytes(&secret).verifying_key().to_bytes();
+    bs58::encode(public_key)
+        .with_alphabet(alphabet)
+        .into_string()
+        == expected_public
+}
+
 // Benchmark: measure single-thread keypair generation rate (attempts per second)
 #[wasm_bindgen]
 pub fn benchmark_single_thread(duration_ms: f64) -> u64 {
@@ -669,5 +947,53 @@ pub fn benchmark_single_thread(duration_ms: f64) -> u64 {
     (attempts as f64 / elapsed_secs) as u64
 }
 
+// Throughput comparison returned by benchmark_batched_derivation
+#[cfg(feature = "bench")]
+#[derive(Serialize)]
+struct DerivationBenchmark {
+    single_per_sec: f64,  // One key derived per loop step
+    batched_per_sec: f64, // DERIVE_BATCH keys derived per loop step
+}
+
+// Benchmark: compare one-at-a-time and batched key derivation (each runs for duration_ms)
+// Only exported from builds with the `bench` feature
+#[cfg(feature = "bench")]
+#[wasm_bindgen]
+pub fn benchmark_batched_derivation(duration_ms: f64) -> JsValue {
+    let mut seed = [0u8; SECRET_LEN];
+    getrandom::getrandom(&mut seed).unwrap();
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let mut secrets = [[0u8; SECRET_LEN]; DERIVE_BATCH];
+    let mut public_keys = [[0u8; SECRET_LEN]; DERIVE_BATCH];
+
+    // One key per step through ed25519-dalek
+    let start = js_sys::Date::now();
+    let mut single = 0u64;
+    while js_sys::Date::now() - start < duration_ms {
+        rng.fill_bytes(&mut secrets[0]);
+        public_keys[0] = SigningKey::from_bytes(&secrets[0]).verifying_key().to_bytes();
+        single += 1;
+    }
+
+    // DERIVE_BATCH keys per step through curve25519-dalek (current inner loop)
+    let start = js_sys::Date::now();
+    let mut batched = 0u64;
+    while js_sys::Date::now() - start < duration_ms {
+        for secret in secrets.iter_mut() {
+            rng.fill_bytes(secret);
+        }
+        derive_public_keys(&secrets, &mut public_keys);
+        batched += DERIVE_BATCH as u64;
+    }
+
+    let elapsed_secs = duration_ms / 1000.0;
+    let result = DerivationBenchmark {
+        single_per_sec: single as f64 / elapsed_secs,
+        batched_per_sec: batched as f64 / elapsed_secs,
+    };
+
+    serde_wasm_bindgen::to_value(&result).unwrap()
+}
+
 #[cfg(test)]
 mod test;
@@ -50,7 +50,338 @@ fn test_suffix_mod_fast_path_equivalence() {
 fn test_suffix_params_fallback_on_long_suffix() {
     let long_suffix = "123456789ABCDEFGHJKLMNPQRSTUV";
     assert!(
-        compute_suffix_params(long_suffix.as_bytes()).is_none(),
+        compute_suffix_params(long_suffix.as_bytes(), BASE58_ALPHABET).is_none(),
         "long suffix should fall back to full encode"
     );
 }
+
+#[test]
+fn test_batched_derivation_matches_single() {
+    let mut secrets = vec![[0u8; SECRET_LEN]; 100];
+    for secret in secrets.iter_mut() {
+        getrandom(secret).unwrap();
+    }
+    secrets[0] = [0u8; SECRET_LEN];
+    secrets[1] = [0xff; SECRET_LEN];
+
+    // Batches smaller than, equal to and spanning several shared inversions
+    for lanes in [1, 3, DERIVE_BATCH, 32, 33, secrets.len()] {
+        let mut public_keys = vec![[0u8; SECRET_LEN]; lanes];
+        derive_public_keys(&secrets[..lanes], &mut public_keys);
+
+        for (secret, public_key) in secrets.iter().zip(public_keys.iter()) {
+            let expected = SigningKey::from_bytes(secret).verifying_key().to_bytes();
+            assert_eq!(public_key, &expected, "mismatch in a batch of {}", lanes);
+        }
+    }
+}
+
+#[test]
+fn test_fill_secrets_consumes_entropy_in_order() {
+    let mut miner = VanityMiner::new("FEEL".to_string());
+    let mut batch = [[0u8; SECRET_LEN]; DERIVE_BATCH];
+    miner.fill_secrets(&mut batch);
+
+    assert_eq!(miner.entropy_offset, SECRET_LEN * DERIVE_BATCH);
+    for (i, secret) in batch.iter().enumerate() {
+        let offset = i * SECRET_LEN;
+        assert_eq!(&secret[..], &miner.entropy_buffer[offset..offset + SECRET_LEN]);
+    }
+    assert_eq!(miner.secret_buffer.as_ref(), &batch[DERIVE_BATCH - 1]);
+}
+
+#[test]
+fn test_random_keypairs_count_and_validity() {
+    let mut rng = ChaCha20Rng::from_seed([7u8; SECRET_LEN]);
+    let count = ENTROPY_CHUNKS + 3;
+    let keypairs = random_keypairs(&mut rng, count);
+    assert_eq!(keypairs.len(), count);
+
+    for keypair in &keypairs {
+        let secret: [u8; SECRET_LEN] = keypair.secret_key.as_slice().try_into().unwrap();
+        let expected = SigningKey::from_bytes(&secret).verifying_key().to_bytes();
+        assert_eq!(keypair.public_key, bs58::encode(expected).into_string());
+    }
+    assert_ne!(keypairs[0].secret_key, keypairs[1].secret_key);
+}
+
+// Remainder of a public key (as a big-endian integer) modulo `modulus`
+fn key_remainder(public_key: &[u8; SECRET_LEN], modulus: u64) -> u64 {
+    let mut remainder = 0u128;
+    for &byte in public_key.iter() {
+        remainder = ((remainder << 8) + byte as u128) % modulus as u128;
+    }
+    remainder as u64
+}
+
+// Add a signed offset to a public key read as a 256-bit big-endian integer
+fn offset_key(public_key: &[u8; SECRET_LEN], delta: i128) -> Option<[u8; SECRET_LEN]> {
+    let hi = u128::from_be_bytes(public_key[..16].try_into().unwrap());
+    let lo = u128::from_be_bytes(public_key[16..].try_into().unwrap());
+    let (lo, hi) = if delta >= 0 {
+        let (lo, carry) = lo.overflowing_add(delta as u128);
+        (lo, hi.checked_add(carry as u128)?)
+    } else {
+        let (lo, borrow) = lo.overflowing_sub(delta.unsigned_abs());
+        (lo, hi.checked_sub(borrow as u128)?)
+    };
+    let mut out = [0u8; SECRET_LEN];
+    out[..16].copy_from_slice(&hi.to_be_bytes());
+    out[16..].copy_from_slice(&lo.to_be_bytes());
+    Some(out)
+}
+
+// Nudge a key so its base58 encoding ends with the digits of `params`
+fn force_suffix(public_key: &[u8; SECRET_LEN], params: &SuffixParams) -> [u8; SECRET_LEN] {
+    let delta = params.value as i128 - key_remainder(public_key, params.modulus) as i128;
+    offset_key(public_key, delta)
+        .or_else(|| offset_key(public_key, delta - params.modulus as i128))
+        .or_else(|| offset_key(public_key, delta + params.modulus as i128))
+        .expect("a 256-bit key always has room for a suffix")
+}
+
+// Random suffix of 1..=9 base58 digits, the longest that keeps the modular path
+fn random_suffix(rng: &mut ChaCha20Rng) -> Vec<u8> {
+    let len = 1 + (rng.next_u32() % 9) as usize;
+    (0..len)
+        .map(|_| BASE58_ALPHABET[(rng.next_u32() % 58) as usize])
+        .collect()
+}
+
+#[test]
+fn test_suffix_prefilter_has_no_false_negatives() {
+    let mut rng = ChaCha20Rng::from_seed([42u8; SECRET_LEN]);
+    for _ in 0..5_000 {
+        let suffix = random_suffix(&mut rng);
+        let params = compute_suffix_params(&suffix, BASE58_ALPHABET)
+            .expect("9 digits fit the modular path");
+
+        let mut random_key = [0u8; SECRET_LEN];
+        rng.fill_bytes(&mut random_key);
+        for public_key in [random_key, force_suffix(&random_key, &params)] {
+            let encoded = bs58::encode(public_key).into_vec();
+            if suffix_matches_exact(&encoded, &suffix) {
+                assert!(
+                    matches_suffix_mod_bytes(&public_key, &params),
+                    "prefilter rejected {} with suffix {}",
+                    bs58::encode(public_key).into_string(),
+                    String::from_utf8_lossy(&suffix)
+                );
+            }
+        }
+
+        // The constructed key must actually exercise the property
+        let forced = force_suffix(&random_key, &params);
+        assert!(suffix_matches_exact(&bs58::encode(forced).into_vec(), &suffix));
+    }
+}
+
+#[test]
+fn test_suffix_prefilter_agrees_with_exact_check_on_short_suffixes() {
+    // With one digit roughly one key in 58 matches, so random keys hit both
+    // outcomes; the prefilter is exact for keys with at least that many digits
+    let mut rng = ChaCha20Rng::from_seed([9u8; SECRET_LEN]);
+    let mut matched = 0;
+    for _ in 0..5_000 {
+        let suffix = [BASE58_ALPHABET[(rng.next_u32() % 58) as usize]];
+        let params = compute_suffix_params(&suffix, BASE58_ALPHABET).unwrap();
+        let mut public_key = [0u8; SECRET_LEN];
+        rng.fill_bytes(&mut public_key);
+
+        let exact = suffix_matches_exact(&bs58::encode(public_key).into_vec(), &suffix);
+        assert_eq!(matches_suffix_mod_bytes(&public_key, &params), exact);
+        matched += exact as usize;
+    }
+    assert!(matched > 0);
+}
+
+#[test]
+fn test_feel_fast_path_matches_brute_force() {
+    // The prefilter reduces the whole key, not a truncation of it, so for the
+    // default suffix it must agree with encoding every key in full
+    let mut miner = VanityMiner::new(DEFAULT_SUFFIX.to_string());
+    let params = miner.suffix_params.expect("FEEL uses the modular path");
+    let mut rng = ChaCha20Rng::from_seed([3u8; SECRET_LEN]);
+    let mut hits = 0;
+    for i in 0..20_000 {
+        let mut public_key = [0u8; SECRET_LEN];
+        rng.fill_bytes(&mut public_key);
+        // Every other key is forced to end in FEEL so both outcomes are common
+        if i % 2 == 0 {
+            public_key = force_suffix(&public_key, &params);
+        }
+
+        let brute_force = bs58::encode(public_key).into_string().ends_with(DEFAULT_SUFFIX);
+        assert_eq!(
+            miner.try_match_suffix(&public_key).is_some(),
+            brute_force,
+            "fast path disagrees on {}",
+            bs58::encode(public_key).into_string()
+        );
+        hits += brute_force as usize;
+    }
+    assert!(hits >= 10_000);
+}
+
+// Ripple's base58 alphabet, a real non-Solana variant
+const RIPPLE_ALPHABET: &str = "rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+#[test]
+fn test_default_alphabet_is_solana() {
+    let miner = VanityMiner::new("FEEL".to_string());
+    assert_eq!(miner.get_alphabet().as_bytes(), BASE58_ALPHABET);
+}
+
+#[test]
+fn test_parse_alphabet_validation() {
+    assert_eq!(parse_alphabet(RIPPLE_ALPHABET).unwrap(), RIPPLE_ALPHABET.as_bytes());
+
+    // Wrong length
+    assert!(parse_alphabet(&RIPPLE_ALPHABET[..57]).is_err());
+    assert!(parse_alphabet(&format!("{}0", RIPPLE_ALPHABET)).is_err());
+    assert!(parse_alphabet("").is_err());
+
+    // Duplicate character
+    let duplicate = format!("r{}", &RIPPLE_ALPHABET[..57]);
+    let err = parse_alphabet(&duplicate).unwrap_err();
+    assert!(err.contains("'r'"), "{}", err);
+
+    // Non-ASCII, even at the right byte length
+    let non_ascii = format!("é{}", &RIPPLE_ALPHABET[..56]);
+    assert_eq!(non_ascii.len(), 58);
+    assert!(parse_alphabet(&non_ascii).is_err());
+}
+
+#[test]
+fn test_custom_alphabet_matches_its_own_encoding() {
+    let alphabet = parse_alphabet(RIPPLE_ALPHABET).unwrap();
+    let bs58_alphabet = bs58::Alphabet::new(&alphabet).unwrap();
+    let mut miner = VanityMiner::from_parts("FEEL".to_string(), alphabet, random_rng());
+    let params = miner.suffix_params.expect("FEEL digits exist in the Ripple alphabet");
+    assert_ne!(
+        params.value,
+        compute_suffix_params(b"FEEL", BASE58_ALPHABET).unwrap().value
+    );
+
+    let mut rng = ChaCha20Rng::from_seed([5u8; SECRET_LEN]);
+    for i in 0..2_000 {
+        let mut public_key = [0u8; SECRET_LEN];
+        rng.fill_bytes(&mut public_key);
+        if i % 2 == 0 {
+            public_key = force_suffix(&public_key, &params);
+        }
+
+        let encoded = bs58::encode(public_key)
+            .with_alphabet(&bs58_alphabet)
+            .into_string();
+        assert_eq!(miner.try_match_suffix(&public_key).is_some(), encoded.ends_with("FEEL"));
+    }
+}
+
+// Secrets a coordinated worker would try first
+fn worker_secrets(seed: &[u8; SECRET_LEN], worker_id: u32) -> Vec<[u8; SECRET_LEN]> {
+    let rng = worker_rng(seed, worker_id, 4).unwrap();
+    let mut miner = VanityMiner::from_parts("FEEL".to_string(), *BASE58_ALPHABET, rng);
+    let mut secrets = vec![[0u8; SECRET_LEN]; ENTROPY_CHUNKS * 4];
+    miner.fill_secrets(&mut secrets);
+    secrets
+}
+
+#[test]
+fn test_workers_produce_disjoint_key_streams() {
+    let seed = [11u8; SECRET_LEN];
+    let first = worker_secrets(&seed, 0);
+    let second = worker_secrets(&seed, 1);
+
+    let first_set: std::collections::HashSet<_> = first.iter().collect();
+    assert_eq!(first_set.len(), first.len());
+    assert!(second.iter().all(|secret| !first_set.contains(secret)));
+
+    // Same seed and worker id replays the same stream
+    assert_eq!(worker_secrets(&seed, 0), first);
+}
+
+#[test]
+fn test_worker_rng_validation() {
+    let seed = [11u8; SECRET_LEN];
+    assert!(worker_rng(&seed, 3, 4).is_ok());
+    assert!(worker_rng(&seed, 4, 4).is_err());
+    assert!(worker_rng(&seed, 0, 0).is_err());
+    assert!(worker_rng(&seed[..16], 0, 4).is_err());
+    assert!(worker_rng(&[0u8; SECRET_LEN], 0, 4).is_err());
+}
+
+#[test]
+fn test_stop_handle_interrupts_a_running_call() {
+    // Too long for the prefilter and practically never matched, so only a stop ends the run
+    let mut miner = VanityMiner::from_parts(
+        "FEELFEELFEELFEEL".to_string(),
+        *BASE58_ALPHABET,
+        ChaCha20Rng::seed_from_u64(5),
+    );
+    let handle = miner.stop_handle();
+    let stopper = std::thread::spawn(move || {
+        while !handle.is_running() {
+            std::thread::yield_now();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        handle.stop();
+    });
+
+    // Same path as mine_sync, minus the JS clock
+    miner.is_running.store(true, Ordering::SeqCst);
+    let started = std::time::Instant::now();
+    let (attempts, found) = miner.run_chunks(u64::MAX, 0.0);
+    stopper.join().unwrap();
+
+    assert!(found.is_none());
+    assert!(attempts > 0, "stopped before any attempt was reported");
+    assert!(attempts < u32::MAX as u64);
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    assert!(!miner.is_running());
+
+    // A stop only ends the call it interrupted
+    miner.is_running.store(true, Ordering::SeqCst);
+    let (attempts, _) = miner.run_chunks(64, 0.0);
+    assert_eq!(attempts, 64);
+}
+
+#[test]
+fn test_verify_keypair_detects_tampering() {
+    let mut rng = ChaCha20Rng::from_seed([9u8; SECRET_LEN]);
+    let keypairs = random_keypairs(&mut rng, 2);
+    let (keypair, other) = (&keypairs[0], &keypairs[1]);
+    assert!(verify_keypair(&keypair.secret_key, &keypair.public_key));
+
+    // One flipped bit in the secret
+    let mut secret = keypair.secret_key.clone();
+    secret[0] ^= 1;
+    assert!(!verify_keypair(&secret, &keypair.public_key));
+
+    // Public key swapped or truncated
+    assert!(!verify_keypair(&keypair.secret_key, &other.public_key));
+    let truncated = &keypair.public_key[..keypair.public_key.len() - 1];
+    assert!(!verify_keypair(&keypair.secret_key, truncated));
+
+    // Secret of the wrong length
+    assert!(!verify_keypair(&keypair.secret_key[..31], &keypair.public_key));
+    assert!(!verify_keypair(&[], &keypair.public_key));
+}
+
+#[test]
+fn test_verify_keypair_uses_the_miner_alphabet() {
+    let miner = VanityMiner::from_parts(
+        "FEEL".to_string(),
+        parse_alphabet(RIPPLE_ALPHABET).unwrap(),
+        random_rng(),
+    );
+    let secret = [3u8; SECRET_LEN];
+    let public_key = SigningKey::from_bytes(&secret).verifying_key().to_bytes();
+    let ripple = bs58::encode(public_key)
+        .with_alphabet(&miner.bs58_alphabet)
+        .into_string();
+
+    assert!(miner.verify_keypair(&secret, &ripple));
+    assert!(!verify_keypair(&secret, &ripple));
+    assert!(verify_keypair(&secret, &bs58::encode(public_key).into_string()));
+}